@@ -0,0 +1,58 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use vrl::compiler::{CompileConfig, Program, compile};
+
+const REMAP: &str = r#"
+    . |= parse_key_value!(.message)
+    .level = upcase(string!(.level))
+    .tags = split(string!(.tags), ",")
+    .duration_ms = to_float!(.duration) * 1000
+    if .level == "ERROR" {
+        .alert = true
+        .priority = 1
+    } else if .level == "WARN" {
+        .priority = 2
+    } else {
+        .priority = 3
+    }
+    .host = downcase(string(.host) ?? "unknown")
+    .tags = map_values(.tags) -> |tag| { strip_whitespace!(tag) }
+    .summary = join!([.level, .host, to_string(.priority)], " ")
+    del(.message)
+"#;
+
+const REGEX: &str = r#"
+    .parsed = parse_regex!(.message, r'^(?P<time>\S+) (?P<level>\w+) (?P<user>[\w.]+)@(?P<host>\S+) (?P<message>.*)$')
+    .groked = parse_groks!(.message, patterns: [
+        "%{TIMESTAMP_ISO8601:time} %{LOGLEVEL:level} %{NOTSPACE:user} %{GREEDYDATA:message}",
+        "%{TIMESTAMP_ISO8601:time} %{LOGLEVEL:level} %{GREEDYDATA:message}",
+        "%{GREEDYDATA:message}"
+    ])
+"#;
+
+fn benchmark_load(c: &mut Criterion) {
+    let fns = vrl::stdlib::all();
+
+    let mut group = c.benchmark_group("vrl/artifact");
+    for (name, source) in [("remap", REMAP), ("regex", REGEX)] {
+        let artifact = compile(source, &fns).unwrap().program.to_artifact(&fns);
+
+        group.bench_with_input(BenchmarkId::new("compile", name), source, |b, source| {
+            b.iter(|| compile(source, &fns).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("from_artifact", name),
+            &artifact,
+            |b, artifact| {
+                b.iter(|| {
+                    Program::from_artifact(artifact, &fns, &mut CompileConfig::default()).unwrap()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(name = vrl_artifact;
+                 config = Criterion::default();
+                 targets = benchmark_load);
+criterion_main!(vrl_artifact);
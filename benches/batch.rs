@@ -0,0 +1,43 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use vrl::compiler::{BatchExecutor, TimeZone, compile};
+use vrl::value;
+use vrl::value::Value;
+
+const SOURCE: &str = r#"
+    . |= parse_key_value!(.message)
+    .level = upcase(string!(.level))
+    .tags = split(string!(.tags), ",")
+    .duration_ms = to_float!(.duration) * 1000
+    del(.message)
+"#;
+
+fn events(count: usize) -> Vec<Value> {
+    (0..count)
+        .map(|i| {
+            let message = format!("level=info tags=a,b,c duration=0.{i} user=user{i}");
+            value!({ message: message, id: (i as i64) })
+        })
+        .collect()
+}
+
+fn benchmark_batch(c: &mut Criterion) {
+    let program = compile(SOURCE, &vrl::stdlib::all()).unwrap().program;
+    let events = events(10_000);
+
+    let mut group = c.benchmark_group("vrl/batch_executor");
+    group.throughput(Throughput::Elements(events.len() as u64));
+    for threads in [1, 2, 4, 8] {
+        let executor = BatchExecutor::new(&program, threads);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &events,
+            |b, events| b.iter(|| executor.resolve(events.iter().cloned(), &TimeZone::default())),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(name = vrl_batch;
+                 config = Criterion::default();
+                 targets = benchmark_batch);
+criterion_main!(vrl_batch);
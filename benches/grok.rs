@@ -0,0 +1,57 @@
+use std::collections::BTreeMap;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use vrl::datadog_grok::{
+    parse_grok::parse_grok, parse_grok_rules::parse_grok_rules, rule_set::GrokRuleSet,
+};
+
+const RULES: usize = 80;
+
+fn rules() -> Vec<String> {
+    (0..RULES)
+        .map(|i| {
+            format!(
+                "%{{notSpace:time}} service{i}: %{{word:level}} user=%{{notSpace:user}} took %{{integer:duration}}ms"
+            )
+        })
+        .collect()
+}
+
+fn benchmark_rules(c: &mut Criterion) {
+    let rules = parse_grok_rules(&rules(), BTreeMap::new()).unwrap();
+    let rule_set = GrokRuleSet::new(rules.clone());
+    let values = [
+        (
+            "first_rule",
+            "2024-01-01T00:00:00Z service0: info user=alice took 12ms".to_string(),
+        ),
+        (
+            "last_rule",
+            format!(
+                "2024-01-01T00:00:00Z service{}: info user=alice took 12ms",
+                RULES - 1
+            ),
+        ),
+        (
+            "no_rule",
+            "2024-01-01T00:00:00Z web: info user=alice took 12ms".to_string(),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("datadog_grok/rules");
+    for (name, value) in &values {
+        group.bench_with_input(BenchmarkId::new("parse_grok", name), value, |b, value| {
+            b.iter(|| parse_grok(black_box(value), &rules))
+        });
+        group.bench_with_input(BenchmarkId::new("rule_set", name), value, |b, value| {
+            b.iter(|| rule_set.parse(black_box(value)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(name = datadog_grok;
+                 config = Criterion::default();
+                 targets = benchmark_rules);
+criterion_main!(datadog_grok);
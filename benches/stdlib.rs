@@ -1766,7 +1766,7 @@ bench_function! {
             patterns: Value::Array(vec![
                 "%{common_prefix} %{_status} %{_message}".into(),
                 "%{common_prefix} %{_message}".into(),
-                ]),
+                ].into()),
             aliases: value!({
                 common_prefix: "%{_timestamp} %{_loglevel}",
                 _timestamp: "%{TIMESTAMP_ISO8601:timestamp}",
@@ -17,15 +17,24 @@ use crate::compiler::{
 use crate::diagnostic::Formatter;
 use crate::owned_metadata_path;
 use crate::value::Secrets;
-use crate::value::Value;
-use clap::Parser;
+use crate::value::kind::InferOptions;
+use crate::value::{Kind, ObjectMap, Value};
+use clap::{Args, Parser, Subcommand};
 
 use super::Error;
 use super::repl;
 
 #[derive(Parser, Debug)]
-#[command(name = "VRL", about = "Vector Remap Language CLI")]
+#[allow(clippy::struct_excessive_bools)]
+#[command(
+    name = "VRL",
+    about = "Vector Remap Language CLI",
+    args_conflicts_with_subcommands = true
+)]
 pub struct Opts {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The VRL program to execute. The program ".foo = true", for example, sets the event object's
     /// `foo` field to `true`.
     #[arg(id = "PROGRAM")]
@@ -48,6 +57,11 @@ pub struct Opts {
     #[arg(short = 'o', long)]
     print_object: bool,
 
+    /// Print the changes made to each event object as a JSON Patch (RFC 6902), instead of the
+    /// result of the final expression.
+    #[arg(short = 'd', long, conflicts_with("print_object"))]
+    diff: bool,
+
     /// The timezone used to parse dates.
     #[arg(short = 'z', long)]
     timezone: Option<String>,
@@ -61,6 +75,73 @@ pub struct Opts {
     print_warnings: bool,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Infer the type of events from a set of samples, and print it.
+    InferKind(InferKindOpts),
+
+    /// Print the lineage of a program: for each path it assigns to, the paths the assigned value
+    /// can be derived from.
+    Lineage(LineageOpts),
+
+    /// Explain, for each line of input, how each grok pattern matches it or why it doesn't.
+    DiagnoseGrok(DiagnoseGrokOpts),
+}
+
+#[derive(Args, Debug)]
+pub struct InferKindOpts {
+    /// The file containing the sample events, one JSON event per line. Defaults to stdin.
+    #[arg(short, long = "input")]
+    input_file: Option<PathBuf>,
+
+    /// The number of nested object or array levels to track. Deeper levels can be of any type.
+    #[arg(long)]
+    max_depth: Option<usize>,
+
+    /// The maximum number of known fields of an object (or elements of an array). Objects with
+    /// more fields are collapsed into an object of unknown fields.
+    #[arg(long)]
+    max_fields: Option<usize>,
+
+    /// Print the inferred type as a JSON Schema, instead of as a type tree.
+    #[arg(long)]
+    json_schema: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct LineageOpts {
+    /// The VRL program to analyze.
+    #[arg(id = "PROGRAM")]
+    program: Option<String>,
+
+    /// The file containing the VRL program to analyze. This can be used instead of `PROGRAM`.
+    #[arg(short, long = "program", conflicts_with("PROGRAM"))]
+    program_file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+pub struct DiagnoseGrokOpts {
+    /// A grok pattern, as accepted by `parse_groks`. Patterns are explained in the order given.
+    #[arg(short, long = "pattern", required = true)]
+    patterns: Vec<String>,
+
+    /// The file containing the aliases referenced in the patterns, as a JSON object.
+    #[arg(short, long)]
+    aliases: Option<PathBuf>,
+
+    /// A directory of pattern files, whose patterns can be referenced in the patterns.
+    #[arg(long = "patterns-dir")]
+    patterns_dirs: Vec<PathBuf>,
+
+    /// Parse the patterns as Logstash grok patterns, instead of Datadog grok patterns.
+    #[arg(long)]
+    logstash: bool,
+
+    /// The file containing the lines to explain. Defaults to stdin.
+    #[arg(short, long = "input")]
+    input_file: Option<PathBuf>,
+}
+
 impl Opts {
     fn timezone(&self) -> Result<TimeZone, Error> {
         if let Some(ref tz) = self.timezone {
@@ -82,17 +163,11 @@ impl Opts {
     }
 
     fn read_into_objects(&self) -> Result<Vec<Value>, Error> {
-        let input = match self.input_file.as_ref() {
-            Some(path) => read(File::open(path)?),
-            None => read(io::stdin()),
-        }?;
+        let input = read_input(self.input_file.as_ref())?;
 
         match input.as_str() {
-            "" => Ok(vec![Value::Object(BTreeMap::default())]),
-            _ => input
-                .lines()
-                .map(|line| Ok(serde_to_vrl(serde_json::from_str(line)?)))
-                .collect::<Result<Vec<Value>, Error>>(),
+            "" => Ok(vec![Value::Object(ObjectMap::new())]),
+            _ => parse_objects(&input),
         }
     }
 
@@ -116,6 +191,13 @@ pub fn cmd(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> exitcode::E
 }
 
 fn run(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> Result<(), Error> {
+    match &opts.command {
+        Some(Command::InferKind(infer_opts)) => return infer_kind(infer_opts),
+        Some(Command::Lineage(lineage_opts)) => return lineage(lineage_opts, &stdlib_functions),
+        Some(Command::DiagnoseGrok(grok_opts)) => return diagnose_grok(grok_opts),
+        None => {}
+    }
+
     let tz = opts.timezone()?;
     // Run the REPL if no program or program file is specified
     if opts.should_open_repl() {
@@ -159,7 +241,8 @@ fn run(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> Result<(), Erro
         }
 
         for mut object in objects {
-            let mut metadata = Value::Object(BTreeMap::new());
+            let original = opts.diff.then(|| object.clone());
+            let mut metadata = Value::Object(ObjectMap::new());
             let mut secrets = Secrets::new();
             let mut target = TargetValueRef {
                 value: &mut object,
@@ -169,11 +252,13 @@ fn run(opts: &Opts, stdlib_functions: Vec<Box<dyn Function>>) -> Result<(), Erro
             let state = RuntimeState::default();
             let runtime = Runtime::new(state);
 
-            let result = execute(&mut target, &program, tz, runtime, opts.runtime).map(|v| {
-                if opts.print_object {
-                    object.to_string()
+            let result = execute(&mut target, &program, tz, runtime, opts.runtime).and_then(|v| {
+                if let Some(original) = &original {
+                    Ok(serde_json::to_string(&original.diff(&object))?)
+                } else if opts.print_object {
+                    Ok(object.to_string())
                 } else {
-                    v.to_string()
+                    Ok(v.to_string())
                 }
             });
 
@@ -203,7 +288,7 @@ fn repl(
         .into_iter()
         .map(|value| TargetValue {
             value,
-            metadata: Value::Object(BTreeMap::new()),
+            metadata: Value::Object(ObjectMap::new()),
             secrets: Secrets::new(),
         })
         .collect();
@@ -211,6 +296,103 @@ fn repl(
     repl::run(quiet, objects, timezone, vrl_runtime, stdlib_functions).map_err(Into::into)
 }
 
+fn infer_kind(opts: &InferKindOpts) -> Result<(), Error> {
+    let input = read_input(opts.input_file.as_ref())?;
+    let kind = Kind::infer(
+        &parse_objects(&input)?,
+        InferOptions {
+            max_depth: opts.max_depth,
+            max_fields: opts.max_fields,
+        },
+    );
+
+    let output = if opts.json_schema {
+        kind.to_json_schema()
+    } else {
+        serde_json::to_value(Value::Object(kind.debug_info()))?
+    };
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    }
+
+    Ok(())
+}
+
+fn lineage(opts: &LineageOpts, stdlib_functions: &[Box<dyn Function>]) -> Result<(), Error> {
+    let source = match (&opts.program, &opts.program_file) {
+        (Some(source), _) => source.clone(),
+        (None, Some(path)) => read(File::open(path)?)?,
+        (None, None) => read(io::stdin())?,
+    };
+
+    let program = compile_with_state(
+        &source,
+        stdlib_functions,
+        &TypeState::default(),
+        CompileConfig::default(),
+    )
+    .map_err(|diagnostics| {
+        Error::Parse(Formatter::new(&source, diagnostics).colored().to_string())
+    })?
+    .program;
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&program.info().target_lineage)?
+        );
+    }
+
+    Ok(())
+}
+
+fn diagnose_grok(opts: &DiagnoseGrokOpts) -> Result<(), Error> {
+    use crate::datadog_grok::{
+        parse_grok,
+        parse_grok_rules::{GrokDialect, parse_grok_rules_with},
+        pattern_library::GrokPatternLibrary,
+    };
+
+    let aliases = match &opts.aliases {
+        Some(path) => serde_json::from_reader(File::open(path)?)?,
+        None => BTreeMap::new(),
+    };
+    let mut library = GrokPatternLibrary::new();
+    for dir in &opts.patterns_dirs {
+        library
+            .load_dir(dir)
+            .map_err(|err| Error::Grok(err.to_string()))?;
+    }
+    let dialect = if opts.logstash {
+        GrokDialect::Logstash
+    } else {
+        GrokDialect::Datadog
+    };
+    let rules = parse_grok_rules_with(&opts.patterns, aliases, dialect, &library)
+        .map_err(|err| Error::Grok(err.to_string()))?;
+
+    for line in read_input(opts.input_file.as_ref())?.lines() {
+        let diagnostics = parse_grok::diagnose_grok(line, &rules)
+            .map_err(|err| Error::Grok(err.to_string()))?
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<_>>();
+
+        #[allow(clippy::print_stdout)]
+        {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&Value::from(diagnostics))?
+            );
+        }
+    }
+
+    Ok(())
+}
+
 fn execute(
     object: &mut impl Target,
     program: &Program,
@@ -243,6 +425,20 @@ fn serde_to_vrl(value: serde_json::Value) -> Value {
     }
 }
 
+fn read_input(input_file: Option<&PathBuf>) -> Result<String, Error> {
+    match input_file {
+        Some(path) => read(File::open(path)?),
+        None => read(io::stdin()),
+    }
+}
+
+fn parse_objects(input: &str) -> Result<Vec<Value>, Error> {
+    input
+        .lines()
+        .map(|line| Ok(serde_to_vrl(serde_json::from_str(line)?)))
+        .collect()
+}
+
 fn read<R: Read>(mut reader: R) -> Result<String, Error> {
     let mut buffer = String::new();
     reader.read_to_string(&mut buffer)?;
@@ -251,5 +447,5 @@ fn read<R: Read>(mut reader: R) -> Result<String, Error> {
 }
 
 fn default_objects() -> Vec<Value> {
-    vec![Value::Object(BTreeMap::new())]
+    vec![Value::Object(ObjectMap::new())]
 }
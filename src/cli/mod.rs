@@ -20,6 +20,9 @@ pub enum Error {
     #[error("input error: {}", .0)]
     Json(#[from] serde_json::Error),
 
+    #[error("grok error: {}", .0)]
+    Grok(String),
+
     #[error("repl feature disabled, program input required")]
     ReplFeature,
 
@@ -0,0 +1,1399 @@
+//! Compiled programs stored as binary artifacts.
+//!
+//! An artifact holds the compiled expressions of a [`Program`] and its [`ProgramInfo`], so that
+//! the program can be loaded without parsing and type checking its source again. It starts with
+//! a header of the artifact format version, the version of this crate, and a fingerprint of the
+//! function registry the program was compiled against. Loading rejects artifacts with a header
+//! that doesn't match.
+//!
+//! The expressions that function implementations compile to are opaque, so each function call
+//! is stored with its arguments, and compiled again by its function when the artifact is loaded.
+//! This includes any expensive work the function does while compiling, such as building regular
+//! expressions, so loading an artifact only saves parsing and type checking the source.
+//! Arguments that are only known to be constant through the type state, such as variables
+//! assigned a literal, are stored as values, so that functions requiring constant arguments can
+//! be compiled without it.
+//!
+//! Type information is stored alongside: the initial [`TypeState`] of the program, and for each
+//! function call the type state it was compiled against, the type definition of its closure
+//! block, and the type definition it compiled to. Functions are compiled again against the same
+//! state, so functions that inspect the types of their arguments, such as `type_def`, behave as
+//! they did in the original program. Loading rejects an artifact if a call compiles to a
+//! different type definition, which happens when a function changed its typing without changing
+//! its signature.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use chrono::DateTime;
+use ordered_float::NotNan;
+
+use crate::compiler::{
+    CompileConfig, Expression, Function, Program, ProgramInfo, Span, TypeDef, TypeState,
+    expression::{
+        Abort, Array, Assignment, Block, Container, Expr, FunctionArgument, FunctionCall, Group,
+        IfStatement, Not, Object, ObjectFields, Op, Predicate, Query, Return, SpreadArray,
+        SpreadObject, Unary, Variable, Variant, assignment,
+        query::Target,
+        spread::{ArrayItem, ObjectItem},
+        unary,
+    },
+    function::{
+        ArgumentList, Closure, FunctionCompileContext,
+        closure::{self, VariableKind},
+    },
+    parser::{Ident, Node, ast::Opcode},
+    state::{ExternalEnv, LocalEnv},
+    type_def::{Details, Fallibility},
+};
+use crate::path::{OwnedSegment, OwnedTargetPath, OwnedValuePath, PathPrefix};
+use crate::value::{
+    KeyString, Kind, ObjectMap, Value, ValueRegex,
+    kind::{Collection, Field, Index, Unknown},
+};
+
+/// The bytes every artifact starts with.
+const MAGIC: &[u8; 4] = b"VRLP";
+
+/// The version of the artifact format, increased on any change to the encoding.
+const FORMAT_VERSION: u16 = 2;
+
+/// The version of this crate, since the behavior of functions can change between versions.
+const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// An error loading a [`Program`] from an artifact.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactError {
+    #[error("not a VRL program artifact")]
+    InvalidHeader,
+
+    #[error("unsupported artifact format version {0}, expected {FORMAT_VERSION}")]
+    FormatVersion(u16),
+
+    #[error("artifact was built by VRL {0}, expected {CRATE_VERSION}")]
+    CrateVersion(String),
+
+    #[error("artifact was built against a different function registry")]
+    FunctionRegistry,
+
+    #[error("malformed artifact: {0}")]
+    Malformed(&'static str),
+
+    #[error("call to undefined function \"{0}\"")]
+    UndefinedFunction(String),
+
+    #[error("function \"{function}\" failed to compile: {message}")]
+    Compilation {
+        function: &'static str,
+        message: String,
+    },
+
+    #[error("function \"{0}\" compiled to a different type definition")]
+    TypeMismatch(&'static str),
+}
+
+type Result<T> = std::result::Result<T, ArtifactError>;
+
+/// The operators, in the order of their encoding.
+const OPCODES: [Opcode; 14] = [
+    Opcode::Mul,
+    Opcode::Div,
+    Opcode::Add,
+    Opcode::Sub,
+    Opcode::Or,
+    Opcode::And,
+    Opcode::Err,
+    Opcode::Ne,
+    Opcode::Eq,
+    Opcode::Ge,
+    Opcode::Gt,
+    Opcode::Le,
+    Opcode::Lt,
+    Opcode::Merge,
+];
+
+/// Serializes `program`, compiled against `fns`, into an artifact.
+pub(crate) fn encode(program: &Program, fns: &[Box<dyn Function>]) -> Vec<u8> {
+    let mut encoder = Encoder(Vec::new());
+    encoder.0.extend_from_slice(MAGIC);
+    encoder.0.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    encoder.str(CRATE_VERSION);
+    encoder.0.extend_from_slice(&fingerprint(fns).to_le_bytes());
+    encoder.info(&program.info);
+    encoder.state(&program.initial_state);
+    encoder.block(&program.expressions);
+    encoder.0
+}
+
+/// Loads a program from an artifact, compiling its function calls with `fns` and `config`.
+pub(crate) fn decode(
+    artifact: &[u8],
+    fns: &[Box<dyn Function>],
+    config: &mut CompileConfig,
+) -> Result<Program> {
+    let mut decoder = Decoder {
+        input: artifact,
+        fns,
+        config,
+    };
+
+    if decoder.take(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+        return Err(ArtifactError::InvalidHeader);
+    }
+    let format_version = u16::from_le_bytes(decoder.array()?);
+    if format_version != FORMAT_VERSION {
+        return Err(ArtifactError::FormatVersion(format_version));
+    }
+    let crate_version = decoder.string()?;
+    if crate_version != CRATE_VERSION {
+        return Err(ArtifactError::CrateVersion(crate_version));
+    }
+    if u64::from_le_bytes(decoder.array()?) != fingerprint(fns) {
+        return Err(ArtifactError::FunctionRegistry);
+    }
+
+    let info = decoder.info()?;
+    let initial_state = decoder.state()?;
+    let expressions = decoder.block()?;
+    if !decoder.input.is_empty() {
+        return Err(ArtifactError::Malformed("trailing bytes"));
+    }
+
+    Ok(Program {
+        initial_state,
+        expressions,
+        info,
+    })
+}
+
+/// A stable hash of the signatures of `fns`, using 64-bit FNV-1a.
+///
+/// The signature of a function is everything the compiler checks a call against: its identifier,
+/// parameters, return kind, purity and closure definition.
+fn fingerprint(fns: &[Box<dyn Function>]) -> u64 {
+    let mut encoder = Encoder(Vec::new());
+    encoder.usize(fns.len());
+    for function in fns {
+        encoder.str(function.identifier());
+        encoder.usize(function.parameters().len());
+        for parameter in function.parameters() {
+            encoder.str(parameter.keyword);
+            encoder.0.extend_from_slice(&parameter.kind.to_le_bytes());
+            encoder.bool(parameter.required);
+            encoder.bool(parameter.default.is_some());
+            if let Some(default) = parameter.default {
+                encoder.value(default);
+            }
+        }
+        encoder
+            .0
+            .extend_from_slice(&function.return_kind().to_le_bytes());
+        encoder.bool(function.pure());
+        encoder.bool(function.deterministic());
+        let closure = function.closure();
+        encoder.bool(closure.is_some());
+        if let Some(closure) = &closure {
+            encoder.closure_definition(closure);
+        }
+    }
+
+    encoder.0.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+struct Encoder(Vec<u8>);
+
+impl Encoder {
+    fn bool(&mut self, value: bool) {
+        self.0.push(u8::from(value));
+    }
+
+    /// Writes `value` as a LEB128 variable-length integer.
+    fn usize(&mut self, value: usize) {
+        let mut value = value as u64;
+        while value >= 0x80 {
+            self.0.push((value & 0x7f) as u8 | 0x80);
+            value >>= 7;
+        }
+        self.0.push(u8::try_from(value).expect("less than 0x80"));
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn bytes(&mut self, value: &[u8]) {
+        self.usize(value.len());
+        self.0.extend_from_slice(value);
+    }
+
+    fn str(&mut self, value: &str) {
+        self.bytes(value.as_bytes());
+    }
+
+    fn span(&mut self, span: Span) {
+        self.usize(span.start());
+        self.usize(span.end());
+    }
+
+    fn info(&mut self, info: &ProgramInfo) {
+        self.bool(info.fallible);
+        self.bool(info.abortable);
+        for paths in [&info.target_queries, &info.target_assignments] {
+            self.usize(paths.len());
+            for path in paths {
+                self.target_path(path);
+            }
+        }
+        self.usize(info.target_lineage.len());
+        for (target, sources) in &info.target_lineage {
+            self.target_path(target);
+            self.usize(sources.len());
+            for source in sources {
+                self.target_path(source);
+            }
+        }
+    }
+
+    fn value(&mut self, value: &Value) {
+        match value {
+            Value::Bytes(bytes) => {
+                self.0.push(0);
+                self.bytes(bytes);
+            }
+            Value::Regex(regex) => {
+                self.0.push(1);
+                self.str(regex.as_str());
+            }
+            Value::Integer(integer) => {
+                self.0.push(2);
+                self.i64(*integer);
+            }
+            Value::Float(float) => {
+                self.0.push(3);
+                self.0.extend_from_slice(&float.to_bits().to_le_bytes());
+            }
+            Value::Boolean(boolean) => {
+                self.0.push(4);
+                self.bool(*boolean);
+            }
+            Value::Timestamp(timestamp) => {
+                self.0.push(5);
+                self.i64(timestamp.timestamp());
+                self.0
+                    .extend_from_slice(&timestamp.timestamp_subsec_nanos().to_le_bytes());
+            }
+            Value::Object(object) => {
+                self.0.push(6);
+                self.usize(object.len());
+                for (key, value) in object {
+                    self.str(key);
+                    self.value(value);
+                }
+            }
+            Value::Array(array) => {
+                self.0.push(7);
+                self.usize(array.len());
+                for value in array {
+                    self.value(value);
+                }
+            }
+            Value::Null => self.0.push(8),
+        }
+    }
+
+    fn path(&mut self, path: &OwnedValuePath) {
+        self.usize(path.segments.len());
+        for segment in &path.segments {
+            match segment {
+                OwnedSegment::Field(field) => {
+                    self.0.push(0);
+                    self.str(field);
+                }
+                OwnedSegment::Index(index) => {
+                    self.0.push(1);
+                    self.i64(*index as i64);
+                }
+            }
+        }
+    }
+
+    fn target_path(&mut self, path: &OwnedTargetPath) {
+        self.prefix(path.prefix);
+        self.path(&path.path);
+    }
+
+    fn prefix(&mut self, prefix: PathPrefix) {
+        self.0.push(match prefix {
+            PathPrefix::Event => 0,
+            PathPrefix::Metadata => 1,
+        });
+    }
+
+    fn kind(&mut self, kind: &Kind) {
+        // `contains_*` is true for every state of `never`, which has none of them set.
+        let primitives = if kind.is_never() {
+            [false; 8]
+        } else {
+            [
+                kind.contains_bytes(),
+                kind.contains_integer(),
+                kind.contains_float(),
+                kind.contains_boolean(),
+                kind.contains_timestamp(),
+                kind.contains_regex(),
+                kind.contains_null(),
+                kind.contains_undefined(),
+            ]
+        };
+        self.0.push(
+            primitives
+                .into_iter()
+                .enumerate()
+                .fold(0, |bits, (bit, set)| bits | (u8::from(set) << bit)),
+        );
+        self.bool(kind.as_array().is_some());
+        if let Some(array) = kind.as_array() {
+            self.collection(array, |encoder, index| encoder.usize(index.to_usize()));
+        }
+        self.bool(kind.as_object().is_some());
+        if let Some(object) = kind.as_object() {
+            self.collection(object, |encoder, field| encoder.str(field.as_str()));
+        }
+    }
+
+    fn collection<T: Ord + Clone>(
+        &mut self,
+        collection: &Collection<T>,
+        mut key: impl FnMut(&mut Self, &T),
+    ) {
+        self.usize(collection.known().len());
+        for (k, kind) in collection.known() {
+            key(self, k);
+            self.kind(kind);
+        }
+        // Unknown kinds that aren't exact are either "any" or "json".
+        let unknown = collection.unknown();
+        match unknown.as_exact() {
+            None if unknown.is_json() => self.0.push(1),
+            None => self.0.push(0),
+            Some(kind) => {
+                self.0.push(2);
+                self.kind(kind);
+            }
+        }
+    }
+
+    fn type_def(&mut self, type_def: &TypeDef) {
+        self.0.push(match type_def.fallibility() {
+            Fallibility::CannotFail => 0,
+            Fallibility::MightFail => 1,
+            Fallibility::AlwaysFails => 2,
+        });
+        self.kind(type_def.kind());
+        self.bool(type_def.is_pure());
+        self.kind(type_def.returns());
+    }
+
+    fn details(&mut self, details: &Details) {
+        self.type_def(&details.type_def);
+        self.bool(details.value.is_some());
+        if let Some(value) = &details.value {
+            self.value(value);
+        }
+    }
+
+    fn state(&mut self, state: &TypeState) {
+        // Variables are sorted, so that equal states have equal encodings.
+        let mut bindings = state.local.bindings.iter().collect::<Vec<_>>();
+        bindings.sort_by_key(|(ident, _)| *ident);
+        self.usize(bindings.len());
+        for (ident, details) in bindings {
+            self.str(ident);
+            self.details(details);
+        }
+        self.details(state.external.target());
+        self.kind(state.external.metadata_kind());
+    }
+
+    fn closure_definition(&mut self, definition: &closure::Definition) {
+        self.bool(definition.is_iterator);
+        self.usize(definition.inputs.len());
+        for input in &definition.inputs {
+            self.str(input.parameter_keyword);
+            self.kind(&input.kind);
+            self.usize(input.variables.len());
+            for variable in &input.variables {
+                match &variable.kind {
+                    VariableKind::Exact(kind) => {
+                        self.0.push(0);
+                        self.kind(kind);
+                    }
+                    VariableKind::Target => self.0.push(1),
+                    VariableKind::TargetInnerValue => self.0.push(2),
+                    VariableKind::TargetInnerKey => self.0.push(3),
+                    VariableKind::Accumulator(keyword) => {
+                        self.0.push(4);
+                        self.str(keyword);
+                    }
+                }
+            }
+            match &input.output {
+                closure::Output::Array { elements } => {
+                    self.0.push(0);
+                    self.usize(elements.len());
+                    for kind in elements {
+                        self.kind(kind);
+                    }
+                }
+                closure::Output::Object { fields } => {
+                    self.0.push(1);
+                    self.usize(fields.len());
+                    for (field, kind) in fields {
+                        self.str(field);
+                        self.kind(kind);
+                    }
+                }
+                closure::Output::Kind(kind) => {
+                    self.0.push(2);
+                    self.kind(kind);
+                }
+            }
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        self.bool(block.new_scope);
+        self.exprs(block.exprs());
+    }
+
+    fn exprs(&mut self, exprs: &[Expr]) {
+        self.usize(exprs.len());
+        for expr in exprs {
+            self.expr(expr);
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Literal(literal) => {
+                self.0.push(0);
+                self.value(&literal.to_value());
+            }
+            Expr::Container(container) => {
+                self.0.push(1);
+                self.container(container);
+            }
+            Expr::IfStatement(if_statement) => {
+                self.0.push(2);
+                self.block(&if_statement.predicate.inner);
+                self.block(&if_statement.if_block);
+                self.bool(if_statement.else_block.is_some());
+                if let Some(else_block) = &if_statement.else_block {
+                    self.block(else_block);
+                }
+            }
+            Expr::Op(op) => {
+                self.0.push(3);
+                let tag = OPCODES.iter().position(|opcode| *opcode == op.opcode);
+                self.usize(tag.expect("all operators are listed"));
+                self.expr(&op.lhs);
+                self.expr(&op.rhs);
+            }
+            Expr::Assignment(assignment) => {
+                self.0.push(4);
+                self.bool(assignment.vivify);
+                match &assignment.variant {
+                    assignment::Variant::Single { target, expr } => {
+                        self.0.push(0);
+                        self.assignment_target(target);
+                        self.expr(expr);
+                    }
+                    assignment::Variant::Infallible {
+                        ok,
+                        err,
+                        expr,
+                        default,
+                    } => {
+                        self.0.push(1);
+                        self.assignment_target(ok);
+                        self.assignment_target(err);
+                        self.expr(expr);
+                        self.value(default);
+                    }
+                }
+            }
+            Expr::Query(query) => {
+                self.0.push(5);
+                match query.target() {
+                    Target::Internal(variable) => {
+                        self.0.push(0);
+                        self.str(variable.ident());
+                    }
+                    Target::External(prefix) => {
+                        self.0.push(1);
+                        self.prefix(*prefix);
+                    }
+                    Target::FunctionCall(call) => {
+                        self.0.push(2);
+                        self.function_call(call);
+                    }
+                    Target::Container(container) => {
+                        self.0.push(3);
+                        self.container(container);
+                    }
+                }
+                self.path(query.path());
+            }
+            Expr::FunctionCall(call) => {
+                self.0.push(6);
+                self.function_call(call);
+            }
+            Expr::Variable(variable) => {
+                self.0.push(7);
+                self.str(variable.ident());
+            }
+            Expr::Noop(_) => self.0.push(8),
+            Expr::Unary(unary) => {
+                self.0.push(9);
+                match &unary.variant {
+                    unary::Variant::Not(not) => self.expr(&not.inner),
+                }
+            }
+            Expr::Abort(abort) => {
+                self.0.push(10);
+                self.span(abort.span);
+                self.bool(abort.message.is_some());
+                if let Some(message) = &abort.message {
+                    self.expr(message);
+                }
+            }
+            Expr::Return(r#return) => {
+                self.0.push(11);
+                self.span(r#return.span);
+                self.expr(&r#return.expr);
+            }
+            // The cache slots are specific to a set of fused programs.
+            Expr::Cached(cached) => self.expr(&cached.inner),
+        }
+    }
+
+    fn container(&mut self, container: &Container) {
+        match &container.variant {
+            Variant::Group(group) => {
+                self.0.push(0);
+                self.expr(&group.inner);
+            }
+            Variant::Block(block) => {
+                self.0.push(1);
+                self.block(block);
+            }
+            Variant::Array(array) => {
+                self.0.push(2);
+                self.exprs(&array.inner);
+            }
+            Variant::Object(object) => {
+                self.0.push(3);
+                self.usize(object.inner.len());
+                for (key, expr) in &object.inner {
+                    self.str(key);
+                    self.expr(expr);
+                }
+            }
+            Variant::SpreadArray(array) => {
+                self.0.push(4);
+                self.usize(array.inner.len());
+                for item in &array.inner {
+                    match item {
+                        ArrayItem::Element(expr) => {
+                            self.0.push(0);
+                            self.expr(expr);
+                        }
+                        ArrayItem::Spread(expr) => {
+                            self.0.push(1);
+                            self.expr(expr);
+                        }
+                    }
+                }
+            }
+            Variant::SpreadObject(object) => {
+                self.0.push(5);
+                self.usize(object.inner.len());
+                for item in &object.inner {
+                    match item {
+                        ObjectItem::Field(key, expr) => {
+                            self.0.push(0);
+                            self.str(key);
+                            self.expr(expr);
+                        }
+                        ObjectItem::Spread(expr) => {
+                            self.0.push(1);
+                            self.expr(expr);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn assignment_target(&mut self, target: &assignment::Target) {
+        match target {
+            assignment::Target::Noop => self.0.push(0),
+            assignment::Target::Internal(ident, path) => {
+                self.0.push(1);
+                self.str(ident);
+                self.path(path);
+            }
+            assignment::Target::External(path) => {
+                self.0.push(2);
+                self.target_path(path);
+            }
+            assignment::Target::Array(elements, rest) => {
+                self.0.push(3);
+                self.usize(elements.len());
+                for element in elements {
+                    self.assignment_target(element);
+                }
+                self.bool(rest.is_some());
+                if let Some(rest) = rest {
+                    self.assignment_target(rest);
+                }
+            }
+            assignment::Target::Object(fields) => {
+                self.0.push(4);
+                self.usize(fields.len());
+                for (key, target) in fields {
+                    self.str(key);
+                    self.assignment_target(target);
+                }
+            }
+        }
+    }
+
+    fn function_call(&mut self, call: &FunctionCall) {
+        self.str(call.ident);
+        self.span(call.span);
+        self.bool(call.abort_on_error);
+        self.bool(call.closure_fallible);
+
+        self.usize(call.arguments.len());
+        for (node, (keyword, constant)) in call.arguments.iter().zip(&call.parameters) {
+            self.span(node.span());
+            let argument = node.inner();
+            self.bool(argument.keyword().is_some());
+            if let (Some(keyword), Some(span)) = (argument.keyword(), argument.keyword_span()) {
+                self.span(span);
+                self.str(keyword);
+            }
+            self.span(argument.expr_span());
+            self.expr(argument.expr());
+            self.str(keyword);
+            self.bool(constant.is_some());
+            if let Some(constant) = constant {
+                self.value(constant);
+            }
+            self.bool(
+                call.arguments_with_unknown_type_validity
+                    .iter()
+                    .any(|(_, unknown)| unknown == node),
+            );
+        }
+
+        self.bool(call.closure.is_some());
+        if let Some(closure) = &call.closure {
+            self.usize(closure.variables.len());
+            for variable in &closure.variables {
+                self.str(variable);
+            }
+            self.block(&closure.block);
+            self.type_def(&closure.block_type_def);
+        }
+
+        self.state(&call.state);
+        self.type_def(&call.type_info(&call.state).result);
+    }
+}
+
+struct Decoder<'a> {
+    input: &'a [u8],
+    fns: &'a [Box<dyn Function>],
+    config: &'a mut CompileConfig,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.input.len() < len {
+            return Err(ArtifactError::Malformed("unexpected end of artifact"));
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("length is checked"))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ArtifactError::Malformed("invalid boolean")),
+        }
+    }
+
+    fn usize(&mut self) -> Result<usize> {
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return usize::try_from(value)
+                    .map_err(|_| ArtifactError::Malformed("integer out of range"));
+            }
+        }
+        Err(ArtifactError::Malformed("integer out of range"))
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.usize()?;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let bytes = self.bytes()?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| ArtifactError::Malformed("invalid UTF-8"))
+    }
+
+    fn span(&mut self) -> Result<Span> {
+        Ok(Span::new(self.usize()?, self.usize()?))
+    }
+
+    /// Decodes a list of items with `item`.
+    fn list<T>(&mut self, mut item: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let len = self.usize()?;
+        // The length isn't trusted for the allocation, as every item takes at least a byte.
+        let mut items = Vec::with_capacity(len.min(self.input.len()));
+        for _ in 0..len {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn option<T>(&mut self, item: impl FnOnce(&mut Self) -> Result<T>) -> Result<Option<T>> {
+        if self.bool()? {
+            item(self).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn info(&mut self) -> Result<ProgramInfo> {
+        Ok(ProgramInfo {
+            fallible: self.bool()?,
+            abortable: self.bool()?,
+            target_queries: self.list(Self::target_path)?,
+            target_assignments: self.list(Self::target_path)?,
+            target_lineage: self
+                .list(|decoder| {
+                    let target = decoder.target_path()?;
+                    let sources = decoder.list(Self::target_path)?;
+                    Ok((target, sources.into_iter().collect::<BTreeSet<_>>()))
+                })?
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+        })
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        let value = match self.u8()? {
+            0 => Value::Bytes(self.bytes()?.to_vec().into()),
+            1 => {
+                let regex = regex::Regex::new(&self.string()?)
+                    .map_err(|_| ArtifactError::Malformed("invalid regex"))?;
+                Value::Regex(ValueRegex::new(Arc::new(regex)))
+            }
+            2 => Value::Integer(self.i64()?),
+            3 => Value::Float(
+                NotNan::new(f64::from_bits(u64::from_le_bytes(self.array()?)))
+                    .map_err(|_| ArtifactError::Malformed("NaN float"))?,
+            ),
+            4 => Value::Boolean(self.bool()?),
+            5 => {
+                let seconds = self.i64()?;
+                let nanoseconds = u32::from_le_bytes(self.array()?);
+                Value::Timestamp(
+                    DateTime::from_timestamp(seconds, nanoseconds)
+                        .ok_or(ArtifactError::Malformed("invalid timestamp"))?,
+                )
+            }
+            6 => Value::Object(
+                self.list(|decoder| Ok((decoder.key()?, decoder.value()?)))?
+                    .into_iter()
+                    .collect::<ObjectMap>(),
+            ),
+            7 => Value::from(self.list(Self::value)?),
+            8 => Value::Null,
+            _ => return Err(ArtifactError::Malformed("invalid value")),
+        };
+        Ok(value)
+    }
+
+    fn key(&mut self) -> Result<KeyString> {
+        self.string().map(Into::into)
+    }
+
+    fn ident(&mut self) -> Result<Ident> {
+        self.string().map(Ident::new)
+    }
+
+    fn path(&mut self) -> Result<OwnedValuePath> {
+        let segments = self.list(|decoder| match decoder.u8()? {
+            0 => Ok(OwnedSegment::Field(decoder.key()?)),
+            1 => isize::try_from(decoder.i64()?)
+                .map(OwnedSegment::Index)
+                .map_err(|_| ArtifactError::Malformed("index out of range")),
+            _ => Err(ArtifactError::Malformed("invalid path segment")),
+        })?;
+        Ok(OwnedValuePath { segments })
+    }
+
+    fn target_path(&mut self) -> Result<OwnedTargetPath> {
+        Ok(OwnedTargetPath {
+            prefix: self.prefix()?,
+            path: self.path()?,
+        })
+    }
+
+    fn prefix(&mut self) -> Result<PathPrefix> {
+        match self.u8()? {
+            0 => Ok(PathPrefix::Event),
+            1 => Ok(PathPrefix::Metadata),
+            _ => Err(ArtifactError::Malformed("invalid path prefix")),
+        }
+    }
+
+    fn kind(&mut self) -> Result<Kind> {
+        let primitives = self.u8()?;
+        let mut kind = Kind::never();
+        let states: [fn(&mut Kind) -> bool; 8] = [
+            Kind::add_bytes,
+            Kind::add_integer,
+            Kind::add_float,
+            Kind::add_boolean,
+            Kind::add_timestamp,
+            Kind::add_regex,
+            Kind::add_null,
+            Kind::add_undefined,
+        ];
+        for (bit, add) in states.into_iter().enumerate() {
+            if primitives & (1 << bit) != 0 {
+                add(&mut kind);
+            }
+        }
+        if let Some(array) =
+            self.option(|decoder| decoder.collection(|decoder| decoder.usize().map(Index::from)))?
+        {
+            kind.add_array(array);
+        }
+        if let Some(object) =
+            self.option(|decoder| decoder.collection(|decoder| decoder.key().map(Field::from)))?
+        {
+            kind.add_object(object);
+        }
+        Ok(kind)
+    }
+
+    fn collection<T: Ord + Clone>(
+        &mut self,
+        mut key: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Collection<T>> {
+        let known = self
+            .list(|decoder| Ok((key(decoder)?, decoder.kind()?)))?
+            .into_iter()
+            .collect();
+        let unknown = match self.u8()? {
+            0 => Unknown::any(),
+            1 => Unknown::json(),
+            2 => Unknown::exact(self.kind()?),
+            _ => return Err(ArtifactError::Malformed("invalid unknown kind")),
+        };
+        Ok(Collection::from_unknown_state(known, unknown))
+    }
+
+    fn type_def(&mut self) -> Result<TypeDef> {
+        let fallibility = match self.u8()? {
+            0 => Fallibility::CannotFail,
+            1 => Fallibility::MightFail,
+            2 => Fallibility::AlwaysFails,
+            _ => return Err(ArtifactError::Malformed("invalid fallibility")),
+        };
+        let type_def = TypeDef::from(self.kind()?).with_fallibility(fallibility);
+        let type_def = if self.bool()? {
+            type_def
+        } else {
+            type_def.impure()
+        };
+        Ok(type_def.with_returns(self.kind()?))
+    }
+
+    fn details(&mut self) -> Result<Details> {
+        Ok(Details {
+            type_def: self.type_def()?,
+            value: self.option(Self::value)?,
+        })
+    }
+
+    fn state(&mut self) -> Result<TypeState> {
+        let bindings = self
+            .list(|decoder| Ok((decoder.ident()?, decoder.details()?)))?
+            .into_iter()
+            .collect();
+        let target = self.details()?;
+        let mut external = ExternalEnv::new_with_kind(Kind::never(), self.kind()?);
+        external.update_target(target);
+        Ok(TypeState {
+            local: LocalEnv { bindings },
+            external,
+        })
+    }
+
+    fn block(&mut self) -> Result<Block> {
+        let new_scope = self.bool()?;
+        let exprs = self.list(Self::expr)?;
+        Ok(if new_scope {
+            Block::new_scoped(exprs)
+        } else {
+            Block::new_inline(exprs)
+        })
+    }
+
+    fn boxed_expr(&mut self) -> Result<Box<Expr>> {
+        self.expr().map(Box::new)
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let expr = match self.u8()? {
+            0 => Expr::from(self.value()?),
+            1 => Expr::Container(self.container()?),
+            2 => Expr::IfStatement(IfStatement {
+                predicate: Predicate {
+                    inner: self.block()?,
+                },
+                if_block: self.block()?,
+                else_block: self.option(Self::block)?,
+            }),
+            3 => {
+                let opcode = *OPCODES
+                    .get(self.usize()?)
+                    .ok_or(ArtifactError::Malformed("invalid operator"))?;
+                Expr::Op(Op {
+                    opcode,
+                    lhs: self.boxed_expr()?,
+                    rhs: self.boxed_expr()?,
+                })
+            }
+            4 => {
+                let vivify = self.bool()?;
+                let variant = match self.u8()? {
+                    0 => assignment::Variant::Single {
+                        target: self.assignment_target()?,
+                        expr: self.boxed_expr()?,
+                    },
+                    1 => assignment::Variant::Infallible {
+                        ok: self.assignment_target()?,
+                        err: self.assignment_target()?,
+                        expr: self.boxed_expr()?,
+                        default: self.value()?,
+                    },
+                    _ => return Err(ArtifactError::Malformed("invalid assignment")),
+                };
+                Expr::Assignment(Assignment { variant, vivify })
+            }
+            5 => {
+                let target = match self.u8()? {
+                    0 => Target::Internal(Variable {
+                        ident: self.ident()?,
+                    }),
+                    1 => Target::External(self.prefix()?),
+                    2 => Target::FunctionCall(self.function_call()?),
+                    3 => Target::Container(self.container()?),
+                    _ => return Err(ArtifactError::Malformed("invalid query target")),
+                };
+                Expr::Query(Query::new(target, self.path()?))
+            }
+            6 => Expr::FunctionCall(self.function_call()?),
+            7 => Expr::Variable(Variable {
+                ident: self.ident()?,
+            }),
+            8 => Expr::Noop(crate::compiler::expression::Noop),
+            9 => Expr::Unary(Unary {
+                variant: unary::Variant::Not(Not {
+                    inner: self.boxed_expr()?,
+                }),
+            }),
+            10 => Expr::Abort(Abort {
+                span: self.span()?,
+                message: self.option(Self::boxed_expr)?,
+            }),
+            11 => Expr::Return(Return {
+                span: self.span()?,
+                expr: self.boxed_expr()?,
+            }),
+            _ => return Err(ArtifactError::Malformed("invalid expression")),
+        };
+        Ok(expr)
+    }
+
+    fn container(&mut self) -> Result<Container> {
+        let variant = match self.u8()? {
+            0 => Variant::Group(Group::new(self.expr()?)),
+            1 => Variant::Block(self.block()?),
+            2 => Variant::Array(Array::from(self.list(Self::expr)?)),
+            3 => Variant::Object(Object::from(
+                self.list(|decoder| Ok((decoder.key()?, decoder.expr()?)))?
+                    .into_iter()
+                    .collect::<ObjectFields>(),
+            )),
+            4 => Variant::SpreadArray(SpreadArray::new(self.list(
+                |decoder| match decoder.u8()? {
+                    0 => Ok(ArrayItem::Element(decoder.expr()?)),
+                    1 => Ok(ArrayItem::Spread(decoder.expr()?)),
+                    _ => Err(ArtifactError::Malformed("invalid array item")),
+                },
+            )?)),
+            5 => Variant::SpreadObject(SpreadObject::new(self.list(
+                |decoder| match decoder.u8()? {
+                    0 => Ok(ObjectItem::Field(decoder.key()?, decoder.expr()?)),
+                    1 => Ok(ObjectItem::Spread(decoder.expr()?)),
+                    _ => Err(ArtifactError::Malformed("invalid object item")),
+                },
+            )?)),
+            _ => return Err(ArtifactError::Malformed("invalid container")),
+        };
+        Ok(Container::new(variant))
+    }
+
+    fn assignment_target(&mut self) -> Result<assignment::Target> {
+        let target = match self.u8()? {
+            0 => assignment::Target::Noop,
+            1 => assignment::Target::Internal(self.ident()?, self.path()?),
+            2 => assignment::Target::External(self.target_path()?),
+            3 => assignment::Target::Array(
+                self.list(Self::assignment_target)?,
+                self.option(|decoder| decoder.assignment_target().map(Box::new))?,
+            ),
+            4 => assignment::Target::Object(
+                self.list(|decoder| Ok((decoder.key()?, decoder.assignment_target()?)))?,
+            ),
+            _ => return Err(ArtifactError::Malformed("invalid assignment target")),
+        };
+        Ok(target)
+    }
+
+    fn function_call(&mut self) -> Result<FunctionCall> {
+        let ident = self.string()?;
+        let (function_id, function) = self
+            .fns
+            .iter()
+            .enumerate()
+            .find(|(_, function)| function.identifier() == ident)
+            .ok_or(ArtifactError::UndefinedFunction(ident))?;
+        let span = self.span()?;
+        let abort_on_error = self.bool()?;
+        let closure_fallible = self.bool()?;
+
+        let mut arguments = Vec::new();
+        let mut parameters = Vec::new();
+        let mut arguments_with_unknown_type_validity = Vec::new();
+        let mut list = ArgumentList::default();
+        for _ in 0..self.usize()? {
+            let argument_span = self.span()?;
+            let keyword =
+                self.option(|decoder| Ok(Node::new(decoder.span()?, decoder.ident()?)))?;
+            let expr = Node::new(self.span()?, self.expr()?);
+            let parameter_keyword = self.string()?;
+            let parameter = function
+                .parameters()
+                .iter()
+                .find(|parameter| parameter.keyword == parameter_keyword)
+                .ok_or(ArtifactError::Malformed("unknown parameter"))?;
+            let constant = self.option(Self::value)?;
+            let unknown_type_validity = self.bool()?;
+
+            list.insert(
+                parameter.keyword,
+                constant
+                    .clone()
+                    .map_or_else(|| expr.inner().clone(), Expr::from),
+            );
+            let argument = Node::new(argument_span, FunctionArgument::new(keyword, expr));
+            if unknown_type_validity {
+                arguments_with_unknown_type_validity.push((*parameter, argument.clone()));
+            }
+            parameters.push((parameter.keyword, constant));
+            arguments.push(argument);
+        }
+
+        let closure = self.option(|decoder| {
+            let variables = decoder.list(Self::ident)?;
+            let block = decoder.block()?;
+            let block_type_def = decoder.type_def()?;
+            Ok(Closure::new(variables, block, block_type_def))
+        })?;
+        if let Some(closure) = &closure {
+            list.set_closure(closure.clone());
+        }
+        let state = self.state()?;
+        let type_def = self.type_def()?;
+
+        let mut compile_ctx = FunctionCompileContext::new(span, std::mem::take(self.config));
+        let expr = function.compile(&state, &mut compile_ctx, list);
+        *self.config = compile_ctx.into_config();
+        let expr = expr.map_err(|error| ArtifactError::Compilation {
+            function: function.identifier(),
+            message: error.message(),
+        })?;
+
+        let call = FunctionCall {
+            abort_on_error,
+            expr,
+            arguments_with_unknown_type_validity,
+            closure_fallible,
+            closure,
+            span,
+            ident: function.identifier(),
+            function_id,
+            arguments: Arc::new(arguments),
+            parameters,
+            state: Arc::new(state),
+            warnings: Vec::new(),
+        };
+        if call.type_info(&call.state).result != type_def {
+            return Err(ArtifactError::TypeMismatch(function.identifier()));
+        }
+        Ok(call)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{
+        Category, Context, FunctionExpression, Resolved, TargetValue, TimeZone, compile,
+        compile_with_state, function::Compiled, runtime::Runtime, state::RuntimeState, value::kind,
+    };
+    use crate::value;
+    use crate::value::Secrets;
+
+    const SOURCE: &str = r#"
+        pattern = r'^(?P<user>\w+)@(?P<host>\S+)$'
+        .parsed = parse_regex!(.email, pattern)
+        .tags = map_values(object!(.tags)) -> |value| { upcase!(value) }
+        [.first, { "b": .second }] = [1, { "b": now() > t'2020-01-01T00:00:00Z' }]
+        .sum, err = .count + 1.5
+        .merged = { "a": [1, ...[2, 3]], ...{ "c": null } }
+        if !exists(.missing) { .missing = 8 - 6 } else { abort }
+        .
+    "#;
+
+    fn run(program: &Program) -> (Value, Value) {
+        let mut target = TargetValue {
+            value: value!({ email: "jane@example.com", tags: { a: "x" }, count: 1 }),
+            metadata: value!({}),
+            secrets: Secrets::default(),
+        };
+        let result = Runtime::new(RuntimeState::default())
+            .resolve(&mut target, program, &TimeZone::default())
+            .unwrap();
+        (result, target.value)
+    }
+
+    #[test]
+    fn round_trip() {
+        let fns = crate::stdlib::all();
+        let program = compile(SOURCE, &fns).unwrap().program;
+
+        let artifact = program.to_artifact(&fns);
+        let loaded =
+            Program::from_artifact(&artifact, &fns, &mut CompileConfig::default()).unwrap();
+
+        assert_eq!(loaded.info(), program.info());
+        assert_eq!(
+            loaded.expressions.to_string(),
+            program.expressions.to_string()
+        );
+        assert_eq!(run(&loaded), run(&program));
+        assert_eq!(
+            run(&loaded).1,
+            value!({
+                email: "jane@example.com",
+                tags: { a: "X" },
+                count: 1,
+                parsed: { user: "jane", host: "example.com" },
+                first: 1,
+                second: true,
+                sum: 2.5,
+                merged: { a: [1, 2, 3], c: null },
+                missing: 2,
+            })
+        );
+        assert_eq!(loaded.to_artifact(&fns), artifact);
+    }
+
+    #[test]
+    fn keeps_type_information() {
+        let fns = crate::stdlib::all();
+        let state = TypeState {
+            local: LocalEnv::default(),
+            external: ExternalEnv::new_with_kind(
+                Kind::object(BTreeMap::from([("count".into(), Kind::integer())])),
+                Kind::object(Collection::any()),
+            ),
+        };
+        let source = r#"
+            x = "a"
+            .local = type_def(x)
+            .external = type_def(.count)
+            .closure = map_values({ "b": 1 }) -> |value| { type_def(value) }
+        "#;
+        let program = compile_with_state(source, &fns, &state, CompileConfig::default())
+            .unwrap()
+            .program;
+
+        let artifact = program.to_artifact(&fns);
+        let loaded =
+            Program::from_artifact(&artifact, &fns, &mut CompileConfig::default()).unwrap();
+
+        assert_eq!(run(&loaded), run(&program));
+        assert_eq!(
+            run(&loaded).1,
+            value!({
+                email: "jane@example.com",
+                tags: { a: "x" },
+                count: 1,
+                local: { bytes: true },
+                external: { integer: true },
+                closure: { b: { integer: true } },
+            })
+        );
+        assert_eq!(loaded.to_artifact(&fns), artifact);
+    }
+
+    /// A function returning `null`, with a type definition that doesn't change its signature.
+    #[derive(Debug)]
+    struct Null(Kind);
+
+    #[derive(Debug, Clone)]
+    struct NullFn(Kind);
+
+    impl FunctionExpression for NullFn {
+        fn resolve(&self, _ctx: &mut Context) -> Resolved {
+            Ok(Value::Null)
+        }
+
+        fn type_def(&self, _state: &TypeState) -> TypeDef {
+            TypeDef::from(self.0.clone())
+        }
+    }
+
+    impl Function for Null {
+        fn identifier(&self) -> &'static str {
+            "null"
+        }
+
+        fn usage(&self) -> &'static str {
+            "Returns null."
+        }
+
+        fn category(&self) -> &'static str {
+            Category::Debug.as_ref()
+        }
+
+        fn return_kind(&self) -> u16 {
+            kind::ANY
+        }
+
+        fn examples(&self) -> &'static [crate::compiler::function::Example] {
+            &[]
+        }
+
+        fn parameters(&self) -> &'static [crate::compiler::Parameter] {
+            &[]
+        }
+
+        fn compile(
+            &self,
+            _state: &TypeState,
+            _ctx: &mut FunctionCompileContext,
+            _arguments: ArgumentList,
+        ) -> Compiled {
+            Ok(NullFn(self.0.clone()).as_expr())
+        }
+    }
+
+    #[test]
+    fn rejects_different_type_definition() {
+        let fns: Vec<Box<dyn Function>> = vec![Box::new(Null(Kind::null()))];
+        let artifact = compile(".a = null()", &fns)
+            .unwrap()
+            .program
+            .to_artifact(&fns);
+        let fns: Vec<Box<dyn Function>> = vec![Box::new(Null(Kind::any()))];
+
+        assert_eq!(
+            Program::from_artifact(&artifact, &fns, &mut CompileConfig::default()).unwrap_err(),
+            ArtifactError::TypeMismatch("null")
+        );
+    }
+
+    #[test]
+    fn rejects_different_function_registry() {
+        let mut fns = crate::stdlib::all();
+        let artifact = compile(".a = upcase!(.b)", &fns)
+            .unwrap()
+            .program
+            .to_artifact(&fns);
+        fns.pop();
+
+        assert_eq!(
+            Program::from_artifact(&artifact, &fns, &mut CompileConfig::default()).unwrap_err(),
+            ArtifactError::FunctionRegistry
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_artifacts() {
+        let fns = crate::stdlib::all();
+        let artifact = compile(".a = upcase!(.b)", &fns)
+            .unwrap()
+            .program
+            .to_artifact(&fns);
+        let load = |artifact: &[u8]| {
+            Program::from_artifact(artifact, &fns, &mut CompileConfig::default()).unwrap_err()
+        };
+
+        assert_eq!(load(b"{}"), ArtifactError::InvalidHeader);
+        assert_eq!(
+            load(&[&artifact[..4], &[3, 0], &artifact[6..]].concat()),
+            ArtifactError::FormatVersion(3)
+        );
+        assert!(matches!(
+            load(&artifact[..artifact.len() - 1]),
+            ArtifactError::Malformed(_)
+        ));
+        assert_eq!(
+            load(&[&artifact[..], &[0]].concat()),
+            ArtifactError::Malformed("trailing bytes")
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::thread;
+
+use crate::value::{ObjectMap, Secrets, Value};
+
+use super::runtime::{Runtime, Terminate};
+use super::{Program, TargetValue, TimeZone};
+
+/// Resolves a [`Program`] over batches of events, on multiple threads.
+///
+/// Each batch is split into one contiguous chunk of events per thread, and each thread resolves
+/// its chunk in order with a single [`Runtime`], which is cleared between events.
+///
+/// Programs are usually run to change the event, but the value of the last expression can be the
+/// result instead, as with [`Runtime::resolve`], so each resolved event is returned with that
+/// value, as a [`BatchOutput`].
+#[derive(Debug, Clone, Copy)]
+pub struct BatchExecutor<'a> {
+    program: &'a Program,
+    threads: usize,
+}
+
+/// An event resolved by a [`BatchExecutor`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchOutput {
+    /// The event, as changed by the program.
+    pub event: Value,
+
+    /// The value the program resolved to, which is the value of its last expression.
+    pub value: Value,
+}
+
+impl<'a> BatchExecutor<'a> {
+    /// Create a new executor of `program`, that uses up to `threads` threads per batch.
+    ///
+    /// A thread count of `0` is treated as `1`.
+    #[must_use]
+    pub fn new(program: &'a Program, threads: usize) -> Self {
+        Self {
+            program,
+            threads: threads.max(1),
+        }
+    }
+
+    /// Resolves the program over each of `events`, with empty metadata and no secrets.
+    ///
+    /// Returns, in the order of `events`, each event as changed by the program along with the
+    /// value the program resolved to, or the error the program terminated with.
+    ///
+    /// To resolve a slice of events, pass `events.iter().cloned()`; cloning a [`Value`] is cheap,
+    /// as objects and arrays are shared until changed.
+    ///
+    /// # Panics
+    ///
+    /// If a function panics while resolving an event, the panic is propagated.
+    pub fn resolve(
+        &self,
+        events: impl IntoIterator<Item = Value>,
+        timezone: &TimeZone,
+    ) -> Vec<Result<BatchOutput, Terminate>> {
+        let mut events = events.into_iter().collect::<Vec<_>>();
+        let threads = self.threads.min(events.len());
+        if threads <= 1 {
+            return self.resolve_chunk(&mut events, *timezone);
+        }
+
+        let chunk_size = events.len().div_ceil(threads);
+        thread::scope(|scope| {
+            let workers = events
+                .chunks_mut(chunk_size)
+                .map(|chunk| scope.spawn(move || self.resolve_chunk(chunk, *timezone)))
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        })
+    }
+
+    fn resolve_chunk(
+        &self,
+        events: &mut [Value],
+        timezone: TimeZone,
+    ) -> Vec<Result<BatchOutput, Terminate>> {
+        let mut runtime = Runtime::default();
+
+        events
+            .iter_mut()
+            .map(|event| {
+                let mut target = TargetValue {
+                    value: std::mem::replace(event, Value::Null),
+                    metadata: Value::Object(ObjectMap::new()),
+                    secrets: Secrets::default(),
+                };
+                let result = runtime.resolve(&mut target, self.program, &timezone);
+                runtime.clear();
+                result.map(|value| BatchOutput {
+                    event: target.value,
+                    value,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{compile, runtime::RuntimeResult};
+    use crate::value;
+
+    fn resolve_outputs(
+        source: &str,
+        events: Vec<Value>,
+        threads: usize,
+    ) -> Vec<Result<BatchOutput, Terminate>> {
+        let program = compile(source, &crate::stdlib::all()).unwrap().program;
+        BatchExecutor::new(&program, threads).resolve(events, &TimeZone::default())
+    }
+
+    fn resolve(source: &str, events: Vec<Value>, threads: usize) -> Vec<RuntimeResult> {
+        resolve_outputs(source, events, threads)
+            .into_iter()
+            .map(|output| output.map(|output| output.event))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_input_order() {
+        let events = (0..100).map(|i| value!({ i: i })).collect::<Vec<_>>();
+
+        for threads in [0, 1, 3, 8, 200] {
+            let results = resolve(".double = int!(.i) * 2", events.clone(), threads);
+
+            assert_eq!(
+                results,
+                (0..100)
+                    .map(|i| Ok(value!({ i: i, double: (i * 2) })))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn errors_per_event() {
+        let results = resolve(
+            "if .i == 1 { abort } else { .n = to_int!(.s) }",
+            vec![
+                value!({ i: 0, s: "1" }),
+                value!({ i: 1, s: "2" }),
+                value!({ i: 2, s: "x" }),
+            ],
+            2,
+        );
+
+        assert_eq!(results[0], Ok(value!({ i: 0, s: "1", n: 1 })));
+        assert!(matches!(results[1], Err(Terminate::Abort(_))));
+        assert!(matches!(results[2], Err(Terminate::Error(_))));
+    }
+
+    #[test]
+    fn resolved_values() {
+        let outputs = resolve_outputs(
+            ".seen = true\nint!(.i) + 1",
+            vec![value!({ i: 1 }), value!({ i: 2 })],
+            2,
+        );
+
+        assert_eq!(
+            outputs,
+            [
+                Ok(BatchOutput {
+                    event: value!({ i: 1, seen: true }),
+                    value: value!(2),
+                }),
+                Ok(BatchOutput {
+                    event: value!({ i: 2, seen: true }),
+                    value: value!(3),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_batch() {
+        assert!(resolve(".", Vec::new(), 4).is_empty());
+    }
+}
@@ -22,6 +22,8 @@ pub enum Category {
     Debug,
     /// Enumeration and iteration functions
     Enumerate,
+    /// Event metadata and secret functions
+    Event,
     /// Path manipulation functions
     Path,
     /// Cryptographic functions
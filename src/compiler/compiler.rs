@@ -5,7 +5,8 @@ use crate::compiler::{
     expression::{
         Abort, Array, Assignment, Block, Container, Expr, Expression, FunctionArgument,
         FunctionCall, Group, IfStatement, Literal, Noop, Not, Object, Op, Predicate, Query, Return,
-        Target, Unary, Variable, assignment, function_call, literal, predicate, query,
+        SpreadArray, SpreadObject, Target, Unary, Variable, assignment, function_call, literal,
+        predicate, query, spread,
     },
     parser::ast::RootExpr,
     program::ProgramInfo,
@@ -17,6 +18,8 @@ use crate::path::{OwnedTargetPath, OwnedValuePath};
 use crate::prelude::{ArgumentList, expression};
 use crate::value::Value;
 
+use super::fused::SharedExpressions;
+use super::lineage;
 use super::state::TypeState;
 
 pub(crate) type DiagnosticsMessages = Vec<Box<dyn DiagnosticMessage>>;
@@ -57,6 +60,9 @@ pub struct Compiler<'a> {
     fallible_expression_error: Option<CompilerError>,
 
     config: CompileConfig,
+
+    /// The expressions shared with other programs, when compiling programs to fuse.
+    shared: Option<&'a mut SharedExpressions>,
 }
 
 // TODO: The diagnostic related code is in dire need of refactoring.
@@ -102,6 +108,18 @@ impl<'a> Compiler<'a> {
         ast: crate::parser::Program,
         state: &TypeState,
         config: CompileConfig,
+    ) -> Result<CompilationResult, DiagnosticList> {
+        Self::compile_shared(fns, ast, state, config, None)
+    }
+
+    /// Compiles a given source into the final [`Program`], tracking the expressions it shares
+    /// with the other programs it is fused with in `shared`.
+    pub(crate) fn compile_shared(
+        fns: &'a [Box<dyn Function>],
+        ast: crate::parser::Program,
+        state: &TypeState,
+        config: CompileConfig,
+        shared: Option<&'a mut SharedExpressions>,
     ) -> Result<CompilationResult, DiagnosticList> {
         let initial_state = state.clone();
         let mut state = state.clone();
@@ -116,6 +134,7 @@ impl<'a> Compiler<'a> {
             skip_missing_query_target: vec![],
             fallible_expression_error: None,
             config,
+            shared,
         };
         let expressions = compiler.compile_root_exprs(ast, &mut state);
 
@@ -131,6 +150,7 @@ impl<'a> Compiler<'a> {
             return Err(errors.into());
         }
 
+        let target_lineage = lineage::target_lineage(&expressions);
         let result = CompilationResult {
             program: Program {
                 expressions: Block::new_inline(expressions),
@@ -139,6 +159,7 @@ impl<'a> Compiler<'a> {
                     abortable: compiler.abortable,
                     target_queries: compiler.external_queries,
                     target_assignments: compiler.external_assignments,
+                    target_lineage,
                 },
                 initial_state,
             },
@@ -207,7 +228,10 @@ impl<'a> Compiler<'a> {
             ));
         }
 
-        Some(expr)
+        Some(match &mut self.shared {
+            Some(shared) => shared.share(self.fns, expr),
+            None => expr,
+        })
     }
 
     fn compile_literal(&mut self, node: Node<ast::Literal>, state: &mut TypeState) -> Option<Expr> {
@@ -254,13 +278,15 @@ impl<'a> Compiler<'a> {
         node: Node<ast::Container>,
         state: &mut TypeState,
     ) -> Option<Container> {
-        use ast::Container::{Array, Block, Group, Object};
+        use ast::Container::{Array, Block, Group, Object, SpreadArray, SpreadObject};
 
         let variant = match node.into_inner() {
             Group(node) => self.compile_group(*node, state)?.into(),
             Block(node) => self.compile_block(node, state)?.into(),
             Array(node) => self.compile_array(node, state)?.into(),
             Object(node) => self.compile_object(node, state)?.into(),
+            SpreadArray(node) => self.compile_spread_array(node, state)?.into(),
+            SpreadObject(node) => self.compile_spread_object(node, state)?.into(),
         };
 
         Some(Container::new(variant))
@@ -346,6 +372,49 @@ impl<'a> Compiler<'a> {
         ))
     }
 
+    fn compile_spread_array(
+        &mut self,
+        node: Node<ast::SpreadArray>,
+        state: &mut TypeState,
+    ) -> Option<SpreadArray> {
+        let mut items = vec![];
+        for item in node.into_inner() {
+            let item = match item {
+                ast::ArrayItem::Element(expr) => {
+                    spread::ArrayItem::Element(self.compile_expr(expr, state)?)
+                }
+                ast::ArrayItem::Spread(expr) => {
+                    spread::ArrayItem::Spread(self.compile_expr(expr, state)?)
+                }
+            };
+            items.push(item);
+        }
+
+        Some(SpreadArray::new(items))
+    }
+
+    fn compile_spread_object(
+        &mut self,
+        node: Node<ast::SpreadObject>,
+        state: &mut TypeState,
+    ) -> Option<SpreadObject> {
+        let mut items = vec![];
+        for item in node.into_inner() {
+            let item = match item {
+                ast::ObjectItem::Field(key, expr) => spread::ObjectItem::Field(
+                    key.into_inner().into(),
+                    self.compile_expr(expr, state)?,
+                ),
+                ast::ObjectItem::Spread(expr) => {
+                    spread::ObjectItem::Spread(self.compile_expr(expr, state)?)
+                }
+            };
+            items.push(item);
+        }
+
+        Some(SpreadObject::new(items))
+    }
+
     fn compile_if_statement(
         &mut self,
         node: Node<ast::IfStatement>,
@@ -494,13 +563,16 @@ impl<'a> Compiler<'a> {
         let original_state = state.clone();
 
         let assignment = node.into_inner();
+        let vivify = match &assignment {
+            Single { op, .. } | Infallible { op, .. } => *op == AssignmentOp::Vivify,
+        };
 
         let node = match assignment {
             Single { target, op, expr } => {
                 let span = expr.span();
 
                 match op {
-                    AssignmentOp::Assign => {
+                    AssignmentOp::Assign | AssignmentOp::Vivify => {
                         let expr = self
                             .compile_expr(*expr, state)
                             .map(|expr| Box::new(Node::new(span, expr)))
@@ -521,7 +593,7 @@ impl<'a> Compiler<'a> {
                 let span = expr.span();
 
                 let node = match op {
-                    AssignmentOp::Assign => {
+                    AssignmentOp::Assign | AssignmentOp::Vivify => {
                         let expr = self
                             .compile_expr(*expr, state)
                             .map(|expr| Box::new(Node::new(span, expr)))
@@ -566,6 +638,7 @@ impl<'a> Compiler<'a> {
             state,
             self.fallible_expression_error.as_ref(),
             &self.config,
+            vivify,
         )
         .map_err(|err| self.diagnostics.push(Box::new(err)))
         .ok()?;
@@ -729,10 +802,8 @@ impl<'a> Compiler<'a> {
                 None => None,
                 Some(block) => {
                     let span = block.span();
-                    match self.compile_block_with_type(block, state) {
-                        Some(block_with_type) => Some(Node::new(span, block_with_type)),
-                        None => return None,
-                    }
+                    let block_with_type = self.compile_closure_block(&builder, &block, state)?;
+                    Some(Node::new(span, block_with_type))
                 }
             };
 
@@ -765,6 +836,46 @@ impl<'a> Compiler<'a> {
         function_info.map(|info| info.1)
     }
 
+    /// Compiles the block of a function closure.
+    ///
+    /// If the closure has accumulator variables, the value returned by the
+    /// block is passed back into them, so the block is compiled again with
+    /// the widened variables until their kind includes what the block
+    /// returns. Anything the discarded compilations recorded is dropped.
+    fn compile_closure_block(
+        &mut self,
+        builder: &function_call::Builder<'_>,
+        block: &Node<ast::Block>,
+        state: &mut TypeState,
+    ) -> Option<(Block, TypeDef)> {
+        let mut state_before_block = state.clone();
+        let mut attempt = 0;
+
+        loop {
+            let diagnostics = self.diagnostics.len();
+            let external_queries = self.external_queries.len();
+            let external_assignments = self.external_assignments.len();
+            let skip_missing_query_target = self.skip_missing_query_target.len();
+
+            let block_with_type = self.compile_block_with_type(block.clone(), state)?;
+
+            let mut widened_state = state_before_block.clone();
+            if !builder.widen_accumulators(&block_with_type.1, &mut widened_state, attempt > 0) {
+                return Some(block_with_type);
+            }
+
+            self.diagnostics.truncate(diagnostics);
+            self.external_queries.truncate(external_queries);
+            self.external_assignments.truncate(external_assignments);
+            self.skip_missing_query_target
+                .truncate(skip_missing_query_target);
+
+            state_before_block = widened_state;
+            *state = state_before_block.clone();
+            attempt += 1;
+        }
+    }
+
     fn compile_function_argument(
         &mut self,
         node: Node<ast::FunctionArgument>,
@@ -775,7 +886,12 @@ impl<'a> Compiler<'a> {
             expr: ast_expr,
         } = node.into_inner();
         let span = ast_expr.span();
-        let expr = self.compile_expr(ast_expr, state)?;
+        let expr = match self.compile_expr(ast_expr, state)? {
+            // Functions such as `exists` and `del` use query arguments as paths, so they aren't
+            // passed as shared expressions.
+            Expr::Cached(cached) if matches!(*cached.inner, Expr::Query(_)) => *cached.inner,
+            expr => expr,
+        };
         let node = Node::new(span, expr);
 
         Some(FunctionArgument::new(ident, node))
@@ -853,6 +969,18 @@ impl<'a> Compiler<'a> {
     fn skip_missing_assignment_target(&mut self, target: &ast::AssignmentTarget) {
         let query = match &target {
             ast::AssignmentTarget::Noop => return,
+            ast::AssignmentTarget::Array(elements, rest) => {
+                for target in elements.iter().chain(rest.as_deref()) {
+                    self.skip_missing_assignment_target(target.inner());
+                }
+                return;
+            }
+            ast::AssignmentTarget::Object(fields) => {
+                for (_, target) in fields {
+                    self.skip_missing_assignment_target(target.inner());
+                }
+                return;
+            }
             ast::AssignmentTarget::Query(ast::Query { target, path }) => {
                 (target.clone().into_inner(), path.clone().into_inner())
             }
@@ -6,6 +6,7 @@ pub use abort::Abort;
 pub use array::Array;
 pub use assignment::Assignment;
 pub use block::Block;
+pub use cached::Cached;
 pub use container::{Container, Variant};
 #[allow(clippy::module_name_repetitions)]
 pub use function::FunctionExpression;
@@ -16,11 +17,12 @@ pub use if_statement::IfStatement;
 pub use literal::Literal;
 pub use noop::Noop;
 pub use not::Not;
-pub use object::Object;
+pub use object::{Object, ObjectFields};
 pub use op::Op;
 pub use predicate::Predicate;
 pub use query::{Query, Target};
 pub use r#return::Return;
+pub use spread::{SpreadArray, SpreadObject};
 pub use unary::Unary;
 pub use variable::Variable;
 
@@ -35,6 +37,7 @@ use super::{Context, TypeDef};
 mod abort;
 mod array;
 mod block;
+mod cached;
 mod function_argument;
 mod group;
 mod if_statement;
@@ -44,6 +47,7 @@ mod not;
 mod object;
 mod op;
 mod r#return;
+pub(crate) mod spread;
 pub(crate) mod unary;
 mod variable;
 
@@ -122,23 +126,24 @@ pub enum Expr {
     Unary(Unary),
     Abort(Abort),
     Return(Return),
+    Cached(Cached),
 }
 
 impl Expr {
     pub fn as_str(&self) -> &str {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Cached, Container, FunctionCall, IfStatement, Literal, Noop, Op,
+            Query, Return, Unary, Variable,
         };
-        use container::Variant::{Array, Block, Group, Object};
+        use container::Variant::{Array, Block, Group, Object, SpreadArray, SpreadObject};
 
         match self {
             Literal(..) => "literal",
             Container(v) => match &v.variant {
                 Group(..) => "group",
                 Block(..) => "block",
-                Array(..) => "array",
-                Object(..) => "object",
+                Array(..) | SpreadArray(..) => "array",
+                Object(..) | SpreadObject(..) => "object",
             },
             IfStatement(..) => "if-statement",
             Op(..) => "operation",
@@ -150,6 +155,7 @@ impl Expr {
             Unary(..) => "unary operation",
             Abort(..) => "abort operation",
             Return(..) => "return",
+            Cached(v) => v.inner.as_str(),
         }
     }
 
@@ -230,8 +236,8 @@ impl Expr {
 impl Expression for Expr {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Cached, Container, FunctionCall, IfStatement, Literal, Noop, Op,
+            Query, Return, Unary, Variable,
         };
 
         match self {
@@ -247,13 +253,14 @@ impl Expression for Expr {
             Unary(v) => v.resolve(ctx),
             Abort(v) => v.resolve(ctx),
             Return(v) => v.resolve(ctx),
+            Cached(v) => v.resolve(ctx),
         }
     }
 
     fn resolve_constant(&self, state: &TypeState) -> Option<Value> {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Cached, Container, FunctionCall, IfStatement, Literal, Noop, Op,
+            Query, Return, Unary, Variable,
         };
 
         match self {
@@ -269,13 +276,14 @@ impl Expression for Expr {
             Unary(v) => Expression::resolve_constant(v, state),
             Abort(v) => Expression::resolve_constant(v, state),
             Return(v) => Expression::resolve_constant(v, state),
+            Cached(v) => Expression::resolve_constant(v, state),
         }
     }
 
     fn type_info(&self, state: &TypeState) -> TypeInfo {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Cached, Container, FunctionCall, IfStatement, Literal, Noop, Op,
+            Query, Return, Unary, Variable,
         };
 
         match self {
@@ -291,6 +299,7 @@ impl Expression for Expr {
             Unary(v) => v.type_info(state),
             Abort(v) => v.type_info(state),
             Return(v) => v.type_info(state),
+            Cached(v) => v.type_info(state),
         }
     }
 }
@@ -298,8 +307,8 @@ impl Expression for Expr {
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
-            Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Return, Unary, Variable,
+            Abort, Assignment, Cached, Container, FunctionCall, IfStatement, Literal, Noop, Op,
+            Query, Return, Unary, Variable,
         };
 
         match self {
@@ -315,6 +324,7 @@ impl fmt::Display for Expr {
             Unary(v) => v.fmt(f),
             Abort(v) => v.fmt(f),
             Return(v) => v.fmt(f),
+            Cached(v) => v.fmt(f),
         }
     }
 }
@@ -393,10 +403,14 @@ impl From<Return> for Expr {
     }
 }
 
+impl From<Cached> for Expr {
+    fn from(cached: Cached) -> Self {
+        Expr::Cached(cached)
+    }
+}
+
 impl From<Value> for Expr {
     fn from(value: Value) -> Self {
-        use std::collections::BTreeMap;
-
         use crate::value::Value::{
             Array, Boolean, Bytes, Float, Integer, Null, Object, Regex, Timestamp,
         };
@@ -410,7 +424,7 @@ impl From<Value> for Expr {
                 let object = super::expression::Object::from(
                     v.into_iter()
                         .map(|(k, v)| (k, v.into()))
-                        .collect::<BTreeMap<_, _>>(),
+                        .collect::<super::expression::ObjectFields>(),
                 );
 
                 Container::new(container::Variant::from(object)).into()
@@ -13,8 +13,8 @@ use super::Expr;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Abort {
-    span: Span,
-    message: Option<Box<Expr>>,
+    pub(crate) span: Span,
+    pub(crate) message: Option<Box<Expr>>,
 }
 
 impl Abort {
@@ -1,6 +1,6 @@
 use std::{collections::BTreeMap, fmt, ops::Deref};
 
-use crate::value::Value;
+use crate::value::{Value, ValueArray};
 use crate::{
     compiler::{
         Context, Expression, TypeDef,
@@ -12,7 +12,7 @@ use crate::{
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Array {
-    inner: Vec<Expr>,
+    pub(crate) inner: Vec<Expr>,
 }
 
 impl Array {
@@ -34,7 +34,7 @@ impl Expression for Array {
         self.inner
             .iter()
             .map(|expr| expr.resolve(ctx))
-            .collect::<Result<Vec<_>, _>>()
+            .collect::<Result<ValueArray, _>>()
             .map(Value::Array)
     }
 
@@ -42,7 +42,7 @@ impl Expression for Array {
         self.inner
             .iter()
             .map(|x| x.resolve_constant(state))
-            .collect::<Option<Vec<_>>>()
+            .collect::<Option<ValueArray>>()
             .map(Value::Array)
     }
 
@@ -17,11 +17,16 @@ use crate::compiler::{
 use crate::diagnostic::{DiagnosticMessage, Label, Note};
 use crate::path::{OwnedSegment, OwnedTargetPath};
 use crate::path::{OwnedValuePath, PathPrefix};
-use crate::value::{Kind, Value};
+use crate::value::{KeyString, Kind, Value, ValueArray, kind::Collection};
 
 #[derive(Clone, PartialEq)]
 pub struct Assignment {
-    variant: Variant<Target, Expr>,
+    pub(crate) variant: Variant<Target, Expr>,
+
+    /// Whether this is an auto-vivifying (`?=`) assignment, which replaces
+    /// any parent path segment that isn't a matching container, instead of
+    /// rejecting the assignment at compile-time.
+    pub(crate) vivify: bool,
 }
 
 impl Assignment {
@@ -31,6 +36,7 @@ impl Assignment {
         state: &TypeState,
         fallible_rhs: Option<&CompilerError>,
         config: &CompileConfig,
+        vivify: bool,
     ) -> Result<Self, Error> {
         let (_, variant) = node.take();
 
@@ -73,15 +79,15 @@ impl Assignment {
                 }
 
                 let expr = expr.into_inner();
-                let target = Target::try_from(target.into_inner())?;
-                verify_mutable(&target, config, expr_span, assignment_span)?;
-                verify_overwritable(
-                    &target,
+                let target = compile_target(
+                    target,
                     state,
-                    target_span,
+                    config,
+                    vivify,
+                    &expr,
                     expr_span,
                     assignment_span,
-                    expr.clone(),
+                    assignment_span,
                 )?;
 
                 Variant::Single {
@@ -128,15 +134,15 @@ impl Assignment {
                 // "ok" target takes on the type definition of the value, but is
                 // set to being infallible, as the error will be captured by the
                 // "err" target.
-                let ok = Target::try_from(ok.into_inner())?;
-                verify_mutable(&ok, config, expr_span, ok_span)?;
-                verify_overwritable(
-                    &ok,
+                let ok = compile_target(
+                    ok,
                     state,
-                    ok_span,
+                    config,
+                    vivify,
+                    &expr,
                     expr_span,
+                    ok_span,
                     assignment_span,
-                    expr.clone(),
                 )?;
 
                 let type_def = type_def.infallible();
@@ -144,15 +150,15 @@ impl Assignment {
 
                 // "err" target is assigned `null` or a string containing the
                 // error message.
-                let err = Target::try_from(err.into_inner())?;
-                verify_mutable(&err, config, expr_span, err_span)?;
-                verify_overwritable(
-                    &err,
+                let err = compile_target(
+                    err,
                     state,
-                    err_span,
+                    config,
+                    vivify,
+                    &expr,
                     expr_span,
+                    err_span,
                     assignment_span,
-                    expr.clone(),
                 )?;
 
                 Variant::Infallible {
@@ -164,26 +170,98 @@ impl Assignment {
             }
         };
 
-        Ok(Self { variant })
+        Ok(Self { variant, vivify })
     }
 
     /// Get a list of targets for this assignment.
     ///
     /// For regular assignments, this contains a single target, for infallible
-    /// assignments, it'll contain both the `ok` and `err` target.
+    /// assignments, it'll contain both the `ok` and `err` target. Destructuring
+    /// patterns are flattened into the targets they contain.
     pub(crate) fn targets(&self) -> Vec<Target> {
         let mut targets = Vec::with_capacity(2);
 
         match &self.variant {
-            Variant::Single { target, .. } => targets.push(target.clone()),
+            Variant::Single { target, .. } => target.flatten_into(&mut targets),
             Variant::Infallible { ok, err, .. } => {
-                targets.push(ok.clone());
-                targets.push(err.clone());
+                ok.flatten_into(&mut targets);
+                err.flatten_into(&mut targets);
             }
         }
 
         targets
     }
+
+    fn op(&self) -> &'static str {
+        if self.vivify { "?=" } else { "=" }
+    }
+}
+
+/// Convert the assignment target into a [`Target`], and verify that it can be
+/// assigned to.
+///
+/// Each target nested within a destructuring pattern is verified on its own.
+#[allow(clippy::too_many_arguments)]
+fn compile_target(
+    target: Node<ast::AssignmentTarget>,
+    state: &TypeState,
+    config: &CompileConfig,
+    vivify: bool,
+    expr: &Expr,
+    expr_span: Span,
+    mutation_span: Span,
+    assignment_span: Span,
+) -> Result<Target, Error> {
+    let (target_span, target) = target.take();
+    let mut compile = |target: Node<ast::AssignmentTarget>| {
+        let span = target.span();
+        compile_target(
+            target,
+            state,
+            config,
+            vivify,
+            expr,
+            expr_span,
+            span,
+            assignment_span,
+        )
+    };
+
+    match target {
+        ast::AssignmentTarget::Array(elements, rest) => {
+            let elements = elements
+                .into_iter()
+                .map(&mut compile)
+                .collect::<Result<_, _>>()?;
+            let rest = rest.map(|rest| compile(*rest).map(Box::new)).transpose()?;
+
+            Ok(Target::Array(elements, rest))
+        }
+        ast::AssignmentTarget::Object(fields) => {
+            let fields = fields
+                .into_iter()
+                .map(|(key, target)| Ok((key.into_inner().into(), compile(target)?)))
+                .collect::<Result<_, _>>()?;
+
+            Ok(Target::Object(fields))
+        }
+        target => {
+            let target = Target::try_from(target)?;
+            verify_mutable(&target, config, expr_span, mutation_span)?;
+            if !vivify {
+                verify_overwritable(
+                    &target,
+                    state,
+                    target_span,
+                    expr_span,
+                    assignment_span,
+                    expr.clone(),
+                )?;
+            }
+
+            Ok(target)
+        }
+    }
 }
 
 fn verify_mutable(
@@ -204,7 +282,7 @@ fn verify_mutable(
                 Ok(())
             }
         }
-        Target::Internal(_, _) | Target::Noop => Ok(()),
+        Target::Internal(_, _) | Target::Noop | Target::Array(..) | Target::Object(_) => Ok(()),
     }
 }
 
@@ -223,7 +301,7 @@ fn verify_overwritable(
     let mut path = target.path();
 
     let root_kind = match target {
-        Target::Noop => Kind::any(),
+        Target::Noop | Target::Array(..) | Target::Object(_) => Kind::any(),
         Target::Internal(ident, _) => state
             .local
             .variable(ident)
@@ -283,7 +361,7 @@ fn verify_overwritable(
 
                 format!(".{path}")
             }
-            Target::Noop => unreachable!(),
+            Target::Noop | Target::Array(..) | Target::Object(_) => unreachable!(),
         };
 
         return Err(Error {
@@ -318,9 +396,10 @@ impl fmt::Display for Assignment {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Variant::{Infallible, Single};
 
+        let op = self.op();
         match &self.variant {
-            Single { target, expr } => write!(f, "{target} = {expr}"),
-            Infallible { ok, err, expr, .. } => write!(f, "{ok}, {err} = {expr}"),
+            Single { target, expr } => write!(f, "{target} {op} {expr}"),
+            Infallible { ok, err, expr, .. } => write!(f, "{ok}, {err} {op} {expr}"),
         }
     }
 }
@@ -329,10 +408,11 @@ impl fmt::Debug for Assignment {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Variant::{Infallible, Single};
 
+        let op = self.op();
         match &self.variant {
-            Single { target, expr } => write!(f, "{target:?} = {expr:?}"),
+            Single { target, expr } => write!(f, "{target:?} {op} {expr:?}"),
             Infallible { ok, err, expr, .. } => {
-                write!(f, "Ok({ok:?}), Err({err:?}) = {expr:?}")
+                write!(f, "Ok({ok:?}), Err({err:?}) {op} {expr:?}")
             }
         }
     }
@@ -345,12 +425,46 @@ pub(crate) enum Target {
     Noop,
     Internal(Ident, OwnedValuePath),
     External(OwnedTargetPath),
+
+    /// An array destructuring pattern, with an optional target for the
+    /// remaining elements.
+    Array(Vec<Target>, Option<Box<Target>>),
+
+    /// An object destructuring pattern.
+    Object(Vec<(KeyString, Target)>),
 }
 
 impl Target {
+    #[allow(clippy::cast_possible_wrap)]
     fn insert_type_def(&self, state: &mut TypeState, new_type_def: TypeDef, value: Option<Value>) {
         match self {
             Self::Noop => {}
+            Self::Array(elements, rest) => {
+                for (index, target) in elements.iter().enumerate() {
+                    let path = OwnedValuePath::from(vec![OwnedSegment::Index(index as isize)]);
+                    let type_def = new_type_def.at_path(&path).upgrade_undefined();
+                    let value = value
+                        .as_ref()
+                        .map(|value| value.get(&path).cloned().unwrap_or(Value::Null));
+                    target.insert_type_def(state, type_def, value);
+                }
+
+                if let Some(rest) = rest {
+                    let type_def = rest_type_def(&new_type_def, elements.len());
+                    let value = value.map(|value| rest_value(value, elements.len()));
+                    rest.insert_type_def(state, type_def, value);
+                }
+            }
+            Self::Object(fields) => {
+                for (key, target) in fields {
+                    let path = OwnedValuePath::from(vec![OwnedSegment::Field(key.clone())]);
+                    let type_def = new_type_def.at_path(&path).upgrade_undefined();
+                    let value = value
+                        .as_ref()
+                        .map(|value| value.get(&path).cloned().unwrap_or(Value::Null));
+                    target.insert_type_def(state, type_def, value);
+                }
+            }
             Self::Internal(ident, path) => {
                 let type_def = match state.local.variable(ident) {
                     None => TypeDef::never().with_type_inserted(path, new_type_def),
@@ -385,10 +499,35 @@ impl Target {
     }
 
     fn insert(&self, value: Value, ctx: &mut Context) {
-        use Target::{External, Internal, Noop};
+        use Target::{Array, External, Internal, Noop, Object};
 
         match self {
             Noop => {}
+            Array(elements, rest) => {
+                let len = elements.len();
+                let mut values = match &value {
+                    Value::Array(values) => values.iter(),
+                    _ => [].iter(),
+                };
+
+                for target in elements {
+                    target.insert(values.next().cloned().unwrap_or(Value::Null), ctx);
+                }
+
+                if let Some(rest) = rest {
+                    rest.insert(rest_value(value, len), ctx);
+                }
+            }
+            Object(fields) => {
+                for (key, target) in fields {
+                    let field = match &value {
+                        Value::Object(object) => object.get(key).cloned(),
+                        _ => None,
+                    };
+
+                    target.insert(field.unwrap_or(Value::Null), ctx);
+                }
+            }
             Internal(ident, path) => {
                 // Get the provided path, or else insert into the variable
                 // without any path appended and return early.
@@ -416,29 +555,93 @@ impl Target {
 
     fn path(&self) -> OwnedValuePath {
         match self {
-            Self::Noop => OwnedValuePath::root(),
+            Self::Noop | Self::Array(..) | Self::Object(_) => OwnedValuePath::root(),
             Self::Internal(_, path) => path.clone(),
             Self::External(target_path) => target_path.path.clone(),
         }
     }
+
+    /// Push this target onto the list of targets, or the targets nested
+    /// within it, if it's a destructuring pattern.
+    fn flatten_into(&self, targets: &mut Vec<Target>) {
+        match self {
+            Self::Array(elements, rest) => {
+                for target in elements.iter().chain(rest.as_deref()) {
+                    target.flatten_into(targets);
+                }
+            }
+            Self::Object(fields) => {
+                for (_, target) in fields {
+                    target.flatten_into(targets);
+                }
+            }
+            target => targets.push(target.clone()),
+        }
+    }
+}
+
+/// The elements of the value that remain after the first `skip` elements are
+/// assigned, which is an empty array if the value isn't an array.
+fn rest_value(value: Value, skip: usize) -> Value {
+    match value {
+        Value::Array(values) => values.into_iter().skip(skip).collect(),
+        _ => Value::Array(ValueArray::new()),
+    }
+}
+
+/// The type of [`rest_value`].
+fn rest_type_def(type_def: &TypeDef, skip: usize) -> TypeDef {
+    let collection = type_def
+        .kind()
+        .as_array()
+        .map_or_else(Collection::empty, |array| {
+            let known = array
+                .known()
+                .iter()
+                .filter_map(|(index, kind)| {
+                    let index = index.to_usize().checked_sub(skip)?;
+                    Some((index.into(), kind.clone()))
+                })
+                .collect();
+
+            Collection::from_parts(known, array.unknown_kind())
+        });
+
+    type_def.clone().with_kind(Kind::array(collection))
 }
 
 impl fmt::Display for Target {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Target::{External, Internal, Noop};
+        use Target::{Array, External, Internal, Noop, Object};
 
         match self {
             Noop => f.write_str("_"),
             Internal(ident, path) if path.is_root() => ident.fmt(f),
             Internal(ident, path) => write!(f, "{ident}{path}"),
             External(path) => write!(f, "{path}"),
+            Array(elements, rest) => {
+                let mut elements = elements.iter().map(ToString::to_string).collect::<Vec<_>>();
+                if let Some(rest) = rest {
+                    elements.push(format!("..{rest}"));
+                }
+
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(key, target)| format!(r#""{key}": {target}"#))
+                    .collect::<Vec<_>>();
+
+                write!(f, "{{ {} }}", fields.join(", "))
+            }
         }
     }
 }
 
 impl fmt::Debug for Target {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Target::{External, Internal, Noop};
+        use Target::{Array, External, Internal, Noop, Object};
 
         match self {
             Noop => f.write_str("Noop"),
@@ -450,6 +653,25 @@ impl fmt::Debug for Target {
                 }
             }
             External(path) => write!(f, "External({path})"),
+            Array(elements, rest) => {
+                let mut elements = elements
+                    .iter()
+                    .map(|target| format!("{target:?}"))
+                    .collect::<Vec<_>>();
+                if let Some(rest) = rest {
+                    elements.push(format!("Rest({rest:?})"));
+                }
+
+                write!(f, "Array([{}])", elements.join(", "))
+            }
+            Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(key, target)| format!(r#""{key}": {target:?}"#))
+                    .collect::<Vec<_>>();
+
+                write!(f, "Object({{ {} }})", fields.join(", "))
+            }
         }
     }
 }
@@ -458,10 +680,29 @@ impl TryFrom<ast::AssignmentTarget> for Target {
     type Error = Error;
 
     fn try_from(target: ast::AssignmentTarget) -> Result<Self, Error> {
-        use Target::{External, Internal, Noop};
+        use Target::{Array, External, Internal, Noop, Object};
 
         let target = match target {
             ast::AssignmentTarget::Noop => Noop,
+            ast::AssignmentTarget::Array(elements, rest) => Array(
+                elements
+                    .into_iter()
+                    .map(|target| Target::try_from(target.into_inner()))
+                    .collect::<Result<_, _>>()?,
+                rest.map(|rest| Target::try_from(rest.into_inner()).map(Box::new))
+                    .transpose()?,
+            ),
+            ast::AssignmentTarget::Object(fields) => Object(
+                fields
+                    .into_iter()
+                    .map(|(key, target)| {
+                        Ok((
+                            key.into_inner().into(),
+                            Target::try_from(target.into_inner())?,
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
             ast::AssignmentTarget::Query(query) => {
                 let ast::Query { target, path } = query;
 
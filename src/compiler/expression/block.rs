@@ -13,7 +13,7 @@ pub struct Block {
 
     // false - This is just an inline block of code
     // true - This is a block of code nested in a child scope
-    new_scope: bool,
+    pub(crate) new_scope: bool,
 }
 
 impl Block {
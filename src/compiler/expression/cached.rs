@@ -0,0 +1,54 @@
+use std::fmt;
+
+use crate::compiler::state::{TypeInfo, TypeState};
+use crate::compiler::{
+    Context, Expression,
+    expression::{Expr, Resolved},
+};
+use crate::value::Value;
+
+/// An expression shared between the programs of a [`FusedPrograms`](crate::compiler::FusedPrograms)
+/// unit, that is resolved at most once per event.
+///
+/// The value is stored in a slot of the runtime state the first time it's resolved, and read
+/// from it afterwards. Errors aren't stored, so a failing expression fails every time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cached {
+    pub(crate) slot: usize,
+    pub(crate) inner: Box<Expr>,
+}
+
+impl Cached {
+    pub(crate) fn new(slot: usize, inner: Expr) -> Self {
+        Self {
+            slot,
+            inner: Box::new(inner),
+        }
+    }
+}
+
+impl Expression for Cached {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        if let Some(value) = ctx.state().cached(self.slot) {
+            return Ok(value.clone());
+        }
+
+        let value = self.inner.resolve(ctx)?;
+        ctx.state_mut().insert_cached(self.slot, value.clone());
+        Ok(value)
+    }
+
+    fn resolve_constant(&self, state: &TypeState) -> Option<Value> {
+        self.inner.resolve_constant(state)
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        self.inner.type_info(state)
+    }
+}
+
+impl fmt::Display for Cached {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
@@ -2,7 +2,7 @@ use std::fmt;
 
 use crate::compiler::{
     Context, Expression,
-    expression::{Array, Block, Group, Object, Resolved, Value},
+    expression::{Array, Block, Group, Object, Resolved, SpreadArray, SpreadObject, Value},
     state::{TypeInfo, TypeState},
 };
 
@@ -24,52 +24,62 @@ pub enum Variant {
     Block(Block),
     Array(Array),
     Object(Object),
+    SpreadArray(SpreadArray),
+    SpreadObject(SpreadObject),
 }
 
 impl Expression for Container {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
-        use Variant::{Array, Block, Group, Object};
+        use Variant::{Array, Block, Group, Object, SpreadArray, SpreadObject};
 
         match &self.variant {
             Group(v) => v.resolve(ctx),
             Block(v) => v.resolve(ctx),
             Array(v) => v.resolve(ctx),
             Object(v) => v.resolve(ctx),
+            SpreadArray(v) => v.resolve(ctx),
+            SpreadObject(v) => v.resolve(ctx),
         }
     }
 
     fn resolve_constant(&self, state: &TypeState) -> Option<Value> {
-        use Variant::{Array, Block, Group, Object};
+        use Variant::{Array, Block, Group, Object, SpreadArray, SpreadObject};
 
         match &self.variant {
             Group(v) => v.resolve_constant(state),
             Block(v) => v.resolve_constant(state),
             Array(v) => v.resolve_constant(state),
             Object(v) => v.resolve_constant(state),
+            SpreadArray(v) => v.resolve_constant(state),
+            SpreadObject(v) => v.resolve_constant(state),
         }
     }
 
     fn type_info(&self, state: &TypeState) -> TypeInfo {
-        use Variant::{Array, Block, Group, Object};
+        use Variant::{Array, Block, Group, Object, SpreadArray, SpreadObject};
 
         match &self.variant {
             Group(v) => v.type_info(state),
             Block(v) => v.type_info(state),
             Array(v) => v.type_info(state),
             Object(v) => v.type_info(state),
+            SpreadArray(v) => v.type_info(state),
+            SpreadObject(v) => v.type_info(state),
         }
     }
 }
 
 impl fmt::Display for Container {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Variant::{Array, Block, Group, Object};
+        use Variant::{Array, Block, Group, Object, SpreadArray, SpreadObject};
 
         match &self.variant {
             Group(v) => v.fmt(f),
             Block(v) => v.fmt(f),
             Array(v) => v.fmt(f),
             Object(v) => v.fmt(f),
+            SpreadArray(v) => v.fmt(f),
+            SpreadObject(v) => v.fmt(f),
         }
     }
 }
@@ -97,3 +107,15 @@ impl From<Object> for Variant {
         Variant::Object(object)
     }
 }
+
+impl From<SpreadArray> for Variant {
+    fn from(array: SpreadArray) -> Self {
+        Variant::SpreadArray(array)
+    }
+}
+
+impl From<SpreadObject> for Variant {
+    fn from(object: SpreadObject) -> Self {
+        Variant::SpreadObject(object)
+    }
+}
@@ -5,7 +5,7 @@ use crate::compiler::expression::function_call::Warning::AbortInfallible;
 use crate::compiler::state::{TypeInfo, TypeState};
 use crate::compiler::{
     CompileConfig, Context, Expression, Function, Resolved, Span, TypeDef,
-    expression::{ExpressionError, FunctionArgument, levenstein},
+    expression::{Expr, ExpressionError, FunctionArgument, levenstein},
     function::{
         ArgumentList, Closure, Example, FunctionCompileContext, Parameter,
         closure::{self, VariableKind},
@@ -17,6 +17,7 @@ use crate::compiler::{
 };
 use crate::diagnostic::{DiagnosticMessage, Label, Note, Severity, Urls};
 use crate::prelude::Note::SeeErrorDocs;
+use crate::value::Value;
 
 pub(crate) struct Builder<'a> {
     abort_on_error: bool,
@@ -25,6 +26,7 @@ pub(crate) struct Builder<'a> {
     ident_span: Span,
     function_id: usize,
     arguments: Arc<Vec<Node<FunctionArgument>>>,
+    parameters: Vec<(&'static str, Option<Value>)>,
     closure: Option<(Vec<Ident>, closure::Input)>,
     list: ArgumentList,
     function: &'a dyn Function,
@@ -40,6 +42,54 @@ impl<'a> Builder<'a> {
         &self.list
     }
 
+    /// Widens the kind of the closure's accumulator variables in `state`, so
+    /// that it includes the kind returned by the closure block.
+    ///
+    /// Returns `false` if the variables already include that kind. Otherwise
+    /// the block has to be compiled again, using the widened variables. If
+    /// `to_any` is set, the variables are widened to any kind, to make sure
+    /// this eventually returns `false`.
+    pub(crate) fn widen_accumulators(
+        &self,
+        block_type_def: &TypeDef,
+        state: &mut TypeState,
+        to_any: bool,
+    ) -> bool {
+        let Some((variables, input)) = &self.closure else {
+            return false;
+        };
+        let found_kind = block_type_def
+            .kind()
+            .union(block_type_def.returns().clone());
+
+        let mut widened = false;
+        for (ident, variable) in variables.iter().zip(&input.variables) {
+            if !matches!(variable.kind, VariableKind::Accumulator(_)) {
+                continue;
+            }
+            let Some(details) = state.local.variable(ident) else {
+                continue;
+            };
+            if details.type_def.kind().is_superset(&found_kind).is_ok() {
+                continue;
+            }
+
+            let kind = if to_any {
+                Kind::any()
+            } else {
+                details.type_def.kind().union(found_kind.clone())
+            };
+            let details = Details {
+                type_def: kind.into(),
+                value: None,
+            };
+            state.local.insert_variable(ident.clone(), details);
+            widened = true;
+        }
+
+        widened
+    }
+
     #[allow(clippy::too_many_lines)]
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
@@ -96,6 +146,7 @@ impl<'a> Builder<'a> {
         let mut list = ArgumentList::default();
 
         let mut arguments_with_unknown_type_validity = vec![];
+        let mut parameters = Vec::with_capacity(arguments.len());
         for node in &arguments {
             let (argument_span, argument) = node.clone().take();
 
@@ -157,6 +208,14 @@ impl<'a> Builder<'a> {
                 });
             }
 
+            // Keep the values of arguments that are only known to be constant through the type
+            // state, so that the call can be compiled again without it.
+            let constant = match argument.expr() {
+                Expr::Literal(_) => None,
+                expr => expr.resolve_constant(state_before_function_args),
+            };
+            parameters.push((parameter.keyword, constant));
+
             list.insert(parameter.keyword, argument.into_inner());
         }
 
@@ -192,6 +251,7 @@ impl<'a> Builder<'a> {
             ident_span,
             function_id,
             arguments: Arc::new(arguments),
+            parameters,
             closure,
             list,
             function: function.as_ref(),
@@ -370,6 +430,21 @@ impl<'a> Builder<'a> {
 
                                     (kind.into(), None)
                                 }
+
+                                // The variable kind starts as the kind of the
+                                // given argument. The compiler widens it with
+                                // the kind returned by the closure block, see
+                                // `Builder::widen_accumulators`.
+                                VariableKind::Accumulator(keyword) => {
+                                    let kind = list
+                                        .arguments
+                                        .get(keyword)
+                                        .map_or_else(Kind::any, |expr| {
+                                            expr.type_info(state).result.kind().clone()
+                                        });
+
+                                    (kind.into(), None)
+                                }
                             };
 
                             let details = Details { type_def, value };
@@ -487,6 +562,8 @@ impl<'a> Builder<'a> {
                 ident: self.function.identifier(),
                 function_id: self.function_id,
                 arguments: self.arguments.clone(),
+                parameters: self.parameters,
+                state: Arc::new(state_before_function_args.clone()),
                 warnings,
             },
             error: invalid_argument_error,
@@ -549,13 +626,13 @@ impl<'a> Builder<'a> {
 
 #[derive(Clone)]
 pub struct FunctionCall {
-    abort_on_error: bool,
-    expr: Box<dyn Expression>,
-    arguments_with_unknown_type_validity: Vec<(Parameter, Node<FunctionArgument>)>,
-    closure_fallible: bool,
+    pub(crate) abort_on_error: bool,
+    pub(crate) expr: Box<dyn Expression>,
+    pub(crate) arguments_with_unknown_type_validity: Vec<(Parameter, Node<FunctionArgument>)>,
+    pub(crate) closure_fallible: bool,
     // will be used with: https://github.com/vectordotdev/vector/issues/13782
     #[allow(dead_code)]
-    closure: Option<Closure>,
+    pub(crate) closure: Option<Closure>,
 
     // used for enhancing runtime error messages (using abort-instruction).
     //
@@ -567,8 +644,16 @@ pub struct FunctionCall {
 
     // May be used by the LLVM runtime. If not, it should be removed
     #[allow(dead_code)]
-    function_id: usize,
-    arguments: Arc<Vec<Node<FunctionArgument>>>,
+    pub(crate) function_id: usize,
+    pub(crate) arguments: Arc<Vec<Node<FunctionArgument>>>,
+
+    // The parameter each of the arguments is passed to, and its value if it is known at
+    // compile-time. Used to compile the call again when loading a program from an artifact.
+    pub(crate) parameters: Vec<(&'static str, Option<Value>)>,
+
+    // The type state the function was compiled against. Used to compile the call again when
+    // loading a program from an artifact.
+    pub(crate) state: Arc<TypeState>,
 
     pub(crate) warnings: Vec<Warning>,
 }
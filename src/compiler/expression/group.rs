@@ -8,7 +8,7 @@ use crate::compiler::{
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Group {
-    inner: Box<Expr>,
+    pub(crate) inner: Box<Expr>,
 }
 
 impl Group {
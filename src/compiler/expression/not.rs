@@ -13,7 +13,7 @@ pub(crate) type Result = std::result::Result<Not, Error>;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Not {
-    inner: Box<Expr>,
+    pub(crate) inner: Box<Expr>,
 }
 
 impl Not {
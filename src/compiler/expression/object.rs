@@ -1,6 +1,6 @@
 use std::{collections::BTreeMap, fmt, ops::Deref};
 
-use crate::value::{KeyString, Value};
+use crate::value::{KeyString, ObjectMap, Value};
 use crate::{
     compiler::{
         Context, Expression, TypeDef,
@@ -10,20 +10,34 @@ use crate::{
     value::Kind,
 };
 
+/// The fields of an object literal.
+///
+/// Fields are sorted by key, unless the `preserve_order` feature is enabled, in
+/// which case fields are kept in the order in which they were written.
+#[cfg(not(feature = "preserve_order"))]
+pub type ObjectFields = BTreeMap<KeyString, Expr>;
+
+/// The fields of an object literal.
+///
+/// Fields are sorted by key, unless the `preserve_order` feature is enabled, in
+/// which case fields are kept in the order in which they were written.
+#[cfg(feature = "preserve_order")]
+pub type ObjectFields = indexmap::IndexMap<KeyString, Expr>;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Object {
-    inner: BTreeMap<KeyString, Expr>,
+    pub(crate) inner: ObjectFields,
 }
 
 impl Object {
     #[must_use]
-    pub fn new(inner: BTreeMap<KeyString, Expr>) -> Self {
+    pub fn new(inner: ObjectFields) -> Self {
         Self { inner }
     }
 }
 
 impl Deref for Object {
-    type Target = BTreeMap<KeyString, Expr>;
+    type Target = ObjectFields;
 
     fn deref(&self) -> &Self::Target {
         &self.inner
@@ -35,7 +49,7 @@ impl Expression for Object {
         self.inner
             .iter()
             .map(|(key, expr)| expr.resolve(ctx).map(|v| (key.clone(), v)))
-            .collect::<Result<BTreeMap<_, _>, _>>()
+            .collect::<Result<ObjectMap, _>>()
             .map(Value::Object)
     }
 
@@ -43,7 +57,7 @@ impl Expression for Object {
         self.inner
             .iter()
             .map(|(key, expr)| expr.resolve_constant(state).map(|v| (key.clone(), v)))
-            .collect::<Option<BTreeMap<_, _>>>()
+            .collect::<Option<ObjectMap>>()
             .map(Value::Object)
     }
 
@@ -97,8 +111,39 @@ impl fmt::Display for Object {
     }
 }
 
-impl From<BTreeMap<KeyString, Expr>> for Object {
-    fn from(inner: BTreeMap<KeyString, Expr>) -> Self {
+impl From<ObjectFields> for Object {
+    fn from(inner: ObjectFields) -> Self {
         Self { inner }
     }
 }
+
+#[cfg(all(test, feature = "preserve_order"))]
+mod tests {
+    use crate::compiler::{TargetValue, TimeZone, compile, runtime::Runtime};
+    use crate::value;
+    use crate::value::Secrets;
+
+    #[test]
+    fn fields_keep_source_order() {
+        let program = compile(
+            r#"{ "zebra": 1, "apple": { "y": true, "x": null }, "mango": 2 }"#,
+            &[],
+        )
+        .unwrap()
+        .program;
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::default(),
+        };
+
+        let value = Runtime::default()
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"zebra":1,"apple":{"y":true,"x":null},"mango":2}"#
+        );
+    }
+}
@@ -15,7 +15,7 @@ pub(crate) type Result = std::result::Result<Predicate, Error>;
 
 #[derive(Clone, PartialEq)]
 pub struct Predicate {
-    inner: Block,
+    pub(crate) inner: Block,
 }
 
 impl Predicate {
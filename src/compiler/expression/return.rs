@@ -12,8 +12,8 @@ use super::{Expr, ExpressionError};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Return {
-    span: Span,
-    expr: Box<Expr>,
+    pub(crate) span: Span,
+    pub(crate) expr: Box<Expr>,
 }
 
 impl Return {
@@ -0,0 +1,274 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::compiler::{
+    Context, Expression, TypeDef,
+    expression::{Expr, Resolved},
+    state::{TypeInfo, TypeState},
+    value::VrlValueConvert,
+};
+use crate::value::{
+    KeyString, Kind, ObjectMap, Value,
+    kind::{Collection, Field, Index},
+};
+
+/// An item of an array literal containing spreads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrayItem {
+    Element(Expr),
+    Spread(Expr),
+}
+
+/// An array literal containing at least one `...` spread.
+///
+/// The elements of each spread array are inserted in place of the spread,
+/// so `[0, ...[1, 2], 3]` resolves to `[0, 1, 2, 3]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadArray {
+    pub(crate) inner: Vec<ArrayItem>,
+}
+
+impl SpreadArray {
+    pub(crate) fn new(inner: Vec<ArrayItem>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Expression for SpreadArray {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut array = Vec::with_capacity(self.inner.len());
+
+        for item in &self.inner {
+            match item {
+                ArrayItem::Element(expr) => array.push(expr.resolve(ctx)?),
+                ArrayItem::Spread(expr) => array.extend(expr.resolve(ctx)?.try_array()?),
+            }
+        }
+
+        Ok(Value::from(array))
+    }
+
+    fn resolve_constant(&self, state: &TypeState) -> Option<Value> {
+        let mut array = Vec::with_capacity(self.inner.len());
+
+        for item in &self.inner {
+            match item {
+                ArrayItem::Element(expr) => array.push(expr.resolve_constant(state)?),
+                ArrayItem::Spread(expr) => {
+                    array.extend(expr.resolve_constant(state)?.try_array().ok()?);
+                }
+            }
+        }
+
+        Some(Value::from(array))
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        let mut state = state.clone();
+        let mut fallible = false;
+        let mut returns = Kind::never();
+
+        // The index of the next element, as long as the length of all
+        // preceding spread arrays is known.
+        let mut next_index = Some(0);
+        let mut known = BTreeMap::new();
+        let mut unknown = Kind::undefined();
+
+        for item in &self.inner {
+            let (expr, is_spread) = match item {
+                ArrayItem::Element(expr) => (expr, false),
+                ArrayItem::Spread(expr) => (expr, true),
+            };
+
+            let type_def = expr.apply_type_info(&mut state).upgrade_undefined();
+            returns = returns.union(type_def.returns().clone());
+
+            // Spreading anything but an array fails at runtime.
+            fallible |= type_def.is_fallible() || (is_spread && !type_def.is_array());
+
+            // If any expression aborts, the entire array aborts
+            if type_def.is_never() {
+                return TypeInfo::new(
+                    state,
+                    TypeDef::never()
+                        .maybe_fallible(fallible)
+                        .with_returns(returns),
+                );
+            }
+
+            if !is_spread {
+                match next_index {
+                    Some(index) => {
+                        known.insert(Index::from(index), type_def.into());
+                        next_index = Some(index + 1);
+                    }
+                    None => unknown = unknown.union(type_def.into()),
+                }
+                continue;
+            }
+
+            let array = type_def
+                .kind()
+                .as_array()
+                .cloned()
+                .unwrap_or_else(Collection::any);
+
+            if let (Some(index), Some(length)) = (next_index, array.exact_length()) {
+                for (i, kind) in array.known() {
+                    known.insert(Index::from(index + i.to_usize()), kind.clone());
+                }
+                next_index = Some(index + length);
+            } else {
+                // Once the length is unknown, so is the position of all
+                // following elements.
+                unknown = unknown.union(array.reduced_kind());
+                next_index = None;
+            }
+        }
+
+        TypeInfo::new(
+            state,
+            TypeDef::array(Collection::from_parts(known, unknown))
+                .maybe_fallible(fallible)
+                .with_returns(returns),
+        )
+    }
+}
+
+impl fmt::Display for SpreadArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items = self
+            .inner
+            .iter()
+            .map(|item| match item {
+                ArrayItem::Element(expr) => expr.to_string(),
+                ArrayItem::Spread(expr) => format!("...{expr}"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "[{items}]")
+    }
+}
+
+/// An item of an object literal containing spreads.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectItem {
+    Field(KeyString, Expr),
+    Spread(Expr),
+}
+
+/// An object literal containing at least one `...` spread.
+///
+/// Items are applied in order, with later items overwriting the fields of
+/// earlier ones. This matches a shallow `merge`, so `{ ...a, "b": 1 }` is
+/// equivalent to `merge(a, { "b": 1 })`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadObject {
+    pub(crate) inner: Vec<ObjectItem>,
+}
+
+impl SpreadObject {
+    pub(crate) fn new(inner: Vec<ObjectItem>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Expression for SpreadObject {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut object = ObjectMap::new();
+
+        for item in &self.inner {
+            match item {
+                ObjectItem::Field(key, expr) => {
+                    object.insert(key.clone(), expr.resolve(ctx)?);
+                }
+                ObjectItem::Spread(expr) => object.extend(expr.resolve(ctx)?.try_object()?),
+            }
+        }
+
+        Ok(Value::Object(object))
+    }
+
+    fn resolve_constant(&self, state: &TypeState) -> Option<Value> {
+        let mut object = ObjectMap::new();
+
+        for item in &self.inner {
+            match item {
+                ObjectItem::Field(key, expr) => {
+                    object.insert(key.clone(), expr.resolve_constant(state)?);
+                }
+                ObjectItem::Spread(expr) => {
+                    object.extend(expr.resolve_constant(state)?.try_object().ok()?);
+                }
+            }
+        }
+
+        Some(Value::Object(object))
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        let mut state = state.clone();
+        let mut fallible = false;
+        let mut returns = Kind::never();
+        let mut object = Collection::<Field>::empty();
+
+        for item in &self.inner {
+            let expr = match item {
+                ObjectItem::Field(_, expr) | ObjectItem::Spread(expr) => expr,
+            };
+
+            let type_def = expr.apply_type_info(&mut state).upgrade_undefined();
+            returns = returns.union(type_def.returns().clone());
+
+            // Spreading anything but an object fails at runtime.
+            fallible |= type_def.is_fallible()
+                || (matches!(item, ObjectItem::Spread(_)) && !type_def.is_object());
+
+            // If any expression aborts, the entire object aborts
+            if type_def.is_never() {
+                return TypeInfo::new(
+                    state,
+                    TypeDef::never()
+                        .maybe_fallible(fallible)
+                        .with_returns(returns),
+                );
+            }
+
+            let other = match item {
+                ObjectItem::Field(key, _) => {
+                    BTreeMap::from([(Field::from(key.clone()), type_def.into())]).into()
+                }
+                ObjectItem::Spread(_) => type_def
+                    .kind()
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_else(Collection::any),
+            };
+
+            object.merge(other, true);
+        }
+
+        TypeInfo::new(
+            state,
+            TypeDef::object(object)
+                .maybe_fallible(fallible)
+                .with_returns(returns),
+        )
+    }
+}
+
+impl fmt::Display for SpreadObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items = self
+            .inner
+            .iter()
+            .map(|item| match item {
+                ObjectItem::Field(key, expr) => format!(r#""{key}": {expr}"#),
+                ObjectItem::Spread(expr) => format!("...{expr}"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "{{ {items} }}")
+    }
+}
@@ -8,7 +8,7 @@ use crate::compiler::{
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Unary {
-    variant: Variant,
+    pub(crate) variant: Variant,
 }
 
 impl Unary {
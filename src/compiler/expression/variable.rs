@@ -12,7 +12,7 @@ use crate::compiler::{
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Variable {
-    ident: Ident,
+    pub(crate) ident: Ident,
 }
 
 impl Variable {
@@ -4,15 +4,12 @@ pub mod closure;
 use crate::diagnostic::{DiagnosticMessage, Label, Note};
 use crate::parser::ast::Ident;
 use crate::path::OwnedTargetPath;
-use crate::value::{KeyString, Value, kind::Collection};
-use std::{
-    collections::{BTreeMap, HashMap},
-    fmt,
-};
+use crate::value::{Value, kind::Collection};
+use std::{collections::HashMap, fmt};
 
 use super::{
     CompileConfig, Span, TypeDef,
-    expression::{Block, Container, Expr, Expression, container::Variant},
+    expression::{Block, Container, Expr, Expression, ObjectFields, container::Variant},
     state::TypeState,
     value::{Kind, kind},
 };
@@ -71,6 +68,13 @@ pub trait Function: Send + Sync + fmt::Debug {
         true
     }
 
+    /// Whether a function always returns the same value for the same arguments. Functions that
+    /// return random values, the current time or the response to a network request aren't
+    /// deterministic.
+    fn deterministic(&self) -> bool {
+        true
+    }
+
     /// One or more examples demonstrating usage of the function in VRL source
     /// code.
     fn examples(&self) -> &'static [Example];
@@ -461,10 +465,7 @@ impl ArgumentList {
         Ok(required(self.optional_regex(keyword, state)?))
     }
 
-    pub fn optional_object(
-        &self,
-        keyword: &'static str,
-    ) -> Result<Option<BTreeMap<KeyString, Expr>>, Error> {
+    pub fn optional_object(&self, keyword: &'static str) -> Result<Option<ObjectFields>, Error> {
         self.optional_expr(keyword)
             .map(|expr| match expr {
                 Expr::Container(Container {
@@ -479,10 +480,7 @@ impl ArgumentList {
             .transpose()
     }
 
-    pub fn required_object(
-        &self,
-        keyword: &'static str,
-    ) -> Result<BTreeMap<KeyString, Expr>, Error> {
+    pub fn required_object(&self, keyword: &'static str) -> Result<ObjectFields, Error> {
         Ok(required(self.optional_object(keyword)?))
     }
 
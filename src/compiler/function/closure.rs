@@ -92,6 +92,14 @@ pub enum VariableKind {
     /// a `Value::bytes()`, if it's known to be exactly an array, it is
     /// a `Value::integer()`, otherwise it is one of the two.
     TargetInnerKey,
+
+    /// The variable [`Kind`] is inferred from the argument of the given
+    /// parameter, and from the kind returned by the closure block, since that
+    /// value is passed back into the variable on the next run.
+    ///
+    /// For example, in `reduce`, the accumulator starts as the `initial`
+    /// argument, and then holds whatever the closure returned.
+    Accumulator(&'static str),
 }
 
 /// The output type required by the closure block.
@@ -219,6 +227,35 @@ where
         Ok(value)
     }
 
+    /// Run the closure to completion, given the provided accumulator/value
+    /// pair, and the runtime context.
+    ///
+    /// The accumulator is moved into the closure, and the return value of the
+    /// closure is expected to be used as the accumulator for the next run.
+    pub fn run_accumulator_value(
+        &self,
+        ctx: &mut Context,
+        accumulator: Value,
+        value: &Value,
+    ) -> Result<Value, ExpressionError> {
+        // TODO: we need to allow `LocalEnv` to take a mutable reference to
+        // values, instead of owning them.
+        let cloned_value = value.clone();
+
+        let accumulator_ident = self.ident(0);
+        let value_ident = self.ident(1);
+
+        let old_accumulator = insert(ctx.state_mut(), accumulator_ident, accumulator);
+        let old_value = insert(ctx.state_mut(), value_ident, cloned_value);
+
+        let value = (self.runner)(ctx)?;
+
+        cleanup(ctx.state_mut(), accumulator_ident, old_accumulator);
+        cleanup(ctx.state_mut(), value_ident, old_value);
+
+        Ok(value)
+    }
+
     /// Run the closure to completion, given the provided key, and the runtime
     /// context.
     ///
@@ -0,0 +1,476 @@
+//! Fused execution of many programs over one event.
+//!
+//! Programs are compiled twice. The first pass finds the expressions that can be shared: those
+//! that only depend on the event, such as queries of the event, operations on them, and calls to
+//! pure and deterministic functions without closures. The second pass compiles the expressions
+//! that occur more than once into [`Cached`] expressions, which are resolved at most once per
+//! event. Expressions are shared if they are structurally equal.
+//!
+//! Programs that can change the event or its secrets, by assigning to them or calling an impure
+//! function such as `del` or `set_secret`, don't share any expressions, and clear the shared
+//! values once they ran, since those could be out of date.
+
+use std::collections::HashMap;
+
+use crate::compiler::{
+    CompileConfig, Compiler, Function, Program, TypeState,
+    expression::{Cached, Expr, FunctionCall, Literal, Variant, query, unary},
+};
+use crate::diagnostic::DiagnosticList;
+use crate::parser::{ast::Opcode, parse};
+use crate::path::{OwnedTargetPath, OwnedValuePath};
+use crate::value::{KeyString, Value};
+
+/// A set of programs compiled into one unit, that share the expressions they have in common.
+///
+/// Resolve it with [`Runtime::resolve_fused`](crate::compiler::runtime::Runtime::resolve_fused).
+#[derive(Debug, Clone)]
+pub struct FusedPrograms {
+    pub(crate) programs: Vec<FusedProgram>,
+    shared: usize,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FusedProgram {
+    pub(crate) program: Program,
+
+    /// Whether the program can change the event, which makes the shared values out of date.
+    pub(crate) mutates: bool,
+}
+
+impl FusedPrograms {
+    /// Compiles `sources` into one unit, sharing the expressions they have in common.
+    ///
+    /// Compilation warnings are discarded.
+    ///
+    /// # Errors
+    ///
+    /// If a program fails to compile, its index in `sources` is returned with its diagnostics.
+    #[allow(clippy::mutable_key_type)] // false positive due to bytes::Bytes
+    pub fn compile<S: AsRef<str>>(
+        sources: &[S],
+        fns: &[Box<dyn Function>],
+        state: &TypeState,
+        mut config: CompileConfig,
+    ) -> Result<Self, (usize, DiagnosticList)> {
+        let mut compiled = Vec::with_capacity(sources.len());
+        let mut counts = HashMap::new();
+        let mut keys = Vec::new();
+        for (index, source) in sources.iter().enumerate() {
+            let ast = parse(source.as_ref())
+                .map_err(|error| (index, DiagnosticList::from(vec![Box::new(error) as Box<_>])))?;
+
+            let mut shared = SharedExpressions::counting();
+            let result =
+                Compiler::compile_shared(fns, ast.clone(), state, config, Some(&mut shared))
+                    .map_err(|diagnostics| (index, diagnostics))?;
+            config = result.config;
+
+            let mutates = shared.impure || !result.program.info.target_assignments.is_empty();
+            if !mutates {
+                for key in shared.keys {
+                    let count = counts.entry(key.clone()).or_insert(0);
+                    if *count == 0 {
+                        keys.push(key);
+                    }
+                    *count += 1;
+                }
+            }
+            compiled.push((ast, result.program, mutates));
+        }
+
+        let mut shared = SharedExpressions::sharing(
+            keys.into_iter()
+                .filter(|key| counts[key] > 1)
+                .enumerate()
+                .map(|(slot, key)| (key, slot))
+                .collect(),
+        );
+        let mut programs = Vec::with_capacity(compiled.len());
+        for (index, (ast, program, mutates)) in compiled.into_iter().enumerate() {
+            let program = if mutates || shared.slots.is_empty() {
+                program
+            } else {
+                let result = Compiler::compile_shared(fns, ast, state, config, Some(&mut shared))
+                    .map_err(|diagnostics| (index, diagnostics))?;
+                config = result.config;
+                result.program
+            };
+            programs.push(FusedProgram { program, mutates });
+        }
+
+        Ok(Self {
+            programs,
+            shared: shared.slots.len(),
+        })
+    }
+
+    /// The fused programs, in the order they were compiled in.
+    pub fn programs(&self) -> impl Iterator<Item = &Program> {
+        self.programs.iter().map(|fused| &fused.program)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.programs.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.programs.is_empty()
+    }
+
+    /// The number of distinct expressions shared between the programs.
+    #[must_use]
+    pub fn shared_expressions(&self) -> usize {
+        self.shared
+    }
+}
+
+/// The expressions shared between programs being fused.
+pub(crate) struct SharedExpressions {
+    /// Whether the shareable expressions are being counted, rather than shared.
+    counting: bool,
+
+    /// The keys of the shareable expressions compiled, when counting them.
+    keys: Vec<Key>,
+
+    /// The cache slot of each shared expression, by key.
+    slots: HashMap<Key, usize>,
+
+    /// Whether an impure function call was compiled.
+    impure: bool,
+}
+
+#[allow(clippy::mutable_key_type)] // false positive due to bytes::Bytes
+impl SharedExpressions {
+    fn counting() -> Self {
+        Self {
+            counting: true,
+            keys: Vec::new(),
+            slots: HashMap::new(),
+            impure: false,
+        }
+    }
+
+    fn sharing(slots: HashMap<Key, usize>) -> Self {
+        Self {
+            counting: false,
+            keys: Vec::new(),
+            slots,
+            impure: false,
+        }
+    }
+
+    /// Records the compiled `expr`, and returns it as a [`Cached`] expression if it is shared.
+    pub(crate) fn share(&mut self, fns: &[Box<dyn Function>], expr: Expr) -> Expr {
+        if let Expr::FunctionCall(call) = &expr
+            && !is_pure(fns, call)
+        {
+            self.impure = true;
+        }
+
+        // Literals and containers are cheaper to resolve again than to share.
+        if !matches!(
+            expr,
+            Expr::Query(_) | Expr::Op(_) | Expr::FunctionCall(_) | Expr::Unary(_)
+        ) {
+            return expr;
+        }
+        let Some(key) = key(fns, &expr) else {
+            return expr;
+        };
+
+        if self.counting {
+            self.keys.push(key);
+            return expr;
+        }
+        match self.slots.get(&key) {
+            Some(slot) => Cached::new(*slot, expr).into(),
+            None => expr,
+        }
+    }
+}
+
+fn is_pure(fns: &[Box<dyn Function>], call: &FunctionCall) -> bool {
+    fns.get(call.function_id)
+        .is_some_and(|function| function.pure())
+}
+
+fn is_deterministic(fns: &[Box<dyn Function>], call: &FunctionCall) -> bool {
+    fns.get(call.function_id)
+        .is_some_and(|function| function.deterministic())
+}
+
+/// The structure of a shareable expression. Expressions with equal keys resolve to the same
+/// value for the same event.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Key {
+    Literal(Value),
+    /// A float literal, by its bits, so that `0.0` and `-0.0` are distinct.
+    Float(u64),
+    Array(Vec<Key>),
+    Object(Vec<(KeyString, Key)>),
+    Op(Box<Key>, Opcode, Box<Key>),
+    Path(OwnedTargetPath),
+    Query(Box<Key>, OwnedValuePath),
+    FunctionCall {
+        function_id: usize,
+        abort_on_error: bool,
+        arguments: Vec<(&'static str, Key)>,
+    },
+    Not(Box<Key>),
+}
+
+/// Returns the key of `expr`, or `None` if `expr` depends on more than the event.
+fn key(fns: &[Box<dyn Function>], expr: &Expr) -> Option<Key> {
+    let key = match expr {
+        Expr::Literal(Literal::Float(float)) => Key::Float(float.into_inner().to_bits()),
+        Expr::Literal(literal) => Key::Literal(literal.to_value()),
+        Expr::Container(container) => match &container.variant {
+            Variant::Group(group) => key(fns, &group.inner)?,
+            Variant::Array(array) => Key::Array(
+                array
+                    .inner
+                    .iter()
+                    .map(|expr| key(fns, expr))
+                    .collect::<Option<_>>()?,
+            ),
+            Variant::Object(object) => Key::Object(
+                object
+                    .inner
+                    .iter()
+                    .map(|(field, expr)| Some((field.clone(), key(fns, expr)?)))
+                    .collect::<Option<_>>()?,
+            ),
+            Variant::Block(_) | Variant::SpreadArray(_) | Variant::SpreadObject(_) => {
+                return None;
+            }
+        },
+        Expr::Op(op) => Key::Op(
+            Box::new(key(fns, &op.lhs)?),
+            op.opcode,
+            Box::new(key(fns, &op.rhs)?),
+        ),
+        Expr::Query(query) => match query.target() {
+            query::Target::External(prefix) => Key::Path(OwnedTargetPath {
+                prefix: *prefix,
+                path: query.path().clone(),
+            }),
+            query::Target::FunctionCall(call) => Key::Query(
+                Box::new(function_call_key(fns, call)?),
+                query.path().clone(),
+            ),
+            query::Target::Container(container) => Key::Query(
+                Box::new(key(fns, &Expr::Container(container.clone()))?),
+                query.path().clone(),
+            ),
+            query::Target::Internal(_) => return None,
+        },
+        Expr::FunctionCall(call) => function_call_key(fns, call)?,
+        Expr::Unary(unary) => match &unary.variant {
+            unary::Variant::Not(not) => Key::Not(Box::new(key(fns, &not.inner)?)),
+        },
+        Expr::Cached(cached) => key(fns, &cached.inner)?,
+        Expr::IfStatement(_)
+        | Expr::Assignment(_)
+        | Expr::Variable(_)
+        | Expr::Noop(_)
+        | Expr::Abort(_)
+        | Expr::Return(_) => return None,
+    };
+    Some(key)
+}
+
+fn function_call_key(fns: &[Box<dyn Function>], call: &FunctionCall) -> Option<Key> {
+    if call.closure.is_some() || !is_pure(fns, call) || !is_deterministic(fns, call) {
+        return None;
+    }
+
+    let arguments = call
+        .arguments
+        .iter()
+        .zip(&call.parameters)
+        .map(|(argument, (keyword, _))| Some((*keyword, key(fns, argument.expr())?)))
+        .collect::<Option<_>>()?;
+    Some(Key::FunctionCall {
+        function_id: call.function_id,
+        abort_on_error: call.abort_on_error,
+        arguments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{
+        TargetValue, TimeZone, compile,
+        runtime::{Runtime, RuntimeResult},
+    };
+    use crate::value;
+    use crate::value::{Secrets, Value};
+
+    fn target(value: Value) -> TargetValue {
+        TargetValue {
+            value,
+            metadata: value!({}),
+            secrets: Secrets::default(),
+        }
+    }
+
+    fn fuse(sources: &[&str]) -> FusedPrograms {
+        FusedPrograms::compile(
+            sources,
+            &crate::stdlib::all(),
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .unwrap()
+    }
+
+    fn resolve_separately(sources: &[&str], event: Value) -> (Vec<RuntimeResult>, Value) {
+        let fns = crate::stdlib::all();
+        let mut target = target(event);
+        let mut runtime = Runtime::default();
+        let results = sources
+            .iter()
+            .map(|source| {
+                let program = compile(source, &fns).unwrap().program;
+                let result = runtime.resolve(&mut target, &program, &TimeZone::default());
+                runtime.clear();
+                result
+            })
+            .collect();
+        (results, target.value)
+    }
+
+    fn resolve_fused(sources: &[&str], event: Value) -> (Vec<RuntimeResult>, Value) {
+        let mut target = target(event);
+        let mut runtime = Runtime::default();
+        let results = runtime.resolve_fused(&mut target, &fuse(sources), &TimeZone::default());
+        assert!(runtime.is_empty());
+        (results, target.value)
+    }
+
+    #[test]
+    fn same_results_as_separate_programs() {
+        let sources = [
+            ".status == 200",
+            r#".status == 200 && contains(string!(.message), "error")"#,
+            r#"contains(string!(.message), "error") || exists(.user)"#,
+            r#"x = string!(.message); contains(x, "error")"#,
+            "!exists(.user)",
+            r#"upcase!(.missing) == "A""#,
+            "to_int!(.status) > 100",
+        ];
+
+        for event in [
+            value!({ status: 200, message: "an error" }),
+            value!({ status: 404, message: "fine", user: "jane" }),
+            value!({ status: "200", message: 1 }),
+        ] {
+            assert_eq!(
+                resolve_fused(&sources, event.clone()),
+                resolve_separately(&sources, event)
+            );
+        }
+    }
+
+    #[test]
+    fn shares_common_expressions() {
+        let fused = fuse(&[".a == 1", ".a == 1 || .b == 2", "exists(.a)", "x = .a; x"]);
+
+        // `.a` and `.a == 1`.
+        assert_eq!(fused.shared_expressions(), 2);
+        assert_eq!(fused.len(), 4);
+        assert!(
+            fused
+                .programs()
+                .all(|program| !program.info().target_queries.is_empty())
+        );
+    }
+
+    #[test]
+    fn programs_that_change_the_event() {
+        let sources = [
+            ".a == 1",
+            ".a = 2\ntrue",
+            ".a == 1",
+            "del(.a)\ntrue",
+            ".a == 1",
+        ];
+        let (results, event) = resolve_fused(&sources, value!({ a: 1 }));
+
+        assert_eq!(
+            results,
+            [true, true, false, true, false].map(|result| Ok(Value::from(result)))
+        );
+        assert_eq!(event, value!({}));
+        assert_eq!(fuse(&sources).shared_expressions(), 2);
+    }
+
+    #[test]
+    fn programs_that_change_secrets() {
+        let sources = [
+            r#"get_secret("key")"#,
+            r#"set_secret("key", "value")"#,
+            r#"get_secret("key")"#,
+            r#"remove_secret("key")"#,
+            r#"get_secret("key")"#,
+        ];
+        let (results, _) = resolve_fused(&sources, value!({}));
+
+        assert_eq!(results, resolve_separately(&sources, value!({})).0);
+        assert_eq!(results[2], Ok(Value::from("value")));
+    }
+
+    #[test]
+    fn nondeterministic_functions_are_not_shared() {
+        let sources = [
+            "uuid_v4()",
+            "uuid_v4()",
+            "now()",
+            "now()",
+            "random_int(0, 1000)",
+            "random_int(0, 1000)",
+        ];
+        let (results, _) = resolve_fused(&sources, value!({}));
+
+        assert_eq!(fuse(&sources).shared_expressions(), 0);
+        assert_ne!(results[0], results[1]);
+    }
+
+    #[test]
+    fn network_functions_are_not_shared() {
+        let fused = fuse(&[
+            "reverse_dns!(.ip)",
+            "reverse_dns!(.ip)",
+            r#"dns_lookup!(.host, "A")"#,
+            r#"dns_lookup!(.host, "A")"#,
+        ]);
+
+        // `.ip` and `.host`.
+        assert_eq!(fused.shared_expressions(), 2);
+    }
+
+    #[test]
+    fn shares_structurally_equal_expressions() {
+        // `.a` is shared, but the float literals only print alike.
+        assert_eq!(fuse(&[".a == 0.0", ".a == -0.0"]).shared_expressions(), 1);
+        assert_eq!(fuse(&[".a == 1", ".a == 1.0"]).shared_expressions(), 1);
+        assert_eq!(fuse(&[".a == 1", "(.a) == 1"]).shared_expressions(), 2);
+    }
+
+    #[test]
+    fn compilation_error() {
+        let (index, _) = FusedPrograms::compile(
+            &[".a", "upcase(.a)"],
+            &crate::stdlib::all(),
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .unwrap_err();
+
+        assert_eq!(index, 1);
+    }
+}
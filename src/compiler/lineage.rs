@@ -0,0 +1,474 @@
+//! Field-level data lineage of a compiled program.
+//!
+//! The analysis walks the compiled expression tree once, in program order, and tracks the target
+//! paths each expression's value can be derived from. Local variables carry the sources of the
+//! values assigned to them, and the result of a function call is derived from all of its
+//! arguments.
+//!
+//! The result is an over-approximation:
+//!
+//! - An assignment inside an `if` statement also depends on the predicate, since whether it
+//!   happens reveals something about the predicate's sources.
+//! - If an `abort` or `return` inside an `if` statement can end the program early, every
+//!   assignment depends on the predicate, since whether the program ran to the end reveals
+//!   something about it.
+//! - Assignments inside `if` statements and closures add to the sources of a variable, rather
+//!   than replacing them. Closure blocks are analyzed until no more sources are added, since they
+//!   can run any number of times.
+//! - A source path covers everything nested under it, so `.` means any part of the event.
+//! - A query of a target path is derived from the path itself, and from the sources of the values
+//!   previously assigned to the path, to paths nested in it, or to paths it is nested in.
+//!
+//! Errors are the exception: a fallible call that ends the program, such as `to_int!(.a)`, doesn't
+//! add its arguments to the sources of the other assignments.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::compiler::expression::{
+    Block, Container, Expr, FunctionCall, Query, Variant,
+    assignment::{self, Target},
+    query,
+    spread::{ArrayItem, ObjectItem},
+    unary,
+};
+use crate::compiler::parser::Ident;
+use crate::path::OwnedTargetPath;
+
+/// The target paths a value can be derived from.
+type Sources = BTreeSet<OwnedTargetPath>;
+
+/// Computes, for each target path the program assigns to, the target paths the assigned values
+/// can be derived from.
+pub(crate) fn target_lineage(exprs: &[Expr]) -> BTreeMap<OwnedTargetPath, Sources> {
+    let mut lineage = Lineage::default();
+    for expr in exprs {
+        lineage.sources(expr);
+    }
+
+    let mut targets = lineage.targets;
+    for sources in targets.values_mut() {
+        sources.extend(lineage.terminations.iter().cloned());
+    }
+    targets
+}
+
+#[derive(Default)]
+struct Lineage {
+    /// The sources of each assigned target path.
+    targets: BTreeMap<OwnedTargetPath, Sources>,
+
+    /// The sources of the value of each local variable.
+    variables: HashMap<Ident, Sources>,
+
+    /// The sources of the predicates of the `if` statements being analyzed.
+    conditions: Vec<Sources>,
+
+    /// The sources of the predicates of the `if` statements that can end the program early.
+    terminations: Sources,
+
+    /// The number of closures being analyzed.
+    closures: usize,
+}
+
+impl Lineage {
+    /// Returns the sources of the value of `expr`, and records the assignments it makes.
+    fn sources(&mut self, expr: &Expr) -> Sources {
+        match expr {
+            Expr::Literal(_) | Expr::Noop(_) => Sources::new(),
+            Expr::Container(container) => self.container(container),
+            Expr::IfStatement(if_statement) => {
+                let predicate = self.block(&if_statement.predicate.inner);
+                self.conditions.push(predicate.clone());
+                let mut sources = predicate;
+                sources.extend(self.block(&if_statement.if_block));
+                if let Some(else_block) = &if_statement.else_block {
+                    sources.extend(self.block(else_block));
+                }
+                self.conditions.pop();
+                sources
+            }
+            Expr::Op(op) => {
+                let mut sources = self.sources(&op.lhs);
+                sources.extend(self.sources(&op.rhs));
+                sources
+            }
+            Expr::Assignment(assignment) => match &assignment.variant {
+                assignment::Variant::Single { target, expr } => {
+                    let sources = self.sources(expr);
+                    self.assign(target, &sources);
+                    sources
+                }
+                assignment::Variant::Infallible { ok, err, expr, .. } => {
+                    let sources = self.sources(expr);
+                    self.assign(ok, &sources);
+                    self.assign(err, &sources);
+                    sources
+                }
+            },
+            Expr::Query(query) => self.query(query),
+            Expr::FunctionCall(call) => self.function_call(call),
+            Expr::Variable(variable) => self.variable(variable.ident()),
+            Expr::Unary(unary) => match &unary.variant {
+                unary::Variant::Not(not) => self.sources(&not.inner),
+            },
+            Expr::Abort(abort) => {
+                if let Some(message) = &abort.message {
+                    self.sources(message);
+                }
+                self.terminate();
+                Sources::new()
+            }
+            Expr::Return(r#return) => {
+                let sources = self.sources(&r#return.expr);
+                self.terminate();
+                sources
+            }
+            Expr::Cached(cached) => self.sources(&cached.inner),
+        }
+    }
+
+    fn block(&mut self, block: &Block) -> Sources {
+        let mut sources = Sources::new();
+        for expr in block.exprs() {
+            sources = self.sources(expr);
+        }
+        sources
+    }
+
+    fn container(&mut self, container: &Container) -> Sources {
+        match &container.variant {
+            Variant::Group(group) => self.sources(&group.inner),
+            Variant::Block(block) => self.block(block),
+            Variant::Array(array) => array
+                .inner
+                .iter()
+                .flat_map(|expr| self.sources(expr))
+                .collect(),
+            Variant::Object(object) => object
+                .inner
+                .values()
+                .flat_map(|expr| self.sources(expr))
+                .collect(),
+            Variant::SpreadArray(array) => array
+                .inner
+                .iter()
+                .flat_map(|item| match item {
+                    ArrayItem::Element(expr) | ArrayItem::Spread(expr) => self.sources(expr),
+                })
+                .collect(),
+            Variant::SpreadObject(object) => object
+                .inner
+                .iter()
+                .flat_map(|item| match item {
+                    ObjectItem::Field(_, expr) | ObjectItem::Spread(expr) => self.sources(expr),
+                })
+                .collect(),
+        }
+    }
+
+    fn query(&mut self, query: &Query) -> Sources {
+        match query.target() {
+            query::Target::External(prefix) => {
+                let path = OwnedTargetPath {
+                    prefix: *prefix,
+                    path: query.path().clone(),
+                };
+                let mut sources = self
+                    .targets
+                    .iter()
+                    .filter(|(assigned, _)| overlaps(assigned, &path))
+                    .flat_map(|(_, sources)| sources.iter().cloned())
+                    .collect::<Sources>();
+                sources.insert(path);
+                sources
+            }
+            query::Target::Internal(variable) => self.variable(variable.ident()),
+            query::Target::FunctionCall(call) => self.function_call(call),
+            query::Target::Container(container) => self.container(container),
+        }
+    }
+
+    fn function_call(&mut self, call: &FunctionCall) -> Sources {
+        let mut sources: Sources = call
+            .arguments
+            .iter()
+            .flat_map(|argument| self.sources(argument.expr()))
+            .collect();
+
+        if let Some(closure) = &call.closure {
+            // The closure variables are bound to parts of the arguments, and the closure can run
+            // any number of times, so its block is analyzed until its sources are stable.
+            self.closures += 1;
+            for variable in &closure.variables {
+                self.variables
+                    .entry(variable.clone())
+                    .or_default()
+                    .extend(sources.iter().cloned());
+            }
+            loop {
+                let before = (self.targets.clone(), self.variables.clone());
+                let block = self.block(&closure.block);
+                let added = block.difference(&sources).count() > 0;
+                sources.extend(block);
+                if !added && before == (self.targets.clone(), self.variables.clone()) {
+                    break;
+                }
+            }
+            self.closures -= 1;
+        }
+
+        sources
+    }
+
+    /// Records that the program can end early under the current conditions.
+    fn terminate(&mut self) {
+        let conditions = self
+            .conditions
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        self.terminations.extend(conditions);
+    }
+
+    fn variable(&self, ident: &Ident) -> Sources {
+        self.variables.get(ident).cloned().unwrap_or_default()
+    }
+
+    /// Records the assignment of a value derived from `sources` to `target`.
+    fn assign(&mut self, target: &Target, sources: &Sources) {
+        let mut sources = sources.clone();
+        sources.extend(self.conditions.iter().flatten().cloned());
+        let conditional = !self.conditions.is_empty() || self.closures > 0;
+
+        match target {
+            Target::Noop => {}
+            Target::Internal(ident, path) => {
+                if path.is_root() && !conditional {
+                    self.variables.insert(ident.clone(), sources);
+                } else {
+                    self.variables
+                        .entry(ident.clone())
+                        .or_default()
+                        .extend(sources);
+                }
+            }
+            Target::External(path) => {
+                self.targets
+                    .entry(path.clone())
+                    .or_default()
+                    .extend(sources);
+            }
+            Target::Array(elements, rest) => {
+                for target in elements.iter().chain(rest.as_deref()) {
+                    self.assign(target, &sources);
+                }
+            }
+            Target::Object(fields) => {
+                for (_, target) in fields {
+                    self.assign(target, &sources);
+                }
+            }
+        }
+    }
+}
+
+/// Returns `true` if one of the paths is nested in, or equal to, the other.
+pub(crate) fn overlaps(a: &OwnedTargetPath, b: &OwnedTargetPath) -> bool {
+    a.prefix == b.prefix
+        && a.path
+            .segments
+            .iter()
+            .zip(&b.path.segments)
+            .all(|(a, b)| a == b)
+}
+
+/// Returns `true` if a value derived from `source` can be assigned to `target`, or to a path
+/// nested in it, according to `lineage`.
+pub(crate) fn reaches(
+    lineage: &BTreeMap<OwnedTargetPath, Sources>,
+    source: &OwnedTargetPath,
+    target: &OwnedTargetPath,
+) -> bool {
+    lineage.iter().any(|(assigned, sources)| {
+        overlaps(assigned, target) && sources.iter().any(|path| overlaps(path, source))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+    use crate::owned_value_path;
+
+    fn event(path: &str) -> OwnedTargetPath {
+        OwnedTargetPath::event(crate::path::parse_value_path(path).unwrap())
+    }
+
+    fn lineage(source: &str) -> BTreeMap<String, Vec<String>> {
+        compile(source, &crate::stdlib::all())
+            .unwrap()
+            .program
+            .info()
+            .target_lineage
+            .iter()
+            .map(|(target, sources)| {
+                (
+                    target.to_string(),
+                    sources.iter().map(ToString::to_string).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn expected(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(target, sources)| {
+                (
+                    (*target).to_owned(),
+                    sources.iter().map(|&source| source.to_owned()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn direct_and_through_variables() {
+        assert_eq!(
+            lineage(
+                r#"
+                x = .user.email
+                y = upcase!(x) + string!(.suffix)
+                .export = y
+                .constant = "a"
+                x = .other
+                .later = x
+                "#
+            ),
+            expected(&[
+                (".constant", &[]),
+                (".export", &[".suffix", ".user.email"]),
+                (".later", &[".other"]),
+            ])
+        );
+    }
+
+    #[test]
+    fn conditions() {
+        assert_eq!(
+            lineage(
+                r#"
+                x = .a
+                if exists(.flag) { x = .b } else { .out = "no" }
+                .result = x
+                "#
+            ),
+            expected(&[(".out", &[".flag"]), (".result", &[".a", ".b", ".flag"])])
+        );
+    }
+
+    #[test]
+    fn early_termination() {
+        assert_eq!(
+            lineage(
+                r#"
+                .before = 1
+                if .user.email == "x" { abort }
+                .export = 1
+                if exists(.skip) { return .value }
+                .after = .a
+                "#
+            ),
+            expected(&[
+                (".after", &[".a", ".skip", ".user.email"]),
+                (".before", &[".skip", ".user.email"]),
+                (".export", &[".skip", ".user.email"]),
+            ])
+        );
+
+        let program = compile(r#"if .user.email == "x" { abort }; .export = 1"#, &[])
+            .unwrap()
+            .program;
+        assert!(
+            program
+                .info()
+                .reaches(&event(".user.email"), &event(".export"))
+        );
+
+        // Ending the program unconditionally doesn't reveal anything.
+        assert_eq!(
+            lineage(".export = .a\nreturn true"),
+            expected(&[(".export", &[".a"])])
+        );
+    }
+
+    #[test]
+    fn closures_and_metadata() {
+        assert_eq!(
+            lineage(
+                r"
+                last = null
+                prev = null
+                for_each(array!(.items)) -> |_index, value| {
+                    prev = last
+                    last = value
+                }
+                .prev = prev
+                .total = %count
+                ",
+            ),
+            expected(&[(".prev", &[".items"]), (".total", &["%count"])])
+        );
+    }
+
+    #[test]
+    fn destructuring_and_root() {
+        assert_eq!(
+            lineage("[.first, { \"b\": .second }] = [.x, .y]\n. = merge!(., %meta)"),
+            expected(&[
+                (".", &[".", ".x", ".y", "%meta"]),
+                (".first", &[".x", ".y"]),
+                (".second", &[".x", ".y"]),
+            ])
+        );
+    }
+
+    #[test]
+    fn through_assigned_targets() {
+        assert_eq!(
+            lineage(".a = .user\n.export = .a"),
+            expected(&[(".a", &[".user"]), (".export", &[".a", ".user"])])
+        );
+        assert_eq!(
+            lineage(".tmp = .user.email\n.export = .tmp.x"),
+            expected(&[
+                (".export", &[".tmp.x", ".user.email"]),
+                (".tmp", &[".user.email"]),
+            ])
+        );
+
+        let program = compile(".tmp = .user.email\n.export = .tmp.x", &[])
+            .unwrap()
+            .program;
+        assert!(
+            program
+                .info()
+                .reaches(&event(".user.email"), &event(".export"))
+        );
+    }
+
+    #[test]
+    fn reaches_nested_paths() {
+        let program = compile(".export.contact = .user", &[]).unwrap().program;
+        let info = program.info();
+
+        assert!(info.reaches(&event(".user.email"), &event(".export")));
+        assert!(info.reaches(&event("."), &event(".export.contact.name")));
+        assert!(!info.reaches(&event(".account"), &event(".export")));
+        assert!(!info.reaches(
+            &OwnedTargetPath::event(owned_value_path!("user")),
+            &event(".other")
+        ));
+    }
+}
@@ -8,6 +8,8 @@ use std::{fmt::Display, str::FromStr};
 use serde::{Deserialize, Serialize};
 
 use crate::compiler::unused_expression_checker::check_for_unused_results;
+pub use artifact::ArtifactError;
+pub use batch::{BatchExecutor, BatchOutput};
 #[cfg(feature = "stdlib")]
 pub use category::Category;
 pub use compiler::{CompilationResult, Compiler};
@@ -16,9 +18,10 @@ pub use datetime::TimeZone;
 pub use expression::{Expression, ExpressionExt, FunctionExpression};
 pub use expression_error::{ExpressionError, Resolved};
 pub use function::{Function, Parameter};
+pub use fused::FusedPrograms;
 pub use program::{Program, ProgramInfo};
 pub use state::{TypeInfo, TypeState};
-pub use target::{SecretTarget, Target, TargetValue, TargetValueRef};
+pub use target::{LazyJsonTarget, SecretTarget, Target, TargetValue, TargetValueRef};
 pub use type_def::TypeDef;
 
 pub(crate) use crate::diagnostic::Span;
@@ -31,11 +34,15 @@ pub use self::deprecation_warning::DeprecationWarning;
 #[allow(clippy::module_inception)]
 mod compiler;
 
+mod artifact;
+mod batch;
 mod compile_config;
 mod context;
 mod datetime;
 mod deprecation_warning;
 mod expression_error;
+mod fused;
+mod lineage;
 mod program;
 mod target;
 #[cfg(any(test, feature = "test"))]
@@ -9,7 +9,7 @@ pub use crate::diagnostic::{DiagnosticMessage, Note, Span};
 pub use crate::example;
 pub use crate::expr;
 pub use crate::value::{
-    KeyString, Kind, ObjectMap, Value, ValueRegex,
+    KeyString, Kind, ObjectMap, Value, ValueArray, ValueRegex,
     kind::{Collection, Field, Index},
     value,
     value::IterItem,
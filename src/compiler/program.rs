@@ -1,7 +1,12 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use crate::path::OwnedTargetPath;
 
 use super::state::{TypeInfo, TypeState};
-use super::{Context, Expression, Resolved, expression::Block};
+use super::{
+    ArtifactError, CompileConfig, Context, Expression, Function, Resolved, artifact,
+    expression::Block, lineage,
+};
 
 #[derive(Debug, Clone)]
 pub struct Program {
@@ -39,6 +44,43 @@ impl Program {
     pub fn resolve(&self, ctx: &mut Context) -> Resolved {
         self.expressions.resolve(ctx)
     }
+
+    /// Serializes the program into a versioned binary artifact, which
+    /// [`Program::from_artifact`] loads without parsing and type checking the
+    /// source again.
+    ///
+    /// `fns` must be the functions the program was compiled with.
+    #[must_use]
+    pub fn to_artifact(&self, fns: &[Box<dyn Function>]) -> Vec<u8> {
+        artifact::encode(self, fns)
+    }
+
+    /// Loads a program from an artifact created by [`Program::to_artifact`].
+    ///
+    /// Loading only saves parsing and type checking the source. The function
+    /// calls in the program are compiled again with `fns` and `config`, so
+    /// functions that do expensive work while compiling, such as building the
+    /// regular expressions of `parse_regex` or the rules of `parse_groks`, do
+    /// it again.
+    ///
+    /// The type information of the program isn't part of the artifact. The
+    /// initial type state of the loaded program is empty, and its functions
+    /// are compiled without the types of their arguments, so a function that
+    /// depends on those types while compiling can compile differently than it
+    /// did for the original program.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifact is malformed, was created by a
+    /// different version of VRL, or was created with functions that differ
+    /// from `fns`.
+    pub fn from_artifact(
+        artifact: &[u8],
+        fns: &[Box<dyn Function>],
+        config: &mut CompileConfig,
+    ) -> Result<Self, ArtifactError> {
+        artifact::decode(artifact, fns, config)
+    }
 }
 
 // This type is re-exposed so renaming it is a breaking change.
@@ -63,4 +105,20 @@ pub struct ProgramInfo {
     /// A list of possible assignments made to the external [`target`](`OwnedTargetPath`) at
     /// runtime.
     pub target_assignments: Vec<OwnedTargetPath>,
+
+    /// For each [`target`](`OwnedTargetPath`) path the program assigns to, the target paths the
+    /// assigned values can be derived from, including through local variables, function
+    /// arguments, and the conditions of `if` statements.
+    ///
+    /// This is an over-approximation, and a source path covers everything nested under it.
+    pub target_lineage: BTreeMap<OwnedTargetPath, BTreeSet<OwnedTargetPath>>,
+}
+
+impl ProgramInfo {
+    /// Returns whether a value derived from `source`, or from a path nested in it, can be
+    /// assigned to `target`, or to a path nested in it.
+    #[must_use]
+    pub fn reaches(&self, source: &OwnedTargetPath, target: &OwnedTargetPath) -> bool {
+        lineage::reaches(&self.target_lineage, source, target)
+    }
 }
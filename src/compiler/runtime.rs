@@ -5,7 +5,7 @@ use crate::value::Value;
 
 use super::ExpressionError;
 use super::TimeZone;
-use super::{Context, Program, Target, state};
+use super::{Context, FusedPrograms, Program, Target, state};
 
 #[allow(clippy::module_name_repetitions)]
 pub type RuntimeResult = Result<Value, Terminate>;
@@ -100,9 +100,9 @@ impl Runtime {
         timezone: &TimeZone,
     ) -> RuntimeResult {
         // Validate that the path is a value.
-        match target.target_get(&OwnedTargetPath::event_root()) {
-            Ok(Some(_)) => {}
-            Ok(None) => {
+        match target.target_contains(&OwnedTargetPath::event_root()) {
+            Ok(true) => {}
+            Ok(false) => {
                 return Err(Terminate::Error(
                     "expected target object, got nothing".to_owned().into(),
                 ));
@@ -126,4 +126,36 @@ impl Runtime {
             Err(err @ ExpressionError::Error { .. }) => Err(Terminate::Error(err)),
         }
     }
+
+    /// Resolves each of the fused `programs` using the same [`Target`], in order.
+    ///
+    /// This is equivalent to resolving the programs one after the other with
+    /// [`Runtime::resolve`], clearing the runtime in between, except that the
+    /// expressions the programs share are resolved at most once.
+    ///
+    /// Returns a [`RuntimeResult`] for each program, in the same order.
+    pub fn resolve_fused(
+        &mut self,
+        target: &mut dyn Target,
+        programs: &FusedPrograms,
+        timezone: &TimeZone,
+    ) -> Vec<RuntimeResult> {
+        self.state.clear();
+
+        let results = programs
+            .programs
+            .iter()
+            .map(|fused| {
+                self.state.clear_variables();
+                let result = self.resolve(target, &fused.program, timezone);
+                if fused.mutates {
+                    self.state.clear_cache();
+                }
+                result
+            })
+            .collect();
+
+        self.state.clear();
+        results
+    }
 }
@@ -176,16 +176,40 @@ impl ExternalEnv {
 pub struct RuntimeState {
     /// The [`Value`] stored in each variable.
     variables: HashMap<Ident, Value>,
+
+    /// The values of the expressions shared between fused programs, resolved for the current
+    /// event.
+    cache: Vec<Option<Value>>,
 }
 
 impl RuntimeState {
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.variables.is_empty()
+        self.variables.is_empty() && self.cache.is_empty()
     }
 
     pub fn clear(&mut self) {
         self.variables.clear();
+        self.cache.clear();
+    }
+
+    pub(crate) fn clear_variables(&mut self) {
+        self.variables.clear();
+    }
+
+    pub(crate) fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+
+    pub(crate) fn cached(&self, slot: usize) -> Option<&Value> {
+        self.cache.get(slot).and_then(Option::as_ref)
+    }
+
+    pub(crate) fn insert_cached(&mut self, slot: usize, value: Value) {
+        if self.cache.len() <= slot {
+            self.cache.resize(slot + 1, None);
+        }
+        self.cache[slot] = Some(value);
     }
 
     #[must_use]
@@ -5,6 +5,10 @@ use crate::path::OwnedTargetPath;
 use crate::path::PathPrefix;
 use crate::value::{Secrets, Value};
 
+pub use json::LazyJsonTarget;
+
+mod json;
+
 /// Any target object you want to remap using VRL has to implement this trait.
 pub trait Target: std::fmt::Debug + SecretTarget {
     /// Insert a given [`Value`] in the provided [`Target`].
@@ -45,6 +49,17 @@ pub trait Target: std::fmt::Debug + SecretTarget {
     /// Error indicating retrieval failure.
     fn target_get(&self, path: &OwnedTargetPath) -> Result<Option<&Value>, String>;
 
+    /// Returns whether a value exists at the given path.
+    ///
+    /// The default implementation uses [`Target::target_get`]. Targets that can answer this
+    /// without materializing the value, such as [`LazyJsonTarget`], override it.
+    ///
+    /// # Errors
+    /// Error indicating retrieval failure.
+    fn target_contains(&self, path: &OwnedTargetPath) -> Result<bool, String> {
+        self.target_get(path).map(|value| value.is_some())
+    }
+
     /// Get a mutable reference to the value for a given path, or `None` if no
     /// value is found.
     ///
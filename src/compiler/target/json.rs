@@ -0,0 +1,360 @@
+use std::cell::OnceCell;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::Deserialize;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde_json::value::RawValue;
+
+use super::{SecretTarget, Target};
+use crate::compiler::ProgramInfo;
+use crate::path::{OwnedSegment, OwnedTargetPath, PathPrefix};
+use crate::value::{KeyString, ObjectMap, Secrets, Value};
+
+/// A [`Target`] over a serialized JSON event, that only deserializes the parts of the event a
+/// program uses.
+///
+/// When created, the top-level fields of the event are indexed without being parsed. The fields
+/// the program queries or assigns, according to its [`ProgramInfo`], are then parsed into a
+/// [`Value`], and the rest are kept as borrowed JSON.
+///
+/// Any access outside of those fields, such as a query of the whole event, parses the rest of the
+/// event. For reads this is cached, and for writes the event is fully parsed from then on.
+#[derive(Debug)]
+pub struct LazyJsonTarget<'a> {
+    /// The parsed part of the event.
+    value: Value,
+
+    /// All top-level fields of the event, in document order, or empty once the event is fully
+    /// parsed.
+    fields: Vec<(KeyString, &'a RawValue)>,
+
+    /// The top-level fields that are parsed into `value`, or that the event doesn't have.
+    parsed: BTreeSet<KeyString>,
+
+    /// The fully parsed event, if it was needed for a read.
+    full: OnceCell<Value>,
+
+    pub metadata: Value,
+    pub secrets: Secrets,
+}
+
+impl<'a> LazyJsonTarget<'a> {
+    /// Index the JSON event in `json`, and parse the top-level fields referenced by `info`.
+    ///
+    /// If the event isn't an object, or the program uses the whole event, it is parsed in full.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` isn't valid JSON.
+    pub fn new(json: &'a [u8], info: &ProgramInfo) -> Result<Self, serde_json::Error> {
+        let mut target = Self {
+            value: Value::Object(ObjectMap::new()),
+            fields: Vec::new(),
+            parsed: BTreeSet::new(),
+            full: OnceCell::new(),
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: Secrets::default(),
+        };
+
+        let raw: &RawValue = serde_json::from_slice(json)?;
+        let paths = info
+            .target_queries
+            .iter()
+            .chain(&info.target_assignments)
+            .filter(|path| path.prefix == PathPrefix::Event);
+        let mut used = BTreeSet::new();
+        let mut uses_root = false;
+        for path in paths {
+            match path.path.segments.first() {
+                Some(OwnedSegment::Field(field)) => {
+                    used.insert(field.clone());
+                }
+                _ => uses_root = true,
+            }
+        }
+
+        if uses_root || !raw.get().trim_start().starts_with('{') {
+            target.value = serde_json::from_str(raw.get())?;
+            return Ok(target);
+        }
+
+        let Fields(fields) = serde_json::from_str(raw.get())?;
+        let mut object = ObjectMap::new();
+        for (field, raw) in &fields {
+            if used.contains(field) {
+                object.insert(field.clone(), serde_json::from_str(raw.get())?);
+            }
+        }
+        target.value = Value::Object(object);
+        target.fields = fields;
+        // Fields missing from the event count as parsed, so they can be added without parsing it.
+        target.parsed = used;
+
+        Ok(target)
+    }
+
+    /// Returns `true` if the whole event has been parsed.
+    #[must_use]
+    pub fn is_fully_parsed(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Parse the rest of the event, and return it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field that wasn't parsed yet can't be converted into a [`Value`].
+    pub fn into_value(mut self) -> Result<Value, String> {
+        self.parse_all()?;
+        Ok(self.value)
+    }
+
+    /// Returns `true` if `path` only touches the parsed part of the event.
+    fn is_parsed(&self, path: &OwnedTargetPath) -> bool {
+        match path.path.segments.first() {
+            _ if self.fields.is_empty() || path.prefix == PathPrefix::Metadata => true,
+            Some(OwnedSegment::Field(field)) => self.parsed.contains(field),
+            _ => false,
+        }
+    }
+
+    /// The event with all fields parsed, keeping changes made to the fields that already were.
+    fn full_value(&self) -> Result<Value, String> {
+        let Value::Object(parsed) = &self.value else {
+            return Ok(self.value.clone());
+        };
+
+        let mut object = ObjectMap::new();
+        for (field, raw) in &self.fields {
+            let value = if self.parsed.contains(field) {
+                match parsed.get(field) {
+                    Some(value) => value.clone(),
+                    // The field was removed.
+                    None => continue,
+                }
+            } else {
+                serde_json::from_str(raw.get()).map_err(|error| error.to_string())?
+            };
+            object.insert(field.clone(), value);
+        }
+        for (field, value) in parsed {
+            if !object.contains_key(field) {
+                object.insert(field.clone(), value.clone());
+            }
+        }
+
+        Ok(Value::Object(object))
+    }
+
+    /// Fully parse the event, so that it can be changed at any path.
+    fn parse_all(&mut self) -> Result<(), String> {
+        if self.fields.is_empty() {
+            return Ok(());
+        }
+
+        self.value = match self.full.take() {
+            Some(value) => value,
+            None => self.full_value()?,
+        };
+        self.fields = Vec::new();
+        self.parsed.clear();
+
+        Ok(())
+    }
+
+    /// Prepare the event to be changed at `path`.
+    fn prepare_mut(&mut self, path: &OwnedTargetPath) -> Result<(), String> {
+        // A cached full event would be out of date after the change, so it becomes the event.
+        if !self.is_parsed(path) || self.full.get().is_some() {
+            self.parse_all()?;
+        }
+        Ok(())
+    }
+}
+
+impl Target for LazyJsonTarget<'_> {
+    fn target_insert(&mut self, target_path: &OwnedTargetPath, value: Value) -> Result<(), String> {
+        self.prepare_mut(target_path)?;
+        match target_path.prefix {
+            PathPrefix::Event => self.value.insert(&target_path.path, value),
+            PathPrefix::Metadata => self.metadata.insert(&target_path.path, value),
+        };
+        Ok(())
+    }
+
+    fn target_get(&self, target_path: &OwnedTargetPath) -> Result<Option<&Value>, String> {
+        let event = if self.is_parsed(target_path) {
+            &self.value
+        } else if let Some(full) = self.full.get() {
+            full
+        } else {
+            let full = self.full_value()?;
+            self.full.get_or_init(|| full)
+        };
+
+        let value = match target_path.prefix {
+            PathPrefix::Event => event.get(&target_path.path),
+            PathPrefix::Metadata => self.metadata.get(&target_path.path),
+        };
+        Ok(value)
+    }
+
+    fn target_contains(&self, target_path: &OwnedTargetPath) -> Result<bool, String> {
+        // The event is always there, even if it isn't parsed.
+        if target_path.prefix == PathPrefix::Event && target_path.path.is_root() {
+            return Ok(true);
+        }
+        self.target_get(target_path).map(|value| value.is_some())
+    }
+
+    fn target_get_mut(
+        &mut self,
+        target_path: &OwnedTargetPath,
+    ) -> Result<Option<&mut Value>, String> {
+        self.prepare_mut(target_path)?;
+        let value = match target_path.prefix {
+            PathPrefix::Event => self.value.get_mut(&target_path.path),
+            PathPrefix::Metadata => self.metadata.get_mut(&target_path.path),
+        };
+        Ok(value)
+    }
+
+    fn target_remove(
+        &mut self,
+        target_path: &OwnedTargetPath,
+        compact: bool,
+    ) -> Result<Option<Value>, String> {
+        self.prepare_mut(target_path)?;
+        let prev_value = match target_path.prefix {
+            PathPrefix::Event => self.value.remove(&target_path.path, compact),
+            PathPrefix::Metadata => self.metadata.remove(&target_path.path, compact),
+        };
+        Ok(prev_value)
+    }
+}
+
+impl SecretTarget for LazyJsonTarget<'_> {
+    fn get_secret(&self, key: &str) -> Option<&str> {
+        self.secrets.get_secret(key)
+    }
+
+    fn insert_secret(&mut self, key: &str, value: &str) {
+        self.secrets.insert_secret(key, value);
+    }
+
+    fn remove_secret(&mut self, key: &str) {
+        self.secrets.remove_secret(key);
+    }
+}
+
+/// The top-level fields of a JSON object, with their values left unparsed.
+struct Fields<'a>(Vec<(KeyString, &'a RawValue)>);
+
+impl<'de> Deserialize<'de> for Fields<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FieldsVisitor;
+
+        impl<'de> Visitor<'de> for FieldsVisitor {
+            type Value = Fields<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Fields<'de>, A::Error> {
+                let mut fields = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some(entry) = map.next_entry()? {
+                    fields.push(entry);
+                }
+                Ok(Fields(fields))
+            }
+        }
+
+        deserializer.deserialize_map(FieldsVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{TimeZone, compile, runtime::Runtime, state::RuntimeState};
+    use crate::value;
+
+    const EVENT: &[u8] = br#"{"a": 1, "b": {"c": [1, 2]}, "d": "unused", "e": {"f": true}}"#;
+
+    fn run<'a>(source: &str, event: &'a [u8]) -> (Value, LazyJsonTarget<'a>) {
+        let program = compile(source, &[]).unwrap().program;
+        let mut target = LazyJsonTarget::new(event, program.info()).unwrap();
+        let result = Runtime::new(RuntimeState::default())
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap();
+        (result, target)
+    }
+
+    #[test]
+    fn parses_used_fields() {
+        let (result, target) = run(".b.c[1] = .a\n.e.f", EVENT);
+
+        assert_eq!(result, value!(true));
+        assert!(!target.is_fully_parsed());
+        assert_eq!(
+            target.parsed,
+            BTreeSet::from(["a".into(), "b".into(), "e".into()])
+        );
+        assert_eq!(
+            target.into_value().unwrap(),
+            value!({ a: 1, b: { c: [1, 1] }, d: "unused", e: { f: true } })
+        );
+    }
+
+    #[test]
+    fn parses_all_for_root() {
+        let (result, target) = run(".", EVENT);
+
+        assert_eq!(
+            result,
+            value!({ a: 1, b: { c: [1, 2] }, d: "unused", e: { f: true } })
+        );
+        assert!(target.is_fully_parsed());
+    }
+
+    #[test]
+    fn keeps_changes_when_parsing_all() {
+        let (_, mut target) = run(".a = 2\n.new = .a", EVENT);
+        let path = OwnedTargetPath::event_root();
+
+        assert_eq!(
+            target.target_get(&path).unwrap(),
+            Some(&value!({ a: 2, b: { c: [1, 2] }, d: "unused", e: { f: true }, new: 2 }))
+        );
+        assert!(!target.is_fully_parsed());
+
+        target
+            .target_remove(
+                &OwnedTargetPath::event(crate::owned_value_path!("d")),
+                false,
+            )
+            .unwrap();
+        assert!(target.is_fully_parsed());
+        assert_eq!(
+            target.into_value().unwrap(),
+            value!({ a: 2, b: { c: [1, 2] }, e: { f: true }, new: 2 })
+        );
+    }
+
+    #[test]
+    fn non_object_event() {
+        let (result, target) = run(".a", b"[1, 2]");
+
+        assert_eq!(result, value!(null));
+        assert_eq!(target.into_value().unwrap(), value!([1, 2]));
+    }
+
+    #[test]
+    fn invalid_json() {
+        let program = compile(".a", &[]).unwrap().program;
+
+        assert!(LazyJsonTarget::new(br#"{"a": 1, "b": }"#, program.info()).is_err());
+    }
+}
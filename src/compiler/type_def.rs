@@ -132,6 +132,11 @@ impl TypeDef {
         &mut self.kind
     }
 
+    #[must_use]
+    pub(crate) fn fallibility(&self) -> &Fallibility {
+        &self.fallibility
+    }
+
     #[must_use]
     pub fn returns(&self) -> &Kind {
         &self.returns
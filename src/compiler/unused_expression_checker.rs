@@ -22,16 +22,24 @@ use crate::compiler::codes::WARNING_UNUSED_CODE;
 use crate::compiler::parser::{Ident, Node};
 use crate::diagnostic::{Diagnostic, DiagnosticList, Label, Note, Severity};
 use crate::parser::ast::{
-    Array, Assignment, AssignmentOp, AssignmentTarget, Block, Container, Expr, FunctionCall,
-    IfStatement, Object, Predicate, QueryTarget, Return, RootExpr, Unary,
+    Array, ArrayItem, Assignment, AssignmentOp, AssignmentTarget, Block, Container, Expr,
+    FunctionCall, IfStatement, Object, ObjectItem, Predicate, QueryTarget, Return, RootExpr,
+    SpreadArray, SpreadObject, Unary,
 };
 use crate::parser::template_string::StringSegment;
 use crate::parser::{Literal, Program, Span};
 use std::collections::{BTreeMap, HashMap};
 use tracing::warn;
 
-const SIDE_EFFECT_FUNCTIONS: [&str; 5] =
-    ["del", "log", "assert", "assert_eq", "set_semantic_meaning"];
+const SIDE_EFFECT_FUNCTIONS: [&str; 7] = [
+    "del",
+    "log",
+    "assert",
+    "assert_eq",
+    "set_semantic_meaning",
+    "set_secret",
+    "remove_secret",
+];
 
 #[must_use]
 pub fn check_for_unused_results(ast: &Program) -> DiagnosticList {
@@ -136,6 +144,38 @@ impl VisitorState {
         }
     }
 
+    fn mark_assignment_target_pending_usage(
+        &mut self,
+        op: &AssignmentOp,
+        target: &Node<AssignmentTarget>,
+    ) {
+        match &target.node {
+            AssignmentTarget::Noop => {}
+            AssignmentTarget::Query(query) => {
+                self.mark_query_target_pending_usage(&query.target);
+            }
+            AssignmentTarget::Internal(ident, path) => {
+                if matches!(op, AssignmentOp::Assign | AssignmentOp::Vivify) && path.is_none() {
+                    self.mark_identifier_pending_usage(ident, &target.span);
+                } else if *op == AssignmentOp::Merge {
+                    // The following example: `x |= {}` falls under shadowing and is not handled.
+                    self.mark_identifier_used(ident);
+                }
+            }
+            AssignmentTarget::External(_path) => {}
+            AssignmentTarget::Array(elements, rest) => {
+                for target in elements.iter().chain(rest.as_deref()) {
+                    self.mark_assignment_target_pending_usage(op, target);
+                }
+            }
+            AssignmentTarget::Object(fields) => {
+                for (_, target) in fields {
+                    self.mark_assignment_target_pending_usage(op, target);
+                }
+            }
+        }
+    }
+
     fn mark_query_target_pending_usage(&mut self, query_target: &Node<QueryTarget>) {
         match &query_target.node {
             QueryTarget::Internal(ident) => {
@@ -254,6 +294,8 @@ impl AstVisitor<'_> {
             Container::Block(block) => self.visit_block(block, state),
             Container::Array(array) => self.visit_array(array, state),
             Container::Object(object) => self.visit_object(object, state),
+            Container::SpreadArray(array) => self.visit_spread_array(array, state),
+            Container::SpreadObject(object) => self.visit_spread_object(object, state),
         }
     }
 
@@ -289,6 +331,29 @@ impl AstVisitor<'_> {
         }
     }
 
+    fn visit_spread_array(&self, array: &Node<SpreadArray>, state: &mut VisitorState) {
+        for item in &array.0 {
+            match item {
+                ArrayItem::Element(expr) | ArrayItem::Spread(expr) => self.visit_node(expr, state),
+            }
+        }
+    }
+
+    fn visit_spread_object(&self, object: &Node<SpreadObject>, state: &mut VisitorState) {
+        if state.is_unused() {
+            state.append_diagnostic(format!("unused object `{object}`"), &object.span);
+        }
+        for item in &object.0 {
+            match item {
+                ObjectItem::Field(_, expr) | ObjectItem::Spread(expr) => {
+                    scoped_visit(state, |state| {
+                        self.visit_node(expr, state);
+                    });
+                }
+            }
+        }
+    }
+
     fn visit_if_statement(&self, if_statement: &Node<IfStatement>, state: &mut VisitorState) {
         match &if_statement.predicate.node {
             Predicate::One(expr) => self.visit_node(expr, state),
@@ -321,21 +386,7 @@ impl AstVisitor<'_> {
             Assignment::Infallible { ok, err, op, .. } => (op, &[ok, err]),
         };
         for target in targets {
-            match &target.node {
-                AssignmentTarget::Noop => {}
-                AssignmentTarget::Query(query) => {
-                    state.mark_query_target_pending_usage(&query.target);
-                }
-                AssignmentTarget::Internal(ident, path) => {
-                    if *op == AssignmentOp::Assign && path.is_none() {
-                        state.mark_identifier_pending_usage(ident, &target.span);
-                    } else if *op == AssignmentOp::Merge {
-                        // The following example: `x |= {}` falls under shadowing and is not handled.
-                        state.mark_identifier_used(ident);
-                    }
-                }
-                AssignmentTarget::External(_path) => {}
-            }
+            state.mark_assignment_target_pending_usage(op, target);
         }
 
         // Visit the assignment right hand side.
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use crate::value::{Value, ValueRegex, kind::Collection};
+use crate::value::{IntoCollection, Value, ValueRegex, kind::Collection};
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 
@@ -135,7 +135,7 @@ impl VrlValueConvert for Value {
 
     fn try_array(self) -> Result<Vec<Value>, ValueError> {
         match self {
-            Value::Array(v) => Ok(v),
+            Value::Array(v) => Ok(v.into_collection()),
             _ => Err(ValueError::Expected {
                 got: self.kind(),
                 expected: Kind::array(Collection::any()),
@@ -31,14 +31,14 @@ impl Error for EncodingError {
 
 /// Encodes input to key value format with specified
 /// delimiters in field order where unspecified fields
-/// will follow after them. `Flattens_boolean` values
+/// will follow after them, in key order. `Flattens_boolean` values
 /// to only a key if true.
 ///
 /// # Errors
 ///
 /// Returns an `EncodingError` if the input contains non-`String` map keys.
-pub fn to_string<V: Serialize>(
-    input: &BTreeMap<KeyString, V>,
+pub fn to_string<'a, V: Serialize + 'a>(
+    input: impl IntoIterator<Item = (&'a KeyString, &'a V)>,
     fields_order: &[KeyString],
     key_value_delimiter: &str,
     field_delimiter: &str,
@@ -84,7 +84,7 @@ pub fn to_string<V: Serialize>(
 }
 
 fn flatten<'a>(
-    input: impl IntoIterator<Item = (&'a KeyString, impl Serialize)> + 'a,
+    input: impl IntoIterator<Item = (&'a KeyString, impl Serialize)>,
     separator: char,
 ) -> Result<BTreeMap<KeyString, Data>, EncodingError> {
     let mut map = BTreeMap::new();
@@ -7,10 +7,14 @@ use super::encode_key_value::{EncodingError, to_string as encode_key_value};
 
 /// Serialize the input value map into a logfmt string.
 ///
+/// Fields are encoded in key order, regardless of the order of the input map.
+///
 /// # Errors
 ///
 /// Returns an `EncodingError` if any of the keys are not strings.
-pub fn encode_map<V: Serialize>(input: &BTreeMap<KeyString, V>) -> Result<String, EncodingError> {
+pub fn encode_map<'a, V: Serialize + 'a>(
+    input: impl IntoIterator<Item = (&'a KeyString, &'a V)>,
+) -> Result<String, EncodingError> {
     encode_key_value(input, &[], "=", " ", true)
 }
 
@@ -0,0 +1,1513 @@
+use crate::value;
+use crate::value::Value;
+
+/// Queries matched against events, with whether each event matches, shared by the tests of
+/// `match_datadog_query` and of the translation of queries into VRL conditions.
+#[allow(clippy::too_many_lines)]
+pub(crate) fn match_cases() -> Vec<(&'static str, Value, &'static str, bool)> {
+    vec![
+        (
+            "message_exists",
+            value!({"message": "test message"}),
+            "_exists_:message",
+            true,
+        ),
+        (
+            "not_message_exists",
+            value!({"message": "test message"}),
+            "NOT _exists_:message",
+            false,
+        ),
+        (
+            "negate_message_exists",
+            value!({"message": "test message"}),
+            "-_exists_:message",
+            false,
+        ),
+        ("facet_exists", value!({"a": "value" }), "_exists_:@a", true),
+        (
+            "not_facet_exists",
+            value!({"a": "value" }),
+            "NOT _exists_:@a",
+            false,
+        ),
+        (
+            "negate_facet_exists",
+            value!({"a": "value" }),
+            "-_exists_:@a",
+            false,
+        ),
+        (
+            "hyphen_facet_exists",
+            value!({"a-b": "value"}),
+            "_exists_:@a-b",
+            true,
+        ),
+        ("tag_bare", value!({"tags": ["a","b","c"]}), "tags:a", true),
+        (
+            "tag_bare_no_match",
+            value!({"tags": ["a","b","c"]}),
+            "tags:d",
+            false,
+        ),
+        (
+            "not_tag_bare",
+            value!({"tags": ["a","b","c"]}),
+            "NOT tags:a",
+            false,
+        ),
+        (
+            "negate_tag_bare",
+            value!({"tags": ["a","b","c"]}),
+            "-tags:a",
+            false,
+        ),
+        (
+            "tag_exists_bare",
+            value!({"tags": ["a","b","c"]}),
+            "_exists_:a",
+            true,
+        ),
+        (
+            "not_tag_exists_bare",
+            value!({"tags": ["a","b","c"]}),
+            "NOT _exists_:a",
+            false,
+        ),
+        (
+            "negate_tag_exists_bare",
+            value!({"tags": ["a","b","c"]}),
+            "-_exists_:a",
+            false,
+        ),
+        (
+            "tag_exists",
+            value!({"tags": ["a:1","b:2","c:3"]}),
+            "_exists_:a",
+            true,
+        ),
+        (
+            "not_tag_exists",
+            value!({"tags": ["a:1","b:2","c:3"]}),
+            "NOT _exists_:a",
+            false,
+        ),
+        (
+            "negate_tag_exists",
+            value!({"tags": ["a:1","b:2","c:3"]}),
+            "-_exists_:a",
+            false,
+        ),
+        ("message_missing", value!({}), "_missing_:message", true),
+        (
+            "not_message_missing",
+            value!({}),
+            "NOT _missing_:message",
+            false,
+        ),
+        (
+            "negate_message_missing",
+            value!({}),
+            "-_missing_:message",
+            false,
+        ),
+        (
+            "facet_missing",
+            value!({"b": "value" }),
+            "_missing_:@a",
+            true,
+        ),
+        (
+            "not_facet_missing",
+            value!({"b": "value" }),
+            "NOT _missing_:@a",
+            false,
+        ),
+        (
+            "negate_facet_missing",
+            value!({"b": "value" }),
+            "-_missing_:@a",
+            false,
+        ),
+        (
+            "hyphen_facet_missing",
+            value!({"a-b": "value"}),
+            "_missing_:@c-d",
+            true,
+        ),
+        (
+            "tag_bare_missing",
+            value!({"tags": ["b","c"]}),
+            "_missing_:a",
+            true,
+        ),
+        (
+            "not_tag_bare_missing",
+            value!({"tags": ["b","c"]}),
+            "NOT _missing_:a",
+            false,
+        ),
+        (
+            "negate_tag_bare_missing",
+            value!({"tags": ["b","c"]}),
+            "-_missing_:a",
+            false,
+        ),
+        (
+            "tag_missing",
+            value!({"tags": ["b:1","c:2"]}),
+            "_missing_:a",
+            true,
+        ),
+        (
+            "not_tag_missing",
+            value!({"tags": ["b:1","c:2"]}),
+            "NOT _missing_:a",
+            false,
+        ),
+        (
+            "negate_tag_missing",
+            value!({"tags": ["b:1","c:2"]}),
+            "-_missing_:a",
+            false,
+        ),
+        (
+            "equals_message",
+            value!({"message": "match by word boundary"}),
+            "match",
+            true,
+        ),
+        (
+            "not_equals_message",
+            value!({"message": "match by word boundary"}),
+            "NOT match",
+            false,
+        ),
+        (
+            "negate_equals_message",
+            value!({"message": "match by word boundary"}),
+            "-match",
+            false,
+        ),
+        (
+            "equals_message_no_match",
+            value!({"message": "another value"}),
+            "match",
+            false,
+        ),
+        (
+            "not_equals_message_no_match",
+            value!({"message": "another value"}),
+            "NOT match",
+            true,
+        ),
+        (
+            "negate_equals_message_no_match",
+            value!({"message": "another value"}),
+            "-match",
+            true,
+        ),
+        (
+            "equals_tag",
+            value!({"tags": ["x:1", "y:2", "z:3"]}),
+            "y:2",
+            true,
+        ),
+        (
+            "not_equals_tag",
+            value!({"tags": ["x:1", "y:2", "z:3"]}),
+            "NOT y:2",
+            false,
+        ),
+        (
+            "negate_equals_tag",
+            value!({"tags": ["x:1", "y:2", "z:3"]}),
+            "-y:2",
+            false,
+        ),
+        (
+            "equals_tag_no_match",
+            value!({"tags": ["x:1", "y:2", "z:3"]}),
+            "y:3",
+            false,
+        ),
+        (
+            "not_equals_tag_no_match",
+            value!({"tags": ["x:1", "y:2", "z:3"]}),
+            "NOT y:3",
+            true,
+        ),
+        (
+            "negate_equals_tag_no_match",
+            value!({"tags": ["x:1", "y:2", "z:3"]}),
+            "-y:3",
+            true,
+        ),
+        ("equals_facet", value!({"z": 1}), "@z:1", true),
+        ("not_equals_facet", value!({"z": 1}), "NOT @z:1", false),
+        ("negate_equals_facet", value!({"z": 1}), "-@z:1", false),
+        (
+            "wildcard_prefix_message",
+            value!({"message": "vector"}),
+            "*tor",
+            true,
+        ),
+        (
+            "not_wildcard_prefix_message",
+            value!({"message": "vector"}),
+            "NOT *tor",
+            false,
+        ),
+        (
+            "negate_wildcard_prefix_message",
+            value!({"message": "vector"}),
+            "-*tor",
+            false,
+        ),
+        (
+            "wildcard_prefix_message_no_match",
+            value!({"message": "torvec"}),
+            "*tor",
+            false,
+        ),
+        (
+            "not_wildcard_prefix_message_no_match",
+            value!({"message": "torvec"}),
+            "NOT *tor",
+            true,
+        ),
+        (
+            "negate_wildcard_prefix_message_no_match",
+            value!({"message": "torvec"}),
+            "-*tor",
+            true,
+        ),
+        (
+            "wildcard_prefix_tag",
+            value!({"tags": ["a:vector"]}),
+            "a:*tor",
+            true,
+        ),
+        (
+            "not_wildcard_prefix_tag",
+            value!({"tags": ["a:vector"]}),
+            "NOT a:*tor",
+            false,
+        ),
+        (
+            "negate_wildcard_prefix_tag",
+            value!({"tags": ["a:vector"]}),
+            "-a:*tor",
+            false,
+        ),
+        (
+            "wildcard_prefix_tag_no_match",
+            value!({"tags": ["b:vector"]}),
+            "a:*tor",
+            false,
+        ),
+        (
+            "not_wildcard_prefix_tag_no_match",
+            value!({"tags": ["b:vector"]}),
+            "NOT a:*tor",
+            true,
+        ),
+        (
+            "negate_wildcard_prefix_tag_no_match",
+            value!({"tags": ["b:vector"]}),
+            "-a:*tor",
+            true,
+        ),
+        (
+            "wildcard_prefix_facet",
+            value!({"a": "vector"}),
+            "@a:*tor",
+            true,
+        ),
+        (
+            "not_wildcard_prefix_facet",
+            value!({"a": "vector"}),
+            "NOT @a:*tor",
+            false,
+        ),
+        (
+            "negate_wildcard_prefix_facet",
+            value!({"a": "vector"}),
+            "-@a:*tor",
+            false,
+        ),
+        (
+            "wildcard_prefix_facet_no_match",
+            value!({"b": "vector"}),
+            "@a:*tor",
+            false,
+        ),
+        (
+            "not_wildcard_prefix_facet_no_match",
+            value!({"b": "vector"}),
+            "NOT @a:*tor",
+            true,
+        ),
+        (
+            "negate_wildcard_prefix_facet_no_match",
+            value!({"b": "vector"}),
+            "-@a:*tor",
+            true,
+        ),
+        (
+            "wildcard_suffix_message",
+            value!({"message": "vector"}),
+            "vec*",
+            true,
+        ),
+        (
+            "not_wildcard_suffix_message",
+            value!({"message": "vector"}),
+            "NOT vec*",
+            false,
+        ),
+        (
+            "negate_wildcard_suffix_message",
+            value!({"message": "vector"}),
+            "-vec*",
+            false,
+        ),
+        (
+            "wildcard_suffix_message_no_match",
+            value!({"message": "torvec"}),
+            "vec*",
+            false,
+        ),
+        (
+            "not_wildcard_suffix_message_no_match",
+            value!({"message": "torvec"}),
+            "NOT vec*",
+            true,
+        ),
+        (
+            "negate_wildcard_suffix_message_no_match",
+            value!({"message": "torvec"}),
+            "-vec*",
+            true,
+        ),
+        (
+            "wildcard_suffix_tag",
+            value!({"tags": ["a:vector"]}),
+            "a:vec*",
+            true,
+        ),
+        (
+            "not_wildcard_suffix_tag",
+            value!({"tags": ["a:vector"]}),
+            "NOT a:vec*",
+            false,
+        ),
+        (
+            "negate_wildcard_suffix_tag",
+            value!({"tags": ["a:vector"]}),
+            "-a:vec*",
+            false,
+        ),
+        (
+            "wildcard_suffix_tag_no_match",
+            value!({"tags": ["b:vector"]}),
+            "a:vec*",
+            false,
+        ),
+        (
+            "not_wildcard_suffix_tag_no_match",
+            value!({"tags": ["b:vector"]}),
+            "NOT a:vec*",
+            true,
+        ),
+        (
+            "negate_wildcard_suffix_tag_no_match",
+            value!({"tags": ["b:vector"]}),
+            "-a:vec*",
+            true,
+        ),
+        (
+            "wildcard_suffix_facet",
+            value!({"a": "vector"}),
+            "@a:vec*",
+            true,
+        ),
+        (
+            "not_wildcard_suffix_facet",
+            value!({"a": "vector"}),
+            "NOT @a:vec*",
+            false,
+        ),
+        (
+            "negate_wildcard_suffix_facet",
+            value!({"a": "vector"}),
+            "-@a:vec*",
+            false,
+        ),
+        (
+            "wildcard_suffix_facet_no_match",
+            value!({"b": "vector"}),
+            "@a:vec*",
+            false,
+        ),
+        (
+            "not_wildcard_suffix_facet_no_match",
+            value!({"b": "vector"}),
+            "NOT @a:vec*",
+            true,
+        ),
+        (
+            "negate_wildcard_suffix_facet_no_match",
+            value!({"b": "vector"}),
+            "-@a:vec*",
+            true,
+        ),
+        (
+            "wildcard_multiple_message",
+            value!({"message": "vector"}),
+            "v*c*r",
+            true,
+        ),
+        (
+            "not_wildcard_multiple_message",
+            value!({"message": "vector"}),
+            "NOT v*c*r",
+            false,
+        ),
+        (
+            "negate_wildcard_multiple_message",
+            value!({"message": "vector"}),
+            "-v*c*r",
+            false,
+        ),
+        (
+            "wildcard_multiple_message_no_match",
+            value!({"message": "torvec"}),
+            "v*c*r",
+            false,
+        ),
+        (
+            "not_wildcard_multiple_message_no_match",
+            value!({"message": "torvec"}),
+            "NOT v*c*r",
+            true,
+        ),
+        (
+            "negate_wildcard_multiple_message_no_match",
+            value!({"message": "torvec"}),
+            "-v*c*r",
+            true,
+        ),
+        (
+            "wildcard_multiple_tag",
+            value!({"tags": ["a:vector"]}),
+            "a:v*c*r",
+            true,
+        ),
+        (
+            "not_wildcard_multiple_tag",
+            value!({"tags": ["a:vector"]}),
+            "NOT a:v*c*r",
+            false,
+        ),
+        (
+            "negate_wildcard_multiple_tag",
+            value!({"tags": ["a:vector"]}),
+            "-a:v*c*r",
+            false,
+        ),
+        (
+            "wildcard_multiple_tag_no_match",
+            value!({"tags": ["b:vector"]}),
+            "a:v*c*r",
+            false,
+        ),
+        (
+            "not_wildcard_multiple_tag_no_match",
+            value!({"tags": ["b:vector"]}),
+            "NOT a:v*c*r",
+            true,
+        ),
+        (
+            "negate_wildcard_multiple_tag_no_match",
+            value!({"tags": ["b:vector"]}),
+            "-a:v*c*r",
+            true,
+        ),
+        (
+            "wildcard_multiple_facet",
+            value!({"a": "vector"}),
+            "@a:v*c*r",
+            true,
+        ),
+        (
+            "not_wildcard_multiple_facet",
+            value!({"a": "vector"}),
+            "NOT @a:v*c*r",
+            false,
+        ),
+        (
+            "negate_wildcard_multiple_facet",
+            value!({"a": "vector"}),
+            "-@a:v*c*r",
+            false,
+        ),
+        (
+            "wildcard_multiple_facet_no_match",
+            value!({"b": "vector"}),
+            "@a:v*c*r",
+            false,
+        ),
+        (
+            "not_wildcard_multiple_facet_no_match",
+            value!({"b": "vector"}),
+            "NOT @a:v*c*r",
+            true,
+        ),
+        (
+            "negate_wildcard_multiple_facet_no_match",
+            value!({"b": "vector"}),
+            "-@a:v*c*r",
+            true,
+        ),
+        (
+            "range_message_unbounded",
+            value!({"message": "1"}),
+            "[* TO *]",
+            true,
+        ),
+        (
+            "not_range_message_unbounded",
+            value!({"message": "1"}),
+            "NOT [* TO *]",
+            false,
+        ),
+        (
+            "negate_range_message_unbounded",
+            value!({"message": "1"}),
+            "-[* TO *]",
+            false,
+        ),
+        (
+            "range_message_lower_bound",
+            value!({"message": "400"}),
+            "[4 TO *]",
+            true,
+        ),
+        (
+            "not_range_message_lower_bound",
+            value!({"message": "400"}),
+            "NOT [4 TO *]",
+            false,
+        ),
+        (
+            "negate_range_message_lower_bound",
+            value!({"message": "400"}),
+            "-[4 TO *]",
+            false,
+        ),
+        (
+            "range_message_lower_bound_no_match",
+            value!({"message": "400"}),
+            "[50 TO *]",
+            false,
+        ),
+        (
+            "not_range_message_lower_bound_no_match",
+            value!({"message": "400"}),
+            "NOT [50 TO *]",
+            true,
+        ),
+        (
+            "negate_range_message_lower_bound_no_match",
+            value!({"message": "400"}),
+            "-[50 TO *]",
+            true,
+        ),
+        (
+            "range_message_lower_bound_string",
+            value!({"message": "400"}),
+            r#"["4" TO *]"#,
+            true,
+        ),
+        (
+            "not_range_message_lower_bound_string",
+            value!({"message": "400"}),
+            r#"NOT ["4" TO *]"#,
+            false,
+        ),
+        (
+            "negate_range_message_lower_bound_string",
+            value!({"message": "400"}),
+            r#"-["4" TO *]"#,
+            false,
+        ),
+        (
+            "range_message_lower_bound_string_no_match",
+            value!({"message": "400"}),
+            r#"["50" TO *]"#,
+            false,
+        ),
+        (
+            "not_range_message_lower_bound_string_no_match",
+            value!({"message": "400"}),
+            r#"NOT ["50" TO *]"#,
+            true,
+        ),
+        (
+            "negate_range_message_lower_bound_string_no_match",
+            value!({"message": "400"}),
+            r#"-["50" TO *]"#,
+            true,
+        ),
+        (
+            "range_message_upper_bound",
+            value!({"message": "300"}),
+            "[* TO 4]",
+            true,
+        ),
+        (
+            "not_range_message_upper_bound",
+            value!({"message": "300"}),
+            "NOT [* TO 4]",
+            false,
+        ),
+        (
+            "negate_range_message_upper_bound",
+            value!({"message": "300"}),
+            "-[* TO 4]",
+            false,
+        ),
+        (
+            "range_message_upper_bound_no_match",
+            value!({"message": "50"}),
+            "[* TO 400]",
+            false,
+        ),
+        (
+            "not_range_message_upper_bound_no_match",
+            value!({"message": "50"}),
+            "NOT [* TO 400]",
+            true,
+        ),
+        (
+            "negate_range_message_upper_bound_no_match",
+            value!({"message": "50"}),
+            "-[* TO 400]",
+            true,
+        ),
+        (
+            "range_message_upper_bound_string",
+            value!({"message": "300"}),
+            r#"[* TO "4"]"#,
+            true,
+        ),
+        (
+            "not_range_message_upper_bound_string",
+            value!({"message": "300"}),
+            r#"NOT [* TO "4"]"#,
+            false,
+        ),
+        (
+            "negate_range_message_upper_bound_string",
+            value!({"message": "300"}),
+            r#"-[* TO "4"]"#,
+            false,
+        ),
+        (
+            "range_message_upper_bound_string_no_match",
+            value!({"message": "50"}),
+            r#"[* TO "400"]"#,
+            false,
+        ),
+        (
+            "not_range_message_upper_bound_string_no_match",
+            value!({"message": "50"}),
+            r#"NOT [* TO "400"]"#,
+            true,
+        ),
+        (
+            "negate_range_message_upper_bound_string_no_match",
+            value!({"message": "50"}),
+            r#"NOT [* TO "400"]"#,
+            true,
+        ),
+        (
+            "range_message_between",
+            value!({"message": 500}),
+            "[1 TO 6]",
+            true,
+        ),
+        (
+            "not_range_message_between",
+            value!({"message": 500}),
+            "NOT [1 TO 6]",
+            false,
+        ),
+        (
+            "negate_range_message_between",
+            value!({"message": 500}),
+            "-[1 TO 6]",
+            false,
+        ),
+        (
+            "range_message_between_no_match",
+            value!({"message": 70}),
+            "[1 TO 6]",
+            false,
+        ),
+        (
+            "not_range_message_between_no_match",
+            value!({"message": 70}),
+            "NOT [1 TO 6]",
+            true,
+        ),
+        (
+            "negate_range_message_between_no_match",
+            value!({"message": 70}),
+            "-[1 TO 6]",
+            true,
+        ),
+        (
+            "range_message_between_string",
+            value!({"message": "500"}),
+            r#"["1" TO "6"]"#,
+            true,
+        ),
+        (
+            "not_range_message_between_string",
+            value!({"message": "500"}),
+            r#"NOT ["1" TO "6"]"#,
+            false,
+        ),
+        (
+            "negate_range_message_between_string",
+            value!({"message": "500"}),
+            r#"-["1" TO "6"]"#,
+            false,
+        ),
+        (
+            "range_message_between_no_match_string",
+            value!({"message": "70"}),
+            r#"["1" TO "6"]"#,
+            false,
+        ),
+        (
+            "not_range_message_between_no_match_string",
+            value!({"message": "70"}),
+            r#"NOT ["1" TO "6"]"#,
+            true,
+        ),
+        (
+            "negate_range_message_between_no_match_string",
+            value!({"message": "70"}),
+            r#"-["1" TO "6"]"#,
+            true,
+        ),
+        ("range_tag_key", value!({"tags": ["a"]}), "a:[* TO *]", true),
+        (
+            "range_tag_key_no_match",
+            value!({"tags": ["b"]}),
+            "a:[* TO *]",
+            false,
+        ),
+        (
+            "range_tag_unbounded",
+            value!({"tags": ["a:1"]}),
+            "a:[* TO *]",
+            true,
+        ),
+        (
+            "not_range_tag_unbounded",
+            value!({"tags": ["a:1"]}),
+            "NOT a:[* TO *]",
+            false,
+        ),
+        (
+            "negate_range_tag_unbounded",
+            value!({"tags": ["a:1"]}),
+            "-a:[* TO *]",
+            false,
+        ),
+        (
+            "range_tag_lower_bound",
+            value!({"tags": ["a:400"]}),
+            "a:[4 TO *]",
+            true,
+        ),
+        (
+            "not_range_tag_lower_bound",
+            value!({"tags": ["a:400"]}),
+            "NOT a:[4 TO *]",
+            false,
+        ),
+        (
+            "negate_range_tag_lower_bound",
+            value!({"tags": ["a:400"]}),
+            "-a:[4 TO *]",
+            false,
+        ),
+        (
+            "range_tag_lower_bound_no_match",
+            value!({"tags": ["a:400"]}),
+            "a:[50 TO *]",
+            false,
+        ),
+        (
+            "not_range_tag_lower_bound_no_match",
+            value!({"tags": ["a:400"]}),
+            "NOT a:[50 TO *]",
+            true,
+        ),
+        (
+            "negate_range_tag_lower_bound_no_match",
+            value!({"tags": ["a:400"]}),
+            "-a:[50 TO *]",
+            true,
+        ),
+        (
+            "range_tag_lower_bound_string",
+            value!({"tags": ["a:400"]}),
+            r#"a:["4" TO *]"#,
+            true,
+        ),
+        (
+            "not_range_tag_lower_bound_string",
+            value!({"tags": ["a:400"]}),
+            r#"NOT a:["4" TO *]"#,
+            false,
+        ),
+        (
+            "negate_range_tag_lower_bound_string",
+            value!({"tags": ["a:400"]}),
+            r#"-a:["4" TO *]"#,
+            false,
+        ),
+        (
+            "range_tag_lower_bound_string_no_match",
+            value!({"tags": ["a:400"]}),
+            r#"a:["50" TO *]"#,
+            false,
+        ),
+        (
+            "not_range_tag_lower_bound_string_no_match",
+            value!({"tags": ["a:400"]}),
+            r#"NOT a:["50" TO *]"#,
+            true,
+        ),
+        (
+            "negate_range_tag_lower_bound_string_no_match",
+            value!({"tags": ["a:400"]}),
+            r#"-a:["50" TO *]"#,
+            true,
+        ),
+        (
+            "range_tag_upper_bound",
+            value!({"tags": ["a:300"]}),
+            "a:[* TO 4]",
+            true,
+        ),
+        (
+            "not_range_tag_upper_bound",
+            value!({"tags": ["a:300"]}),
+            "NOT a:[* TO 4]",
+            false,
+        ),
+        (
+            "negate_range_tag_upper_bound",
+            value!({"tags": ["a:300"]}),
+            "-a:[* TO 4]",
+            false,
+        ),
+        (
+            "range_tag_upper_bound_no_match",
+            value!({"tags": ["a:50"]}),
+            "a:[* TO 400]",
+            false,
+        ),
+        (
+            "not_range_tag_upper_bound_no_match",
+            value!({"tags": ["a:50"]}),
+            "NOT a:[* TO 400]",
+            true,
+        ),
+        (
+            "negate_range_tag_upper_bound_no_match",
+            value!({"tags": ["a:50"]}),
+            "-a:[* TO 400]",
+            true,
+        ),
+        (
+            "range_tag_upper_bound_string",
+            value!({"tags": ["a:300"]}),
+            r#"a:[* TO "4"]"#,
+            true,
+        ),
+        (
+            "not_range_tag_upper_bound_string",
+            value!({"tags": ["a:300"]}),
+            r#"NOT a:[* TO "4"]"#,
+            false,
+        ),
+        (
+            "negate_range_tag_upper_bound_string",
+            value!({"tags": ["a:300"]}),
+            r#"-a:[* TO "4"]"#,
+            false,
+        ),
+        (
+            "range_tag_upper_bound_string_no_match",
+            value!({"tags": ["a:50"]}),
+            r#"a:[* TO "400"]"#,
+            false,
+        ),
+        (
+            "not_range_tag_upper_bound_string_no_match",
+            value!({"tags": ["a:50"]}),
+            r#"NOT a:[* TO "400"]"#,
+            true,
+        ),
+        (
+            "negate_range_tag_upper_bound_string_no_match",
+            value!({"tags": ["a:50"]}),
+            r#"-a:[* TO "400"]"#,
+            true,
+        ),
+        (
+            "range_tag_between",
+            value!({"tags": ["a:500"]}),
+            "a:[1 TO 6]",
+            true,
+        ),
+        (
+            "not_range_tag_between",
+            value!({"tags": ["a:500"]}),
+            "NOT a:[1 TO 6]",
+            false,
+        ),
+        (
+            "negate_range_tag_between",
+            value!({"tags": ["a:500"]}),
+            "-a:[1 TO 6]",
+            false,
+        ),
+        (
+            "range_tag_between_no_match",
+            value!({"tags": ["a:70"]}),
+            "a:[1 TO 6]",
+            false,
+        ),
+        (
+            "not_range_tag_between_no_match",
+            value!({"tags": ["a:70"]}),
+            "NOT a:[1 TO 6]",
+            true,
+        ),
+        (
+            "negate_range_tag_between_no_match",
+            value!({"tags": ["a:70"]}),
+            "-a:[1 TO 6]",
+            true,
+        ),
+        (
+            "range_tag_between_string",
+            value!({"tags": ["a:500"]}),
+            r#"a:["1" TO "6"]"#,
+            true,
+        ),
+        (
+            "not_range_tag_between_string",
+            value!({"tags": ["a:500"]}),
+            r#"NOT a:["1" TO "6"]"#,
+            false,
+        ),
+        (
+            "negate_range_tag_between_string",
+            value!({"tags": ["a:500"]}),
+            r#"-a:["1" TO "6"]"#,
+            false,
+        ),
+        (
+            "range_tag_between_no_match_string",
+            value!({"tags": ["a:70"]}),
+            r#"a:["1" TO "6"]"#,
+            false,
+        ),
+        (
+            "not_range_tag_between_no_match_string",
+            value!({"tags": ["a:70"]}),
+            r#"NOT a:["1" TO "6"]"#,
+            true,
+        ),
+        (
+            "negate_range_tag_between_no_match_string",
+            value!({"tags": ["a:70"]}),
+            r#"-a:["1" TO "6"]"#,
+            true,
+        ),
+        (
+            "range_facet_unbounded",
+            value!({"a": 1}),
+            "@a:[* TO *]",
+            true,
+        ),
+        (
+            "not_range_facet_unbounded",
+            value!({"a": 1}),
+            "NOT @a:[* TO *]",
+            false,
+        ),
+        (
+            "negate_range_facet_unbounded",
+            value!({"a": 1}),
+            "-@a:[* TO *]",
+            false,
+        ),
+        (
+            "range_facet_lower_bound",
+            value!({"a": 5}),
+            "@a:[4 TO *]",
+            true,
+        ),
+        (
+            "not_range_facet_lower_bound",
+            value!({"a": 5}),
+            "NOT @a:[4 TO *]",
+            false,
+        ),
+        (
+            "negate_range_facet_lower_bound",
+            value!({"a": 5}),
+            "-@a:[4 TO *]",
+            false,
+        ),
+        (
+            "range_facet_lower_bound_no_match",
+            value!({"a": 5}),
+            "@a:[50 TO *]",
+            false,
+        ),
+        (
+            "not_range_facet_lower_bound_no_match",
+            value!({"a": 5}),
+            "NOT @a:[50 TO *]",
+            true,
+        ),
+        (
+            "negate_range_facet_lower_bound_no_match",
+            value!({"a": 5}),
+            "-@a:[50 TO *]",
+            true,
+        ),
+        (
+            "range_facet_lower_bound_string",
+            value!({"a": "5"}),
+            r#"@a:["4" TO *]"#,
+            true,
+        ),
+        (
+            "not_range_facet_lower_bound_string",
+            value!({"a": "5"}),
+            r#"NOT @a:["4" TO *]"#,
+            false,
+        ),
+        (
+            "negate_range_facet_lower_bound_string",
+            value!({"a": "5"}),
+            r#"-@a:["4" TO *]"#,
+            false,
+        ),
+        (
+            "range_facet_lower_bound_string_no_match",
+            value!({"a": "400"}),
+            r#"@a:["50" TO *]"#,
+            false,
+        ),
+        (
+            "not_range_facet_lower_bound_string_no_match",
+            value!({"a": "400"}),
+            r#"NOT @a:["50" TO *]"#,
+            true,
+        ),
+        (
+            "negate_range_facet_lower_bound_string_no_match",
+            value!({"a": "400"}),
+            r#"-@a:["50" TO *]"#,
+            true,
+        ),
+        (
+            "range_facet_upper_bound",
+            value!({"a": 1}),
+            "@a:[* TO 4]",
+            true,
+        ),
+        (
+            "not_range_facet_upper_bound",
+            value!({"a": 1}),
+            "NOT @a:[* TO 4]",
+            false,
+        ),
+        (
+            "negate_range_facet_upper_bound",
+            value!({"a": 1}),
+            "-@a:[* TO 4]",
+            false,
+        ),
+        (
+            "range_facet_upper_bound_no_match",
+            value!({"a": 500}),
+            "@a:[* TO 400]",
+            false,
+        ),
+        (
+            "not_range_facet_upper_bound_no_match",
+            value!({"a": 500}),
+            "NOT @a:[* TO 400]",
+            true,
+        ),
+        (
+            "negate_range_facet_upper_bound_no_match",
+            value!({"a": 500}),
+            "-@a:[* TO 400]",
+            true,
+        ),
+        (
+            "range_facet_upper_bound_string",
+            value!({"a": "3"}),
+            r#"@a:[* TO "4"]"#,
+            true,
+        ),
+        (
+            "not_range_facet_upper_bound_string",
+            value!({"a": "3"}),
+            r#"NOT @a:[* TO "4"]"#,
+            false,
+        ),
+        (
+            "negate_range_facet_upper_bound_string",
+            value!({"a": "3"}),
+            r#"-@a:[* TO "4"]"#,
+            false,
+        ),
+        (
+            "range_facet_upper_bound_string_no_match",
+            value!({"a": "5"}),
+            r#"@a:[* TO "400"]"#,
+            false,
+        ),
+        (
+            "not_range_facet_upper_bound_string_no_match",
+            value!({"a": "5"}),
+            r#"NOT @a:[* TO "400"]"#,
+            true,
+        ),
+        (
+            "negate_range_facet_upper_bound_string_no_match",
+            value!({"a": "5"}),
+            r#"-@a:[* TO "400"]"#,
+            true,
+        ),
+        ("range_facet_between", value!({"a": 5}), "@a:[1 TO 6]", true),
+        (
+            "not_range_facet_between",
+            value!({"a": 5}),
+            "NOT @a:[1 TO 6]",
+            false,
+        ),
+        (
+            "negate_range_facet_between",
+            value!({"a": 5}),
+            "-@a:[1 TO 6]",
+            false,
+        ),
+        (
+            "range_facet_between_no_match",
+            value!({"a": 200}),
+            "@a:[1 TO 6]",
+            false,
+        ),
+        (
+            "not_range_facet_between_no_match",
+            value!({"a": 200}),
+            "NOT @a:[1 TO 6]",
+            true,
+        ),
+        (
+            "negate_range_facet_between_no_match",
+            value!({"a": 200}),
+            "-@a:[1 TO 6]",
+            true,
+        ),
+        (
+            "range_facet_between_string",
+            value!({"a": "500"}),
+            r#"@a:["1" TO "6"]"#,
+            true,
+        ),
+        (
+            "not_range_facet_between_string",
+            value!({"a": "500"}),
+            r#"NOT @a:["1" TO "6"]"#,
+            false,
+        ),
+        (
+            "negate_range_facet_between_string",
+            value!({"a": "500"}),
+            r#"-@a:["1" TO "6"]"#,
+            false,
+        ),
+        (
+            "range_facet_between_no_match_string",
+            value!({"a": "7"}),
+            r#"@a:["1" TO "60"]"#,
+            false,
+        ),
+        (
+            "not_range_facet_between_no_match_string",
+            value!({"a": "7"}),
+            r#"NOT @a:["1" TO "60"]"#,
+            true,
+        ),
+        (
+            "negate_range_facet_between_no_match_string",
+            value!({"a": "7"}),
+            r#"-@a:["1" TO "60"]"#,
+            true,
+        ),
+        (
+            "exclusive_range_message",
+            value!({"message": "100"}),
+            "{1 TO 2}",
+            true,
+        ),
+        (
+            "not_exclusive_range_message",
+            value!({"message": "100"}),
+            "NOT {1 TO 2}",
+            false,
+        ),
+        (
+            "negate_exclusive_range_message",
+            value!({"message": "100"}),
+            "-{1 TO 2}",
+            false,
+        ),
+        (
+            "exclusive_range_message_no_match",
+            value!({"message": "1"}),
+            "{1 TO 2}",
+            false,
+        ),
+        (
+            "not_exclusive_range_message_no_match",
+            value!({"message": "1"}),
+            "NOT {1 TO 2}",
+            true,
+        ),
+        (
+            "negate_exclusive_range_message_no_match",
+            value!({"message": "1"}),
+            "-{1 TO 2}",
+            true,
+        ),
+        (
+            "exclusive_range_message_lower",
+            value!({"message": "200"}),
+            "{1 TO *}",
+            true,
+        ),
+        (
+            "not_exclusive_range_message_lower",
+            value!({"message": "200"}),
+            "NOT {1 TO *}",
+            false,
+        ),
+        (
+            "negate_exclusive_range_message_lower",
+            value!({"message": "200"}),
+            "-{1 TO *}",
+            false,
+        ),
+        (
+            "exclusive_range_message_lower_no_match",
+            value!({"message": "1"}),
+            "{1 TO *}",
+            false,
+        ),
+        (
+            "not_exclusive_range_message_lower_no_match",
+            value!({"message": "1"}),
+            "NOT {1 TO *}",
+            true,
+        ),
+        (
+            "negate_exclusive_range_message_lower_no_match",
+            value!({"message": "1"}),
+            "-{1 TO *}",
+            true,
+        ),
+        (
+            "exclusive_range_message_upper",
+            value!({"message": "200"}),
+            "{* TO 3}",
+            true,
+        ),
+        (
+            "not_exclusive_range_message_upper",
+            value!({"message": "200"}),
+            "NOT {* TO 3}",
+            false,
+        ),
+        (
+            "negate_exclusive_range_message_upper",
+            value!({"message": "200"}),
+            "-{* TO 3}",
+            false,
+        ),
+        (
+            "exclusive_range_message_upper_no_match",
+            value!({"message": "3"}),
+            "{* TO 3}",
+            false,
+        ),
+        (
+            "not_exclusive_range_message_upper_no_match",
+            value!({"message": "3"}),
+            "NOT {* TO 3}",
+            true,
+        ),
+        (
+            "negate_exclusive_range_message_upper_no_match",
+            value!({"message": "3"}),
+            "-{* TO 3}",
+            true,
+        ),
+        (
+            "message_and",
+            value!({"message": "this contains that"}),
+            "this AND that",
+            true,
+        ),
+        (
+            "message_and_not",
+            value!({"message": "this contains that"}),
+            "this AND NOT that",
+            false,
+        ),
+        (
+            "message_or",
+            value!({"message": "only contains that"}),
+            "this OR that",
+            true,
+        ),
+        (
+            "message_or_not",
+            value!({"message": "only contains that"}),
+            "this OR NOT that",
+            false,
+        ),
+        (
+            "message_and_or",
+            value!({"message": "this contains that"}),
+            "this AND (that OR the_other)",
+            true,
+        ),
+        (
+            "not_message_and_or",
+            value!({"message": "this contains that"}),
+            "this AND NOT (that OR the_other)",
+            false,
+        ),
+        (
+            "negate_message_and_or",
+            value!({"message": "this contains that"}),
+            "this AND -(that OR the_other)",
+            false,
+        ),
+        (
+            "message_and_or_2",
+            value!({"message": "this contains the_other"}),
+            "this AND (that OR the_other)",
+            true,
+        ),
+        (
+            "not_message_and_or_2",
+            value!({"message": "this contains the_other"}),
+            "this AND NOT (that OR the_other)",
+            false,
+        ),
+        (
+            "negate_message_and_or_2",
+            value!({"message": "this contains the_other"}),
+            "this AND -(that OR the_other)",
+            false,
+        ),
+        (
+            "message_or_and",
+            value!({"message": "just this"}),
+            "this OR (that AND the_other)",
+            true,
+        ),
+        (
+            "message_or_and_no_match",
+            value!({"message": "that and nothing else"}),
+            "this OR (that AND the_other)",
+            false,
+        ),
+        (
+            "message_or_and_2",
+            value!({"message": "that plus the_other"}),
+            "this OR (that AND the_other)",
+            true,
+        ),
+        (
+            "message_or_and_2_no_match",
+            value!({"message": "nothing plus the_other"}),
+            "this OR (that AND the_other)",
+            false,
+        ),
+        (
+            "kitchen_sink",
+            value!({"host": "this"}),
+            "host:this OR ((@b:test* AND c:that) AND d:the_other @e:[1 TO 5])",
+            true,
+        ),
+        (
+            "kitchen_sink_2",
+            value!({"tags": ["c:that", "d:the_other"], "b": "testing", "e": 3}),
+            "host:this OR ((@b:test* AND c:that) AND d:the_other @e:[1 TO 5])",
+            true,
+        ),
+        (
+            "integer_range_float_value_match",
+            value!({"level": 7.0}),
+            "@level:[7 TO 10]",
+            true,
+        ),
+        (
+            "integer_range_float_value_no_match",
+            value!({"level": 6.9}),
+            "@level:[7 TO 10]",
+            false,
+        ),
+        (
+            "float_range_integer_value_match",
+            value!({"level": 7}),
+            "@level:[7.0 TO 10.0]",
+            true,
+        ),
+        (
+            "float_range_integer_value_no_match",
+            value!({"level": 6}),
+            "@level:[7.0 TO 10.0]",
+            false,
+        ),
+        ("path_parser_hyphen", value!({"a-b": "3"}), "@a-b:3", true),
+        (
+            "quoted_query_key_match",
+            value!({"a-b": 1}),
+            "@\\\"a-b\\\":1",
+            true,
+        ),
+    ]
+}
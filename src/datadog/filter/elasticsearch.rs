@@ -0,0 +1,333 @@
+use serde_json::{Value, json};
+
+use super::mapping::FieldName;
+use super::{BooleanType, Field, FieldMapping, QueryNode, Resolver};
+use crate::datadog::search::{Comparison, ComparisonValue};
+use crate::path::PathParseError;
+
+impl QueryNode {
+    /// Translates the query into the Elasticsearch query DSL, with the fields named by
+    /// `mapping`.
+    ///
+    /// Default fields are expected to be analyzed `text` fields, so terms match whole phrases of
+    /// words in them, and prefixes and wildcards match words. Other fields, including tags, are
+    /// expected to be `keyword` fields, and are matched as a whole.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the query contains an invalid path.
+    pub fn to_elasticsearch(&self, mapping: &FieldMapping) -> Result<Value, PathParseError> {
+        let query = match self {
+            Self::MatchNoDocs => json!({ "match_none": {} }),
+            Self::MatchAllDocs => json!({ "match_all": {} }),
+            Self::AttributeExists { attr } => any(fields(mapping, attr, exists)?),
+            Self::AttributeMissing { attr } => all(fields(mapping, attr, |name, field| {
+                not(&exists(name, field))
+            })?),
+            Self::AttributeTerm { attr, value }
+            | Self::QuotedAttribute {
+                attr,
+                phrase: value,
+            } => any(fields(mapping, attr, |name, field| {
+                equals(name, field, value)
+            })?),
+            Self::AttributePrefix { attr, prefix } => any(fields(mapping, attr, |name, field| {
+                self::prefix(name, field, prefix)
+            })?),
+            Self::AttributeWildcard { attr, wildcard } => {
+                any(fields(mapping, attr, |name, field| {
+                    self::wildcard(name, field, wildcard)
+                })?)
+            }
+            Self::AttributeComparison {
+                attr,
+                comparator,
+                value,
+            } => any(fields(mapping, attr, |name, field| {
+                range(name, field, &[(*comparator, value)])
+            })?),
+            Self::AttributeRange {
+                attr,
+                lower,
+                lower_inclusive,
+                upper,
+                upper_inclusive,
+            } => {
+                let lower_op = if *lower_inclusive {
+                    Comparison::Gte
+                } else {
+                    Comparison::Gt
+                };
+                let upper_op = if *upper_inclusive {
+                    Comparison::Lte
+                } else {
+                    Comparison::Lt
+                };
+                let bounds = [(lower_op, lower), (upper_op, upper)]
+                    .into_iter()
+                    .filter(|(_, value)| **value != ComparisonValue::Unbounded)
+                    .collect::<Vec<_>>();
+
+                // If both bounds are wildcards, just check that the field exists to catch the
+                // special case for "tags".
+                if bounds.is_empty() {
+                    any(fields(mapping, attr, exists)?)
+                } else {
+                    any(fields(mapping, attr, |name, field| {
+                        range(name, field, &bounds)
+                    })?)
+                }
+            }
+            Self::NegatedNode { node } => not(&node.to_elasticsearch(mapping)?),
+            Self::Boolean { oper, nodes } => {
+                let queries = nodes
+                    .iter()
+                    .map(|node| node.to_elasticsearch(mapping))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                match oper {
+                    BooleanType::And => all(queries),
+                    BooleanType::Or => any(queries),
+                }
+            }
+        };
+
+        Ok(query)
+    }
+}
+
+/// Returns a query of each field of `attr` that is stored, named as in `mapping`.
+fn fields(
+    mapping: &FieldMapping,
+    attr: &str,
+    query: impl Fn(String, Field) -> Value,
+) -> Result<Vec<Value>, PathParseError> {
+    let mut queries = Vec::new();
+    for field in mapping.build_fields(attr) {
+        let name = match mapping.name(&field)? {
+            FieldName::Mapped(name) => name.to_owned(),
+            FieldName::Path { prefix, segments } => format!("{prefix}{}", segments.join(".")),
+            FieldName::Absent => continue,
+        };
+        queries.push(query(name, field));
+    }
+
+    Ok(queries)
+}
+
+fn any(mut queries: Vec<Value>) -> Value {
+    match queries.len() {
+        0 => json!({ "match_none": {} }),
+        1 => queries.remove(0),
+        _ => json!({ "bool": { "should": queries, "minimum_should_match": 1 } }),
+    }
+}
+
+fn all(mut queries: Vec<Value>) -> Value {
+    match queries.len() {
+        0 => json!({ "match_all": {} }),
+        1 => queries.remove(0),
+        _ => json!({ "bool": { "filter": queries } }),
+    }
+}
+
+fn not(query: &Value) -> Value {
+    json!({ "bool": { "must_not": [query] } })
+}
+
+/// Escapes the characters of `value` that have a special meaning in a wildcard query.
+fn wildcard_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '*' | '?') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn exists(name: String, field: Field) -> Value {
+    match field {
+        // The tag matches using either 'key' or 'key:value' syntax.
+        Field::Tag(tag) => any(vec![
+            json!({ "term": { name.clone(): tag } }),
+            json!({ "prefix": { name: format!("{tag}:") } }),
+        ]),
+        _ => json!({ "exists": { "field": name } }),
+    }
+}
+
+fn equals(name: String, field: Field, to_match: &str) -> Value {
+    match field {
+        // Default fields are matched by words.
+        Field::Default(_) => json!({ "match_phrase": { name: to_match } }),
+        // Individual tags are compared by element key:value.
+        Field::Tag(tag) => json!({ "term": { name: format!("{tag}:{to_match}") } }),
+        // Everything else, including a literal "tags" field, is matched by equality.
+        _ => json!({ "term": { name: to_match } }),
+    }
+}
+
+fn prefix(name: String, field: Field, prefix: &str) -> Value {
+    match field {
+        Field::Default(_) => json!({ "match_phrase_prefix": { name: prefix } }),
+        Field::Tag(tag) => json!({ "prefix": { name: format!("{tag}:{prefix}") } }),
+        _ => json!({ "prefix": { name: prefix } }),
+    }
+}
+
+fn wildcard(name: String, field: Field, wildcard: &str) -> Value {
+    let pattern = wildcard
+        .split('*')
+        .map(wildcard_escape)
+        .collect::<Vec<_>>()
+        .join("*");
+
+    match field {
+        Field::Tag(tag) => {
+            json!({ "wildcard": { name: format!("{}:{pattern}", wildcard_escape(&tag)) } })
+        }
+        _ => json!({ "wildcard": { name: pattern } }),
+    }
+}
+
+fn range(name: String, field: Field, bounds: &[(Comparison, &ComparisonValue)]) -> Value {
+    let mut range = serde_json::Map::new();
+    match field {
+        // Tag values are compared as strings, within the tags that start with the key.
+        Field::Tag(tag) => {
+            for (comparator, value) in bounds {
+                range.insert(operator(*comparator), json!(format!("{tag}:{value}")));
+            }
+            if !bounds
+                .iter()
+                .any(|(comparator, _)| matches!(comparator, Comparison::Gt | Comparison::Gte))
+            {
+                range.insert(operator(Comparison::Gte), json!(format!("{tag}:")));
+            }
+            if !bounds
+                .iter()
+                .any(|(comparator, _)| matches!(comparator, Comparison::Lt | Comparison::Lte))
+            {
+                // The character after ':'.
+                range.insert(operator(Comparison::Lt), json!(format!("{tag};")));
+            }
+        }
+        // Attributes are compared numerically if the value is numeric.
+        Field::Attribute(_) => {
+            for (comparator, value) in bounds {
+                let value = match value {
+                    ComparisonValue::Integer(integer) => json!(integer),
+                    ComparisonValue::Float(float) => json!(float),
+                    value => json!(value.to_string()),
+                };
+                range.insert(operator(*comparator), value);
+            }
+        }
+        // All other values are compared as strings.
+        _ => {
+            for (comparator, value) in bounds {
+                range.insert(operator(*comparator), json!(value.to_string()));
+            }
+        }
+    }
+
+    json!({ "range": { name: range } })
+}
+
+fn operator(comparator: Comparison) -> String {
+    match comparator {
+        Comparison::Gt => "gt",
+        Comparison::Gte => "gte",
+        Comparison::Lt => "lt",
+        Comparison::Lte => "lte",
+    }
+    .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(query: &str) -> Value {
+        let mapping = FieldMapping::new()
+            .without_field("custom.error.message")
+            .without_field("custom.error.stack")
+            .without_field("custom.title")
+            .without_field("_default_")
+            .with_attribute_prefix("attributes.");
+
+        query
+            .parse::<QueryNode>()
+            .unwrap()
+            .to_elasticsearch(&mapping)
+            .unwrap()
+    }
+
+    #[test]
+    fn default_fields() {
+        assert_eq!(
+            translate("error"),
+            json!({ "match_phrase": { "message": "error" } })
+        );
+        assert_eq!(
+            "vec*"
+                .parse::<QueryNode>()
+                .unwrap()
+                .to_elasticsearch(&FieldMapping::new())
+                .unwrap()["bool"]["should"][3],
+            json!({ "match_phrase_prefix": { "custom.title": "vec" } })
+        );
+    }
+
+    #[test]
+    fn attributes_and_reserved_fields() {
+        assert_eq!(
+            translate("@http.status:[400 TO 500] AND service:web* AND NOT @user.name:j?n*"),
+            json!({ "bool": { "filter": [
+                { "range": { "attributes.http.status": { "gte": 400, "lte": 500 } } },
+                { "prefix": { "service": "web" } },
+                { "bool": { "must_not": [
+                    { "wildcard": { "attributes.user.name": "j\\?n*" } },
+                ] } },
+            ] } })
+        );
+        assert_eq!(
+            translate("_missing_:@a OR @b:>=x"),
+            json!({ "bool": { "should": [
+                { "bool": { "must_not": [{ "exists": { "field": "attributes.a" } }] } },
+                { "range": { "attributes.b": { "gte": "x" } } },
+            ], "minimum_should_match": 1 } })
+        );
+    }
+
+    #[test]
+    fn tags() {
+        assert_eq!(
+            translate("env:prod AND tags:beta AND _exists_:team"),
+            json!({ "bool": { "filter": [
+                { "term": { "tags": "env:prod" } },
+                { "term": { "tags": "beta" } },
+                { "bool": { "should": [
+                    { "term": { "tags": "team" } },
+                    { "prefix": { "tags": "team:" } },
+                ], "minimum_should_match": 1 } },
+            ] } })
+        );
+        assert_eq!(
+            translate("version:>2 OR version:[1 TO 3]"),
+            json!({ "bool": { "should": [
+                { "range": { "tags": { "gt": "version:2", "lt": "version;" } } },
+                { "range": { "tags": { "gte": "version:1", "lte": "version:3" } } },
+            ], "minimum_should_match": 1 } })
+        );
+    }
+
+    #[test]
+    fn invalid_path() {
+        let node = "@a%:3".parse::<QueryNode>().unwrap();
+
+        assert!(node.to_elasticsearch(&FieldMapping::new()).is_err());
+    }
+}
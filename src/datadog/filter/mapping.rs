@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use super::{Field, Resolver};
+use crate::path::{OwnedSegment, PathParseError, parse_value_path};
+
+/// Names the fields of documents, or the columns of rows, that Datadog Search Syntax fields are
+/// stored in, for translating queries into other query languages.
+///
+/// Attributes are stored under their path, prefixed with the attribute prefix. Default and
+/// reserved fields are stored under their own name, unless they are mapped to another name, or
+/// to none at all, in which case they are treated as missing. Tags are stored in a single field,
+/// as an array of `key:value` strings.
+#[derive(Clone, Debug)]
+pub struct FieldMapping {
+    fields: HashMap<String, Option<String>>,
+    attribute_prefix: String,
+    tags: String,
+}
+
+/// The name a [`Field`] is stored under.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum FieldName<'a> {
+    /// A name given by the mapping, which is used as-is.
+    Mapped(&'a str),
+
+    /// The path segments of a field stored under its own name, after `prefix`.
+    Path {
+        prefix: &'a str,
+        segments: Vec<String>,
+    },
+
+    /// The field isn't stored.
+    Absent,
+}
+
+impl Default for FieldMapping {
+    fn default() -> Self {
+        Self {
+            fields: HashMap::new(),
+            attribute_prefix: String::new(),
+            tags: "tags".to_owned(),
+        }
+    }
+}
+
+impl FieldMapping {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the default or reserved field `field` under `name`.
+    #[must_use]
+    pub fn with_field(mut self, field: impl Into<String>, name: impl Into<String>) -> Self {
+        self.fields.insert(field.into(), Some(name.into()));
+        self
+    }
+
+    /// Treats the default or reserved field `field` as missing.
+    #[must_use]
+    pub fn without_field(mut self, field: impl Into<String>) -> Self {
+        self.fields.insert(field.into(), None);
+        self
+    }
+
+    /// Prefixes the names of attributes with `prefix`, such as `attributes.`.
+    #[must_use]
+    pub fn with_attribute_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.attribute_prefix = prefix.into();
+        self
+    }
+
+    /// Stores tags under `name`.
+    #[must_use]
+    pub fn with_tags(mut self, name: impl Into<String>) -> Self {
+        self.tags = name.into();
+        self
+    }
+
+    /// The name tags are stored under.
+    #[must_use]
+    pub fn tags(&self) -> &str {
+        &self.tags
+    }
+
+    /// Returns the name `field` is stored under.
+    pub(crate) fn name(&self, field: &Field) -> Result<FieldName<'_>, PathParseError> {
+        let (path, prefix) = match field {
+            Field::Tag(_) => return Ok(FieldName::Mapped(&self.tags)),
+            Field::Reserved(f) if f == "tags" => return Ok(FieldName::Mapped(&self.tags)),
+            Field::Default(f) | Field::Reserved(f) => match self.fields.get(f) {
+                Some(Some(name)) => return Ok(FieldName::Mapped(name)),
+                Some(None) => return Ok(FieldName::Absent),
+                None => (f, ""),
+            },
+            Field::Attribute(f) => (f, self.attribute_prefix.as_str()),
+        };
+
+        // Indices can't be part of a name.
+        let segments = parse_value_path(path)?
+            .segments
+            .into_iter()
+            .map(|segment| match segment {
+                OwnedSegment::Field(field) => Ok(field.into()),
+                OwnedSegment::Index(_) => {
+                    Err(PathParseError::InvalidPathSyntax { path: path.clone() })
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(FieldName::Path { prefix, segments })
+    }
+}
+
+impl Resolver for FieldMapping {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        let mapping = FieldMapping::new()
+            .with_field("message", "body")
+            .without_field("custom.title")
+            .with_attribute_prefix("attributes.")
+            .with_tags("labels");
+
+        assert_eq!(
+            mapping.name(&Field::Default("message".to_owned())),
+            Ok(FieldName::Mapped("body"))
+        );
+        assert_eq!(
+            mapping.name(&Field::Default("custom.title".to_owned())),
+            Ok(FieldName::Absent)
+        );
+        assert_eq!(
+            mapping.name(&Field::Reserved("host".to_owned())),
+            Ok(FieldName::Path {
+                prefix: "",
+                segments: vec!["host".to_owned()]
+            })
+        );
+        assert_eq!(
+            mapping.name(&Field::Attribute(".a.\"b-c\"".to_owned())),
+            Ok(FieldName::Path {
+                prefix: "attributes.",
+                segments: vec!["a".to_owned(), "b-c".to_owned()]
+            })
+        );
+        assert_eq!(
+            mapping.name(&Field::Tag("env".to_owned())),
+            Ok(FieldName::Mapped("labels"))
+        );
+        assert!(mapping.name(&Field::Attribute(".a[0]".to_owned())).is_err());
+    }
+}
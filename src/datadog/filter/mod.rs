@@ -1,13 +1,24 @@
 #![deny(warnings, clippy::pedantic)]
 
+#[cfg(test)]
+pub(crate) mod cases;
+mod elasticsearch;
 #[allow(clippy::module_inception)]
 mod filter;
+mod mapping;
 mod matcher;
 pub mod regex;
 mod resolver;
+mod sql;
+#[cfg(feature = "parser")]
+mod vrl;
 
 pub use filter::*;
+pub use mapping::FieldMapping;
 pub use matcher::*;
 pub use resolver::*;
+pub use sql::{SqlCondition, SqlDialect, SqlParameter};
+#[cfg(feature = "parser")]
+pub use vrl::FromVrlError;
 
 use super::search::{BooleanType, Field, QueryNode, normalize_fields};
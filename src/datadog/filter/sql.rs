@@ -0,0 +1,540 @@
+use super::mapping::FieldName;
+use super::{BooleanType, Field, FieldMapping, QueryNode, Resolver};
+use crate::datadog::search::{Comparison, ComparisonValue};
+use crate::path::PathParseError;
+
+/// The dialect of SQL that queries are translated into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// Uses `?` placeholders, and an `Array(String)` column of tags.
+    ClickHouse,
+
+    /// Uses `$1` placeholders, and a `text[]` column of tags.
+    PostgreSql,
+}
+
+/// A parameter of a translated SQL condition.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlParameter {
+    String(String),
+    Integer(i64),
+    Float(f64),
+}
+
+/// A SQL condition for a `WHERE` clause, with placeholders for its parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SqlCondition {
+    pub sql: String,
+
+    /// The values of the placeholders, in order.
+    pub parameters: Vec<SqlParameter>,
+}
+
+impl QueryNode {
+    /// Translates the query into a SQL condition in `dialect`, with the columns named by
+    /// `mapping`.
+    ///
+    /// Values from the query are passed as parameters, and names of attributes are quoted.
+    /// Nested paths are read from JSON in `PostgreSql`, with `->` and `->>` on the column of
+    /// their first segment, and as nested columns in `ClickHouse`. Mapped names are used as-is,
+    /// so they can be any column expression.
+    ///
+    /// Terms are matched against default fields by word boundary, with a regular expression, and
+    /// a negated condition also matches rows where the condition is `NULL`.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the query contains an invalid path.
+    pub fn to_sql(
+        &self,
+        mapping: &FieldMapping,
+        dialect: SqlDialect,
+    ) -> Result<SqlCondition, PathParseError> {
+        let mut translator = Translator {
+            mapping,
+            dialect,
+            parameters: Vec::new(),
+        };
+        let condition = translator.node(self)?;
+
+        Ok(SqlCondition {
+            sql: condition.sql,
+            parameters: translator.parameters,
+        })
+    }
+}
+
+struct Condition {
+    sql: String,
+
+    /// The operator joining the operands of the condition, if any, which determines whether it
+    /// needs parentheses.
+    operator: Option<BooleanType>,
+}
+
+impl Condition {
+    fn new(sql: String) -> Self {
+        Self {
+            sql,
+            operator: None,
+        }
+    }
+}
+
+struct Translator<'a> {
+    mapping: &'a FieldMapping,
+    dialect: SqlDialect,
+    parameters: Vec<SqlParameter>,
+}
+
+impl Translator<'_> {
+    fn node(&mut self, node: &QueryNode) -> Result<Condition, PathParseError> {
+        let condition = match node {
+            QueryNode::MatchNoDocs => Condition::new("FALSE".to_owned()),
+            QueryNode::MatchAllDocs => Condition::new("TRUE".to_owned()),
+            QueryNode::AttributeExists { attr } => {
+                let conditions =
+                    self.fields(attr, |this, column, field| this.exists(column, &field))?;
+                join(BooleanType::Or, conditions)
+            }
+            QueryNode::AttributeMissing { attr } => {
+                let conditions = self.fields(attr, |this, column, field| match field {
+                    Field::Tag(_) => not(&this.exists(column, &field)),
+                    _ => Condition::new(format!("{column} IS NULL")),
+                })?;
+                join(BooleanType::And, conditions)
+            }
+            QueryNode::AttributeTerm { attr, value }
+            | QueryNode::QuotedAttribute {
+                attr,
+                phrase: value,
+            } => {
+                let conditions = self.fields(attr, |this, column, field| {
+                    this.equals(column, &field, value)
+                })?;
+                join(BooleanType::Or, conditions)
+            }
+            QueryNode::AttributePrefix { attr, prefix } => {
+                let conditions = self.fields(attr, |this, column, field| {
+                    this.wildcard(column, field, &format!("{prefix}*"))
+                })?;
+                join(BooleanType::Or, conditions)
+            }
+            QueryNode::AttributeWildcard { attr, wildcard } => {
+                let conditions = self.fields(attr, |this, column, field| {
+                    this.wildcard(column, field, wildcard)
+                })?;
+                join(BooleanType::Or, conditions)
+            }
+            QueryNode::AttributeComparison {
+                attr,
+                comparator,
+                value,
+            } => {
+                let conditions = self.fields(attr, |this, column, field| {
+                    this.compare(column, &field, &[(*comparator, value)])
+                })?;
+                join(BooleanType::Or, conditions)
+            }
+            QueryNode::AttributeRange {
+                attr,
+                lower,
+                lower_inclusive,
+                upper,
+                upper_inclusive,
+            } => {
+                let lower_op = if *lower_inclusive {
+                    Comparison::Gte
+                } else {
+                    Comparison::Gt
+                };
+                let upper_op = if *upper_inclusive {
+                    Comparison::Lte
+                } else {
+                    Comparison::Lt
+                };
+                let bounds = [(lower_op, lower), (upper_op, upper)]
+                    .into_iter()
+                    .filter(|(_, value)| **value != ComparisonValue::Unbounded)
+                    .collect::<Vec<_>>();
+
+                // If both bounds are wildcards, just check that the field exists to catch the
+                // special case for "tags".
+                let conditions = self.fields(attr, |this, column, field| {
+                    if bounds.is_empty() {
+                        this.exists(column, &field)
+                    } else {
+                        this.compare(column, &field, &bounds)
+                    }
+                })?;
+                join(BooleanType::Or, conditions)
+            }
+            QueryNode::NegatedNode { node } => not(&self.node(node)?),
+            QueryNode::Boolean { oper, nodes } => {
+                let conditions = nodes
+                    .iter()
+                    .map(|node| self.node(node))
+                    .collect::<Result<Vec<_>, _>>()?;
+                join(*oper, conditions)
+            }
+        };
+
+        Ok(condition)
+    }
+
+    /// Returns a condition of each field of `attr` that is stored, with its column.
+    fn fields(
+        &mut self,
+        attr: &str,
+        mut condition: impl FnMut(&mut Self, &str, Field) -> Condition,
+    ) -> Result<Vec<Condition>, PathParseError> {
+        let mut conditions = Vec::new();
+        for field in self.mapping.build_fields(attr) {
+            let column = match self.mapping.name(&field)? {
+                FieldName::Mapped(name) => name.to_owned(),
+                FieldName::Path { prefix, segments } => self.dialect.path(prefix, &segments),
+                FieldName::Absent => continue,
+            };
+            conditions.push(condition(self, &column, field));
+        }
+
+        Ok(conditions)
+    }
+
+    /// Adds a parameter, and returns its placeholder.
+    fn parameter(&mut self, parameter: SqlParameter) -> String {
+        self.parameters.push(parameter);
+        match self.dialect {
+            SqlDialect::ClickHouse => "?".to_owned(),
+            SqlDialect::PostgreSql => format!("${}", self.parameters.len()),
+        }
+    }
+
+    fn string(&mut self, value: impl Into<String>) -> String {
+        self.parameter(SqlParameter::String(value.into()))
+    }
+
+    /// Returns a condition that holds if `condition` holds for any tag in `column`, as `tag`.
+    fn any_tag(&self, column: &str, condition: &str) -> Condition {
+        Condition::new(match self.dialect {
+            SqlDialect::ClickHouse => format!("arrayExists(tag -> {condition}, {column})"),
+            SqlDialect::PostgreSql => {
+                format!("EXISTS (SELECT 1 FROM unnest({column}) AS t(tag) WHERE {condition})")
+            }
+        })
+    }
+
+    fn matches_words(&mut self, column: &str, wildcard: &str) -> Condition {
+        let boundary = match self.dialect {
+            SqlDialect::ClickHouse => r"\b",
+            SqlDialect::PostgreSql => r"\y",
+        };
+        let regex = regex::escape(wildcard).replace(r"\*", ".*");
+        let pattern = self.string(format!("{boundary}{regex}{boundary}"));
+
+        Condition::new(match self.dialect {
+            SqlDialect::ClickHouse => format!("match({column}, {pattern})"),
+            SqlDialect::PostgreSql => format!("{column} ~ {pattern}"),
+        })
+    }
+
+    fn exists(&mut self, column: &str, field: &Field) -> Condition {
+        match field {
+            // The tag matches using either 'key' or 'key:value' syntax.
+            Field::Tag(tag) => {
+                let key = self.string(tag);
+                let prefix = self.string(format!("{}:%", like_escape(tag)));
+                self.any_tag(column, &format!("tag = {key} OR tag LIKE {prefix}"))
+            }
+            _ => Condition::new(format!("{column} IS NOT NULL")),
+        }
+    }
+
+    fn equals(&mut self, column: &str, field: &Field, to_match: &str) -> Condition {
+        let value = match field {
+            // Default fields are compared by word boundary.
+            Field::Default(_) => return self.matches_words(column, to_match),
+            // A literal "tags" field should match by key.
+            Field::Reserved(f) if f == "tags" => self.string(to_match),
+            // Individual tags are compared by element key:value.
+            Field::Tag(tag) => self.string(format!("{tag}:{to_match}")),
+            // Everything else is matched by string equality.
+            _ => {
+                let value = self.string(to_match);
+                return Condition::new(format!("{column} = {value}"));
+            }
+        };
+
+        Condition::new(match self.dialect {
+            SqlDialect::ClickHouse => format!("has({column}, {value})"),
+            SqlDialect::PostgreSql => format!("{value} = ANY({column})"),
+        })
+    }
+
+    fn wildcard(&mut self, column: &str, field: Field, wildcard: &str) -> Condition {
+        let pattern = wildcard
+            .split('*')
+            .map(like_escape)
+            .collect::<Vec<_>>()
+            .join("%");
+
+        match field {
+            Field::Default(_) => self.matches_words(column, wildcard),
+            Field::Tag(tag) => {
+                let pattern = self.string(format!("{}:{pattern}", like_escape(&tag)));
+                self.any_tag(column, &format!("tag LIKE {pattern}"))
+            }
+            _ => {
+                let pattern = self.string(pattern);
+                Condition::new(format!("{column} LIKE {pattern}"))
+            }
+        }
+    }
+
+    fn compare(
+        &mut self,
+        column: &str,
+        field: &Field,
+        bounds: &[(Comparison, &ComparisonValue)],
+    ) -> Condition {
+        // Tag values are compared as strings, within the tags that start with the key.
+        if let Field::Tag(tag) = field {
+            let prefix = self.string(format!("{}:%", like_escape(tag)));
+            let mut conditions = vec![format!("tag LIKE {prefix}")];
+            for (comparator, value) in bounds {
+                let value = self.string(format!("{tag}:{value}"));
+                conditions.push(format!("tag {} {value}", comparator.as_lucene()));
+            }
+            return self.any_tag(column, &conditions.join(" AND "));
+        }
+
+        // Attributes are compared numerically if the value is numeric, or as strings otherwise.
+        // All other values are compared as strings. JSON values are read as text, so they are
+        // cast to compare them numerically.
+        let numeric_column = match self.mapping.name(field) {
+            Ok(FieldName::Path { segments, .. })
+                if self.dialect == SqlDialect::PostgreSql && segments.len() > 1 =>
+            {
+                format!("({column})::numeric")
+            }
+            _ => column.to_owned(),
+        };
+        let conditions = bounds
+            .iter()
+            .map(|(comparator, value)| {
+                let (column, value) = match (field, value) {
+                    (Field::Attribute(_), ComparisonValue::Integer(integer)) => (
+                        numeric_column.as_str(),
+                        self.parameter(SqlParameter::Integer(*integer)),
+                    ),
+                    (Field::Attribute(_), ComparisonValue::Float(float)) => (
+                        numeric_column.as_str(),
+                        self.parameter(SqlParameter::Float(*float)),
+                    ),
+                    _ => (column, self.string(value.to_string())),
+                };
+                Condition::new(format!("{column} {} {value}", comparator.as_lucene()))
+            })
+            .collect();
+        join(BooleanType::And, conditions)
+    }
+}
+
+impl SqlDialect {
+    fn quote(self, identifier: &str) -> String {
+        match self {
+            SqlDialect::ClickHouse => {
+                format!("`{}`", identifier.replace('\\', r"\\").replace('`', r"\`"))
+            }
+            SqlDialect::PostgreSql => format!("\"{}\"", identifier.replace('"', "\"\"")),
+        }
+    }
+
+    /// Returns the column expression of a path of `segments` after `prefix`.
+    fn path(self, prefix: &str, segments: &[String]) -> String {
+        if let (SqlDialect::PostgreSql, [column, keys @ .., last]) = (self, segments) {
+            let mut path = format!("{prefix}{}", self.quote(column));
+            for key in keys {
+                path.push_str("->");
+                path.push_str(&string_literal(key));
+            }
+            path.push_str("->>");
+            path.push_str(&string_literal(last));
+            return path;
+        }
+
+        let segments = segments
+            .iter()
+            .map(|segment| self.quote(segment))
+            .collect::<Vec<_>>();
+        format!("{prefix}{}", segments.join("."))
+    }
+}
+
+/// Returns `value` as a `PostgreSql` string literal.
+fn string_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Escapes the characters of `value` that have a special meaning in a `LIKE` pattern.
+fn like_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Joins `conditions` with `operator`. An empty `AND` is `TRUE`, and an empty `OR` is `FALSE`.
+fn join(operator: BooleanType, mut conditions: Vec<Condition>) -> Condition {
+    let (separator, empty) = match operator {
+        BooleanType::And => (" AND ", "TRUE"),
+        BooleanType::Or => (" OR ", "FALSE"),
+    };
+
+    match conditions.len() {
+        0 => Condition::new(empty.to_owned()),
+        1 => conditions.remove(0),
+        _ => Condition {
+            sql: conditions
+                .iter()
+                .map(|condition| match condition.operator {
+                    Some(other) if other != operator => format!("({})", condition.sql),
+                    _ => condition.sql.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(separator),
+            operator: Some(operator),
+        },
+    }
+}
+
+/// Negates `condition`, treating `NULL` as false.
+fn not(condition: &Condition) -> Condition {
+    Condition::new(format!("NOT COALESCE({}, FALSE)", condition.sql))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(query: &str, dialect: SqlDialect) -> SqlCondition {
+        let mapping = FieldMapping::new()
+            .with_field("message", "body")
+            .without_field("custom.error.message")
+            .without_field("custom.error.stack")
+            .without_field("custom.title")
+            .without_field("_default_")
+            .with_attribute_prefix("attributes.");
+
+        query
+            .parse::<QueryNode>()
+            .unwrap()
+            .to_sql(&mapping, dialect)
+            .unwrap()
+    }
+
+    fn strings(values: &[&str]) -> Vec<SqlParameter> {
+        values
+            .iter()
+            .map(|value| SqlParameter::String((*value).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn clickhouse() {
+        assert_eq!(
+            translate(
+                "error AND service:web* AND (@http.status:>=500 OR NOT @user-id:1_0)",
+                SqlDialect::ClickHouse
+            ),
+            SqlCondition {
+                sql: "match(body, ?) AND `service` LIKE ? AND (attributes.`http`.`status` >= ? OR NOT COALESCE(attributes.`user-id` = ?, FALSE))".to_owned(),
+                parameters: vec![
+                    SqlParameter::String(r"\berror\b".to_owned()),
+                    SqlParameter::String("web%".to_owned()),
+                    SqlParameter::Integer(500),
+                    SqlParameter::String("1_0".to_owned()),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn postgresql() {
+        assert_eq!(
+            translate("v*c_r OR _missing_:@a OR @b:[1.5 TO \"z\"]", SqlDialect::PostgreSql),
+            SqlCondition {
+                sql: r#"body ~ $1 OR attributes."a" IS NULL OR (attributes."b" >= $2 AND attributes."b" <= $3)"#.to_owned(),
+                parameters: vec![
+                    SqlParameter::String(r"\yv.*c_r\y".to_owned()),
+                    SqlParameter::Float(1.5),
+                    SqlParameter::String("z".to_owned()),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn postgresql_nested_attributes() {
+        assert_eq!(
+            translate(
+                "@user.name:jane AND @http.response.status:>=500 AND _exists_:@a.b",
+                SqlDialect::PostgreSql
+            ),
+            SqlCondition {
+                sql: r#"attributes."user"->>'name' = $1 AND (attributes."http"->'response'->>'status')::numeric >= $2 AND attributes."a"->>'b' IS NOT NULL"#.to_owned(),
+                parameters: vec![
+                    SqlParameter::String("jane".to_owned()),
+                    SqlParameter::Integer(500),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn tags() {
+        assert_eq!(
+            translate("env:prod AND tags:beta AND _exists_:team", SqlDialect::ClickHouse),
+            SqlCondition {
+                sql: "has(tags, ?) AND has(tags, ?) AND arrayExists(tag -> tag = ? OR tag LIKE ?, tags)".to_owned(),
+                parameters: strings(&["env:prod", "beta", "team", "team:%"]),
+            }
+        );
+        assert_eq!(
+            translate("NOT env:prod* AND version:[1 TO *]", SqlDialect::PostgreSql),
+            SqlCondition {
+                sql: "NOT COALESCE(EXISTS (SELECT 1 FROM unnest(tags) AS t(tag) WHERE tag LIKE $1), FALSE) AND EXISTS (SELECT 1 FROM unnest(tags) AS t(tag) WHERE tag LIKE $2 AND tag >= $3)".to_owned(),
+                parameters: strings(&["env:prod%", "version:%", "version:1"]),
+            }
+        );
+    }
+
+    #[test]
+    fn default_fields() {
+        let sql = "hello"
+            .parse::<QueryNode>()
+            .unwrap()
+            .to_sql(&FieldMapping::new(), SqlDialect::PostgreSql)
+            .unwrap()
+            .sql;
+
+        assert_eq!(
+            sql,
+            r#""message" ~ $1 OR "custom"->'error'->>'message' ~ $2 OR "custom"->'error'->>'stack' ~ $3 OR "custom"->>'title' ~ $4 OR "_default_" ~ $5"#
+        );
+    }
+
+    #[test]
+    fn invalid_path() {
+        let node = "@a%:3".parse::<QueryNode>().unwrap();
+
+        assert!(
+            node.to_sql(&FieldMapping::new(), SqlDialect::ClickHouse)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,1236 @@
+//! Translation between Datadog Search Syntax queries and VRL conditions.
+//!
+//! [`QueryNode::to_vrl`] mirrors [`QueryNode::build_matcher`] with the matching logic of the
+//! `match_datadog_query` function: each attribute is resolved into fields, and each field is
+//! tested with a VRL expression that matches the values the function would match.
+//!
+//! [`QueryNode::from_vrl`] is the reverse, best-effort translation. It understands the
+//! expressions generated by [`QueryNode::to_vrl`], and simple conditions written by hand, such as
+//! comparisons of event fields with literals, `exists`, `starts_with`, `ends_with`, `contains`,
+//! `includes` on `.tags`, and `match` with regexes that only contain literals and `.*`.
+
+use std::fmt::Write as _;
+
+use super::regex::{wildcard_regex, word_regex};
+use super::{BooleanType, Field, QueryNode, normalize_fields};
+use crate::datadog::search::{Comparison, ComparisonValue, DEFAULT_FIELD};
+use crate::parser::ast::{
+    Container, Expr, FunctionCall, Literal, Opcode, QueryTarget, RootExpr, Unary,
+};
+use crate::path::{OwnedValuePath, PathParseError, PathPrefix, parse_value_path};
+
+/// The tags of an event, as an array.
+const TAGS: &str = "array(.tags) ?? []";
+
+/// The characters escaped by [`regex::escape`].
+const REGEX_META_CHARACTERS: &str = r"\.+*?()|[]{}^$#&-~";
+
+/// An error translating a VRL condition into a Datadog Search Syntax query.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum FromVrlError {
+    #[error("invalid VRL: {0}")]
+    Parse(#[from] crate::parser::Error),
+
+    #[error("`{0}` has no equivalent Datadog search query")]
+    Unsupported(String),
+}
+
+impl QueryNode {
+    /// Returns a VRL boolean expression that is `true` for the events this query matches, as
+    /// `match_datadog_query(., query)` would.
+    ///
+    /// Numeric comparisons of attributes only fall back to comparing strings for string values.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the query contains an invalid path.
+    pub fn to_vrl(&self) -> Result<String, PathParseError> {
+        Ok(self.condition()?.source)
+    }
+
+    /// Translates a VRL boolean expression of the event into a query, on a best-effort basis.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `source` isn't a single valid VRL expression, or if it contains
+    /// expressions that have no equivalent query.
+    pub fn from_vrl(source: &str) -> Result<Self, FromVrlError> {
+        let program = crate::parser::parse(source)?;
+        let [root] = program.0.as_slice() else {
+            return Err(FromVrlError::Unsupported(source.trim().to_owned()));
+        };
+        let RootExpr::Expr(expr) = root.inner() else {
+            return Err(FromVrlError::Unsupported(source.trim().to_owned()));
+        };
+
+        Cond::parse(expr)?.into_query_node()
+    }
+
+    fn condition(&self) -> Result<Condition, PathParseError> {
+        let condition = match self {
+            Self::MatchNoDocs => Condition::term("false"),
+            Self::MatchAllDocs => Condition::term("true"),
+            Self::AttributeExists { attr } => any(fields(attr, exists)?),
+            Self::AttributeMissing { attr } => all(fields(attr, |field| Ok(exists(field)?.not()))?),
+            Self::AttributeTerm { attr, value }
+            | Self::QuotedAttribute {
+                attr,
+                phrase: value,
+            } => any(fields(attr, |field| equals(field, value))?),
+            Self::AttributePrefix { attr, prefix } => {
+                any(fields(attr, |field| self::prefix(field, prefix))?)
+            }
+            Self::AttributeWildcard { attr, wildcard } => {
+                any(fields(attr, |field| self::wildcard(field, wildcard))?)
+            }
+            Self::AttributeComparison {
+                attr,
+                comparator,
+                value,
+            } => any(fields(attr, |field| compare(field, *comparator, value))?),
+            Self::AttributeRange {
+                attr,
+                lower,
+                lower_inclusive,
+                upper,
+                upper_inclusive,
+            } => any(fields(attr, |field| {
+                range(field, lower, *lower_inclusive, upper, *upper_inclusive)
+            })?),
+            Self::NegatedNode { node } => node.condition()?.not(),
+            Self::Boolean { oper, nodes } => {
+                let conditions = nodes
+                    .iter()
+                    .map(QueryNode::condition)
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                join(*oper, conditions)
+            }
+        };
+
+        Ok(condition)
+    }
+}
+
+/// A VRL boolean expression.
+struct Condition {
+    source: String,
+    kind: Kind,
+}
+
+/// How the operands of a [`Condition`] are joined, which determines whether it needs parentheses.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// A literal, function call or negation.
+    Term,
+    /// A comparison of two operands.
+    Comparison,
+    /// Operands joined by `&&` or `||`.
+    Boolean(BooleanType),
+}
+
+impl Condition {
+    fn term(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            kind: Kind::Term,
+        }
+    }
+
+    fn comparison(source: String) -> Self {
+        Self {
+            source,
+            kind: Kind::Comparison,
+        }
+    }
+
+    fn not(self) -> Self {
+        if self.kind == Kind::Term {
+            Self::term(format!("!{}", self.source))
+        } else {
+            Self::term(format!("!({})", self.source))
+        }
+    }
+
+    /// Returns the source of the condition as an operand of `operator`.
+    fn operand(&self, operator: BooleanType) -> String {
+        match self.kind {
+            Kind::Boolean(kind) if kind != operator => format!("({})", self.source),
+            _ => self.source.clone(),
+        }
+    }
+}
+
+/// Joins `conditions` with `operator`. An empty `&&` is `true`, and an empty `||` is `false`.
+fn join(operator: BooleanType, mut conditions: Vec<Condition>) -> Condition {
+    let separator = match operator {
+        BooleanType::And => " && ",
+        BooleanType::Or => " || ",
+    };
+
+    match conditions.len() {
+        0 => Condition::term(matches!(operator, BooleanType::And).to_string()),
+        1 => conditions.remove(0),
+        _ => Condition {
+            source: conditions
+                .iter()
+                .map(|condition| condition.operand(operator))
+                .collect::<Vec<_>>()
+                .join(separator),
+            kind: Kind::Boolean(operator),
+        },
+    }
+}
+
+fn any(conditions: Vec<Condition>) -> Condition {
+    join(BooleanType::Or, conditions)
+}
+
+fn all(conditions: Vec<Condition>) -> Condition {
+    join(BooleanType::And, conditions)
+}
+
+fn fields(
+    attr: &str,
+    condition: impl Fn(Field) -> Result<Condition, PathParseError>,
+) -> Result<Vec<Condition>, PathParseError> {
+    normalize_fields(attr).into_iter().map(condition).collect()
+}
+
+/// Returns the VRL query of the event field that `field` is looked up in.
+fn path(field: &Field) -> Result<String, PathParseError> {
+    let path = match field {
+        Field::Default(p) | Field::Reserved(p) | Field::Attribute(p) => parse_value_path(p)?,
+        Field::Tag(_) => parse_value_path("tags")?,
+    };
+
+    Ok(crate::path::OwnedTargetPath::event(path).to_string())
+}
+
+/// Returns the string value of the field at `path`, or an empty string if it has none.
+fn string_value(path: &str) -> String {
+    format!(r#"to_string({path}) ?? """#)
+}
+
+fn string_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' | '{' => {
+                literal.push('\\');
+                literal.push(c);
+            }
+            '\n' => literal.push_str(r"\n"),
+            '\r' => literal.push_str(r"\r"),
+            '\t' => literal.push_str(r"\t"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn regex_literal(regex: &regex::Regex) -> String {
+    format!("r'{}'", regex.as_str().replace('\'', r"\'"))
+}
+
+fn exists(field: Field) -> Result<Condition, PathParseError> {
+    let path = path(&field)?;
+
+    Ok(match field {
+        // The tag matches using either 'key' or 'key:value' syntax.
+        Field::Tag(tag) => Condition::term(format!(
+            "match_array({TAGS}, r'^{}(:|$)')",
+            regex::escape(&tag).replace('\'', r"\'")
+        )),
+        _ => Condition::term(format!("exists({path})")),
+    })
+}
+
+fn equals(field: Field, to_match: &str) -> Result<Condition, PathParseError> {
+    let path = path(&field)?;
+
+    Ok(match field {
+        // Default fields are compared by word boundary, if they're strings.
+        Field::Default(_) => Condition::term(format!(
+            r#"match(string({path}) ?? "", {})"#,
+            regex_literal(&word_regex(to_match))
+        )),
+        // A literal "tags" field should match by key.
+        Field::Reserved(f) if f == "tags" => {
+            Condition::term(format!("includes({TAGS}, {})", string_literal(to_match)))
+        }
+        // Individual tags are compared by element key:value.
+        Field::Tag(tag) => Condition::term(format!(
+            "includes({TAGS}, {})",
+            string_literal(&format!("{tag}:{to_match}"))
+        )),
+        // Everything else is matched by string equality.
+        _ => Condition::comparison(format!(
+            "({}) == {}",
+            string_value(&path),
+            string_literal(to_match)
+        )),
+    })
+}
+
+fn prefix(field: Field, prefix: &str) -> Result<Condition, PathParseError> {
+    let path = path(&field)?;
+
+    Ok(match field {
+        Field::Default(_) => Condition::term(format!(
+            "match({}, {})",
+            string_value(&path),
+            regex_literal(&word_regex(&format!("{prefix}*")))
+        )),
+        Field::Tag(tag) => Condition::term(format!(
+            "match_array({TAGS}, {})",
+            regex_literal(&wildcard_regex(&format!("{tag}:{prefix}*")))
+        )),
+        _ => Condition::term(format!(
+            "starts_with({}, {})",
+            string_value(&path),
+            string_literal(prefix)
+        )),
+    })
+}
+
+fn wildcard(field: Field, wildcard: &str) -> Result<Condition, PathParseError> {
+    let path = path(&field)?;
+
+    Ok(match field {
+        Field::Default(_) => Condition::term(format!(
+            "match({}, {})",
+            string_value(&path),
+            regex_literal(&word_regex(wildcard))
+        )),
+        Field::Tag(tag) => Condition::term(format!(
+            "match_array({TAGS}, {})",
+            regex_literal(&wildcard_regex(&format!("{tag}:{wildcard}")))
+        )),
+        _ => Condition::term(format!(
+            "match({}, {})",
+            string_value(&path),
+            regex_literal(&wildcard_regex(wildcard))
+        )),
+    })
+}
+
+fn compare(
+    field: Field,
+    comparator: Comparison,
+    comparison_value: &ComparisonValue,
+) -> Result<Condition, PathParseError> {
+    let path = path(&field)?;
+    let op = comparator.as_lucene();
+    let rhs = comparison_value.to_string();
+
+    Ok(match (field, comparison_value) {
+        // Attributes are compared numerically if the value is numeric, or as strings otherwise.
+        (Field::Attribute(_), ComparisonValue::Integer(_) | ComparisonValue::Float(_)) => {
+            let number = match comparison_value {
+                ComparisonValue::Float(float) => format!("{float:?}"),
+                _ => rhs.clone(),
+            };
+
+            any(vec![
+                all(vec![
+                    any(vec![
+                        Condition::term(format!("is_integer({path})")),
+                        Condition::term(format!("is_float({path})")),
+                    ]),
+                    Condition::comparison(format!("to_float!({path}) {op} {number}")),
+                ]),
+                all(vec![
+                    Condition::term(format!("is_string({path})")),
+                    Condition::comparison(format!("string!({path}) {op} {}", string_literal(&rhs))),
+                ]),
+            ])
+        }
+        // Tag values need extracting by "key:value" to be compared.
+        (Field::Tag(tag), _) => Condition::comparison(format!(
+            r#"length(filter({TAGS}) -> |_index, tag| {{ parts = split(to_string(tag) ?? "", ":", limit: 2); length(parts) == 2 && parts[0] == {} && string!(parts[1]) {op} {} }}) > 0"#,
+            string_literal(&tag),
+            string_literal(&rhs)
+        )),
+        // All other values are compared as strings.
+        _ => {
+            let comparison = Condition::comparison(format!(
+                "({}) {op} {}",
+                string_value(&path),
+                string_literal(&rhs)
+            ));
+
+            // A missing field has no value, rather than an empty one.
+            if compare_strings("", comparator, &rhs) {
+                all(vec![Condition::term(format!("exists({path})")), comparison])
+            } else {
+                comparison
+            }
+        }
+    })
+}
+
+fn compare_strings(lhs: &str, comparator: Comparison, rhs: &str) -> bool {
+    match comparator {
+        Comparison::Lt => lhs < rhs,
+        Comparison::Lte => lhs <= rhs,
+        Comparison::Gt => lhs > rhs,
+        Comparison::Gte => lhs >= rhs,
+    }
+}
+
+/// Mirrors [`Filter::range`](super::Filter::range).
+fn range(
+    field: Field,
+    lower: &ComparisonValue,
+    lower_inclusive: bool,
+    upper: &ComparisonValue,
+    upper_inclusive: bool,
+) -> Result<Condition, PathParseError> {
+    let lower_op = if lower_inclusive {
+        Comparison::Gte
+    } else {
+        Comparison::Gt
+    };
+    let upper_op = if upper_inclusive {
+        Comparison::Lte
+    } else {
+        Comparison::Lt
+    };
+
+    match (lower, upper) {
+        (ComparisonValue::Unbounded, ComparisonValue::Unbounded) => exists(field),
+        (ComparisonValue::Unbounded, _) => compare(field, upper_op, upper),
+        (_, ComparisonValue::Unbounded) => compare(field, lower_op, lower),
+        _ => Ok(all(vec![
+            compare(field.clone(), lower_op, lower)?,
+            compare(field, upper_op, upper)?,
+        ])),
+    }
+}
+
+/// A VRL condition being translated into a query.
+#[derive(Debug, Clone, PartialEq)]
+enum Cond {
+    Bool(bool),
+
+    /// A check of the type of a field, which only guards other tests of the field.
+    Guard(OwnedValuePath, String),
+
+    Test(Target, Test),
+    Not(Box<Cond>),
+    And(Vec<Cond>),
+    Or(Vec<Cond>),
+}
+
+/// What a [`Test`] applies to.
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Path(OwnedValuePath),
+
+    /// Any of the default fields.
+    Default,
+
+    /// A tag, by key.
+    Tag(String),
+
+    /// The `.tags` array.
+    Tags,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Test {
+    Exists,
+    Missing,
+    Equals(String),
+
+    /// A wildcard matched on word boundaries.
+    Words(String),
+
+    /// A wildcard matched against the whole value.
+    Pattern(String),
+
+    Compare(Comparison, Bound),
+    Range {
+        lower: Bound,
+        lower_inclusive: bool,
+        upper: Bound,
+        upper_inclusive: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Bound {
+    Number(ComparisonValue),
+    String(String),
+}
+
+impl Cond {
+    fn parse(expr: &Expr) -> Result<Self, FromVrlError> {
+        let unsupported = || Err(FromVrlError::Unsupported(expr.to_string()));
+
+        match expr {
+            Expr::Literal(literal) => match literal.inner() {
+                Literal::Boolean(value) => Ok(Self::Bool(*value)),
+                _ => unsupported(),
+            },
+            Expr::Container(container) => match container.inner() {
+                Container::Group(group) => Self::parse(&group.0),
+                _ => unsupported(),
+            },
+            Expr::Unary(unary) => match unary.inner() {
+                Unary::Not(not) => Ok(Self::parse(&not.1)?.negate()),
+            },
+            Expr::Op(op) => {
+                let (lhs, rhs) = (&op.0, &op.2);
+                match op.1.inner() {
+                    Opcode::And => Ok(and(vec![Self::parse(lhs)?, Self::parse(rhs)?])),
+                    Opcode::Or => Ok(or(vec![Self::parse(lhs)?, Self::parse(rhs)?])),
+                    opcode => comparison(expr, lhs, *opcode, rhs),
+                }
+            }
+            Expr::FunctionCall(call) => function_call(expr, call),
+            _ => unsupported(),
+        }
+    }
+
+    fn negate(self) -> Self {
+        match self {
+            Self::Bool(value) => Self::Bool(!value),
+            Self::Test(target, Test::Exists) => Self::Test(target, Test::Missing),
+            Self::Test(target, Test::Missing) => Self::Test(target, Test::Exists),
+            Self::Not(cond) => *cond,
+            cond => Self::Not(Box::new(cond)),
+        }
+    }
+
+    /// Replaces the tests of the field at `path` with tests of the default fields.
+    fn retarget(&self, path: &OwnedValuePath) -> Self {
+        match self {
+            Self::Test(Target::Path(p), test) if p == path => {
+                Self::Test(Target::Default, test.clone())
+            }
+            Self::Not(cond) => Self::Not(Box::new(cond.retarget(path))),
+            Self::And(conds) => Self::And(conds.iter().map(|c| c.retarget(path)).collect()),
+            Self::Or(conds) => Self::Or(conds.iter().map(|c| c.retarget(path)).collect()),
+            cond => cond.clone(),
+        }
+    }
+
+    fn into_query_node(self) -> Result<QueryNode, FromVrlError> {
+        Ok(match self {
+            Self::Bool(true) => QueryNode::MatchAllDocs,
+            Self::Bool(false) => QueryNode::MatchNoDocs,
+            Self::Guard(_, source) => return Err(FromVrlError::Unsupported(source)),
+            Self::Test(target, test) => test.into_query_node(&target)?,
+            Self::Not(cond) => QueryNode::NegatedNode {
+                node: Box::new(cond.into_query_node()?),
+            },
+            Self::And(conds) => QueryNode::new_boolean(BooleanType::And, query_nodes(conds)?),
+            Self::Or(conds) => QueryNode::new_boolean(BooleanType::Or, query_nodes(conds)?),
+        })
+    }
+}
+
+fn query_nodes(conds: Vec<Cond>) -> Result<Vec<QueryNode>, FromVrlError> {
+    conds.into_iter().map(Cond::into_query_node).collect()
+}
+
+fn flatten(conds: Vec<Cond>, operator: BooleanType) -> Vec<Cond> {
+    conds
+        .into_iter()
+        .flat_map(|cond| match (cond, operator) {
+            (Cond::And(conds), BooleanType::And) | (Cond::Or(conds), BooleanType::Or) => conds,
+            (cond, _) => vec![cond],
+        })
+        .collect()
+}
+
+fn and(conds: Vec<Cond>) -> Cond {
+    let mut conds = flatten(conds, BooleanType::And);
+
+    // Tests of a field are false if it's missing, so checks of its type or existence that guard
+    // them are redundant.
+    let tested = conds
+        .iter()
+        .filter_map(|cond| match cond {
+            Cond::Test(target, test) if !matches!(test, Test::Exists | Test::Missing) => {
+                Some(target.clone())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    conds.retain(|cond| match cond {
+        Cond::Guard(path, _) => !tested.contains(&Target::Path(path.clone())),
+        Cond::Test(target, Test::Exists) => !tested.contains(target),
+        _ => true,
+    });
+
+    // Lower and upper bounds of the same target are a range.
+    while let Some((lower, upper)) = range_bounds(&conds) {
+        let Cond::Test(target, Test::Compare(upper_op, upper_bound)) = conds.remove(upper) else {
+            unreachable!("upper bound is a comparison");
+        };
+        let lower = if upper < lower { lower - 1 } else { lower };
+        let Cond::Test(_, Test::Compare(lower_op, lower_bound)) = conds.remove(lower) else {
+            unreachable!("lower bound is a comparison");
+        };
+        conds.insert(
+            lower,
+            Cond::Test(
+                target,
+                Test::Range {
+                    lower: lower_bound,
+                    lower_inclusive: matches!(lower_op, Comparison::Gte),
+                    upper: upper_bound,
+                    upper_inclusive: matches!(upper_op, Comparison::Lte),
+                },
+            ),
+        );
+    }
+
+    let mut conds = default_fields(conds);
+    if conds.len() == 1 {
+        conds.remove(0)
+    } else {
+        Cond::And(conds)
+    }
+}
+
+/// Returns the indices of a lower and an upper bound of the same target in `conds`.
+fn range_bounds(conds: &[Cond]) -> Option<(usize, usize)> {
+    conds.iter().enumerate().find_map(|(lower, cond)| {
+        let Cond::Test(target, Test::Compare(Comparison::Gt | Comparison::Gte, _)) = cond else {
+            return None;
+        };
+        let upper = conds.iter().position(|cond| {
+            matches!(cond, Cond::Test(t, Test::Compare(Comparison::Lt | Comparison::Lte, _)) if t == target)
+        })?;
+        Some((lower, upper))
+    })
+}
+
+fn or(conds: Vec<Cond>) -> Cond {
+    let mut conds = flatten(conds, BooleanType::Or);
+
+    if let [Cond::Guard(path, _), ..] = conds.as_slice()
+        && conds
+            .iter()
+            .all(|cond| matches!(cond, Cond::Guard(p, _) if p == path))
+    {
+        return conds.remove(0);
+    }
+
+    // Attributes are compared numerically if their value is numeric, and as strings otherwise.
+    let numeric = conds
+        .iter()
+        .filter_map(|cond| match cond {
+            Cond::Test(target, Test::Compare(op, Bound::Number(number))) => {
+                Some((target.clone(), *op, number.to_string()))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    conds.retain(|cond| match cond {
+        Cond::Test(target, Test::Compare(op, Bound::String(string))) => !numeric
+            .iter()
+            .any(|(t, o, number)| t == target && o == op && number == string),
+        _ => true,
+    });
+
+    let mut conds = default_fields(conds);
+    if conds.len() == 1 {
+        conds.remove(0)
+    } else {
+        Cond::Or(conds)
+    }
+}
+
+/// Replaces runs of identical conditions of each of the default fields with one of `_default_`.
+fn default_fields(mut conds: Vec<Cond>) -> Vec<Cond> {
+    let paths = normalize_fields(DEFAULT_FIELD)
+        .iter()
+        .map(|field| parse_value_path(field.as_str()))
+        .collect::<Result<Vec<_>, _>>()
+        .expect("default fields are valid paths");
+
+    let mut index = 0;
+    while index + paths.len() <= conds.len() {
+        let default = conds[index].retarget(&paths[0]);
+        if default != conds[index]
+            && conds[index..]
+                .iter()
+                .zip(&paths)
+                .all(|(cond, path)| cond.retarget(path) == default)
+        {
+            conds.splice(index..index + paths.len(), [default]);
+        }
+        index += 1;
+    }
+
+    conds
+}
+
+fn comparison(expr: &Expr, lhs: &Expr, opcode: Opcode, rhs: &Expr) -> Result<Cond, FromVrlError> {
+    let unsupported = || FromVrlError::Unsupported(expr.to_string());
+
+    if opcode == Opcode::Gt
+        && matches!(literal(rhs), Some(Literal::Integer(0)))
+        && let Expr::FunctionCall(call) = lhs
+    {
+        return tag_comparison(expr, call).ok_or_else(unsupported);
+    }
+
+    let (value, opcode, literal) = match (literal(lhs), literal(rhs)) {
+        (None, Some(literal)) => (lhs, opcode, literal),
+        (Some(literal), None) => {
+            let opcode = match opcode {
+                Opcode::Gt => Opcode::Lt,
+                Opcode::Ge => Opcode::Le,
+                Opcode::Lt => Opcode::Gt,
+                Opcode::Le => Opcode::Ge,
+                opcode => opcode,
+            };
+            (rhs, opcode, literal)
+        }
+        _ => return Err(unsupported()),
+    };
+    let target = Target::Path(value_path(value).ok_or_else(unsupported)?);
+
+    let comparator = match opcode {
+        Opcode::Eq | Opcode::Ne => {
+            let value = match literal {
+                Literal::Integer(value) => value.to_string(),
+                Literal::Float(value) => value.to_string(),
+                Literal::Boolean(value) => value.to_string(),
+                literal => string(literal).ok_or_else(unsupported)?,
+            };
+            let test = Cond::Test(target, Test::Equals(value));
+            return Ok(if opcode == Opcode::Eq {
+                test
+            } else {
+                test.negate()
+            });
+        }
+        Opcode::Gt => Comparison::Gt,
+        Opcode::Ge => Comparison::Gte,
+        Opcode::Lt => Comparison::Lt,
+        Opcode::Le => Comparison::Lte,
+        _ => return Err(unsupported()),
+    };
+    let bound = match literal {
+        Literal::Integer(value) => Bound::Number(ComparisonValue::Integer(*value)),
+        Literal::Float(value) => Bound::Number(ComparisonValue::Float(value.into_inner())),
+        literal => Bound::String(string(literal).ok_or_else(unsupported)?),
+    };
+
+    Ok(Cond::Test(target, Test::Compare(comparator, bound)))
+}
+
+/// Recognizes the comparison of tag values generated by [`compare`].
+fn tag_comparison(expr: &Expr, length: &FunctionCall) -> Option<Cond> {
+    let [Expr::FunctionCall(filter)] = arguments(length, &["value"])?.as_slice() else {
+        return None;
+    };
+    let closure = filter.closure.as_ref()?;
+    let Expr::Op(op) = &closure.block.0.last()?.inner() else {
+        return None;
+    };
+    let Expr::Op(tag) = op.0.inner() else {
+        return None;
+    };
+    let (Expr::Op(key), Expr::Op(value)) = (tag.2.inner(), op.2.inner()) else {
+        return None;
+    };
+    let key = string(literal(&key.2)?)?;
+    let comparator = match value.1.inner() {
+        Opcode::Gt => Comparison::Gt,
+        Opcode::Ge => Comparison::Gte,
+        Opcode::Lt => Comparison::Lt,
+        Opcode::Le => Comparison::Lte,
+        _ => return None,
+    };
+    let value = string(literal(&value.2)?)?;
+
+    // Only the exact expression is understood.
+    let condition = compare(
+        Field::Tag(key.clone()),
+        comparator,
+        &ComparisonValue::String(value.clone()),
+    )
+    .ok()?;
+    let program = crate::parser::parse(condition.source).ok()?;
+    let [root] = program.0.as_slice() else {
+        return None;
+    };
+    if root.to_string() != expr.to_string() {
+        return None;
+    }
+
+    Some(Cond::Test(
+        Target::Tag(key),
+        Test::Compare(comparator, Bound::String(value)),
+    ))
+}
+
+fn function_call(expr: &Expr, call: &FunctionCall) -> Result<Cond, FromVrlError> {
+    let unsupported = || FromVrlError::Unsupported(expr.to_string());
+    if call.closure.is_some() {
+        return Err(unsupported());
+    }
+
+    let cond = match call.ident.inner().as_ref() {
+        "exists" => {
+            let [field] = arguments(call, &["field"]).ok_or_else(unsupported)?[..] else {
+                return Err(unsupported());
+            };
+            Cond::Test(
+                Target::Path(value_path(field).ok_or_else(unsupported)?),
+                Test::Exists,
+            )
+        }
+        "is_integer" | "is_float" | "is_string" => {
+            let [value] = arguments(call, &["value"]).ok_or_else(unsupported)?[..] else {
+                return Err(unsupported());
+            };
+            Cond::Guard(value_path(value).ok_or_else(unsupported)?, expr.to_string())
+        }
+        function @ ("starts_with" | "ends_with" | "contains") => {
+            let [value, substring] =
+                arguments(call, &["value", "substring"]).ok_or_else(unsupported)?[..]
+            else {
+                return Err(unsupported());
+            };
+            let path = value_path(value).ok_or_else(unsupported)?;
+            let substring = literal(substring)
+                .and_then(string)
+                .filter(|substring| !substring.contains('*'))
+                .ok_or_else(unsupported)?;
+            let pattern = match function {
+                "starts_with" => format!("{substring}*"),
+                "ends_with" => format!("*{substring}"),
+                _ => format!("*{substring}*"),
+            };
+            Cond::Test(Target::Path(path), Test::Pattern(pattern))
+        }
+        "match" => {
+            let [value, pattern] =
+                arguments(call, &["value", "pattern"]).ok_or_else(unsupported)?[..]
+            else {
+                return Err(unsupported());
+            };
+            let path = value_path(value).ok_or_else(unsupported)?;
+            let Some(Literal::Regex(pattern)) = literal(pattern) else {
+                return Err(unsupported());
+            };
+            let test = if let Some(words) = pattern
+                .strip_prefix(r"\b")
+                .and_then(|pattern| pattern.strip_suffix(r"\b"))
+                .and_then(parse_wildcard)
+            {
+                Test::Words(words)
+            } else {
+                Test::Pattern(whole_wildcard(pattern).ok_or_else(unsupported)?)
+            };
+            Cond::Test(Target::Path(path), test)
+        }
+        "includes" => {
+            let [value, item] = arguments(call, &["value", "item"]).ok_or_else(unsupported)?[..]
+            else {
+                return Err(unsupported());
+            };
+            if !is_tags(value) {
+                return Err(unsupported());
+            }
+            let item = literal(item).and_then(string).ok_or_else(unsupported)?;
+            match item.split_once(':') {
+                Some((tag, value)) if is_tag(tag) => {
+                    Cond::Test(Target::Tag(tag.to_owned()), Test::Equals(value.to_owned()))
+                }
+                _ => Cond::Test(Target::Tags, Test::Equals(item)),
+            }
+        }
+        "match_array" => match_array(call).ok_or_else(unsupported)?,
+        _ => return Err(unsupported()),
+    };
+
+    Ok(cond)
+}
+
+/// Recognizes the matches of tags generated by [`exists`], [`prefix`] and [`wildcard`].
+fn match_array(call: &FunctionCall) -> Option<Cond> {
+    let [value, pattern] = arguments(call, &["value", "pattern"])?[..] else {
+        return None;
+    };
+    let Some(Literal::Regex(pattern)) = literal(pattern) else {
+        return None;
+    };
+    if !is_tags(value) {
+        return None;
+    }
+
+    if let Some(tag) = pattern
+        .strip_prefix('^')
+        .and_then(|pattern| pattern.strip_suffix("(:|$)"))
+        .and_then(parse_wildcard)
+        .filter(|tag| !tag.contains('*') && is_tag(tag))
+    {
+        return Some(Cond::Test(Target::Tag(tag), Test::Exists));
+    }
+
+    let pattern = whole_wildcard(pattern)?;
+    match pattern.split_once(':') {
+        Some((tag, pattern)) if !tag.contains('*') && is_tag(tag) => Some(Cond::Test(
+            Target::Tag(tag.to_owned()),
+            Test::Pattern(pattern.to_owned()),
+        )),
+        _ => None,
+    }
+}
+
+/// Returns the arguments of `call` in the order of `parameters`, if it has exactly those.
+fn arguments<'a>(call: &'a FunctionCall, parameters: &[&str]) -> Option<Vec<&'a Expr>> {
+    let mut arguments = vec![None; parameters.len()];
+    for (index, argument) in call.arguments.iter().enumerate() {
+        let index = match &argument.ident {
+            Some(ident) => parameters
+                .iter()
+                .position(|parameter| *parameter == ident.inner().as_ref())?,
+            None => index,
+        };
+        *arguments.get_mut(index)? = Some(argument.expr.inner());
+    }
+
+    arguments.into_iter().collect()
+}
+
+fn literal(expr: &Expr) -> Option<&Literal> {
+    match expr {
+        Expr::Literal(literal) => Some(literal.inner()),
+        _ => None,
+    }
+}
+
+fn string(literal: &Literal) -> Option<String> {
+    match literal {
+        Literal::String(template) if template.0.is_empty() => Some(String::new()),
+        Literal::String(template) => template.as_literal_string().map(ToOwned::to_owned),
+        Literal::RawString(string) => Some(string.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the path of the event field that `expr` is the value of, looking through type
+/// assertions and conversions, and fallbacks for errors.
+fn value_path(expr: &Expr) -> Option<OwnedValuePath> {
+    match expr {
+        Expr::Query(query) => match query.target.inner() {
+            QueryTarget::External(PathPrefix::Event) => Some(query.path.inner().clone()),
+            _ => None,
+        },
+        Expr::Container(container) => match container.inner() {
+            Container::Group(group) => value_path(&group.0),
+            _ => None,
+        },
+        Expr::FunctionCall(call)
+            if call.closure.is_none()
+                && matches!(
+                    call.ident.inner().as_ref(),
+                    "string" | "to_string" | "int" | "float" | "to_float" | "array"
+                ) =>
+        {
+            let [value] = arguments(call, &["value"])?[..] else {
+                return None;
+            };
+            value_path(value)
+        }
+        Expr::Op(op)
+            if *op.1.inner() == Opcode::Err
+                && matches!(op.2.inner(), Expr::Literal(_) | Expr::Container(_)) =>
+        {
+            value_path(&op.0)
+        }
+        _ => None,
+    }
+}
+
+fn is_tags(expr: &Expr) -> bool {
+    value_path(expr).is_some_and(|path| path.to_string() == "tags")
+}
+
+/// Returns `true` if `attr` is resolved into a tag.
+fn is_tag(attr: &str) -> bool {
+    matches!(normalize_fields(attr).as_slice(), [Field::Tag(_)])
+}
+
+/// Returns the wildcard that `pattern` matches, if it only contains literals escaped by
+/// [`regex::escape`], and `.*`.
+fn parse_wildcard(pattern: &str) -> Option<String> {
+    let mut wildcard = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                '*' => return None,
+                c if REGEX_META_CHARACTERS.contains(c) => wildcard.push(c),
+                _ => return None,
+            },
+            '.' if chars.next()? == '*' => wildcard.push('*'),
+            c if REGEX_META_CHARACTERS.contains(c) => return None,
+            c => wildcard.push(c),
+        }
+    }
+
+    Some(wildcard)
+}
+
+/// Returns the wildcard that `pattern` matches against a whole value.
+fn whole_wildcard(pattern: &str) -> Option<String> {
+    parse_wildcard(pattern.strip_prefix('^')?.strip_suffix('$')?)
+}
+
+impl Test {
+    fn into_query_node(self, target: &Target) -> Result<QueryNode, FromVrlError> {
+        Ok(match self {
+            Test::Exists => QueryNode::AttributeExists {
+                attr: attribute(target, Usage::Exists)?,
+            },
+            Test::Missing => QueryNode::AttributeMissing {
+                attr: attribute(target, Usage::Exists)?,
+            },
+            Test::Equals(value) => QueryNode::AttributeTerm {
+                attr: attribute(target, Usage::Value)?,
+                value,
+            },
+            Test::Words(words) => wildcard_node(attribute(target, Usage::Words)?, words),
+            Test::Pattern(pattern) => wildcard_node(attribute(target, Usage::Value)?, pattern),
+            Test::Compare(comparator, value) => {
+                let attr = attribute(target, Usage::Compare(&[&value]))?;
+                QueryNode::AttributeComparison {
+                    value: comparison_value(&attr, value),
+                    attr,
+                    comparator,
+                }
+            }
+            Test::Range {
+                lower,
+                lower_inclusive,
+                upper,
+                upper_inclusive,
+            } => {
+                let attr = attribute(target, Usage::Compare(&[&lower, &upper]))?;
+                QueryNode::AttributeRange {
+                    lower: comparison_value(&attr, lower),
+                    lower_inclusive,
+                    upper: comparison_value(&attr, upper),
+                    upper_inclusive,
+                    attr,
+                }
+            }
+        })
+    }
+}
+
+fn wildcard_node(attr: String, wildcard: String) -> QueryNode {
+    match wildcard.find('*') {
+        None => QueryNode::AttributeTerm {
+            attr,
+            value: wildcard,
+        },
+        Some(index) if index == wildcard.len() - 1 => QueryNode::AttributePrefix {
+            attr,
+            prefix: wildcard[..index].to_owned(),
+        },
+        Some(_) => QueryNode::AttributeWildcard { attr, wildcard },
+    }
+}
+
+fn comparison_value(attr: &str, bound: Bound) -> ComparisonValue {
+    match bound {
+        Bound::Number(number) => number,
+        // Attributes compare numbers numerically, unless the bound is a string.
+        Bound::String(string) if attr.starts_with('@') => ComparisonValue::String(string),
+        Bound::String(string) => {
+            if let Ok(integer) = string.parse::<i64>() {
+                ComparisonValue::Integer(integer)
+            } else if let Ok(float) = string.parse::<f64>() {
+                ComparisonValue::Float(float)
+            } else {
+                ComparisonValue::String(string)
+            }
+        }
+    }
+}
+
+/// How a [`Target`] is tested, which determines the attribute with the same semantics.
+#[derive(Clone, Copy)]
+enum Usage<'a> {
+    Exists,
+    Value,
+    Words,
+    Compare(&'a [&'a Bound]),
+}
+
+fn attribute(target: &Target, usage: Usage) -> Result<String, FromVrlError> {
+    let unsupported = |target: &str| {
+        FromVrlError::Unsupported(match &usage {
+            Usage::Exists => format!("exists({target})"),
+            Usage::Value => format!("the value of {target}"),
+            Usage::Words => format!("the words of {target}"),
+            Usage::Compare(_) => format!("the comparison of {target}"),
+        })
+    };
+
+    let path = match target {
+        Target::Default => return Ok(DEFAULT_FIELD.to_owned()),
+        Target::Tag(tag) => return Ok(tag.clone()),
+        Target::Tags => {
+            return match usage {
+                Usage::Value => Ok("tags".to_owned()),
+                _ => Err(unsupported(".tags")),
+            };
+        }
+        Target::Path(path) => path.to_string(),
+    };
+    if path.contains('@') {
+        return Err(unsupported(&format!(".{path}")));
+    }
+
+    let field = normalize_fields(&path);
+    let default = matches!(field.as_slice(), [Field::Default(_)]);
+    let reserved = matches!(field.as_slice(), [Field::Reserved(f)] if f != "tags");
+    let attr = match usage {
+        Usage::Words if default => path,
+        Usage::Words => return Err(unsupported(&format!(".{path}"))),
+        Usage::Exists if default || reserved => path,
+        Usage::Value if reserved => path,
+        Usage::Compare(bounds)
+            if (default || reserved)
+                && bounds.iter().all(|bound| matches!(bound, Bound::String(_))) =>
+        {
+            path
+        }
+        _ => {
+            let mut attr = String::with_capacity(path.len() + 1);
+            let _ = write!(attr, "@{path}");
+            attr
+        }
+    };
+
+    Ok(attr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{TargetValue, TimeZone, compile, runtime::Runtime};
+    use crate::datadog_filter::cases::match_cases;
+    use crate::value;
+    use crate::value::{Secrets, Value};
+
+    fn matches(source: &str, event: &Value) -> bool {
+        let program = compile(source, &crate::stdlib::all())
+            .unwrap_or_else(|error| panic!("{source}: {error:?}"))
+            .program;
+        let mut target = TargetValue {
+            value: event.clone(),
+            metadata: value!({}),
+            secrets: Secrets::default(),
+        };
+
+        Runtime::default()
+            .resolve(&mut target, &program, &TimeZone::default())
+            .unwrap_or_else(|error| panic!("{source}: {error}"))
+            == Value::Boolean(true)
+    }
+
+    #[test]
+    fn round_trip_match_datadog_query_cases() {
+        for (_, event, query, want) in match_cases() {
+            let node = query.parse::<QueryNode>().unwrap();
+            let source = node.to_vrl().unwrap();
+            assert_eq!(matches(&source, &event), want, "{query} as {source}");
+
+            let negated = QueryNode::NegatedNode {
+                node: Box::new(node.clone()),
+            };
+            let negated = negated.to_vrl().unwrap();
+            assert_eq!(matches(&negated, &event), !want, "NOT {query} as {negated}");
+
+            let node = QueryNode::from_vrl(&source)
+                .unwrap_or_else(|error| panic!("{query} as {source}: {error}"));
+            let source = node.to_vrl().unwrap();
+            assert_eq!(matches(&source, &event), want, "{query} as {source}");
+        }
+    }
+
+    #[test]
+    fn invalid_path() {
+        let node = "@a%:3".parse::<QueryNode>().unwrap();
+
+        assert!(node.to_vrl().is_err());
+    }
+
+    #[test]
+    fn to_vrl() {
+        let node = r#"@user.name:jane* AND NOT env:prod AND status:"a \"b\"""#
+            .parse::<QueryNode>()
+            .unwrap();
+
+        assert_eq!(
+            node.to_vrl().unwrap(),
+            r#"starts_with(to_string(.user.name) ?? "", "jane") && !includes(array(.tags) ?? [], "env:prod") && (to_string(.status) ?? "") == "a \"b\"""#
+        );
+    }
+
+    #[test]
+    fn from_vrl() {
+        let cases = [
+            (
+                r#".status == "error" && exists(.user)"#,
+                "status:error AND _exists_:@user",
+            ),
+            (
+                r#".duration > 100 || starts_with(.host, "web") || .level <= "warn""#,
+                "@duration:>100 OR host:web* OR @level:<=warn",
+            ),
+            (
+                r#"!includes(.tags, "env:prod") && match(.message, r'\berror\b')"#,
+                "NOT env:prod AND message:error",
+            ),
+            (
+                r#".message != "a" && contains(.path, "admin") && .code >= 500 && .code < 600"#,
+                "NOT @message:a AND @path:*admin* AND @code:[500 TO 600}",
+            ),
+            ("!exists(.user) || false", "_missing_:@user OR -*:*"),
+        ];
+
+        for (source, want) in cases {
+            let node = QueryNode::from_vrl(source).unwrap_or_else(|error| panic!("{error}"));
+            assert_eq!(node.to_lucene(), want, "{source}");
+        }
+    }
+
+    #[test]
+    fn from_vrl_unsupported() {
+        for source in [
+            ".a + 1 == 2",
+            r#"upcase!(.a) == "A""#,
+            "is_string(.a)",
+            r"match(.a, r'\d+')",
+            r"match(.custom, r'\bword\b')",
+            ".a = 1",
+            ".a == 1\n.b == 2",
+        ] {
+            assert!(
+                matches!(
+                    QueryNode::from_vrl(source),
+                    Err(FromVrlError::Unsupported(_))
+                ),
+                "{source}"
+            );
+        }
+    }
+}
@@ -38,6 +38,11 @@ impl<'a> Matches<'a> {
         Matches { captures, names }
     }
 
+    /// Returns the text matched by the whole pattern.
+    pub fn matched(&self) -> &'a str {
+        self.captures.at(0).unwrap_or("")
+    }
+
     /// Returns a tuple of key/value with all the matches found.
     ///
     /// Note that if no match is found, the value is empty.
@@ -108,6 +113,11 @@ impl Pattern {
         }
     }
 
+    /// Returns the names of the captures of this pattern.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.keys().map(String::as_str)
+    }
+
     /// Matches this compiled `Pattern` against the text and returns the matches.
     #[inline]
     pub fn match_against<'a>(&'a self, text: &'a str) -> Result<Option<Matches<'a>>, FatalError> {
@@ -17,3 +17,5 @@ mod matchers;
 pub mod parse_grok;
 mod parse_grok_pattern;
 pub mod parse_grok_rules;
+pub mod pattern_library;
+pub mod rule_set;
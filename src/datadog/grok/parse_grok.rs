@@ -1,10 +1,12 @@
+use std::collections::HashMap;
+
 use super::{
+    grok::Matches,
     grok_filter::apply_filter,
     parse_grok_rules::{GrokField, GrokRule},
 };
 use crate::path::parse_value_path;
 use crate::value::{ObjectMap, Value};
-use std::collections::BTreeMap;
 
 /// Errors which cause the Datadog grok algorithm to stop processing and not return a parsed result.
 #[derive(thiserror::Error, Debug, PartialEq, Eq)]
@@ -46,6 +48,122 @@ pub fn parse_grok(
     Err(FatalError::NoMatch)
 }
 
+/// Explains how a grok rule matched, or failed to match, a value.
+#[derive(PartialEq, Debug)]
+pub struct GrokRuleDiagnostic {
+    /// The rule, as it was written.
+    pub rule: String,
+    /// Whether the rule matched the whole value.
+    pub matched: bool,
+    /// The longest prefix of the value matched by the leading sub-patterns of the rule. For
+    /// Logstash rules, which match anywhere in the value, this is the text they matched.
+    pub matched_prefix: String,
+    /// The sub-pattern where matching failed. This is `None` if the rule matched, or if all of its
+    /// sub-patterns matched but left text at the end of the value.
+    pub failed_pattern: Option<String>,
+    /// The fields captured by the matched sub-patterns.
+    pub parsed: Value,
+    /// List of internal errors that were encounted while parsing the captured fields.
+    pub internal_errors: Vec<InternalError>,
+}
+
+impl From<GrokRuleDiagnostic> for Value {
+    fn from(diagnostic: GrokRuleDiagnostic) -> Self {
+        let GrokRuleDiagnostic {
+            rule,
+            matched,
+            matched_prefix,
+            failed_pattern,
+            parsed,
+            internal_errors,
+        } = diagnostic;
+
+        Value::from(ObjectMap::from([
+            ("rule".into(), Value::from(rule)),
+            ("matched".into(), Value::from(matched)),
+            ("matched_prefix".into(), Value::from(matched_prefix)),
+            ("failed_pattern".into(), Value::from(failed_pattern)),
+            ("fields".into(), parsed),
+            (
+                "errors".into(),
+                internal_errors
+                    .iter()
+                    .map(|error| Value::from(error.to_string()))
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+        ]))
+    }
+}
+
+/// Applies each of the grok rules to a given source field value, explaining for every rule how
+/// far it matched: the longest matched prefix, the sub-pattern where matching failed, and the
+/// fields captured up to that point.
+///
+/// This compiles the leading sub-patterns of rules that don't match, so it's meant for
+/// debugging rules rather than for parsing.
+pub fn diagnose_grok(
+    source_field: &str,
+    grok_rules: &[GrokRule],
+) -> Result<Vec<GrokRuleDiagnostic>, FatalError> {
+    grok_rules
+        .iter()
+        .map(|rule| diagnose_grok_rule(source_field, rule))
+        .collect()
+}
+
+fn diagnose_grok_rule(
+    source: &str,
+    grok_rule: &GrokRule,
+) -> Result<GrokRuleDiagnostic, FatalError> {
+    if let Some(matches) = grok_rule.pattern.match_against(source)? {
+        let ParsedGrokObject {
+            parsed,
+            internal_errors,
+        } = parse_matches(&matches, &grok_rule.fields);
+        return Ok(GrokRuleDiagnostic {
+            rule: grok_rule.source.clone(),
+            matched: true,
+            matched_prefix: matches.matched().to_string(),
+            failed_pattern: None,
+            parsed,
+            internal_errors,
+        });
+    }
+
+    // find the longest run of leading sub-patterns that matches
+    let steps = grok_rule.steps();
+    let mut grok = grok_rule.grok();
+    for index in (0..steps.len()).rev() {
+        let Some(pattern) = grok_rule.compile_prefix(index, &mut grok) else {
+            continue;
+        };
+        if let Some(matches) = pattern.match_against(source)? {
+            let ParsedGrokObject {
+                parsed,
+                internal_errors,
+            } = parse_matches(&matches, &grok_rule.fields);
+            return Ok(GrokRuleDiagnostic {
+                rule: grok_rule.source.clone(),
+                matched: false,
+                matched_prefix: matches.matched().to_string(),
+                failed_pattern: steps.get(index + 1).map(|step| step.source.clone()),
+                parsed,
+                internal_errors,
+            });
+        }
+    }
+
+    Ok(GrokRuleDiagnostic {
+        rule: grok_rule.source.clone(),
+        matched: false,
+        matched_prefix: String::new(),
+        failed_pattern: steps.first().map(|step| step.source.clone()),
+        parsed: Value::Object(ObjectMap::new()),
+        internal_errors: vec![],
+    })
+}
+
 /// Tries to parse a given string with a given grok rule.
 /// Returns a parsed object and any internal errors encountered during operation, or errors
 /// if any were fatal.
@@ -56,80 +174,86 @@ pub fn parse_grok(
 ///
 /// Internal Errors:
 /// - FailedToApplyFilter - matches the rule, but there was a runtime error while applying on of the filters
-fn apply_grok_rule(source: &str, grok_rule: &GrokRule) -> Result<ParsedGrokObject, FatalError> {
-    let mut parsed = Value::Object(BTreeMap::new());
+pub(super) fn apply_grok_rule(
+    source: &str,
+    grok_rule: &GrokRule,
+) -> Result<ParsedGrokObject, FatalError> {
+    match grok_rule.pattern.match_against(source) {
+        Ok(Some(matches)) => Ok(parse_matches(&matches, &grok_rule.fields)),
+        Ok(None) => Err(FatalError::NoMatch),
+        Err(e) => Err(e),
+    }
+}
+
+/// Builds the parsed object from the captures of a grok rule, applying the filters of its fields.
+fn parse_matches(matches: &Matches<'_>, fields: &HashMap<String, GrokField>) -> ParsedGrokObject {
+    let mut parsed = Value::Object(ObjectMap::new());
     let mut internal_errors = vec![];
 
-    match grok_rule.pattern.match_against(source) {
-        Ok(Some(matches)) => {
-            for (name, match_str) in matches.iter() {
-                if match_str.is_empty() {
-                    continue;
-                }
+    for (name, match_str) in matches.iter() {
+        if match_str.is_empty() {
+            continue;
+        }
 
-                let mut value = Some(Value::from(match_str));
-
-                if let Some(GrokField {
-                    lookup: field,
-                    filters,
-                }) = grok_rule.fields.get(name)
-                {
-                    for filter in filters {
-                        if let Some(ref mut v) = value {
-                            value = match apply_filter(v, filter) {
-                                Ok(Value::Null) => None,
-                                Ok(v) if v.is_object() => Some(parse_keys_as_path(v)),
-                                Ok(v) => Some(v),
-                                Err(e) => {
-                                    internal_errors.push(e);
-                                    None
-                                }
-                            };
+        let mut value = Some(Value::from(match_str));
+
+        if let Some(GrokField {
+            lookup: field,
+            filters,
+        }) = fields.get(name)
+        {
+            for filter in filters {
+                if let Some(ref mut v) = value {
+                    value = match apply_filter(v, filter) {
+                        Ok(Value::Null) => None,
+                        Ok(v) if v.is_object() => Some(parse_keys_as_path(v)),
+                        Ok(v) => Some(v),
+                        Err(e) => {
+                            internal_errors.push(e);
+                            None
                         }
-                    }
+                    };
+                }
+            }
 
-                    if let Some(value) = value {
-                        match value {
-                            // root-level maps must be merged
-                            Value::Object(map) if field.is_root() => {
-                                parsed.as_object_mut().expect("root is object").extend(map);
-                            }
-                            // anything else at the root leve must be ignored
-                            _ if field.is_root() => {}
-                            // otherwise just apply VRL lookup insert logic
-                            _ => match parsed.get(field).cloned() {
-                                Some(Value::Array(mut values)) => {
-                                    values.push(value);
-                                    parsed.insert(field, values);
-                                }
-                                Some(v) => {
-                                    parsed.insert(field, Value::Array(vec![v, value]));
-                                }
-                                None => {
-                                    parsed.insert(field, value);
-                                }
-                            },
-                        };
+            if let Some(value) = value {
+                match value {
+                    // root-level maps must be merged
+                    Value::Object(map) if field.is_root() => {
+                        parsed.as_object_mut().expect("root is object").extend(map);
                     }
-                } else {
-                    // this must be a regex named capturing group (?<name>group),
-                    // where name can only be alphanumeric - thus we do not need to parse field names(no nested fields)
-                    parsed
-                        .as_object_mut()
-                        .expect("parsed value is not an object")
-                        .insert(name.to_string().into(), value.into());
-                }
+                    // anything else at the root leve must be ignored
+                    _ if field.is_root() => {}
+                    // otherwise just apply VRL lookup insert logic
+                    _ => match parsed.get(field).cloned() {
+                        Some(Value::Array(mut values)) => {
+                            values.push(value);
+                            parsed.insert(field, values);
+                        }
+                        Some(v) => {
+                            parsed.insert(field, Value::from(vec![v, value]));
+                        }
+                        None => {
+                            parsed.insert(field, value);
+                        }
+                    },
+                };
             }
+        } else {
+            // this must be a regex named capturing group (?<name>group),
+            // where name can only be alphanumeric - thus we do not need to parse field names(no nested fields)
+            parsed
+                .as_object_mut()
+                .expect("parsed value is not an object")
+                .insert(name.to_string().into(), value.into());
+        }
+    }
 
-            postprocess_value(&mut parsed);
+    postprocess_value(&mut parsed);
 
-            Ok(ParsedGrokObject {
-                parsed,
-                internal_errors,
-            })
-        }
-        Ok(None) => Err(FatalError::NoMatch),
-        Err(e) => Err(e),
+    ParsedGrokObject {
+        parsed,
+        internal_errors,
     }
 }
 
@@ -173,8 +297,10 @@ mod tests {
     use ordered_float::NotNan;
     use tracing_test::traced_test;
 
-    use super::super::parse_grok_rules::parse_grok_rules;
+    use super::super::parse_grok_rules::{GrokDialect, parse_grok_rules, parse_grok_rules_with};
+    use super::super::pattern_library::GrokPatternLibrary;
     use super::*;
+    use std::collections::BTreeMap;
 
     const FIXTURE_ROOT: &str = "tests/data/fixtures/parse_grok";
 
@@ -403,7 +529,7 @@ mod tests {
             r#"'{ "json_field1": "value2" }' '{ "json_field2": "value3" }'"#,
             Ok(Value::from(btreemap! {
                 "nested" => btreemap! {
-                    "json" =>  Value::Array(vec! [
+                    "json" =>  Value::from(vec! [
                         Value::from(btreemap! { "json_field1" => Value::Bytes("value2".into()) }),
                         Value::from(btreemap! { "json_field2" => Value::Bytes("value3".into()) }),
                     ]),
@@ -527,7 +653,7 @@ mod tests {
             parsed,
             Value::from(btreemap! {
                 "nested" => btreemap! {
-                   "field" =>  Value::Array(vec![1.into(), "INFO".into(), "message".into()]),
+                   "field" =>  Value::from(vec![Value::from(1), Value::from("INFO"), Value::from("message")]),
                 },
             })
         );
@@ -747,62 +873,62 @@ mod tests {
             (
                 "%{data:field:array}",
                 "[1,2]",
-                Ok(Value::Array(vec!["1".into(), "2".into()])),
+                Ok(Value::from(vec![Value::from("1"), Value::from("2")])),
             ),
             (
                 r#"%{data:field:array("\\t")}"#,
                 "[1\t2]",
-                Ok(Value::Array(vec!["1".into(), "2".into()])),
+                Ok(Value::from(vec![Value::from("1"), Value::from("2")])),
             ),
             (
                 r#"%{data:field:array("[]","\\n")}"#,
                 "[1\n2]",
-                Ok(Value::Array(vec!["1".into(), "2".into()])),
+                Ok(Value::from(vec![Value::from("1"), Value::from("2")])),
             ),
             (
                 r#"%{data:field:array("","-")}"#,
                 "1-2",
-                Ok(Value::Array(vec!["1".into(), "2".into()])),
+                Ok(Value::from(vec![Value::from("1"), Value::from("2")])),
             ),
             (
                 "%{data:field:array(integer)}",
                 "[1,2]",
-                Ok(Value::Array(vec![1.into(), 2.into()])),
+                Ok(Value::from(vec![Value::from(1), Value::from(2)])),
             ),
             (
                 r#"%{data:field:array(";", integer)}"#,
                 "[1;2]",
-                Ok(Value::Array(vec![1.into(), 2.into()])),
+                Ok(Value::from(vec![Value::from(1), Value::from(2)])),
             ),
             (
                 r#"%{data:field:array("{}",";", integer)}"#,
                 "{1;2}",
-                Ok(Value::Array(vec![1.into(), 2.into()])),
+                Ok(Value::from(vec![Value::from(1), Value::from(2)])),
             ),
             (
                 "%{data:field:array(number)}",
                 "[1,2]",
-                Ok(Value::Array(vec![1.into(), 2.into()])),
+                Ok(Value::from(vec![Value::from(1), Value::from(2)])),
             ),
             (
                 "%{data:field:array(integer)}",
                 "[1,2]",
-                Ok(Value::Array(vec![1.into(), 2.into()])),
+                Ok(Value::from(vec![Value::from(1), Value::from(2)])),
             ),
             (
                 "%{data:field:array(scale(10))}",
                 "[1,2.1]",
-                Ok(Value::Array(vec![10.into(), 21.into()])),
+                Ok(Value::from(vec![Value::from(10), Value::from(21)])),
             ),
             (
                 r#"%{data:field:array(";", scale(10))}"#,
                 "[1;2.1]",
-                Ok(Value::Array(vec![10.into(), 21.into()])),
+                Ok(Value::from(vec![Value::from(10), Value::from(21)])),
             ),
             (
                 r#"%{data:field:array("{}",";", scale(10))}"#,
                 "{1;2.1}",
-                Ok(Value::Array(vec![10.into(), 21.into()])),
+                Ok(Value::from(vec![Value::from(10), Value::from(21)])),
             ),
         ]);
 
@@ -1133,7 +1259,7 @@ mod tests {
         assert_eq!(
             parsed,
             Value::from(btreemap! {
-                 "field" =>  Value::Array(vec![1.into(), 2.into()]),
+                 "field" =>  Value::from(vec![Value::from(1), Value::from(2)]),
             })
         );
     }
@@ -1238,7 +1364,7 @@ mod tests {
                 "(?-s)%{data:field} (?s)%{data:field}",
                 "abc d\ne",
                 Ok(Value::from(btreemap! {
-                    "field" => Value::Array(vec!["abc".into(), "d\ne".into()]),
+                    "field" => Value::from(vec![Value::from("abc"), Value::from("d\ne")]),
                 })),
             ),
         ]);
@@ -1338,7 +1464,7 @@ mod tests {
             (
                 "%{data::json}",
                 r#"{"root": {"object": {"empty": {}}, "string": "abc" }}"#,
-                Ok(Value::Object(btreemap!(
+                Ok(Value::from(btreemap!(
                     "root" => btreemap! (
                         "string" => "abc"
                     )
@@ -1347,7 +1473,7 @@ mod tests {
             (
                 "%{data:field:json}",
                 r#"{"root": {"object": {"empty": {}}, "string": "abc" }}"#,
-                Ok(Value::Object(btreemap!(
+                Ok(Value::from(btreemap!(
                     "field" => btreemap!(
                         "root" => btreemap! (
                             "string" => "abc"
@@ -1357,7 +1483,7 @@ mod tests {
             (
                 r#"%{notSpace:network.destination.ip:nullIf("-")}"#,
                 "-",
-                Ok(Value::Object(btreemap!())),
+                Ok(Value::from(btreemap!())),
             ),
         ]);
     }
@@ -1366,11 +1492,155 @@ mod tests {
         test_full_grok(vec![(
             "%{data::json}",
             r#"{"a.b": "c"}"#,
-            Ok(Value::Object(btreemap!(
+            Ok(Value::from(btreemap!(
                 "a" => btreemap! (
                     "b" => "c"
                 )
             ))),
         )]);
     }
+
+    #[test]
+    fn diagnoses_rules() {
+        let rules = parse_grok_rules(
+            &[
+                "%{date(\"yyyy-MM-dd\"):date} %{word:level} user=%{integer:user} %{data:message}"
+                    .to_string(),
+                "%{_prefix} (%{integer:status} )?%{data:message}".to_string(),
+                "%{date(\"yyyy-MM-dd\"):date} %{word:level}".to_string(),
+            ],
+            btreemap! {
+                "_prefix" => "%{date(\"yyyy-MM-dd\"):date} %{word:level}".to_string(),
+            },
+        )
+        .expect("couldn't parse rules");
+        let diagnostics = diagnose_grok("2020-10-02 info user=alice logged in", &rules).unwrap();
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                GrokRuleDiagnostic {
+                    rule: rules[0].source.clone(),
+                    matched: false,
+                    matched_prefix: "2020-10-02 info user=".to_string(),
+                    failed_pattern: Some("%{integer:user}".to_string()),
+                    parsed: Value::from(btreemap! {
+                        "date" => 1_601_596_800_000_i64,
+                        "level" => "info",
+                    }),
+                    internal_errors: vec![],
+                },
+                GrokRuleDiagnostic {
+                    rule: rules[1].source.clone(),
+                    matched: true,
+                    matched_prefix: "2020-10-02 info user=alice logged in".to_string(),
+                    failed_pattern: None,
+                    parsed: Value::from(btreemap! {
+                        "date" => 1_601_596_800_000_i64,
+                        "level" => "info",
+                        "message" => "user=alice logged in",
+                    }),
+                    internal_errors: vec![],
+                },
+                GrokRuleDiagnostic {
+                    rule: rules[2].source.clone(),
+                    matched: false,
+                    matched_prefix: "2020-10-02 info".to_string(),
+                    failed_pattern: None,
+                    parsed: Value::from(btreemap! {
+                        "date" => 1_601_596_800_000_i64,
+                        "level" => "info",
+                    }),
+                    internal_errors: vec![],
+                },
+            ]
+        );
+
+        let diagnostics = diagnose_grok("oops", &rules[..1]).unwrap();
+        assert_eq!(diagnostics[0].matched_prefix, "");
+        assert_eq!(
+            diagnostics[0].failed_pattern.as_deref(),
+            Some("%{date(\"yyyy-MM-dd\"):date}")
+        );
+    }
+
+    #[test]
+    fn parses_logstash_rules() {
+        let rules = parse_grok_rules_with(
+            &[
+                "%{IP:[client][ip]} %{WORD:method} (?<path>/[^ ]*) %{NUMBER:bytes:int} %{NUMBER:duration:float}%{SUFFIX}"
+                    .to_string(),
+            ],
+            btreemap! { "SUFFIX" => " *%{WORD:status}?".to_string() },
+            GrokDialect::Logstash,
+            &GrokPatternLibrary::default(),
+        )
+        .expect("couldn't parse rules");
+        let parsed = parse_grok("at 10.0.0.1 GET /index.html 1024 0.25 ok.", &rules)
+            .unwrap()
+            .parsed;
+
+        assert_eq!(
+            parsed,
+            Value::from(btreemap! {
+                "client" => btreemap! { "ip" => "10.0.0.1" },
+                "method" => "GET",
+                "path" => "/index.html",
+                "bytes" => 1024,
+                "duration" => 0.25,
+                "status" => "ok",
+            })
+        );
+        assert_eq!(
+            parse_grok_rules_with(
+                &["%{NUMBER:bytes:long}".to_string()],
+                BTreeMap::new(),
+                GrokDialect::Logstash,
+                &GrokPatternLibrary::default(),
+            )
+            .unwrap_err(),
+            super::super::parse_grok_rules::Error::UnknownFilter("long".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_with_pattern_library() {
+        let library = GrokPatternLibrary::from_dir("tests/data/grok/patterns").unwrap();
+        let value = "12:30:00 user=alice";
+
+        let rules = parse_grok_rules_with(
+            &["%{APP_TIME:time} %{APP_USER:user}".to_string()],
+            BTreeMap::new(),
+            GrokDialect::Datadog,
+            &library,
+        )
+        .expect("couldn't parse rules");
+        assert_eq!(
+            parse_grok(value, &rules).unwrap().parsed,
+            Value::from(btreemap! {
+                "time" => "12:30:00",
+                "user" => "user=alice",
+            })
+        );
+
+        let rules = parse_grok_rules_with(
+            &["%{APP_LINE} %{WORD:extra}".to_string()],
+            BTreeMap::new(),
+            GrokDialect::Logstash,
+            &library,
+        )
+        .expect("couldn't parse rules");
+        let diagnostics = diagnose_grok(value, &rules).unwrap();
+        assert_eq!(diagnostics[0].matched_prefix, value);
+        assert_eq!(diagnostics[0].failed_pattern.as_deref(), Some(" "));
+        assert_eq!(
+            diagnostics[0].parsed,
+            Value::from(btreemap! {
+                "time" => "12:30:00",
+                "user" => "user=alice",
+            })
+        );
+
+        assert!(parse_grok_rules(&["%{APP_TIME:time}".to_string()], BTreeMap::new()).is_err());
+    }
 }
@@ -1,18 +1,19 @@
-use crate::path::OwnedValuePath;
+use crate::path::{OwnedSegment, OwnedValuePath};
 use crate::value::{KeyString, Value};
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 use std::{
     collections::{BTreeMap, HashMap},
     convert::TryFrom,
 };
 use tracing::error;
 
-use super::grok::Grok;
+use super::grok::{Grok, Pattern};
 use super::{
     ast::{self, Destination, GrokPattern},
     grok_filter::GrokFilter,
     matchers::{date, date::DateFilter},
     parse_grok_pattern::parse_grok_pattern,
+    pattern_library::GrokPatternLibrary,
 };
 
 static GROK_PATTERN_RE: LazyLock<onig::Regex> = LazyLock::new(|| {
@@ -27,6 +28,39 @@ pub struct GrokRule {
     pub pattern: super::grok::Pattern,
     /// a map of capture names(grok0, grok1, ...) to field information.
     pub fields: HashMap<String, GrokField>,
+    /// the rule as it was written
+    pub source: String,
+    /// the dialect the rule is written in
+    dialect: GrokDialect,
+    /// the pattern definitions available to the rule, in addition to the built-in ones
+    library: Arc<GrokPatternLibrary>,
+    /// the regular expression of the rule, before it's anchored and compiled
+    regex: String,
+    /// the top-level sub-patterns of the rule, used to explain why it doesn't match
+    steps: Vec<GrokStep>,
+}
+
+/// The dialect grok rules are written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GrokDialect {
+    /// Datadog grok rules: `%{matcher:field:filter}`, with the Datadog matchers and filters, and
+    /// aliases referenced like matchers. Rules match the whole value.
+    #[default]
+    Datadog,
+    /// Logstash grok rules: `%{PATTERN:field:type}`, where the optional `type` converts the value
+    /// to an `int` or a `float`, and fields are referenced as `[outer][inner]`. Regex named
+    /// captures `(?<field>...)` are extracted as fields too, and aliases are treated as
+    /// additional pattern definitions. Rules match anywhere in the value.
+    Logstash,
+}
+
+/// A top-level sub-pattern of a grok rule: a `%{...}` pattern, or the text between two of them.
+#[derive(Clone, Debug)]
+pub struct GrokStep {
+    /// the sub-pattern as it was written
+    pub source: String,
+    /// the end of the sub-pattern in the rule's regular expression
+    regex_end: usize,
 }
 
 /// A grok field, that should be extracted, with its lookup path and
@@ -48,6 +82,8 @@ pub struct GrokRuleParseContext {
     pub aliases: BTreeMap<KeyString, String>,
     /// used to detect cycles in alias definitions
     pub alias_stack: Vec<String>,
+    /// the top-level sub-patterns of the rule
+    pub steps: Vec<GrokStep>,
 }
 
 impl GrokRuleParseContext {
@@ -61,6 +97,16 @@ impl GrokRuleParseContext {
         self.fields.insert(grok_name.to_string(), field);
     }
 
+    /// records a top-level sub-pattern of the rule, ending at the end of the current regex
+    fn push_step(&mut self, source: &str) {
+        if self.alias_stack.is_empty() && !source.is_empty() {
+            self.steps.push(GrokStep {
+                source: source.to_string(),
+                regex_end: self.regex.len(),
+            });
+        }
+    }
+
     /// adds a filter to a field, associated with this grok alias
     fn register_filter(&mut self, grok_name: &str, filter: GrokFilter) {
         self.fields
@@ -74,6 +120,7 @@ impl GrokRuleParseContext {
             fields: HashMap::new(),
             aliases,
             alias_stack: vec![],
+            steps: vec![],
         }
     }
 
@@ -117,19 +164,55 @@ pub fn parse_grok_rules(
     patterns: &[String],
     aliases: BTreeMap<KeyString, String>,
 ) -> Result<Vec<GrokRule>, Error> {
-    let mut grok = Grok::with_patterns();
+    parse_grok_rules_with(
+        patterns,
+        aliases,
+        GrokDialect::Datadog,
+        &GrokPatternLibrary::default(),
+    )
+}
 
-    patterns
-        .iter()
-        .filter(|&r| !r.is_empty())
-        .map(|r| {
-            parse_pattern(
-                r,
-                &mut GrokRuleParseContext::new(aliases.clone()),
-                &mut grok,
-            )
-        })
-        .collect::<Result<Vec<GrokRule>, Error>>()
+/// Parses grok rules written in a given dialect, with the patterns of a library available in
+/// addition to the built-in patterns.
+pub fn parse_grok_rules_with(
+    patterns: &[String],
+    aliases: BTreeMap<KeyString, String>,
+    dialect: GrokDialect,
+    library: &GrokPatternLibrary,
+) -> Result<Vec<GrokRule>, Error> {
+    let patterns = patterns.iter().filter(|&r| !r.is_empty());
+    match dialect {
+        GrokDialect::Datadog => {
+            let library = Arc::new(library.clone());
+            let mut grok = grok_with_library(&library);
+            patterns
+                .map(|r| {
+                    let mut context = GrokRuleParseContext::new(aliases.clone());
+                    parse_pattern(r, &mut context, &mut grok, &library)
+                })
+                .collect()
+        }
+        GrokDialect::Logstash => {
+            let mut library = library.clone();
+            for (name, definition) in aliases {
+                library.insert(name.to_string(), definition);
+            }
+            let library = Arc::new(library);
+            let mut grok = grok_with_library(&library);
+            patterns
+                .map(|r| parse_logstash_pattern(r, &mut grok, &library))
+                .collect()
+        }
+    }
+}
+
+/// Creates a `Grok` with the built-in patterns and the patterns of a library.
+fn grok_with_library(library: &GrokPatternLibrary) -> Grok {
+    let mut grok = Grok::with_patterns();
+    for (name, definition) in library.definitions() {
+        grok.insert_definition(name, definition);
+    }
+    grok
 }
 
 ///
@@ -173,20 +256,11 @@ fn parse_pattern(
     pattern: &str,
     context: &mut GrokRuleParseContext,
     grok: &mut Grok,
+    library: &Arc<GrokPatternLibrary>,
 ) -> Result<GrokRule, Error> {
     parse_grok_rule(pattern, context)?;
-    let pattern = [
-        // In Oniguruma the (?m) modifier is used to enable the DOTALL mode(dot includes newlines),
-        // as opposed to the (?s) modifier in other regex flavors.
-        // \A, \z - parses from the beginning to the end of string, not line(until \n)
-        r"(?m)\A", // (?m) enables the DOTALL mode by default
-        &context
-            .regex
-            .replace("(?s)", "(?m)")
-            .replace("(?-s)", "(?-m)"),
-        r"\z",
-    ]
-    .concat();
+    let source = pattern.to_string();
+    let pattern = anchored_regex(&context.regex, true);
 
     // compile pattern
     let pattern = grok
@@ -196,9 +270,126 @@ fn parse_pattern(
     Ok(GrokRule {
         pattern,
         fields: context.fields.clone(),
+        source,
+        dialect: GrokDialect::Datadog,
+        library: Arc::clone(library),
+        regex: context.regex.clone(),
+        steps: context.steps.clone(),
     })
 }
 
+/// Parses a Logstash grok rule. Its `%{PATTERN:field:type}` patterns are left for `Grok` to
+/// expand, and every capture of the compiled pattern is extracted as a field.
+fn parse_logstash_pattern(
+    pattern: &str,
+    grok: &mut Grok,
+    library: &Arc<GrokPatternLibrary>,
+) -> Result<GrokRule, Error> {
+    let mut steps = vec![];
+    let mut regex_i = 0;
+    for (start, end) in GROK_PATTERN_RE.find_iter(pattern) {
+        for step_end in [start, end] {
+            if step_end > regex_i {
+                steps.push(GrokStep {
+                    source: pattern[regex_i..step_end].to_string(),
+                    regex_end: step_end,
+                });
+                regex_i = step_end;
+            }
+        }
+    }
+    if pattern.len() > regex_i {
+        steps.push(GrokStep {
+            source: pattern[regex_i..].to_string(),
+            regex_end: pattern.len(),
+        });
+    }
+
+    let compiled = grok
+        .compile(pattern, true)
+        .map_err(|e| Error::InvalidGrokExpression(pattern.to_string(), e.to_string()))?;
+    let fields = compiled
+        .names()
+        .map(|name| Ok((name.to_string(), logstash_field(name)?)))
+        .collect::<Result<_, Error>>()?;
+
+    Ok(GrokRule {
+        pattern: compiled,
+        fields,
+        source: pattern.to_string(),
+        dialect: GrokDialect::Logstash,
+        library: Arc::clone(library),
+        regex: pattern.to_string(),
+        steps,
+    })
+}
+
+/// Returns the field a Logstash capture, `field` or `field:type`, is extracted to.
+fn logstash_field(name: &str) -> Result<GrokField, Error> {
+    let (field, filters) = match name.rsplit_once(':') {
+        Some((field, "int")) => (field, vec![GrokFilter::Integer]),
+        Some((field, "float")) => (field, vec![GrokFilter::Number]),
+        Some((_, filter)) => return Err(Error::UnknownFilter(filter.to_string())),
+        None => (name, vec![]),
+    };
+
+    // `[outer][inner]` references a nested field, anything else a top-level field
+    let lookup = match field
+        .strip_prefix('[')
+        .and_then(|field| field.strip_suffix(']'))
+    {
+        Some(path) => path
+            .split("][")
+            .map(OwnedSegment::field)
+            .collect::<Vec<_>>()
+            .into(),
+        None => OwnedValuePath::single_field(field),
+    };
+
+    Ok(GrokField { lookup, filters })
+}
+
+/// Anchors a rule's regular expression to the beginning of the value, and to its end if `to_end`.
+fn anchored_regex(regex: &str, to_end: bool) -> String {
+    [
+        // In Oniguruma the (?m) modifier is used to enable the DOTALL mode(dot includes newlines),
+        // as opposed to the (?s) modifier in other regex flavors.
+        // \A, \z - parses from the beginning to the end of string, not line(until \n)
+        r"(?m)\A", // (?m) enables the DOTALL mode by default
+        &regex.replace("(?s)", "(?m)").replace("(?-s)", "(?-m)"),
+        if to_end { r"\z" } else { "" },
+    ]
+    .concat()
+}
+
+impl GrokRule {
+    /// The top-level sub-patterns of the rule, in order.
+    pub fn steps(&self) -> &[GrokStep] {
+        &self.steps
+    }
+
+    /// The regular expression of the rule, with its grok patterns not yet expanded.
+    pub(super) fn regex(&self) -> &str {
+        &self.regex
+    }
+
+    /// Creates a `Grok` with the pattern definitions available to the rule.
+    pub(super) fn grok(&self) -> Grok {
+        grok_with_library(&self.library)
+    }
+
+    /// Compiles the sub-patterns of the rule up to and including `steps()[index]`, to match a
+    /// prefix of a value. Returns `None` if they aren't a valid expression on their own, such as
+    /// when they end inside a group.
+    pub(super) fn compile_prefix(&self, index: usize, grok: &mut Grok) -> Option<Pattern> {
+        let regex = &self.regex[..self.steps[index].regex_end];
+        match self.dialect {
+            GrokDialect::Datadog => grok.compile(&anchored_regex(regex, false), true).ok(),
+            GrokDialect::Logstash => grok.compile(regex, true).ok(),
+        }
+    }
+}
+
 /// Parses a given rule to a pure grok pattern with a set of post-processing filters.
 ///
 /// # Arguments
@@ -210,12 +401,15 @@ fn parse_grok_rule(rule: &str, context: &mut GrokRuleParseContext) -> Result<(),
     let mut regex_i = 0;
     for (start, end) in GROK_PATTERN_RE.find_iter(rule) {
         context.append_regex(&rule[regex_i..start]);
+        context.push_step(&rule[regex_i..start]);
         regex_i = end;
         let pattern = parse_grok_pattern(&rule[start..end])
             .map_err(|e| Error::InvalidGrokExpression(rule[start..end].to_string(), e))?;
         resolve_grok_pattern(&pattern, context)?;
+        context.push_step(&rule[start..end]);
     }
     context.append_regex(&rule[regex_i..]);
+    context.push_step(&rule[regex_i..]);
 
     Ok(())
 }
@@ -0,0 +1,120 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A library of grok pattern definitions, loaded at runtime in addition to the built-in patterns.
+///
+/// Pattern files use the same format as the built-in ones: a `NAME definition` per line, with
+/// blank lines and lines starting with `#` ignored. Definitions of the library take precedence
+/// over the built-in definitions of the same name.
+///
+/// To make a library available to the grok functions of a program, set it in the program's
+/// `CompileConfig` with `set_custom`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GrokPatternLibrary {
+    definitions: BTreeMap<String, String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to read grok patterns from '{}': {}", .0.display(), .1)]
+    Io(PathBuf, io::Error),
+    #[error("invalid grok pattern definition at '{}' line {}: expected 'NAME definition'", .0.display(), .1)]
+    InvalidDefinition(PathBuf, usize),
+}
+
+impl GrokPatternLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the patterns of every file in a directory, in the order of their names.
+    pub fn from_dir(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut library = Self::new();
+        library.load_dir(path)?;
+        Ok(library)
+    }
+
+    /// Loads the patterns of every file in a directory, in the order of their names. Later
+    /// definitions replace earlier ones of the same name.
+    pub fn load_dir(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let mut files = fs::read_dir(path)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|e| e.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|e| Error::Io(path.to_owned(), e))?;
+        files.retain(|file| file.is_file());
+        files.sort();
+
+        files.iter().try_for_each(|file| self.load_file(file))
+    }
+
+    /// Loads the patterns of a pattern file.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|e| Error::Io(path.to_owned(), e))?;
+
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, definition) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| Error::InvalidDefinition(path.to_owned(), index + 1))?;
+            self.insert(name, definition.trim_start());
+        }
+
+        Ok(())
+    }
+
+    /// Defines the pattern `name`, replacing any previous definition.
+    pub fn insert(&mut self, name: impl Into<String>, definition: impl Into<String>) {
+        self.definitions.insert(name.into(), definition.into());
+    }
+
+    /// The pattern definitions, by name.
+    pub fn definitions(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.definitions
+            .iter()
+            .map(|(name, definition)| (name.as_str(), definition.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_pattern_directories() {
+        let library = GrokPatternLibrary::from_dir("tests/data/grok/patterns").unwrap();
+
+        assert_eq!(
+            library.definitions().collect::<Vec<_>>(),
+            [
+                ("APP_LINE", "%{APP_TIME:time} %{APP_USER:user}"),
+                ("APP_TIME", "%{TIME}"),
+                ("APP_USER", "user=%{USERNAME}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        let dir = Path::new("tests/data/grok/invalid_patterns");
+
+        assert_eq!(
+            GrokPatternLibrary::from_dir(dir).unwrap_err().to_string(),
+            format!(
+                "invalid grok pattern definition at '{}' line 3: expected 'NAME definition'",
+                dir.join("invalid.pattern").display()
+            )
+        );
+        assert!(GrokPatternLibrary::from_dir(dir.join("missing")).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    iter::Peekable,
+    mem,
+    str::Chars,
+};
+
+use aho_corasick::AhoCorasick;
+
+use super::{
+    parse_grok::{FatalError, ParsedGrokObject, apply_grok_rule},
+    parse_grok_rules::GrokRule,
+};
+
+/// The shortest literal used to rule out grok rules. Shorter literals, such as the spaces between
+/// sub-patterns, are in most values and would only slow down the search.
+const MIN_LITERAL_LEN: usize = 2;
+
+/// A list of grok rules that are matched together against values.
+///
+/// Rather than trying each rule in turn, the literal text that each rule requires is searched for
+/// in the value once, with a single automaton, and only the rules whose literals were all found
+/// are tried. Values are parsed with the first of those rules that matches, as `parse_grok` does
+/// with the full list.
+#[derive(Clone, Debug)]
+pub struct GrokRuleSet {
+    rules: Vec<GrokRule>,
+    /// the automaton of the literals required by the rules, if any rule requires one
+    literals: Option<AhoCorasick>,
+    /// the indices of the literals required by each rule
+    required: Vec<Vec<usize>>,
+}
+
+impl GrokRuleSet {
+    pub fn new(rules: Vec<GrokRule>) -> Self {
+        let mut literals = HashMap::new();
+        let mut required = rules
+            .iter()
+            .map(|rule| {
+                required_literals(rule.regex())
+                    .into_iter()
+                    .map(|literal| {
+                        let next = literals.len();
+                        *literals.entry(literal).or_insert(next)
+                    })
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut patterns = vec![String::new(); literals.len()];
+        for (literal, index) in literals {
+            patterns[index] = literal;
+        }
+        let literals = if patterns.is_empty() {
+            None
+        } else {
+            // Without an automaton, every rule is tried.
+            let automaton = AhoCorasick::new(&patterns).ok();
+            if automaton.is_none() {
+                required.iter_mut().for_each(Vec::clear);
+            }
+            automaton
+        };
+
+        Self {
+            rules,
+            literals,
+            required,
+        }
+    }
+
+    /// The rules, in the order they are tried.
+    pub fn rules(&self) -> &[GrokRule] {
+        &self.rules
+    }
+
+    /// Parses a value with the first rule that matches it.
+    ///
+    /// Rules ruled out by their literals aren't matched against the value, so unlike `parse_grok`
+    /// they can't fail with a regex engine error.
+    pub fn parse(&self, source: &str) -> Result<ParsedGrokObject, FatalError> {
+        let found = self.found_literals(source);
+
+        for (rule, required) in self.rules.iter().zip(&self.required) {
+            if !required.iter().all(|&literal| found[literal]) {
+                continue;
+            }
+            match apply_grok_rule(source, rule) {
+                Err(FatalError::NoMatch) => continue,
+                other => return other,
+            }
+        }
+        Err(FatalError::NoMatch)
+    }
+
+    /// Returns whether each literal is in the value.
+    fn found_literals(&self, source: &str) -> Vec<bool> {
+        let Some(literals) = &self.literals else {
+            return vec![];
+        };
+
+        let mut found = vec![false; literals.patterns_len()];
+        for literal in literals.find_overlapping_iter(source) {
+            found[literal.pattern().as_usize()] = true;
+        }
+        found
+    }
+}
+
+/// Returns literal strings that every match of a grok rule's regular expression contains.
+///
+/// The analysis is conservative: text that it doesn't understand is skipped, and it stops at a
+/// case-insensitive or extended mode modifier. A rule with an alternation outside of any group
+/// doesn't require any literal.
+fn required_literals(regex: &str) -> Vec<String> {
+    let mut literals = vec![];
+    let mut literal = String::new();
+    let mut depth = 0usize;
+    let mut chars = regex.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped.is_ascii_punctuation() || escaped == ' ' => {
+                    if depth == 0 {
+                        literal.push(escaped);
+                    }
+                }
+                // escapes followed by code points, properties or group references
+                Some('x' | 'u' | 'o' | 'p' | 'P' | 'k' | 'g' | '0'..='9') => {
+                    end_literal(&mut literal, &mut literals);
+                    while chars
+                        .next_if(|c| c.is_ascii_alphanumeric() || "{}<>'-+".contains(*c))
+                        .is_some()
+                    {}
+                }
+                // control characters and quoted text
+                Some('c' | 'C' | 'M' | 'Q') | None => break,
+                Some(_) => end_literal(&mut literal, &mut literals),
+            },
+            '%' if chars.peek() == Some(&'{') => {
+                end_literal(&mut literal, &mut literals);
+                skip_grok_pattern(&mut chars);
+            }
+            '(' => {
+                end_literal(&mut literal, &mut literals);
+                if chars.next_if_eq(&'?').is_some() {
+                    let mut modifiers = String::new();
+                    while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic() || *c == '-') {
+                        modifiers.push(c);
+                    }
+                    if chars.next_if_eq(&')').is_some() {
+                        if modifiers.contains(['i', 'x']) {
+                            break;
+                        }
+                        continue;
+                    }
+                }
+                depth += 1;
+            }
+            ')' => {
+                end_literal(&mut literal, &mut literals);
+                depth = depth.saturating_sub(1);
+            }
+            '|' if depth == 0 => return vec![],
+            // quantifiers which can make the previous character optional
+            '?' | '*' | '{' => {
+                literal.pop();
+                end_literal(&mut literal, &mut literals);
+                if c == '{' {
+                    while chars.next_if(|c| c.is_ascii_digit() || *c == ',').is_some() {}
+                    chars.next_if_eq(&'}');
+                }
+            }
+            '[' => {
+                end_literal(&mut literal, &mut literals);
+                skip_class(&mut chars);
+            }
+            '+' | '.' | '^' | '$' | '|' => end_literal(&mut literal, &mut literals),
+            c if depth == 0 => literal.push(c),
+            _ => {}
+        }
+    }
+    end_literal(&mut literal, &mut literals);
+
+    literals
+}
+
+fn end_literal(literal: &mut String, literals: &mut Vec<String>) {
+    if literal.len() >= MIN_LITERAL_LEN {
+        literals.push(mem::take(literal));
+    } else {
+        literal.clear();
+    }
+}
+
+/// Skips a `%{...}` grok pattern, after its `%`. Braces in quoted filter arguments, such as
+/// `%{regex("[^}]*"):x}`, are skipped along with the strings.
+fn skip_grok_pattern(chars: &mut Peekable<Chars<'_>>) {
+    let mut depth = 0usize;
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth <= 1 => return,
+            '}' => depth -= 1,
+            '"' | '\'' => skip_string(chars, c),
+            _ => {}
+        }
+    }
+}
+
+/// Skips a quoted string, including escaped quotes, after its opening `quote`.
+fn skip_string(chars: &mut Peekable<Chars<'_>>, quote: char) {
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            c if c == quote => return,
+            _ => {}
+        }
+    }
+}
+
+/// Skips a character class, including nested classes, after its opening bracket.
+fn skip_class(chars: &mut Peekable<Chars<'_>>) {
+    chars.next_if_eq(&'^');
+    // a leading closing bracket is part of the class
+    chars.next_if_eq(&']');
+
+    let mut depth = 1usize;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '[' => depth += 1,
+            ']' if depth == 1 => return,
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_grok::parse_grok;
+    use super::super::parse_grok_rules::{GrokDialect, parse_grok_rules, parse_grok_rules_with};
+    use super::super::pattern_library::GrokPatternLibrary;
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn finds_required_literals() {
+        let tests: &[(&str, &[&str])] = &[
+            ("%{word:a} user=%{word:b} took", &[" user=", " took"]),
+            (r"\[%{word:a}\] id=\d+ ok", &["] id=", " ok"]),
+            ("ab+c xy?z", &["ab", "c x"]),
+            ("a b{2,3}cd", &["a ", "cd"]),
+            ("start (?:middle|other) end", &["start ", " end"]),
+            ("start [a-z|]+ end", &["start ", " end"]),
+            ("GET (?<path>/api) HTTP", &["GET ", " HTTP"]),
+            (r"\x41BC user=", &[" user="]),
+            ("(?m)start (?i)end", &["start "]),
+            ("first|second", &[]),
+            ("%{data:a=\\d{2}} done", &[" done"]),
+            (r#"%{regex("[^}]*"):a} done"#, &[" done"]),
+            (r#"%{regex("a\"}b"):a} done"#, &[" done"]),
+            (r#"%{date("HH:mm:ss}"):a} done"#, &[" done"]),
+        ];
+
+        for (regex, literals) in tests {
+            assert_eq!(&required_literals(regex), literals, "{regex}");
+        }
+    }
+
+    #[test]
+    fn skips_rules_without_their_literals() {
+        let rules = parse_grok_rules(
+            &[
+                "%{word:action} user=%{word:user}".to_string(),
+                "%{word:action} id=%{integer:id}".to_string(),
+                "%{data:message}".to_string(),
+            ],
+            BTreeMap::new(),
+        )
+        .expect("couldn't parse rules");
+        let rule_set = GrokRuleSet::new(rules);
+
+        assert_eq!(rule_set.required, [vec![0], vec![1], vec![]]);
+        assert_eq!(rule_set.found_literals("login id=3"), [false, true]);
+    }
+
+    #[test]
+    fn parses_like_parse_grok_with_filter_arguments() {
+        let aliases = BTreeMap::from([("_time".into(), r#"%{date("HH:mm:ss"):time}"#.to_string())]);
+        let rules = [
+            r#"%{regex("[^}]*"):id} user=%{word:user}"#.to_string(),
+            r#"%{regex("[a-z]+"):action} took %{number:duration}"#.to_string(),
+            r#"%{date("yyyy-MM-dd'T'HH:mm:ss"):date} %{word:level}"#.to_string(),
+            "%{_time} %{data:message}".to_string(),
+        ];
+        let values = [
+            "abc user=alice",
+            "{x user=bob",
+            "login took 12",
+            "2024-01-02T03:04:05 info",
+            "03:04:05 started",
+            "no match",
+        ];
+
+        let rules = parse_grok_rules(&rules, aliases).expect("couldn't parse rules");
+        let rule_set = GrokRuleSet::new(rules.clone());
+        for value in values {
+            assert_eq!(rule_set.parse(value), parse_grok(value, &rules), "{value}");
+        }
+        assert!(rule_set.parse("{x user=bob").is_ok());
+    }
+
+    #[test]
+    fn parses_like_parse_grok() {
+        let aliases = BTreeMap::from([
+            ("_status".into(), "%{integer:status}|failed".to_string()),
+            ("_user".into(), "user=%{notSpace:user}".to_string()),
+        ]);
+        let rules = [
+            "%{word:action} %{_user} took %{integer:duration}ms".to_string(),
+            "%{word:action} %{_user}".to_string(),
+            "request %{_status}".to_string(),
+            r"\[%{word:level}\] %{data:message}".to_string(),
+            "(?i)ERROR %{data:message}".to_string(),
+            "%{notSpace:host}: %{data:message:keyvalue}".to_string(),
+        ];
+        let values = [
+            "login user=alice took 12ms",
+            "login user=alice took long",
+            "request 503",
+            "failed",
+            "[info] started",
+            "error: disk full",
+            "error disk full",
+            "web-1: a=1 b=2",
+            "no match",
+            "",
+        ];
+
+        let datadog_rules =
+            parse_grok_rules(&rules, aliases.clone()).expect("couldn't parse rules");
+        let logstash_rules = parse_grok_rules_with(
+            &[
+                "%{WORD:action} user=%{USERNAME:user}".to_string(),
+                r"\[%{LOGLEVEL:level}\]".to_string(),
+            ],
+            BTreeMap::new(),
+            GrokDialect::Logstash,
+            &GrokPatternLibrary::new(),
+        )
+        .expect("couldn't parse rules");
+
+        for rules in [datadog_rules, logstash_rules] {
+            let rule_set = GrokRuleSet::new(rules.clone());
+            for value in values {
+                assert_eq!(rule_set.parse(value), parse_grok(value, &rules), "{value}");
+            }
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+
+use super::field::{Field, normalize_fields};
+use super::node::{BooleanType, Comparison, ComparisonValue, QueryNode};
+use super::normalize::{Bound, string_order};
+
+/// A likely mistake in a query, found by [`QueryNode::lint`]. Each variant holds the part of
+/// the query it was found in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Lint {
+    /// Clauses that can't both match, such as `a:1 AND -a:1`.
+    Contradiction(String),
+
+    /// A wildcard that starts with `*`, which has to be checked against every value.
+    LeadingWildcard(String),
+
+    /// A range, or bounds of the same attribute, that no value can be within.
+    ImpossibleRange(String),
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Contradiction(query) => write!(f, "`{query}` can never match"),
+            Self::LeadingWildcard(query) => {
+                write!(f, "`{query}` starts with a wildcard, which is slow")
+            }
+            Self::ImpossibleRange(query) => write!(f, "no value can be within `{query}`"),
+        }
+    }
+}
+
+impl QueryNode {
+    /// Returns the likely mistakes in the query: contradicting clauses, leading wildcards and
+    /// impossible ranges.
+    ///
+    /// Bounds are only compared if they're always compared with values as strings, since string
+    /// values of attributes are compared with numeric bounds as strings. Bounds of tags in
+    /// different clauses aren't compared, since each can be matched by a different tag.
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        lint(self, &mut lints);
+        lints
+    }
+}
+
+fn lint(node: &QueryNode, lints: &mut Vec<Lint>) {
+    match node {
+        QueryNode::AttributeWildcard { wildcard, .. } if wildcard.starts_with('*') => {
+            lints.push(Lint::LeadingWildcard(node.to_lucene()));
+        }
+        QueryNode::AttributeRange {
+            attr,
+            lower,
+            lower_inclusive,
+            upper,
+            upper_inclusive,
+        } if *lower != ComparisonValue::Unbounded && *upper != ComparisonValue::Unbounded => {
+            let lower = Bound {
+                value: lower.clone(),
+                inclusive: *lower_inclusive,
+            };
+            let upper = Bound {
+                value: upper.clone(),
+                inclusive: *upper_inclusive,
+            };
+            if is_empty(attr, &lower, &upper) {
+                lints.push(Lint::ImpossibleRange(node.to_lucene()));
+            }
+        }
+        QueryNode::NegatedNode { node } => lint(node, lints),
+        QueryNode::Boolean {
+            oper: BooleanType::And,
+            ..
+        } => {
+            let mut clauses = Vec::new();
+            conjunction(node, &mut clauses);
+            for clause in &clauses {
+                lint(clause, lints);
+            }
+
+            let clauses = clauses
+                .into_iter()
+                .map(QueryNode::normalize)
+                .collect::<Vec<_>>();
+            contradictions(&clauses, lints);
+            impossible_bounds(&clauses, lints);
+        }
+        QueryNode::Boolean { nodes, .. } => {
+            for node in nodes {
+                lint(node, lints);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collects the clauses of a conjunction, including those of nested conjunctions.
+fn conjunction<'a>(node: &'a QueryNode, clauses: &mut Vec<&'a QueryNode>) {
+    match node {
+        QueryNode::Boolean {
+            oper: BooleanType::And,
+            nodes,
+        } => {
+            for node in nodes {
+                conjunction(node, clauses);
+            }
+        }
+        node => clauses.push(node),
+    }
+}
+
+fn contradictions(clauses: &[QueryNode], lints: &mut Vec<Lint>) {
+    for clause in clauses {
+        let opposite = match clause {
+            QueryNode::NegatedNode { node } => (**node).clone(),
+            QueryNode::AttributeMissing { attr } => {
+                QueryNode::AttributeExists { attr: attr.clone() }
+            }
+            _ => continue,
+        };
+
+        if clauses.contains(&opposite) {
+            lints.push(Lint::Contradiction(format!(
+                "{} AND {}",
+                opposite.to_lucene(),
+                clause.to_lucene()
+            )));
+        }
+    }
+}
+
+/// Finds lower and upper bounds of the same attribute in different clauses of a conjunction
+/// that no value can be within.
+fn impossible_bounds(clauses: &[QueryNode], lints: &mut Vec<Lint>) {
+    let bounds = clauses
+        .iter()
+        .filter_map(|clause| Some((clause, bounds(clause)?)))
+        .collect::<Vec<_>>();
+
+    for (lower_clause, (attr, lower, _)) in &bounds {
+        let Some(lower) = lower else {
+            continue;
+        };
+        for (upper_clause, (other, _, upper)) in &bounds {
+            if lower_clause == upper_clause || attr != other {
+                continue;
+            }
+            if let Some(upper) = upper
+                && is_empty(attr, lower, upper)
+            {
+                lints.push(Lint::ImpossibleRange(format!(
+                    "{} AND {}",
+                    lower_clause.to_lucene(),
+                    upper_clause.to_lucene()
+                )));
+            }
+        }
+    }
+}
+
+/// Returns the attribute of a comparison or range, and its lower and upper bounds. Attributes
+/// that resolve to multiple fields or to tags are skipped, since each field or tag can be within
+/// other bounds.
+fn bounds(node: &QueryNode) -> Option<(&str, Option<Bound>, Option<Bound>)> {
+    let bound = |value: &ComparisonValue, inclusive: bool| {
+        (*value != ComparisonValue::Unbounded).then(|| Bound {
+            value: value.clone(),
+            inclusive,
+        })
+    };
+
+    let (attr, lower, upper) = match node {
+        QueryNode::AttributeComparison {
+            attr,
+            comparator,
+            value,
+        } => match comparator {
+            Comparison::Gt => (attr, bound(value, false), None),
+            Comparison::Gte => (attr, bound(value, true), None),
+            Comparison::Lt => (attr, None, bound(value, false)),
+            Comparison::Lte => (attr, None, bound(value, true)),
+        },
+        QueryNode::AttributeRange {
+            attr,
+            lower,
+            lower_inclusive,
+            upper,
+            upper_inclusive,
+        } => (
+            attr,
+            bound(lower, *lower_inclusive),
+            bound(upper, *upper_inclusive),
+        ),
+        _ => return None,
+    };
+
+    match normalize_fields(attr).as_slice() {
+        [Field::Tag(_)] => None,
+        [_] => Some((attr.as_str(), lower, upper)),
+        _ => None,
+    }
+}
+
+/// Returns `true` if no value of `attr` can be within `lower` and `upper`. Only bounds that are
+/// compared as strings are compared, since string values are within some numeric bounds that
+/// numbers aren't, such as `"2"` within `>10` and `<5`.
+fn is_empty(attr: &str, lower: &Bound, upper: &Bound) -> bool {
+    let (Some(lower_order), Some(upper_order)) = (
+        string_order(attr, &lower.value),
+        string_order(attr, &upper.value),
+    ) else {
+        return false;
+    };
+
+    match lower_order.cmp(&upper_order) {
+        std::cmp::Ordering::Greater => true,
+        std::cmp::Ordering::Equal => !(lower.inclusive && upper.inclusive),
+        std::cmp::Ordering::Less => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(query: &str) -> Vec<String> {
+        query
+            .parse::<QueryNode>()
+            .unwrap()
+            .lint()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn contradictions() {
+        assert_eq!(lint("a:1 AND -a:1"), ["`a:1 AND NOT a:1` can never match"]);
+        assert_eq!(
+            lint("service:web AND (_exists_:@user AND NOT _exists_:@user)"),
+            ["`_exists_:@user AND _missing_:@user` can never match"]
+        );
+        assert_eq!(
+            lint("(a AND b) OR (c AND NOT (NOT (NOT c)))"),
+            ["`c AND NOT c` can never match"]
+        );
+        assert!(lint("a:1 OR -a:1").is_empty());
+    }
+
+    #[test]
+    fn leading_wildcards() {
+        assert_eq!(
+            lint("@user:*son AND service:web*"),
+            ["`@user:*son` starts with a wildcard, which is slow"]
+        );
+    }
+
+    #[test]
+    fn impossible_ranges() {
+        assert_eq!(
+            lint("a:[b TO a] OR @b:{\"x\" TO \"x\"} OR @c:[\"x\" TO \"x\"]"),
+            [
+                "no value can be within `a:[b TO a]`",
+                "no value can be within `@b:{x TO x}`",
+            ]
+        );
+        assert_eq!(
+            lint("@a:>b AND x AND @a:<=a"),
+            ["no value can be within `@a:>b AND @a:<=a`"]
+        );
+        assert_eq!(
+            lint("status:>b AND status:<a"),
+            ["no value can be within `status:>b AND status:<a`"]
+        );
+
+        // Values of reserved fields are compared as strings, and each default field can be
+        // within other bounds.
+        assert!(lint("status:[10 TO 5] OR (>9 AND <1)").is_empty());
+
+        // String values of attributes are compared with numeric bounds as strings, so "2" is
+        // within both bounds, and each bound of a tag can be matched by a different tag.
+        assert!(lint("@a:>10 AND @a:<5").is_empty());
+        assert!(lint("@a:[10 TO 5]").is_empty());
+        assert!(lint("a:>5 AND a:<3").is_empty());
+    }
+}
@@ -7,9 +7,14 @@
 
 mod field;
 mod grammar;
+mod lint;
 mod node;
+mod normalize;
 mod parser;
+mod pretty;
 
 pub use field::{Field, normalize_fields};
+pub use grammar::DEFAULT_FIELD;
+pub use lint::Lint;
 pub use node::{BooleanType, Comparison, ComparisonValue, QueryNode};
 pub use parser::Error as ParseError;
@@ -0,0 +1,311 @@
+use std::cmp::Ordering;
+
+use super::field::normalize_fields;
+use super::node::{BooleanType, Comparison, ComparisonValue, QueryNode};
+
+impl QueryNode {
+    /// Returns an equivalent query in canonical form, so that equivalent queries written
+    /// differently can be compared by their Lucene form.
+    ///
+    /// Nested booleans of the same type are flattened, and their clauses are deduplicated and
+    /// sorted. Negations are pushed into the clauses that have an opposite, and ranges with an
+    /// unbounded side are written as comparisons. Comparisons of the same attribute in a
+    /// conjunction are merged into ranges, and redundant bounds are dropped where the values are
+    /// always compared as strings.
+    pub fn normalize(&self) -> QueryNode {
+        match self {
+            QueryNode::AttributeRange {
+                attr,
+                lower,
+                lower_inclusive,
+                upper,
+                upper_inclusive,
+            } => match (lower, upper) {
+                (ComparisonValue::Unbounded, ComparisonValue::Unbounded) => {
+                    QueryNode::AttributeExists { attr: attr.clone() }
+                }
+                (ComparisonValue::Unbounded, _) => QueryNode::AttributeComparison {
+                    attr: attr.clone(),
+                    comparator: upper_comparison(*upper_inclusive),
+                    value: upper.clone(),
+                },
+                (_, ComparisonValue::Unbounded) => QueryNode::AttributeComparison {
+                    attr: attr.clone(),
+                    comparator: lower_comparison(*lower_inclusive),
+                    value: lower.clone(),
+                },
+                _ => self.clone(),
+            },
+            QueryNode::NegatedNode { node } => match node.normalize() {
+                QueryNode::NegatedNode { node } => *node,
+                QueryNode::MatchAllDocs => QueryNode::MatchNoDocs,
+                QueryNode::MatchNoDocs => QueryNode::MatchAllDocs,
+                QueryNode::AttributeExists { attr } => QueryNode::AttributeMissing { attr },
+                QueryNode::AttributeMissing { attr } => QueryNode::AttributeExists { attr },
+                node => QueryNode::NegatedNode {
+                    node: Box::new(node),
+                },
+            },
+            QueryNode::Boolean { oper, nodes } => {
+                boolean(*oper, nodes.iter().map(QueryNode::normalize).collect())
+            }
+            _ => self.clone(),
+        }
+    }
+}
+
+fn boolean(oper: BooleanType, nodes: Vec<QueryNode>) -> QueryNode {
+    let (identity, absorbing) = match oper {
+        BooleanType::And => (QueryNode::MatchAllDocs, QueryNode::MatchNoDocs),
+        BooleanType::Or => (QueryNode::MatchNoDocs, QueryNode::MatchAllDocs),
+    };
+
+    let mut clauses = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        match node {
+            QueryNode::Boolean { oper: inner, nodes } if inner == oper => clauses.extend(nodes),
+            node => clauses.push(node),
+        }
+    }
+    if clauses.contains(&absorbing) {
+        return absorbing;
+    }
+    clauses.retain(|clause| *clause != identity);
+
+    if oper == BooleanType::And {
+        clauses = merge_ranges(clauses);
+    }
+    clauses.sort_by_cached_key(QueryNode::to_lucene);
+    clauses.dedup();
+
+    match clauses.len() {
+        0 => identity,
+        _ => QueryNode::new_boolean(oper, clauses),
+    }
+}
+
+fn lower_comparison(inclusive: bool) -> Comparison {
+    if inclusive {
+        Comparison::Gte
+    } else {
+        Comparison::Gt
+    }
+}
+
+fn upper_comparison(inclusive: bool) -> Comparison {
+    if inclusive {
+        Comparison::Lte
+    } else {
+        Comparison::Lt
+    }
+}
+
+/// A bound of the values of an attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Bound {
+    pub(super) value: ComparisonValue,
+    pub(super) inclusive: bool,
+}
+
+/// The lower and upper bounds of the values of an attribute, in a conjunction.
+#[derive(Debug, Default)]
+struct Bounds {
+    lower: Vec<Bound>,
+    upper: Vec<Bound>,
+}
+
+impl Bounds {
+    /// Adds the bounds of `node`, if it's a comparison or a range.
+    fn add(&mut self, node: &QueryNode) -> bool {
+        match node {
+            QueryNode::AttributeComparison {
+                comparator, value, ..
+            } if *value != ComparisonValue::Unbounded => {
+                let bound = Bound {
+                    value: value.clone(),
+                    inclusive: matches!(comparator, Comparison::Gte | Comparison::Lte),
+                };
+                match comparator {
+                    Comparison::Gt | Comparison::Gte => self.lower.push(bound),
+                    Comparison::Lt | Comparison::Lte => self.upper.push(bound),
+                }
+                true
+            }
+            QueryNode::AttributeRange {
+                lower,
+                lower_inclusive,
+                upper,
+                upper_inclusive,
+                ..
+            } if *lower != ComparisonValue::Unbounded && *upper != ComparisonValue::Unbounded => {
+                self.lower.push(Bound {
+                    value: lower.clone(),
+                    inclusive: *lower_inclusive,
+                });
+                self.upper.push(Bound {
+                    value: upper.clone(),
+                    inclusive: *upper_inclusive,
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the bounds of each attribute compared in `clauses`, in order, and the other
+    /// clauses. Only attributes that resolve to a single field are included, since the bounds
+    /// of each field of the default attribute are independent.
+    fn collect(clauses: Vec<QueryNode>) -> (Vec<(String, Bounds)>, Vec<QueryNode>) {
+        let mut bounds: Vec<(String, Bounds)> = Vec::new();
+        let mut others = Vec::new();
+        for clause in clauses {
+            let attr = match &clause {
+                QueryNode::AttributeComparison { attr, .. }
+                | QueryNode::AttributeRange { attr, .. }
+                    if normalize_fields(attr).len() == 1 =>
+                {
+                    attr
+                }
+                _ => {
+                    others.push(clause);
+                    continue;
+                }
+            };
+
+            let index = match bounds.iter().position(|(a, _)| a == attr) {
+                Some(index) => index,
+                None => {
+                    bounds.push((attr.clone(), Bounds::default()));
+                    bounds.len() - 1
+                }
+            };
+            if !bounds[index].1.add(&clause) {
+                others.push(clause);
+            }
+        }
+
+        (bounds, others)
+    }
+}
+
+/// Returns the string that `value` is compared as, when compared with values of `attr`, if it's
+/// always compared as a string. Attributes are compared numerically with numeric values.
+pub(super) fn string_order(attr: &str, value: &ComparisonValue) -> Option<String> {
+    match value {
+        ComparisonValue::String(value) => Some(value.clone()),
+        ComparisonValue::Unbounded => None,
+        _ if attr.starts_with('@') => None,
+        value => Some(value.to_string()),
+    }
+}
+
+/// Keeps the tightest of `bounds`, if they're all compared as strings. `tighter` is the order of
+/// a bound's value that makes it tighter.
+fn tightest(attr: &str, bounds: Vec<Bound>, tighter: Ordering) -> Vec<Bound> {
+    let keys = bounds
+        .iter()
+        .map(|bound| string_order(attr, &bound.value))
+        .collect::<Option<Vec<_>>>();
+    let Some(keys) = keys else {
+        return bounds;
+    };
+
+    bounds
+        .into_iter()
+        .zip(keys)
+        .reduce(|current, next| match next.1.cmp(&current.1) {
+            Ordering::Equal if !next.0.inclusive => next,
+            order if order == tighter => next,
+            _ => current,
+        })
+        .map(|(bound, _)| vec![bound])
+        .unwrap_or_default()
+}
+
+/// Merges the lower and upper bounds of each attribute in a conjunction into ranges.
+fn merge_ranges(clauses: Vec<QueryNode>) -> Vec<QueryNode> {
+    let (bounds, mut clauses) = Bounds::collect(clauses);
+
+    for (attr, bounds) in bounds {
+        let lower = tightest(&attr, bounds.lower, Ordering::Greater);
+        let upper = tightest(&attr, bounds.upper, Ordering::Less);
+        let ranges = lower.len().min(upper.len());
+
+        for (lower, upper) in lower.iter().zip(&upper) {
+            clauses.push(QueryNode::AttributeRange {
+                attr: attr.clone(),
+                lower: lower.value.clone(),
+                lower_inclusive: lower.inclusive,
+                upper: upper.value.clone(),
+                upper_inclusive: upper.inclusive,
+            });
+        }
+        for bound in &lower[ranges..] {
+            clauses.push(QueryNode::AttributeComparison {
+                attr: attr.clone(),
+                comparator: lower_comparison(bound.inclusive),
+                value: bound.value.clone(),
+            });
+        }
+        for bound in &upper[ranges..] {
+            clauses.push(QueryNode::AttributeComparison {
+                attr: attr.clone(),
+                comparator: upper_comparison(bound.inclusive),
+                value: bound.value.clone(),
+            });
+        }
+    }
+
+    clauses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(query: &str) -> String {
+        query.parse::<QueryNode>().unwrap().normalize().to_lucene()
+    }
+
+    #[test]
+    fn equivalent_queries() {
+        for (queries, want) in [
+            (
+                &["b AND (a AND c)", "c AND b AND a", "a AND b AND c AND a"][..],
+                "a AND b AND c",
+            ),
+            (
+                &["host:x OR (service:y OR host:x)", "service:y OR host:x"],
+                "host:x OR service:y",
+            ),
+            (&["NOT (NOT a)", "-(-a)", "a"], "a"),
+            (&["NOT _exists_:@x", "_missing_:@x"], "_missing_:@x"),
+            (&["a OR *:*", "*:*", "NOT (-*:*)"], "*:*"),
+            (&["a AND -*:*", "-*:*"], "-*:*"),
+            (&["@a:[1 TO *]", "@a:>=1"], "@a:>=1"),
+            (&["@a:[* TO *]", "_exists_:@a"], "_exists_:@a"),
+            (&["@a:>=1 AND @a:<=5", "@a:[1 TO 5]"], "@a:[1 TO 5]"),
+            (&["@a:<=5 AND x AND @a:>=1"], "@a:[1 TO 5] AND x"),
+            (
+                &["status:>=b AND status:>a AND status:<z", "status:[b TO z}"],
+                "status:[b TO z}",
+            ),
+        ] {
+            for query in queries {
+                assert_eq!(normalize(query), want, "{query}");
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_bounds_compared_numerically_or_as_strings() {
+        // String values of `@a` are compared as strings, so both bounds are needed.
+        assert_eq!(normalize("@a:>5 AND @a:>10"), "@a:>10 AND @a:>5");
+        assert_eq!(normalize("@a:>b AND @a:>a"), "@a:>b");
+    }
+
+    #[test]
+    fn keeps_default_field_bounds() {
+        assert_eq!(normalize(">1 AND <5"), "<5 AND >1");
+    }
+}
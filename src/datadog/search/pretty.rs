@@ -0,0 +1,124 @@
+use super::node::{BooleanType, QueryNode};
+
+/// The number of spaces each nested group is indented by.
+const INDENT: usize = 2;
+
+impl QueryNode {
+    /// Returns the query in query syntax, like [`QueryNode::to_lucene`], with boolean groups that
+    /// don't fit in `width` columns broken into one clause per line, and nested groups indented.
+    pub fn to_lucene_pretty(&self, width: usize) -> String {
+        Printer { width }.render(self, 0)
+    }
+}
+
+struct Printer {
+    width: usize,
+}
+
+impl Printer {
+    fn fits(&self, indent: usize, query: &str) -> bool {
+        indent + query.len() <= self.width
+    }
+
+    /// Renders `node` as a query, starting at column `indent`.
+    fn render(&self, node: &QueryNode, indent: usize) -> String {
+        let flat = node.to_lucene();
+        if self.fits(indent, &flat) {
+            return flat;
+        }
+
+        match node {
+            QueryNode::Boolean { oper, nodes } if !nodes.is_empty() => {
+                let conjunction = match oper {
+                    BooleanType::And => "AND ",
+                    BooleanType::Or => "OR ",
+                };
+                let padding = " ".repeat(indent);
+
+                nodes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, node)| {
+                        let clause = self.clause(node, indent);
+                        if index == 0 {
+                            clause
+                        } else {
+                            format!("{padding}{conjunction}{clause}")
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            _ => self.clause(node, indent),
+        }
+    }
+
+    /// Renders `node` as a clause of a boolean group.
+    fn clause(&self, node: &QueryNode, indent: usize) -> String {
+        match node {
+            QueryNode::Boolean { .. } => self.group(node, indent),
+            QueryNode::NegatedNode { node } => match **node {
+                QueryNode::Boolean { .. } | QueryNode::NegatedNode { .. } => {
+                    format!("NOT {}", self.group(node, indent))
+                }
+                _ => format!("NOT {}", node.to_lucene()),
+            },
+            _ => node.to_lucene(),
+        }
+    }
+
+    /// Renders `node` in parentheses, on its own indented lines if it doesn't fit.
+    fn group(&self, node: &QueryNode, indent: usize) -> String {
+        let flat = format!("({})", node.to_lucene());
+        if self.fits(indent, &flat) {
+            return flat;
+        }
+
+        format!(
+            "(\n{}{}\n{})",
+            " ".repeat(indent + INDENT),
+            self.render(node, indent + INDENT),
+            " ".repeat(indent)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaks_long_groups() {
+        let query = "host:this OR ((@b:test* AND c:that) AND d:the_other @e:[1 TO 5]) OR NOT (service:web AND -status:error)";
+        let node = query.parse::<QueryNode>().unwrap();
+
+        assert_eq!(
+            node.to_lucene_pretty(40),
+            "\
+host:this
+OR (
+  (@b:test* AND c:that)
+  AND d:the_other
+  AND @e:[1 TO 5]
+)
+OR NOT (service:web AND NOT status:error)"
+        );
+        assert_eq!(node.to_lucene_pretty(200), node.to_lucene());
+    }
+
+    #[test]
+    fn reparses_to_the_same_query() {
+        for query in [
+            "a AND (b OR c OR (d AND NOT (e OR f))) AND @x:[1 TO 5} AND -(g OR h)",
+            "NOT (NOT (a OR b)) OR c",
+            "*:* AND _missing_:@a",
+        ] {
+            let node = query.parse::<QueryNode>().unwrap();
+
+            for width in [0, 10, 20, 1000] {
+                let pretty = node.to_lucene_pretty(width);
+                assert_eq!(pretty.parse::<QueryNode>().unwrap(), node, "{pretty}");
+            }
+        }
+    }
+}
@@ -383,30 +383,80 @@ pub enum Container {
     Block(Node<Block>),
     Array(Node<Array>),
     Object(Node<Object>),
+    SpreadArray(Node<SpreadArray>),
+    SpreadObject(Node<SpreadObject>),
+}
+
+impl Container {
+    /// Creates an array container from the items of an array literal.
+    ///
+    /// Literals without any spread items result in a regular `Array`.
+    pub(crate) fn from_array(node: Node<Vec<ArrayItem>>) -> Self {
+        let (span, items) = node.take();
+
+        if items.iter().any(ArrayItem::is_spread) {
+            return Container::SpreadArray(Node::new(span, SpreadArray(items)));
+        }
+
+        let array = items
+            .into_iter()
+            .filter_map(|item| match item {
+                ArrayItem::Element(expr) => Some(expr),
+                ArrayItem::Spread(_) => None,
+            })
+            .collect();
+
+        Container::Array(Node::new(span, array))
+    }
+
+    /// Creates an object container from the items of an object literal.
+    ///
+    /// Literals without any spread items result in a regular `Object`.
+    pub(crate) fn from_object(node: Node<Vec<ObjectItem>>) -> Self {
+        let (span, items) = node.take();
+
+        if items.iter().any(ObjectItem::is_spread) {
+            return Container::SpreadObject(Node::new(span, SpreadObject(items)));
+        }
+
+        let object = items
+            .into_iter()
+            .filter_map(|item| match item {
+                ObjectItem::Field(key, expr) => Some((key, expr)),
+                ObjectItem::Spread(_) => None,
+            })
+            .collect();
+
+        Container::Object(Node::new(span, object))
+    }
 }
 
 impl fmt::Display for Container {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Container::{Array, Block, Group, Object};
+        use Container::{Array, Block, Group, Object, SpreadArray, SpreadObject};
 
         match self {
             Group(v) => v.fmt(f),
             Block(v) => v.fmt(f),
             Array(v) => v.fmt(f),
             Object(v) => v.fmt(f),
+            SpreadArray(v) => v.fmt(f),
+            SpreadObject(v) => v.fmt(f),
         }
     }
 }
 
 impl fmt::Debug for Container {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Container::{Array, Block, Group, Object};
+        use Container::{Array, Block, Group, Object, SpreadArray, SpreadObject};
 
         let value = match self {
             Group(v) => format!("{v:?}"),
             Block(v) => format!("{v:?}"),
             Array(v) => format!("{v:?}"),
             Object(v) => format!("{v:?}"),
+            SpreadArray(v) => format!("{v:?}"),
+            SpreadObject(v) => format!("{v:?}"),
         };
 
         write!(f, "Container({value})")
@@ -547,6 +597,84 @@ impl FromIterator<Node<Expr>> for Array {
     }
 }
 
+// -----------------------------------------------------------------------------
+// spread array
+// -----------------------------------------------------------------------------
+
+/// An item of an array literal.
+#[derive(Clone, PartialEq)]
+pub enum ArrayItem {
+    /// A single element, e.g. `1` in `[1, ...a]`.
+    Element(Node<Expr>),
+
+    /// An array whose elements are spliced in, e.g. `...a` in `[1, ...a]`.
+    Spread(Node<Expr>),
+}
+
+impl ArrayItem {
+    #[must_use]
+    pub fn is_spread(&self) -> bool {
+        matches!(self, ArrayItem::Spread(_))
+    }
+}
+
+impl fmt::Display for ArrayItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrayItem::Element(expr) => expr.fmt(f),
+            ArrayItem::Spread(expr) => write!(f, "...{expr}"),
+        }
+    }
+}
+
+impl fmt::Debug for ArrayItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrayItem::Element(expr) => write!(f, "{expr:?}"),
+            ArrayItem::Spread(expr) => write!(f, "Spread({expr:?})"),
+        }
+    }
+}
+
+/// An array literal containing at least one spread item.
+#[derive(Clone, PartialEq)]
+pub struct SpreadArray(pub(crate) Vec<ArrayItem>);
+
+impl fmt::Display for SpreadArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items = self
+            .0
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "[{items}]")
+    }
+}
+
+impl fmt::Debug for SpreadArray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items = self
+            .0
+            .iter()
+            .map(|item| format!("{item:?}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "SpreadArray([{items}])")
+    }
+}
+
+impl IntoIterator for SpreadArray {
+    type Item = ArrayItem;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
 // -----------------------------------------------------------------------------
 // object
 // -----------------------------------------------------------------------------
@@ -598,6 +726,87 @@ impl FromIterator<(Node<String>, Node<Expr>)> for Object {
     }
 }
 
+// -----------------------------------------------------------------------------
+// spread object
+// -----------------------------------------------------------------------------
+
+/// An item of an object literal.
+#[derive(Clone, PartialEq)]
+pub enum ObjectItem {
+    /// A single field, e.g. `"a": 1` in `{ "a": 1, ...b }`.
+    Field(Node<String>, Node<Expr>),
+
+    /// An object whose fields are merged in, e.g. `...b` in `{ "a": 1, ...b }`.
+    Spread(Node<Expr>),
+}
+
+impl ObjectItem {
+    #[must_use]
+    pub fn is_spread(&self) -> bool {
+        matches!(self, ObjectItem::Spread(_))
+    }
+}
+
+impl fmt::Display for ObjectItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectItem::Field(key, expr) => write!(f, r#""{key}": {expr}"#),
+            ObjectItem::Spread(expr) => write!(f, "...{expr}"),
+        }
+    }
+}
+
+impl fmt::Debug for ObjectItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectItem::Field(key, expr) => write!(f, r#""{key}": {expr:?}"#),
+            ObjectItem::Spread(expr) => write!(f, "Spread({expr:?})"),
+        }
+    }
+}
+
+/// An object literal containing at least one spread item.
+///
+/// Unlike `Object`, the items are kept in their original order, as later
+/// items overwrite fields set by earlier ones.
+#[derive(Clone, PartialEq)]
+pub struct SpreadObject(pub(crate) Vec<ObjectItem>);
+
+impl fmt::Display for SpreadObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items = self
+            .0
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "{{ {items} }}")
+    }
+}
+
+impl fmt::Debug for SpreadObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items = self
+            .0
+            .iter()
+            .map(|item| format!("{item:?}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(f, "SpreadObject({{ {items} }})")
+    }
+}
+
+impl IntoIterator for SpreadObject {
+    type Item = ObjectItem;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
 // -----------------------------------------------------------------------------
 // if statement
 // -----------------------------------------------------------------------------
@@ -704,7 +913,7 @@ impl fmt::Debug for Op {
 }
 
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum Opcode {
     Mul,
     Div,
@@ -820,26 +1029,32 @@ pub enum Assignment {
 pub enum AssignmentOp {
     Assign,
     Merge,
+
+    /// Assigns the value, creating any missing or non-container parent
+    /// segments of the target path along the way.
+    Vivify,
 }
 
 impl fmt::Display for AssignmentOp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use AssignmentOp::{Assign, Merge};
+        use AssignmentOp::{Assign, Merge, Vivify};
 
         match self {
             Assign => write!(f, "="),
             Merge => write!(f, "|="),
+            Vivify => write!(f, "?="),
         }
     }
 }
 
 impl fmt::Debug for AssignmentOp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use AssignmentOp::{Assign, Merge};
+        use AssignmentOp::{Assign, Merge, Vivify};
 
         match self {
             Assign => write!(f, "AssignmentOp(=)"),
             Merge => write!(f, "AssignmentOp(|=)"),
+            Vivify => write!(f, "AssignmentOp(?=)"),
         }
     }
 }
@@ -874,6 +1089,17 @@ pub enum AssignmentTarget {
     Query(Query),
     Internal(Ident, Option<OwnedValuePath>),
     External(Option<OwnedTargetPath>),
+
+    /// Destructures an array, assigning each element to the target at the
+    /// same position, and any remaining elements to the optional rest target.
+    Array(
+        Vec<Node<AssignmentTarget>>,
+        Option<Box<Node<AssignmentTarget>>>,
+    ),
+
+    /// Destructures an object, assigning the value of each field to its
+    /// target.
+    Object(Vec<(Node<String>, Node<AssignmentTarget>)>),
 }
 
 impl AssignmentTarget {
@@ -905,13 +1131,48 @@ impl AssignmentTarget {
                     ),
                 },
             )),
+            AssignmentTarget::Array(elements, rest) => {
+                let elements = elements
+                    .iter()
+                    .chain(rest.as_deref())
+                    .map(|target| Node::new(target.span, target.node.to_expr(target.span)))
+                    .collect();
+
+                Expr::Container(Node::new(
+                    span,
+                    Container::Array(Node::new(span, Array(elements))),
+                ))
+            }
+            AssignmentTarget::Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(key, target)| {
+                        let expr = Node::new(target.span, target.node.to_expr(target.span));
+                        (key.clone(), expr)
+                    })
+                    .collect();
+
+                Expr::Container(Node::new(
+                    span,
+                    Container::Object(Node::new(span, Object(fields))),
+                ))
+            }
         }
     }
+
+    /// Returns `true` if this target is a destructuring pattern.
+    #[must_use]
+    pub fn is_pattern(&self) -> bool {
+        matches!(
+            self,
+            AssignmentTarget::Array(..) | AssignmentTarget::Object(_)
+        )
+    }
 }
 
 impl fmt::Display for AssignmentTarget {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use AssignmentTarget::{External, Internal, Noop, Query};
+        use AssignmentTarget::{Array, External, Internal, Noop, Object, Query};
 
         match self {
             Noop => f.write_str("_"),
@@ -920,13 +1181,29 @@ impl fmt::Display for AssignmentTarget {
             Internal(ident, _) => ident.fmt(f),
             External(Some(path)) => write!(f, "{path}"),
             External(_) => f.write_str("."),
+            Array(elements, rest) => {
+                let mut elements = elements.iter().map(ToString::to_string).collect::<Vec<_>>();
+                if let Some(rest) = rest {
+                    elements.push(format!("..{rest}"));
+                }
+
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(key, target)| format!(r#""{key}": {target}"#))
+                    .collect::<Vec<_>>();
+
+                write!(f, "{{ {} }}", fields.join(", "))
+            }
         }
     }
 }
 
 impl fmt::Debug for AssignmentTarget {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use AssignmentTarget::{External, Internal, Noop, Query};
+        use AssignmentTarget::{Array, External, Internal, Noop, Object, Query};
 
         match self {
             Noop => f.write_str("Noop"),
@@ -935,6 +1212,25 @@ impl fmt::Debug for AssignmentTarget {
             Internal(ident, _) => write!(f, "Internal({ident})"),
             External(Some(path)) => write!(f, "External({path})"),
             External(_) => f.write_str("External(.)"),
+            Array(elements, rest) => {
+                let mut elements = elements
+                    .iter()
+                    .map(|target| format!("{target:?}"))
+                    .collect::<Vec<_>>();
+                if let Some(rest) = rest {
+                    elements.push(format!("Rest({rest:?})"));
+                }
+
+                write!(f, "Array([{}])", elements.join(", "))
+            }
+            Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(key, target)| format!(r#""{key}": {target:?}"#))
+                    .collect::<Vec<_>>();
+
+                write!(f, "Object({{ {} }})", fields.join(", "))
+            }
         }
     }
 }
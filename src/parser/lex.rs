@@ -1,4 +1,4 @@
-use std::{fmt, iter::Peekable, str::CharIndices};
+use std::{collections::HashMap, fmt, iter::Peekable, str::CharIndices};
 
 use crate::diagnostic::{DiagnosticMessage, Label, Span};
 use ordered_float::NotNan;
@@ -269,14 +269,19 @@ pub(crate) struct Lexer<'input> {
     ///   ~~~~~~~~~~  0..10
     ///    ~~~~       1..5
     rquery_indices: Vec<usize>,
+
+    /// The position after the closing delimiter of each array, object or
+    /// group scanned while checking for destructuring patterns, by the
+    /// position of its opening delimiter, or `None` if it isn't closed.
+    delimiter_ends: HashMap<usize, Option<usize>>,
 }
 
 impl<'input> Lexer<'input> {
     fn next_token(&mut self) -> Option<SpannedResult<'input, usize>> {
         use Token::{
-            Ampersand, Arrow, Bang, Colon, Comma, Dot, Escape, InvalidToken, LBrace, LBracket,
-            LParen, LQuery, Newline, Percent, RBrace, RBracket, RParen, RQuery, SemiColon,
-            Underscore,
+            Ampersand, Arrow, Bang, Colon, Comma, Dot, DotDot, Ellipsis, Escape, InvalidToken,
+            LBrace, LBracket, LParen, LPatternBrace, LPatternBracket, LQuery, Newline, Percent,
+            RBrace, RBracket, RParen, RQuery, SemiColon, Underscore,
         };
 
         loop {
@@ -317,11 +322,22 @@ impl<'input> Lexer<'input> {
                     '\\' => Some(Ok(self.token(start, Escape))),
 
                     '(' => Some(Ok(self.open(start, LParen))),
+                    '[' if self.pattern_start(start) => Some(Ok(self.open(start, LPatternBracket))),
+                    '{' if self.pattern_start(start) => Some(Ok(self.open(start, LPatternBrace))),
                     '[' => Some(Ok(self.open(start, LBracket))),
                     '{' => Some(Ok(self.open(start, LBrace))),
                     '}' => Some(Ok(self.close(start, RBrace))),
                     ']' => Some(Ok(self.close(start, RBracket))),
                     ')' => Some(Ok(self.close(start, RParen))),
+                    '.' if self.input[start..].starts_with("...") => {
+                        self.bump();
+                        self.bump();
+                        Some(Ok(self.token(start, Ellipsis)))
+                    }
+                    '.' if self.test_peek(|ch| ch == '.') => {
+                        self.bump();
+                        Some(Ok(self.token(start, DotDot)))
+                    }
                     '.' => Some(Ok(self.token(start, Dot))),
                     '%' => Some(Ok(self.token(start, Percent))),
                     '&' if !matches!(self.peek(), Some((_, '&'))) => {
@@ -412,6 +428,8 @@ pub enum Token<S> {
     Colon,
     Comma,
     Dot,
+    DotDot,
+    Ellipsis,
     LBrace,
     LBracket,
     LParen,
@@ -428,6 +446,7 @@ pub enum Token<S> {
 
     Equals,
     MergeEquals,
+    VivifyEquals,
     Bang,
     Question,
 
@@ -463,17 +482,33 @@ pub enum Token<S> {
     /// above two groups of examples.
     LQuery,
     RQuery,
+
+    /// The LPattern{Bracket,Brace} tokens replace the opening `[` or `{` of an
+    /// array or object that is the target of an assignment, and thus
+    /// represents a destructuring pattern instead of a literal.
+    ///
+    /// ```text
+    /// [a, ..b] = c       => LPatternBracket, Identifier, Comma, DotDot, ...
+    /// { "a": a } = b     => LPatternBrace, StringLiteral, Colon, ...
+    /// [a], err = b       => LPatternBracket, Identifier, RBracket, Comma, ...
+    /// ```
+    ///
+    /// Without these tokens, the parser would have to decide whether an
+    /// identifier inside the brackets is an expression or an assignment
+    /// target before seeing the assignment operator.
+    LPatternBracket,
+    LPatternBrace,
 }
 
 impl<S> Token<S> {
     pub(crate) fn map<R>(self, f: impl Fn(S) -> R) -> Token<R> {
         use self::Token::{
-            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
-            FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
-            ReservedIdentifier, Return, SemiColon, StringLiteral, TimestampLiteral, True,
-            Underscore,
+            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, DotDot, Ellipsis, Else, Equals,
+            Escape, False, FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral,
+            InvalidToken, LBrace, LBracket, LParen, LPatternBrace, LPatternBracket, LQuery,
+            MergeEquals, Newline, Null, Operator, PathField, Percent, Question, RBrace, RBracket,
+            RParen, RQuery, RawStringLiteral, RegexLiteral, ReservedIdentifier, Return, SemiColon,
+            StringLiteral, TimestampLiteral, True, Underscore, VivifyEquals,
         };
 
         match self {
@@ -508,6 +543,8 @@ impl<S> Token<S> {
             Colon => Colon,
             Comma => Comma,
             Dot => Dot,
+            DotDot => DotDot,
+            Ellipsis => Ellipsis,
             LBrace => LBrace,
             LBracket => LBracket,
             LParen => LParen,
@@ -524,11 +561,15 @@ impl<S> Token<S> {
 
             Equals => Equals,
             MergeEquals => MergeEquals,
+            VivifyEquals => VivifyEquals,
             Bang => Bang,
             Question => Question,
 
             LQuery => LQuery,
             RQuery => RQuery,
+
+            LPatternBracket => LPatternBracket,
+            LPatternBrace => LPatternBrace,
         }
     }
 }
@@ -539,12 +580,12 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Token::{
-            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
-            FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
-            ReservedIdentifier, Return, SemiColon, StringLiteral, TimestampLiteral, True,
-            Underscore,
+            Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, DotDot, Ellipsis, Else, Equals,
+            Escape, False, FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral,
+            InvalidToken, LBrace, LBracket, LParen, LPatternBrace, LPatternBracket, LQuery,
+            MergeEquals, Newline, Null, Operator, PathField, Percent, Question, RBrace, RBracket,
+            RParen, RQuery, RawStringLiteral, RegexLiteral, ReservedIdentifier, Return, SemiColon,
+            StringLiteral, TimestampLiteral, True, Underscore, VivifyEquals,
         };
 
         let s = match *self {
@@ -573,6 +614,8 @@ where
             Colon => "Colon",
             Comma => "Comma",
             Dot => "Dot",
+            DotDot => "DotDot",
+            Ellipsis => "Ellipsis",
             LBrace => "LBrace",
             LBracket => "LBracket",
             LParen => "LParen",
@@ -589,11 +632,15 @@ where
 
             Equals => "Equals",
             MergeEquals => "MergeEquals",
+            VivifyEquals => "VivifyEquals",
             Bang => "Bang",
             Question => "Question",
 
             LQuery => "LQuery",
             RQuery => "RQuery",
+
+            LPatternBracket => "LPatternBracket",
+            LPatternBrace => "LPatternBrace",
         };
 
         s.fmt(f)
@@ -739,8 +786,8 @@ impl<'input> Lexer<'input> {
     fn open(&mut self, start: usize, token: Token<&'input str>) -> Spanned<'input, usize> {
         match &token {
             Token::LParen => self.open_parens += 1,
-            Token::LBracket => self.open_brackets += 1,
-            Token::LBrace => self.open_braces += 1,
+            Token::LBracket | Token::LPatternBracket => self.open_brackets += 1,
+            Token::LBrace | Token::LPatternBrace => self.open_braces += 1,
             _ => {}
         }
 
@@ -796,6 +843,11 @@ impl<'input> Lexer<'input> {
             return Ok(false);
         }
 
+        // A `..` prefix marks the rest of a destructuring pattern, not a query.
+        if self.input[start..].starts_with("..") {
+            return Ok(false);
+        }
+
         // Track if the current chain is a valid one.
         //
         // A valid chain consists of a target, and a path to query that target.
@@ -1083,6 +1135,96 @@ impl<'input> Lexer<'input> {
         Ok(true)
     }
 
+    /// Checks whether the array or object starting at `start` is a
+    /// destructuring pattern, by looking past its closing delimiter for an
+    /// assignment operator. Merging into a pattern is not supported.
+    ///
+    /// Valid examples:
+    ///
+    ///   [a, b] = ...
+    ///   { "a": a } ?= ...
+    ///   [a, b], err = ...
+    ///
+    /// Delimiters nested within a query are never the start of a pattern.
+    fn pattern_start(&mut self, start: usize) -> bool {
+        if !self.rquery_indices.is_empty() {
+            return false;
+        }
+
+        if !self.delimiter_ends.contains_key(&start) {
+            self.scan_delimiters(start);
+        }
+        let Some(Some(end)) = self.delimiter_ends.get(&start).copied() else {
+            return false;
+        };
+
+        let whitespace = [' ', '\t'];
+        let mut remainder = self.input[end..].trim_start_matches(whitespace);
+
+        // The pattern can be the "ok" target of an infallible assignment.
+        if let Some(err_target) = remainder.strip_prefix(',') {
+            remainder = err_target
+                .trim_start_matches(whitespace)
+                .trim_start_matches(|ch| is_ident_continue(ch) || ch == '.' || ch == '%')
+                .trim_start_matches(whitespace);
+        }
+
+        (remainder.starts_with('=') && !remainder.starts_with("==")) || remainder.starts_with("?=")
+    }
+
+    /// Records the end of the delimiter opened at `start`, and of every
+    /// delimiter nested in it, skipping over literals and comments. This way,
+    /// each nested array or object is only scanned once.
+    ///
+    /// Delimiters that aren't closed are recorded without an end.
+    fn scan_delimiters(&mut self, start: usize) {
+        let input = self.input;
+        let mut open = vec![];
+        let mut pos = start;
+
+        while let Some(ch) = input[pos..].chars().next() {
+            let rest = &input[pos..];
+            let literal = match ch {
+                '[' | '{' | '(' => {
+                    open.push(pos);
+                    None
+                }
+                ']' | '}' | ')' => {
+                    if let Some(opened) = open.pop() {
+                        self.delimiter_ends.insert(opened, Some(pos + 1));
+                    }
+                    if open.is_empty() {
+                        return;
+                    }
+                    None
+                }
+                '"' => Some(Lexer::new(&input[pos + 1..]).string_literal(0)),
+                _ if rest.starts_with("s'") => {
+                    Some(Lexer::new(&input[pos + 1..]).raw_string_literal(0))
+                }
+                _ if rest.starts_with("r'") => Some(Lexer::new(&input[pos + 1..]).regex_literal(0)),
+                _ if rest.starts_with("t'") => {
+                    Some(Lexer::new(&input[pos + 1..]).timestamp_literal(0))
+                }
+                '#' => {
+                    pos += rest.find('\n').unwrap_or(rest.len());
+                    continue;
+                }
+                _ => None,
+            };
+
+            match literal {
+                Some(Ok((_, _, literal_end))) => pos += literal_end + 1,
+                Some(Err(_)) => break,
+                None => pos += ch.len_utf8(),
+            }
+        }
+
+        for opened in open {
+            self.delimiter_ends.insert(opened, None);
+        }
+    }
+
     fn string_literal(&mut self, start: usize) -> SpannedResult<'input, usize> {
         let content_start = self.next_index();
 
@@ -1172,6 +1314,7 @@ impl<'input> Lexer<'input> {
         let token = match op {
             "=" => Token::Equals,
             "|=" => Token::MergeEquals,
+            "?=" => Token::VivifyEquals,
             "?" => Token::Question,
             op => Token::Operator(op),
         };
@@ -1222,6 +1365,7 @@ impl<'input> Lexer<'input> {
             open_parens: 0,
             rquery_indices: vec![],
             query_start: None,
+            delimiter_ends: HashMap::new(),
         }
     }
 
@@ -1360,10 +1504,10 @@ mod test {
     #![allow(clippy::print_stdout)] // tests
 
     use super::super::lex::Token::{
-        Arrow, Bang, Colon, Comma, Dot, Else, Equals, FloatLiteral, FunctionCall, Identifier, If,
-        IntegerLiteral, LBrace, LBracket, LParen, LQuery, Newline, Operator, PathField, Percent,
-        RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral, StringLiteral,
-        TimestampLiteral, True,
+        Arrow, Bang, Colon, Comma, Dot, DotDot, Ellipsis, Else, Equals, FloatLiteral, FunctionCall,
+        Identifier, If, IntegerLiteral, LBrace, LBracket, LParen, LPatternBrace, LPatternBracket,
+        LQuery, Newline, Operator, PathField, Percent, RBrace, RBracket, RParen, RQuery,
+        RawStringLiteral, RegexLiteral, StringLiteral, TimestampLiteral, True, VivifyEquals,
     };
     use super::*;
 
@@ -2017,6 +2161,125 @@ mod test {
         );
     }
 
+    #[test]
+    fn vivify_assignment() {
+        test(
+            data(".a.b ?= 1"),
+            vec![
+                ("~        ", LQuery),
+                ("~        ", Dot),
+                (" ~       ", Identifier("a")),
+                ("  ~      ", Dot),
+                ("   ~     ", Identifier("b")),
+                ("   ~     ", RQuery),
+                ("     ~~  ", VivifyEquals),
+                ("        ~", IntegerLiteral(1)),
+            ],
+        );
+    }
+
+    #[test]
+    fn destructuring_patterns() {
+        test(
+            data("[a, ..b] = c"),
+            vec![
+                ("~           ", LPatternBracket),
+                (" ~          ", Identifier("a")),
+                ("  ~         ", Comma),
+                ("    ~~      ", DotDot),
+                ("      ~     ", Identifier("b")),
+                ("       ~    ", RBracket),
+                ("         ~  ", Equals),
+                ("           ~", Identifier("c")),
+            ],
+        );
+
+        test(
+            data(r#"{ "a": [a] }, err = b"#),
+            vec![
+                ("~                    ", LPatternBrace),
+                (
+                    "  ~~~                ",
+                    StringLiteral(StringLiteralToken("a")),
+                ),
+                ("     ~               ", Colon),
+                ("       ~             ", LBracket),
+                ("        ~            ", Identifier("a")),
+                ("         ~           ", RBracket),
+                ("           ~         ", RBrace),
+                ("            ~        ", Comma),
+                ("              ~~~    ", Identifier("err")),
+                ("                  ~  ", Equals),
+                ("                    ~", Identifier("b")),
+            ],
+        );
+    }
+
+    #[test]
+    fn destructuring_lookalikes() {
+        test(
+            data("[a] == b"),
+            vec![
+                ("~       ", LBracket),
+                (" ~      ", Identifier("a")),
+                ("  ~     ", RBracket),
+                ("    ~~  ", Operator("==")),
+                ("       ~", Identifier("b")),
+            ],
+        );
+
+        test(
+            data("a[0] = b"),
+            vec![
+                ("~       ", LQuery),
+                ("~       ", Identifier("a")),
+                (" ~      ", LBracket),
+                ("  ~     ", IntegerLiteral(0)),
+                ("   ~    ", RBracket),
+                ("   ~    ", RQuery),
+                ("     ~  ", Equals),
+                ("       ~", Identifier("b")),
+            ],
+        );
+
+        // Delimiters within literals don't close the array.
+        test(
+            data("[a, b] = [r'[}] = ', 1]"),
+            vec![
+                ("~                      ", LPatternBracket),
+                (" ~                     ", Identifier("a")),
+                ("  ~                    ", Comma),
+                ("    ~                  ", Identifier("b")),
+                ("     ~                 ", RBracket),
+                ("       ~               ", Equals),
+                ("         ~             ", LBracket),
+                ("          ~~~~~~~~~    ", RegexLiteral("[}] = ")),
+                ("                   ~   ", Comma),
+                ("                     ~ ", IntegerLiteral(1)),
+                ("                      ~", RBracket),
+            ],
+        );
+    }
+
+    #[test]
+    fn spread() {
+        test(
+            data("[...a, ....b]"),
+            vec![
+                ("~            ", LBracket),
+                (" ~~~         ", Ellipsis),
+                ("    ~        ", Identifier("a")),
+                ("     ~       ", Comma),
+                ("       ~~~   ", Ellipsis),
+                ("          ~  ", LQuery),
+                ("          ~  ", Dot),
+                ("           ~ ", Identifier("b")),
+                ("           ~ ", RQuery),
+                ("            ~", RBracket),
+            ],
+        );
+    }
+
     #[test]
     fn invalid_queries() {
         test(
@@ -154,7 +154,7 @@ fn parse_array<'a, E: HashParseError<&'a str>>(input: &'a str) -> IResult<&'a st
                     preceded(sp, char(']')),
                 )),
             ),
-            Value::Array,
+            Value::from,
         ),
     )
     .parse(input)
@@ -4,14 +4,12 @@
 use crate::compiler::prelude::*;
 use regex::{Regex, RegexBuilder};
 // Re-export `roxmltree` to match the public API of `process_node`.
+use crate::value::ObjectEntry as Entry;
 use roxmltree::NodeType;
 pub use roxmltree::{Document, Node};
 use rust_decimal::prelude::Zero;
+use std::borrow::Cow;
 use std::sync::LazyLock;
-use std::{
-    borrow::Cow,
-    collections::{BTreeMap, btree_map::Entry},
-};
 
 /// A lazily initialized regular expression that matches excess whitespace between XML/HTML tags.
 ///
@@ -156,7 +154,7 @@ pub fn parse_xml(value: Value, options: ParseOptions) -> Resolved {
 pub fn process_node(node: Node, config: &ParseXmlConfig) -> Value {
     // Helper to recurse over a `Node`s children, and build an object.
     let recurse = |node: Node| -> ObjectMap {
-        let mut map = BTreeMap::new();
+        let mut map = ObjectMap::new();
 
         // Expand attributes, if required.
         if config.include_attr {
@@ -187,7 +185,8 @@ pub fn process_node(node: Node, config: &ParseXmlConfig) -> Value {
                     match v {
                         Value::Array(v) => v.push(value),
                         v => {
-                            let prev = std::mem::replace(v, Value::Array(Vec::with_capacity(2)));
+                            let prev =
+                                std::mem::replace(v, Value::Array(ValueArray::with_capacity(2)));
                             if let Value::Array(v) = v {
                                 v.extend_from_slice(&[prev, value]);
                             }
@@ -224,7 +223,7 @@ pub fn process_node(node: Node, config: &ParseXmlConfig) -> Value {
 
                         // If the node is an element, treat it as an object.
                         if node.is_element() {
-                            let mut map = BTreeMap::new();
+                            let mut map = ObjectMap::new();
 
                             map.insert(
                                 node.tag_name().name().to_string().into(),
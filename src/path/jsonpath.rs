@@ -0,0 +1,869 @@
+//! JSONPath (RFC 9535) queries.
+//!
+//! This contains the syntax tree and parser of JSONPath queries. Queries are evaluated against a
+//! `Value` using `Value::query_json_path`.
+//!
+//! All of RFC 9535 is supported, including filter selectors with the standard function
+//! extensions (`length`, `count`, `match`, `search` and `value`). Queries are checked to be
+//! well-typed while parsing, so e.g. comparing the result of `match` is rejected.
+//!
+//! Queries that only consist of names, indices, wildcards and descendant segments with a single
+//! selector can be converted into an [`OwnedValueQuery`] (or, if they are singular, into an
+//! [`OwnedValuePath`]).
+
+use std::fmt;
+
+use ordered_float::NotNan;
+use regex::Regex;
+
+use super::{OwnedValuePath, OwnedValueQuery, QuerySegment};
+use crate::value::{KeyString, Value};
+
+/// The range of integers allowed in a query, which is the range of integers that can be
+/// represented exactly by an IEEE 754 double.
+const MAX_INT: i64 = (1 << 53) - 1;
+
+/// A parsed JSONPath query.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    pub segments: Vec<JsonPathSegment>,
+}
+
+/// A child (`.a`, `[...]`) or descendant (`..a`, `..[...]`) segment.
+#[derive(Debug, Clone)]
+pub struct JsonPathSegment {
+    /// Whether the selectors are applied to the input and all of its descendants, instead of
+    /// only the input.
+    pub descendant: bool,
+    pub selectors: Vec<Selector>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Selector {
+    /// Selects the member of an object with the given name.
+    Name(KeyString),
+    /// Selects all members of an object, or all elements of an array.
+    Wildcard,
+    /// Selects the element of an array at the given index. Negative indices count from the end.
+    Index(i64),
+    /// Selects the elements of an array in the range `start..end`, every `step` elements.
+    Slice {
+        start: Option<i64>,
+        end: Option<i64>,
+        step: Option<i64>,
+    },
+    /// Selects the members of an object, or elements of an array, for which the expression is
+    /// true.
+    Filter(LogicalExpr),
+}
+
+/// A query used inside a filter expression.
+#[derive(Debug, Clone)]
+pub struct FilterQuery {
+    /// Whether the query starts at the current node (`@`) rather than the root (`$`).
+    pub relative: bool,
+    pub segments: Vec<JsonPathSegment>,
+}
+
+#[derive(Debug, Clone)]
+pub enum LogicalExpr {
+    Or(Vec<LogicalExpr>),
+    And(Vec<LogicalExpr>),
+    Not(Box<LogicalExpr>),
+    Comparison(Comparable, ComparisonOp, Comparable),
+    /// True if the query selects at least one node.
+    Exists(FilterQuery),
+    /// A function returning a logical result (`match` or `search`).
+    Function(Function),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A value used in a comparison, or as a function argument.
+#[derive(Debug, Clone)]
+pub enum Comparable {
+    Literal(Value),
+    /// A singular query, selecting at most one node.
+    Query(FilterQuery),
+    /// A function returning a value (`length`, `count` or `value`).
+    Function(Box<Function>),
+}
+
+#[derive(Debug, Clone)]
+pub enum Function {
+    Length(Comparable),
+    Count(FilterQuery),
+    Match(Comparable, Pattern),
+    Search(Comparable, Pattern),
+    Value(FilterQuery),
+}
+
+/// The regular expression argument of `match` and `search`.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// A string literal, compiled when the query is parsed. This is `None` if the literal isn't a
+    /// valid regular expression, in which case nothing matches.
+    Literal(Option<Regex>),
+    Dynamic(Comparable),
+}
+
+/// An error while parsing a JSONPath query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPathParseError {
+    /// The byte offset in the query where the error was found.
+    pub position: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for JsonPathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid JSONPath query at position {}: {}",
+            self.position, self.reason
+        )
+    }
+}
+
+impl std::error::Error for JsonPathParseError {}
+
+impl JsonPath {
+    /// Parses a JSONPath query.
+    pub fn parse(query: &str) -> Result<Self, JsonPathParseError> {
+        let mut parser = Parser { src: query, pos: 0 };
+
+        if !parser.eat('$') {
+            return Err(parser.error("a query must start with `$`"));
+        }
+        let segments = parser.parse_segments()?;
+        if parser.pos < query.len() {
+            return Err(parser.error("unexpected character"));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Converts the query into a value query, if it only consists of names, indices, wildcards
+    /// and descendant segments with a single selector.
+    pub fn to_value_query(&self) -> Option<OwnedValueQuery> {
+        let mut segments = vec![];
+        for segment in &self.segments {
+            let [selector] = segment.selectors.as_slice() else {
+                return None;
+            };
+            if segment.descendant {
+                segments.push(QuerySegment::Descendants);
+            }
+            segments.push(match selector {
+                Selector::Name(name) => QuerySegment::Field(name.clone()),
+                Selector::Index(index) => QuerySegment::Index(isize::try_from(*index).ok()?),
+                Selector::Wildcard => QuerySegment::Wildcard,
+                Selector::Slice { .. } | Selector::Filter(_) => return None,
+            });
+        }
+        Some(segments.into())
+    }
+
+    /// Converts the query into a value path, if it is a singular query: a query that only
+    /// consists of child segments with a single name or index selector.
+    pub fn to_value_path(&self) -> Option<OwnedValuePath> {
+        self.to_value_query()?.to_path()
+    }
+}
+
+impl FilterQuery {
+    /// Returns true if the query selects at most one node.
+    pub fn is_singular(&self) -> bool {
+        self.segments.iter().all(|segment| {
+            !segment.descendant
+                && matches!(
+                    segment.selectors.as_slice(),
+                    [Selector::Name(_) | Selector::Index(_)]
+                )
+        })
+    }
+}
+
+/// The result type of a function, as defined by the RFC type system.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FunctionType {
+    Value,
+    Logical,
+}
+
+impl Function {
+    fn result_type(&self) -> FunctionType {
+        match self {
+            Self::Length(_) | Self::Count(_) | Self::Value(_) => FunctionType::Value,
+            Self::Match(..) | Self::Search(..) => FunctionType::Logical,
+        }
+    }
+}
+
+/// An operand of a filter expression, before it is known how it is used.
+enum Operand {
+    Literal(Value),
+    Query(FilterQuery),
+    Function(Function),
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, reason: impl Into<String>) -> JsonPathParseError {
+        JsonPathParseError {
+            position: self.pos,
+            reason: reason.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        if self.src[self.pos..].starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), JsonPathParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{c}`")))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_segments(&mut self) -> Result<Vec<JsonPathSegment>, JsonPathParseError> {
+        let mut segments = vec![];
+        loop {
+            // Whitespace is allowed before a segment, but must not be consumed if no segment
+            // follows, since it might precede an operator in a filter expression.
+            let start = self.pos;
+            self.skip_whitespace();
+            match self.peek() {
+                Some('.' | '[') => segments.push(self.parse_segment()?),
+                _ => {
+                    self.pos = start;
+                    return Ok(segments);
+                }
+            }
+        }
+    }
+
+    fn parse_segment(&mut self) -> Result<JsonPathSegment, JsonPathParseError> {
+        if self.eat('[') {
+            return Ok(JsonPathSegment {
+                descendant: false,
+                selectors: self.parse_bracketed()?,
+            });
+        }
+
+        self.expect('.')?;
+        let descendant = self.eat('.');
+        let selectors = match self.peek() {
+            Some('*') => {
+                self.bump();
+                vec![Selector::Wildcard]
+            }
+            Some('[') if descendant => {
+                self.bump();
+                self.parse_bracketed()?
+            }
+            Some(c) if is_name_first(c) => vec![Selector::Name(self.parse_member_name().into())],
+            _ => return Err(self.error("expected a member name or `*`")),
+        };
+
+        Ok(JsonPathSegment {
+            descendant,
+            selectors,
+        })
+    }
+
+    fn parse_member_name(&mut self) -> &str {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| is_name_first(c) || c.is_ascii_digit())
+        {
+            self.bump();
+        }
+        &self.src[start..self.pos]
+    }
+
+    /// Parses the selectors of a bracketed segment, after the opening bracket.
+    fn parse_bracketed(&mut self) -> Result<Vec<Selector>, JsonPathParseError> {
+        let mut selectors = vec![];
+        loop {
+            self.skip_whitespace();
+            selectors.push(self.parse_selector()?);
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(selectors);
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected `,` or `]`"));
+            }
+        }
+    }
+
+    fn parse_selector(&mut self) -> Result<Selector, JsonPathParseError> {
+        match self.peek() {
+            Some('\'' | '"') => Ok(Selector::Name(self.parse_string()?.into())),
+            Some('*') => {
+                self.bump();
+                Ok(Selector::Wildcard)
+            }
+            Some('?') => {
+                self.bump();
+                self.skip_whitespace();
+                Ok(Selector::Filter(self.parse_or()?))
+            }
+            _ => {
+                let start = self.parse_optional_int()?;
+                let start_pos = self.pos;
+                self.skip_whitespace();
+                if !self.eat(':') {
+                    self.pos = start_pos;
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("expected a selector"));
+                }
+
+                self.skip_whitespace();
+                let end = self.parse_optional_int()?;
+                let end_pos = self.pos;
+                self.skip_whitespace();
+                let step = if self.eat(':') {
+                    self.skip_whitespace();
+                    self.parse_optional_int()?
+                } else {
+                    self.pos = end_pos;
+                    None
+                };
+
+                Ok(Selector::Slice { start, end, step })
+            }
+        }
+    }
+
+    fn parse_optional_int(&mut self) -> Result<Option<i64>, JsonPathParseError> {
+        if matches!(self.peek(), Some('-' | '0'..='9')) {
+            self.parse_int().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_int(&mut self) -> Result<i64, JsonPathParseError> {
+        let start = self.pos;
+        let negative = self.eat('-');
+        let digits_start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+
+        let digits = &self.src[digits_start..self.pos];
+        if digits.is_empty() {
+            return Err(self.error("expected a digit"));
+        }
+        if (digits.starts_with('0') && digits.len() > 1) || (negative && digits == "0") {
+            self.pos = start;
+            return Err(self.error("invalid integer"));
+        }
+
+        match self.src[start..self.pos].parse::<i64>() {
+            Ok(int) if (-MAX_INT..=MAX_INT).contains(&int) => Ok(int),
+            _ => {
+                self.pos = start;
+                Err(self.error("integer out of range"))
+            }
+        }
+    }
+
+    /// Parses a single- or double-quoted string literal.
+    fn parse_string(&mut self) -> Result<String, JsonPathParseError> {
+        let quote = self.bump().expect("quote");
+        let mut string = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => return Ok(string),
+                Some('\\') => {
+                    let c = match self.bump() {
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some(c @ ('/' | '\\')) => c,
+                        Some(c) if c == quote => c,
+                        Some('u') => self.parse_unicode_escape()?,
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+                    string.push(c);
+                }
+                Some(c) if c < '\u{20}' => {
+                    return Err(self.error("control characters must be escaped"));
+                }
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    /// Parses the hex digits of a `\u` escape, including the low surrogate of a pair.
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonPathParseError> {
+        let high = self.parse_hex4()?;
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if !self.eat_str("\\u") {
+                    return Err(self.error("expected a low surrogate"));
+                }
+                let low = self.parse_hex4()?;
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    return Err(self.error("invalid low surrogate"));
+                }
+                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+            }
+            0xDC00..=0xDFFF => return Err(self.error("unexpected low surrogate")),
+            code => code,
+        };
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, JsonPathParseError> {
+        let hex = self
+            .src
+            .get(self.pos..self.pos + 4)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("expected 4 hex digits"))?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(hex, 16).expect("hex digits"))
+    }
+
+    fn parse_or(&mut self) -> Result<LogicalExpr, JsonPathParseError> {
+        let mut exprs = vec![self.parse_and()?];
+        loop {
+            let start = self.pos;
+            self.skip_whitespace();
+            if self.eat_str("||") {
+                self.skip_whitespace();
+                exprs.push(self.parse_and()?);
+            } else {
+                self.pos = start;
+                break;
+            }
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            LogicalExpr::Or(exprs)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<LogicalExpr, JsonPathParseError> {
+        let mut exprs = vec![self.parse_basic()?];
+        loop {
+            let start = self.pos;
+            self.skip_whitespace();
+            if self.eat_str("&&") {
+                self.skip_whitespace();
+                exprs.push(self.parse_basic()?);
+            } else {
+                self.pos = start;
+                break;
+            }
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            LogicalExpr::And(exprs)
+        })
+    }
+
+    fn parse_basic(&mut self) -> Result<LogicalExpr, JsonPathParseError> {
+        if self.eat('!') {
+            self.skip_whitespace();
+            let expr = if self.peek() == Some('(') {
+                self.parse_paren()?
+            } else {
+                let position = self.pos;
+                let operand = self.parse_operand()?;
+                Self::to_test(operand, position)?
+            };
+            return Ok(LogicalExpr::Not(Box::new(expr)));
+        }
+
+        if self.peek() == Some('(') {
+            return self.parse_paren();
+        }
+
+        let position = self.pos;
+        let left = self.parse_operand()?;
+
+        let start = self.pos;
+        self.skip_whitespace();
+        let Some(op) = self.parse_comparison_op() else {
+            self.pos = start;
+            return Self::to_test(left, position);
+        };
+
+        let left = Self::to_comparable(left, position)?;
+        self.skip_whitespace();
+        let position = self.pos;
+        let right = self.parse_operand()?;
+        let right = Self::to_comparable(right, position)?;
+
+        Ok(LogicalExpr::Comparison(left, op, right))
+    }
+
+    fn parse_paren(&mut self) -> Result<LogicalExpr, JsonPathParseError> {
+        self.expect('(')?;
+        self.skip_whitespace();
+        let expr = self.parse_or()?;
+        self.skip_whitespace();
+        self.expect(')')?;
+        Ok(expr)
+    }
+
+    fn parse_comparison_op(&mut self) -> Option<ComparisonOp> {
+        for (token, op) in [
+            ("==", ComparisonOp::Eq),
+            ("!=", ComparisonOp::Ne),
+            ("<=", ComparisonOp::Le),
+            (">=", ComparisonOp::Ge),
+            ("<", ComparisonOp::Lt),
+            (">", ComparisonOp::Gt),
+        ] {
+            if self.eat_str(token) {
+                return Some(op);
+            }
+        }
+        None
+    }
+
+    fn to_test(operand: Operand, position: usize) -> Result<LogicalExpr, JsonPathParseError> {
+        match operand {
+            Operand::Query(query) => Ok(LogicalExpr::Exists(query)),
+            Operand::Function(function) if function.result_type() == FunctionType::Logical => {
+                Ok(LogicalExpr::Function(function))
+            }
+            Operand::Function(_) => Err(JsonPathParseError {
+                position,
+                reason: "the result of this function must be compared".to_owned(),
+            }),
+            Operand::Literal(_) => Err(JsonPathParseError {
+                position,
+                reason: "a literal must be compared".to_owned(),
+            }),
+        }
+    }
+
+    fn to_comparable(operand: Operand, position: usize) -> Result<Comparable, JsonPathParseError> {
+        let error = |reason: &str| JsonPathParseError {
+            position,
+            reason: reason.to_owned(),
+        };
+
+        match operand {
+            Operand::Literal(value) => Ok(Comparable::Literal(value)),
+            Operand::Query(query) if query.is_singular() => Ok(Comparable::Query(query)),
+            Operand::Query(_) => Err(error("only singular queries can be compared")),
+            Operand::Function(function) if function.result_type() == FunctionType::Value => {
+                Ok(Comparable::Function(Box::new(function)))
+            }
+            Operand::Function(_) => Err(error("the result of this function can't be compared")),
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, JsonPathParseError> {
+        match self.peek() {
+            Some('@' | '$') => Ok(Operand::Query(self.parse_filter_query()?)),
+            Some('\'' | '"') => Ok(Operand::Literal(self.parse_string()?.into())),
+            Some('-' | '0'..='9') => Ok(Operand::Literal(self.parse_number()?)),
+            Some('a'..='z') => {
+                let start = self.pos;
+                while matches!(self.peek(), Some('a'..='z' | '0'..='9' | '_')) {
+                    self.bump();
+                }
+                let name = &self.src[start..self.pos];
+
+                if self.peek() == Some('(') {
+                    let name = name.to_owned();
+                    self.bump();
+                    return self.parse_function(&name, start).map(Operand::Function);
+                }
+
+                match name {
+                    "true" => Ok(Operand::Literal(true.into())),
+                    "false" => Ok(Operand::Literal(false.into())),
+                    "null" => Ok(Operand::Literal(Value::Null)),
+                    _ => {
+                        self.pos = start;
+                        Err(self.error("expected a literal, query or function"))
+                    }
+                }
+            }
+            _ => Err(self.error("expected a literal, query or function")),
+        }
+    }
+
+    fn parse_filter_query(&mut self) -> Result<FilterQuery, JsonPathParseError> {
+        let relative = self.bump() == Some('@');
+        let segments = self.parse_segments()?;
+        Ok(FilterQuery { relative, segments })
+    }
+
+    fn parse_number(&mut self) -> Result<Value, JsonPathParseError> {
+        let start = self.pos;
+        self.eat('-');
+        let int_start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+        let int = &self.src[int_start..self.pos];
+        if int.is_empty() || (int.starts_with('0') && int.len() > 1) {
+            self.pos = start;
+            return Err(self.error("invalid number"));
+        }
+
+        let mut is_float = false;
+        if self.eat('.') {
+            is_float = true;
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(self.error("expected a digit"));
+            }
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.bump();
+            }
+        }
+        if self.eat('e') || self.eat('E') {
+            is_float = true;
+            if !self.eat('+') {
+                self.eat('-');
+            }
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(self.error("expected a digit"));
+            }
+            while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.bump();
+            }
+        }
+
+        let number = &self.src[start..self.pos];
+        if !is_float && let Ok(int) = number.parse::<i64>() {
+            return Ok(Value::Integer(int));
+        }
+        number
+            .parse::<f64>()
+            .ok()
+            .filter(|float| float.is_finite())
+            .and_then(|float| NotNan::new(float).ok())
+            .map(Value::Float)
+            .ok_or_else(|| self.error("number out of range"))
+    }
+
+    fn parse_function(
+        &mut self,
+        name: &str,
+        position: usize,
+    ) -> Result<Function, JsonPathParseError> {
+        self.skip_whitespace();
+        let function = match name {
+            "length" => Function::Length(self.parse_value_argument()?),
+            "count" => Function::Count(self.parse_nodes_argument()?),
+            "value" => Function::Value(self.parse_nodes_argument()?),
+            "match" | "search" => {
+                let value = self.parse_value_argument()?;
+                self.skip_whitespace();
+                self.expect(',')?;
+                self.skip_whitespace();
+                let pattern = match self.parse_value_argument()? {
+                    Comparable::Literal(Value::Bytes(pattern)) => {
+                        let pattern = String::from_utf8_lossy(&pattern);
+                        Pattern::Literal(if name == "match" {
+                            Regex::new(&format!("^(?:{pattern})$")).ok()
+                        } else {
+                            Regex::new(&pattern).ok()
+                        })
+                    }
+                    pattern => Pattern::Dynamic(pattern),
+                };
+
+                if name == "match" {
+                    Function::Match(value, pattern)
+                } else {
+                    Function::Search(value, pattern)
+                }
+            }
+            _ => {
+                return Err(JsonPathParseError {
+                    position,
+                    reason: format!("unknown function `{name}`"),
+                });
+            }
+        };
+        self.skip_whitespace();
+        self.expect(')')?;
+        Ok(function)
+    }
+
+    fn parse_value_argument(&mut self) -> Result<Comparable, JsonPathParseError> {
+        let position = self.pos;
+        let operand = self.parse_operand()?;
+        Self::to_comparable(operand, position)
+    }
+
+    fn parse_nodes_argument(&mut self) -> Result<FilterQuery, JsonPathParseError> {
+        match self.parse_operand()? {
+            Operand::Query(query) => Ok(query),
+            _ => Err(self.error("expected a query")),
+        }
+    }
+}
+
+fn is_name_first(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::path::{parse_value_path, parse_value_query};
+
+    #[test]
+    fn parse_valid() {
+        for query in [
+            "$",
+            "$.a",
+            "$.a.b",
+            "$['a']",
+            r#"$["a", 'b']"#,
+            "$[0]",
+            "$[-1]",
+            "$[*]",
+            "$.*",
+            "$..a",
+            "$..*",
+            "$..[0, 'a']",
+            "$[1:3]",
+            "$[::-1]",
+            "$[ 1 : 3 : 2 ]",
+            "$ .a [0]",
+            "$.λ",
+            "$[?@.a]",
+            "$[?!@.a]",
+            "$[?@.a == 1]",
+            "$[?@.a!='x' && (@.b < 2 || !(@.c))]",
+            "$[?@.a == $.b[0]]",
+            "$[?length(@.a) > 2]",
+            "$[?count(@..*) >= 1]",
+            "$[?match(@.a, 'a.*')]",
+            "$[?search(@.a, $.pattern)]",
+            "$[?value(@..a) == -1.5e3]",
+            "$[?@.a == true && @.b == null]",
+            r#"$["é😀\n"]"#,
+        ] {
+            assert!(JsonPath::parse(query).is_ok(), "{query}");
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for query in [
+            "",
+            "a",
+            " $",
+            "$ ",
+            "$.",
+            "$..",
+            "$.1",
+            "$[",
+            "$[]",
+            "$[01]",
+            "$[-0]",
+            "$[9007199254740992]",
+            "$['a'",
+            r#"$["\z"]"#,
+            r#"$["\ud800"]"#,
+            "$[?1]",
+            "$[?@.a == 01]",
+            "$[?@..a == 1]",
+            "$[?@.* == 1]",
+            "$[?length(@.a)]",
+            "$[?match(@.a, 'a') == true]",
+            "$[?count(1) == 1]",
+            "$[?foo(@.a)]",
+            "$[?!@.a == 1]",
+            "$[?@.a === 1]",
+        ] {
+            assert!(JsonPath::parse(query).is_err(), "{query}");
+        }
+
+        assert_eq!(
+            JsonPath::parse("$.a[x]").unwrap_err().to_string(),
+            "invalid JSONPath query at position 4: expected a selector"
+        );
+    }
+
+    #[test]
+    fn to_value_query() {
+        for (json_path, query) in [
+            ("$", Some(".")),
+            ("$.a[0]['b c']", Some(r#".a[0]."b c""#)),
+            ("$.a[*]..b", Some(".a[*]..b")),
+            ("$..[-1]", Some("..[-1]")),
+            ("$['a', 'b']", None),
+            ("$[0:1]", None),
+            ("$[?@.a]", None),
+        ] {
+            assert_eq!(
+                JsonPath::parse(json_path).unwrap().to_value_query(),
+                query.map(|query| parse_value_query(query).unwrap()),
+                "{json_path}"
+            );
+        }
+
+        assert_eq!(
+            JsonPath::parse("$.a[1]").unwrap().to_value_path(),
+            Some(parse_value_path(".a[1]").unwrap())
+        );
+        assert_eq!(JsonPath::parse("$..a").unwrap().to_value_path(), None);
+    }
+}
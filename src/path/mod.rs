@@ -62,6 +62,12 @@
 //!
 //! To convert a string into an owned path, use either [parse_value_path] or [parse_target_path].
 //!
+//! # Queries
+//! [OwnedValueQuery] extends an owned path with wildcards (`.*`, `[*]`) and recursive descent
+//! (`..`), so it can match any number of values. Use [parse_value_query] to parse one. Queries
+//! are a separate type rather than a kind of [OwnedValuePath], since they aren't valid paths in
+//! VRL programs, only string arguments of the query functions.
+//!
 //! # String Paths
 //! [ValuePath] and [TargetPath] are implemented for [&str]. That means a raw / unparsed string can
 //! be used as a path. This use is discouraged, and may be removed in the future. It mostly
@@ -90,14 +96,19 @@ use snafu::Snafu;
 
 pub use borrowed::{BorrowedSegment, BorrowedTargetPath, BorrowedValuePath};
 pub use concat::PathConcat;
+pub use jsonpath::{JsonPath, JsonPathParseError};
+pub(crate) use owned::is_json_pointer_index;
 pub use owned::{OwnedSegment, OwnedTargetPath, OwnedValuePath};
+pub use query::{OwnedValueQuery, QuerySegment, parse_value_query};
 
 use self::jit::JitValuePath;
 
 mod borrowed;
 mod concat;
 mod jit;
+pub mod jsonpath;
 mod owned;
+mod query;
 
 #[derive(Clone, Debug, Eq, PartialEq, Snafu)]
 pub enum PathParseError {
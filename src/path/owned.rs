@@ -100,6 +100,76 @@ impl OwnedValuePath {
     pub fn push(&mut self, segment: OwnedSegment) {
         self.segments.push(segment);
     }
+
+    /// Format the path as a JSON Pointer (RFC 6901), e.g. `/foo/0/bar`.
+    pub fn to_json_pointer(&self) -> String {
+        let mut pointer = String::new();
+        for segment in &self.segments {
+            pointer.push('/');
+            match segment {
+                OwnedSegment::Field(field) => {
+                    pointer.push_str(&field.replace('~', "~0").replace('/', "~1"));
+                }
+                OwnedSegment::Index(index) => {
+                    write!(pointer, "{index}").expect("Could not write to string");
+                }
+            }
+        }
+        pointer
+    }
+
+    /// Parse a JSON Pointer (RFC 6901).
+    ///
+    /// A JSON Pointer doesn't distinguish between array indices and object fields, so tokens that
+    /// are valid array indices (such as `0` or `12`) are parsed as index segments, and all other
+    /// tokens as field segments. Consumers that resolve the path against a value are expected to
+    /// treat an index segment as a field when the value is an object.
+    pub fn from_json_pointer(pointer: &str) -> Result<Self, PathParseError> {
+        let invalid = || PathParseError::InvalidPathSyntax {
+            path: pointer.to_owned(),
+        };
+
+        if pointer.is_empty() {
+            return Ok(Self::root());
+        }
+
+        let tokens = pointer.strip_prefix('/').ok_or_else(invalid)?;
+        let mut segments = Vec::new();
+        for token in tokens.split('/') {
+            let segment = match token.parse() {
+                Ok(index) if is_json_pointer_index(token) => OwnedSegment::Index(index),
+                _ => OwnedSegment::Field(unescape_pointer_token(token).ok_or_else(invalid)?.into()),
+            };
+            segments.push(segment);
+        }
+
+        Ok(segments.into())
+    }
+}
+
+/// Returns `true` if a JSON Pointer token is an array index, which RFC 6901 restricts to `0` or
+/// digits without a leading zero.
+pub(crate) fn is_json_pointer_index(token: &str) -> bool {
+    token == "0"
+        || (!token.is_empty()
+            && !token.starts_with('0')
+            && token.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn unescape_pointer_token(token: &str) -> Option<String> {
+    let mut output = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '~' => match chars.next() {
+                Some('0') => output.push('~'),
+                Some('1') => output.push('/'),
+                _ => return None,
+            },
+            c => output.push(c),
+        }
+    }
+    Some(output)
 }
 
 // OwnedValuePath values must have at least one segment.
@@ -288,7 +358,7 @@ impl From<&OwnedValuePath> for String {
     }
 }
 
-fn serialize_field(string: &mut String, field: &str, separator: Option<&str>) {
+pub(super) fn serialize_field(string: &mut String, field: &str, separator: Option<&str>) {
     // These characters should match the ones from the parser, implemented in `JitLookup`
     let needs_quotes = field.is_empty()
         || field
@@ -447,6 +517,31 @@ mod test {
     use super::*;
     use crate::path::parse_value_path;
 
+    #[test]
+    fn json_pointer() {
+        let path = parse_value_path(r#"foo[0]."a/b~c"[12].bar"#).unwrap();
+        let pointer = path.to_json_pointer();
+        assert_eq!(pointer, "/foo/0/a~1b~0c/12/bar");
+        assert_eq!(OwnedValuePath::from_json_pointer(&pointer), Ok(path));
+
+        assert_eq!(OwnedValuePath::root().to_json_pointer(), "");
+        assert_eq!(
+            OwnedValuePath::from_json_pointer(""),
+            Ok(OwnedValuePath::root())
+        );
+        assert_eq!(
+            OwnedValuePath::from_json_pointer("/01/-/"),
+            Ok(vec![
+                OwnedSegment::field("01"),
+                OwnedSegment::field("-"),
+                OwnedSegment::field("")
+            ]
+            .into())
+        );
+        assert!(OwnedValuePath::from_json_pointer("foo").is_err());
+        assert!(OwnedValuePath::from_json_pointer("/a~2").is_err());
+    }
+
     #[test]
     fn to_alternative_components() -> Result<(), PathParseError> {
         let limit = 3;
@@ -0,0 +1,391 @@
+//! Queries are paths that can match more than one value.
+//!
+//! On top of the field and index segments of a regular path, a query supports:
+//!
+//! - wildcards (`.*` or `[*]`), matching every field of an object or every element of an array.
+//! - recursive descent (`..`), matching the rest of the query at any depth, including the current
+//!   one. For example, `..password` matches every `password` field, wherever it is nested.
+//!
+//! | query                | matches                                           |
+//! |----------------------|---------------------------------------------------|
+//! | `.users[*].id`       | the `id` field of every element of `users`        |
+//! | `.headers.*`         | every field of `headers`                          |
+//! | `..password`         | every `password` field, at any depth              |
+//! | `.a..[0]`            | the first element of every array within `a`       |
+//!
+//! Queries aren't part of the VRL path grammar: `.users[*].id` isn't a valid path expression in
+//! a program. They are only written as string arguments of functions such as `get_all` and
+//! `remove_all`, and parsed with [`parse_value_query`] when the function is compiled.
+
+use std::fmt::{self, Display, Formatter, Write};
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use super::owned::serialize_field;
+use super::{OwnedSegment, OwnedValuePath, PathParseError};
+use crate::value::KeyString;
+
+/// A pre-parsed query, which can match any number of values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct OwnedValueQuery {
+    pub segments: Vec<QuerySegment>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum QuerySegment {
+    Field(KeyString),
+    Index(isize),
+    /// Matches every field of an object, or every element of an array.
+    Wildcard,
+    /// Matches the rest of the query against the current value, and all values nested in it.
+    Descendants,
+}
+
+impl OwnedValueQuery {
+    pub fn root() -> Self {
+        vec![].into()
+    }
+
+    /// Returns true if the query contains no wildcards or recursive descent, and can therefore
+    /// match at most one value.
+    pub fn is_path(&self) -> bool {
+        self.segments
+            .iter()
+            .all(|segment| matches!(segment, QuerySegment::Field(_) | QuerySegment::Index(_)))
+    }
+
+    /// Converts the query into a regular path, if it can match at most one value.
+    pub fn to_path(&self) -> Option<OwnedValuePath> {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                QuerySegment::Field(field) => Some(OwnedSegment::Field(field.clone())),
+                QuerySegment::Index(index) => Some(OwnedSegment::Index(*index)),
+                QuerySegment::Wildcard | QuerySegment::Descendants => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(OwnedValuePath::from)
+    }
+}
+
+impl From<Vec<QuerySegment>> for OwnedValueQuery {
+    fn from(segments: Vec<QuerySegment>) -> Self {
+        Self { segments }
+    }
+}
+
+impl From<OwnedValuePath> for OwnedValueQuery {
+    fn from(path: OwnedValuePath) -> Self {
+        path.segments
+            .into_iter()
+            .map(|segment| match segment {
+                OwnedSegment::Field(field) => QuerySegment::Field(field),
+                OwnedSegment::Index(index) => QuerySegment::Index(index),
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+impl Display for OwnedValueQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", String::from(self))
+    }
+}
+
+impl From<&OwnedValueQuery> for String {
+    fn from(query: &OwnedValueQuery) -> Self {
+        if query.segments.is_empty() {
+            return ".".to_owned();
+        }
+
+        let mut output = String::new();
+        let mut after_descendants = false;
+        for segment in &query.segments {
+            let separator = (!after_descendants).then_some(".");
+            match segment {
+                QuerySegment::Field(field) => serialize_field(&mut output, field, separator),
+                QuerySegment::Index(index) => {
+                    write!(output, "[{index}]").expect("Could not write to string");
+                }
+                QuerySegment::Wildcard => {
+                    output.push_str(separator.unwrap_or_default());
+                    output.push('*');
+                }
+                QuerySegment::Descendants => output.push_str(".."),
+            }
+            after_descendants = matches!(segment, QuerySegment::Descendants);
+        }
+        output
+    }
+}
+
+impl From<OwnedValueQuery> for String {
+    fn from(query: OwnedValueQuery) -> Self {
+        Self::from(&query)
+    }
+}
+
+impl FromStr for OwnedValueQuery {
+    type Err = PathParseError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        parse_value_query(src)
+    }
+}
+
+impl TryFrom<String> for OwnedValueQuery {
+    type Error = PathParseError;
+
+    fn try_from(src: String) -> Result<Self, Self::Error> {
+        src.parse()
+    }
+}
+
+/// Parses a value query, which is a value path that can also contain wildcards (`.*`, `[*]`) and
+/// recursive descent (`..`).
+///
+/// Every valid value path is also a valid query.
+pub fn parse_value_query(query: &str) -> Result<OwnedValueQuery, PathParseError> {
+    QueryParser {
+        src: query,
+        chars: query.char_indices().peekable(),
+    }
+    .parse()
+    .ok_or_else(|| PathParseError::InvalidPathSyntax {
+        path: query.to_owned(),
+    })
+}
+
+struct QueryParser<'a> {
+    src: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl QueryParser<'_> {
+    fn parse(mut self) -> Option<OwnedValueQuery> {
+        let mut segments = vec![];
+
+        // A leading `.` refers to the root. Unlike later segments, a field may follow directly.
+        match self.peek() {
+            None => return None,
+            Some('.') => {
+                self.chars.next();
+                match self.peek() {
+                    None => return Some(segments.into()),
+                    Some('.') => {
+                        self.chars.next();
+                        segments.push(QuerySegment::Descendants);
+                        segments.push(self.parse_after_dot(true)?);
+                    }
+                    Some('[') => {}
+                    Some(_) => segments.push(self.parse_after_dot(false)?),
+                }
+            }
+            Some('[') => {}
+            Some(_) => segments.push(self.parse_after_dot(false)?),
+        }
+
+        while let Some((_, c)) = self.chars.next() {
+            match c {
+                '.' => {
+                    if self.peek() == Some('.') {
+                        self.chars.next();
+                        segments.push(QuerySegment::Descendants);
+                        segments.push(self.parse_after_dot(true)?);
+                    } else {
+                        segments.push(self.parse_after_dot(false)?);
+                    }
+                }
+                '[' => segments.push(self.parse_bracket()?),
+                _ => return None,
+            }
+        }
+
+        Some(segments.into())
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    /// Parses the segment following a `.` (or `..`, in which case an index is also allowed).
+    fn parse_after_dot(&mut self, allow_index: bool) -> Option<QuerySegment> {
+        match self.peek()? {
+            '*' => {
+                self.chars.next();
+                Some(QuerySegment::Wildcard)
+            }
+            '"' => {
+                self.chars.next();
+                self.parse_quoted()
+            }
+            '[' if allow_index => {
+                self.chars.next();
+                self.parse_bracket()
+            }
+            c if is_field_char(c) => Some(self.parse_field()),
+            _ => None,
+        }
+    }
+
+    fn parse_field(&mut self) -> QuerySegment {
+        let (start, _) = *self.chars.peek().expect("field is not empty");
+        let mut end = self.src.len();
+        while let Some(&(index, c)) = self.chars.peek() {
+            if !is_field_char(c) {
+                end = index;
+                break;
+            }
+            self.chars.next();
+        }
+        QuerySegment::Field(self.src[start..end].into())
+    }
+
+    fn parse_quoted(&mut self) -> Option<QuerySegment> {
+        let mut field = String::new();
+        loop {
+            match self.chars.next()?.1 {
+                '"' => return Some(QuerySegment::Field(field.into())),
+                '\\' => match self.chars.next()?.1 {
+                    c @ ('\\' | '"') => field.push(c),
+                    _ => return None,
+                },
+                c => field.push(c),
+            }
+        }
+    }
+
+    /// Parses the contents of `[...]`, after the opening bracket.
+    fn parse_bracket(&mut self) -> Option<QuerySegment> {
+        let segment = if self.peek()? == '*' {
+            self.chars.next();
+            QuerySegment::Wildcard
+        } else {
+            let negative = self.peek()? == '-';
+            if negative {
+                self.chars.next();
+            }
+
+            let mut digits = String::new();
+            while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+                digits.push(c);
+                self.chars.next();
+            }
+            let index = digits.parse::<isize>().ok()?;
+            QuerySegment::Index(if negative { -index } else { index })
+        };
+
+        (self.chars.next()?.1 == ']').then_some(segment)
+    }
+}
+
+// These characters should match the ones from the path parser, implemented in `JitValuePath`.
+fn is_field_char(c: char) -> bool {
+    matches!(c, 'A'..='Z' | 'a'..='z' | '_' | '0'..='9' | '@' | '-')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::path::parse_value_path;
+
+    #[test]
+    fn parse() {
+        use QuerySegment::{Descendants, Field, Index, Wildcard};
+
+        let test_cases: Vec<(&str, Option<Vec<QuerySegment>>)> = vec![
+            (".", Some(vec![])),
+            ("", None),
+            (".foo", Some(vec![Field("foo".into())])),
+            (
+                "foo.bar",
+                Some(vec![Field("foo".into()), Field("bar".into())]),
+            ),
+            ("[0]", Some(vec![Index(0)])),
+            (".[-1]", Some(vec![Index(-1)])),
+            (".*", Some(vec![Wildcard])),
+            ("*", Some(vec![Wildcard])),
+            (
+                ".users[*].id",
+                Some(vec![Field("users".into()), Wildcard, Field("id".into())]),
+            ),
+            (
+                "..password",
+                Some(vec![Descendants, Field("password".into())]),
+            ),
+            (
+                r#".a.."b c""#,
+                Some(vec![Field("a".into()), Descendants, Field("b c".into())]),
+            ),
+            (
+                ".a..*",
+                Some(vec![Field("a".into()), Descendants, Wildcard]),
+            ),
+            (
+                ".a..[0]",
+                Some(vec![Field("a".into()), Descendants, Index(0)]),
+            ),
+            ("..", None),
+            (".a..", None),
+            ("...a", None),
+            (".a.", None),
+            (".a[*", None),
+            (".a[]", None),
+            (".a[x]", None),
+            (".a*", None),
+            (".**", None),
+            (r#"."a"#, None),
+        ];
+
+        for (query, expected) in test_cases {
+            assert_eq!(
+                parse_value_query(query).ok(),
+                expected.map(OwnedValueQuery::from),
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn serialize() {
+        for query in [
+            ".",
+            ".foo",
+            ".foo[0].bar",
+            ".*",
+            ".[*]",
+            "..password",
+            r#".a.."b c"[-1]"#,
+            ".a..*",
+            ".a..[0]",
+        ] {
+            let parsed = parse_value_query(query).unwrap();
+            assert_eq!(
+                parse_value_query(&parsed.to_string()),
+                Ok(parsed),
+                "{query}"
+            );
+        }
+
+        assert_eq!(parse_value_query("[*]").unwrap().to_string(), ".*");
+        assert_eq!(parse_value_query("foo[*]").unwrap().to_string(), ".foo.*");
+    }
+
+    #[test]
+    fn paths_are_queries() {
+        for src in [".", ".foo", "foo.bar[1]", r#".a."b.c"[-2]"#] {
+            let path = parse_value_path(src).unwrap();
+            let query = parse_value_query(src).unwrap();
+
+            assert!(query.is_path());
+            assert_eq!(query.to_path(), Some(path.clone()));
+            assert_eq!(query, OwnedValueQuery::from(path));
+        }
+
+        assert_eq!(parse_value_query(".a.*").unwrap().to_path(), None);
+    }
+}
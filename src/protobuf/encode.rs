@@ -268,7 +268,7 @@ mod tests {
     fn test_encode_integers() {
         let message = encode_message(
             &test_message_descriptor("Integers"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("i32".into(), Value::Integer(-1234)),
                 ("i64".into(), Value::Integer(-9876)),
                 ("u32".into(), Value::Integer(1234)),
@@ -287,7 +287,7 @@ mod tests {
     fn test_encode_integers_from_bytes() {
         let message = encode_message(
             &test_message_descriptor("Integers"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("i32".into(), Value::Bytes(Bytes::from("-1234"))),
                 ("i64".into(), Value::Bytes(Bytes::from("-9876"))),
                 ("u32".into(), Value::Bytes(Bytes::from("1234"))),
@@ -306,7 +306,7 @@ mod tests {
     fn test_encode_floats() {
         let message = encode_message(
             &test_message_descriptor("Floats"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("d".into(), Value::Float(NotNan::new(11.0).unwrap())),
                 ("f".into(), Value::Float(NotNan::new(2.0).unwrap())),
             ])),
@@ -321,7 +321,7 @@ mod tests {
     fn test_encode_bytes_as_float() {
         let message = encode_message(
             &test_message_descriptor("Floats"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("d".into(), Value::Bytes(Bytes::from("11.0"))),
                 ("f".into(), Value::Bytes(Bytes::from("2.0"))),
             ])),
@@ -336,7 +336,7 @@ mod tests {
     fn test_encode_integer_as_double() {
         let message = encode_message(
             &test_message_descriptor("Floats"),
-            Value::Object(BTreeMap::from([("d".into(), Value::Integer(42))])),
+            Value::from(BTreeMap::from([("d".into(), Value::Integer(42))])),
             &Options::default(),
         )
         .unwrap();
@@ -348,7 +348,7 @@ mod tests {
         let bytes = Bytes::from(vec![0, 1, 2, 3]);
         let message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 ("text".into(), Value::Bytes(Bytes::from("vector"))),
                 ("binary".into(), Value::Bytes(bytes.clone())),
             ])),
@@ -363,19 +363,19 @@ mod tests {
     fn test_encode_map() {
         let message = encode_message(
             &test_message_descriptor("Map"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 (
                     "names".into(),
-                    Value::Object(BTreeMap::from([
+                    Value::from(BTreeMap::from([
                         ("forty-four".into(), Value::Integer(44)),
                         ("one".into(), Value::Integer(1)),
                     ])),
                 ),
                 (
                     "people".into(),
-                    Value::Object(BTreeMap::from([(
+                    Value::from(BTreeMap::from([(
                         "mark".into(),
-                        Value::Object(BTreeMap::from([
+                        Value::from(BTreeMap::from([
                             ("nickname".into(), Value::Bytes(Bytes::from("jeff"))),
                             ("age".into(), Value::Integer(22)),
                         ])),
@@ -421,7 +421,7 @@ mod tests {
     fn test_encode_enum() {
         let message = encode_message(
             &test_message_descriptor("Enum"),
-            Value::Object(BTreeMap::from([
+            Value::from(BTreeMap::from([
                 (
                     "breakfast".into(),
                     Value::Bytes(Bytes::from("fruit_tomato")),
@@ -441,7 +441,7 @@ mod tests {
     fn test_encode_timestamp() {
         let message = encode_message(
             &test_message_descriptor("Timestamp"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "morning".into(),
                 Value::Timestamp(
                     DateTime::from_timestamp(8675, 309).expect("could not compute timestamp"),
@@ -459,9 +459,9 @@ mod tests {
     fn test_encode_repeated_primitive() {
         let message = encode_message(
             &test_message_descriptor("RepeatedPrimitive"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "numbers".into(),
-                Value::Array(vec![
+                Value::from(vec![
                     Value::Integer(8),
                     Value::Integer(6),
                     Value::Integer(4),
@@ -481,15 +481,15 @@ mod tests {
     fn test_encode_repeated_message() {
         let message = encode_message(
             &test_message_descriptor("RepeatedMessage"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "messages".into(),
-                Value::Array(vec![
-                    Value::Object(BTreeMap::from([(
+                Value::from(vec![
+                    Value::from(BTreeMap::from([(
                         "text".into(),
                         Value::Bytes(Bytes::from("vector")),
                     )])),
-                    Value::Object(BTreeMap::from([("index".into(), Value::Integer(4444))])),
-                    Value::Object(BTreeMap::from([
+                    Value::from(BTreeMap::from([("index".into(), Value::Integer(4444))])),
+                    Value::from(BTreeMap::from([
                         ("text".into(), Value::Bytes(Bytes::from("protobuf"))),
                         ("index".into(), Value::Integer(1)),
                     ])),
@@ -524,21 +524,21 @@ mod tests {
     fn test_encode_value_as_string() {
         let mut message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([("text".into(), Value::Boolean(true))])),
+            Value::from(BTreeMap::from([("text".into(), Value::Boolean(true))])),
             &Options::default(),
         )
         .unwrap();
         assert_eq!(Some("true"), mfield!(message, "text").as_str());
         message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([("text".into(), Value::Integer(123))])),
+            Value::from(BTreeMap::from([("text".into(), Value::Integer(123))])),
             &Options::default(),
         )
         .unwrap();
         assert_eq!(Some("123"), mfield!(message, "text").as_str());
         message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "text".into(),
                 Value::Float(NotNan::new(45.67).unwrap()),
             )])),
@@ -548,7 +548,7 @@ mod tests {
         assert_eq!(Some("45.67"), mfield!(message, "text").as_str());
         message = encode_message(
             &test_message_descriptor("Bytes"),
-            Value::Object(BTreeMap::from([(
+            Value::from(BTreeMap::from([(
                 "text".into(),
                 Value::Timestamp(
                     DateTime::from_timestamp(8675, 309).expect("could not compute timestamp"),
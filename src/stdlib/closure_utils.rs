@@ -0,0 +1,43 @@
+use crate::compiler::prelude::*;
+use std::cmp::Ordering;
+
+/// Returns the first element of the array for which the closure returned the
+/// key that is ordered as `wanted` against all other keys, or `null` if the
+/// array is empty.
+pub(crate) fn select_by<T>(
+    value: Value,
+    wanted: Ordering,
+    ctx: &mut Context,
+    runner: &closure::Runner<T>,
+) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    let mut selected: Option<(Value, Value)> = None;
+
+    for (index, value) in value.try_array()?.into_iter().enumerate() {
+        let key = runner.run_index_value(ctx, index, &value)?;
+
+        match &selected {
+            Some((selected_key, _)) if key.total_cmp(selected_key) != wanted => {}
+            _ => selected = Some((key, value)),
+        }
+    }
+
+    Ok(selected.map_or(Value::Null, |(_, value)| value))
+}
+
+/// Returns the kind of the selected element, which is `null` if the array can
+/// be empty.
+pub(crate) fn selected_type_def(type_def: &TypeDef) -> TypeDef {
+    let Some(array) = type_def.as_array() else {
+        return TypeDef::any();
+    };
+
+    let mut kind = array.reduced_kind();
+    if array.known().is_empty() {
+        kind.add_null();
+    }
+
+    TypeDef::from(kind)
+}
@@ -1,5 +1,6 @@
 use super::util;
 use crate::compiler::prelude::*;
+use crate::value::IntoCollection;
 use std::sync::LazyLock;
 
 static DEFAULT_RECURSIVE: LazyLock<Value> = LazyLock::new(|| Value::Boolean(true));
@@ -51,7 +52,7 @@ fn compact(
 
     match value {
         Value::Object(object) => Ok(Value::from(compact_object(object, &options))),
-        Value::Array(arr) => Ok(Value::from(compact_array(arr, &options))),
+        Value::Array(arr) => Ok(Value::from(compact_array(arr.into_collection(), &options))),
         value => Err(ValueError::Expected {
             got: value.kind(),
             expected: Kind::array(Collection::any()) | Kind::object(Collection::any()),
@@ -234,7 +235,9 @@ impl FunctionExpression for CompactFn {
 /// Compact the value if we are recursing - otherwise, just return the value untouched.
 fn recurse_compact(value: Value, options: &CompactOptions) -> Value {
     match value {
-        Value::Array(array) if options.recursive => Value::from(compact_array(array, options)),
+        Value::Array(array) if options.recursive => {
+            Value::from(compact_array(array.into_collection(), options))
+        }
         Value::Object(object) if options.recursive => Value::from(compact_object(object, options)),
         _ => value,
     }
@@ -272,6 +275,7 @@ fn compact_array(array: Vec<Value>, options: &CompactOptions) -> Vec<Value> {
 mod test {
     use super::*;
     use crate::btreemap;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_compacted_array() {
@@ -286,14 +290,14 @@ mod test {
             ),
             (
                 vec![1.into(), 2.into()],
-                vec![1.into(), Value::Array(vec![]), 2.into()],
+                vec![1.into(), Value::Array(ValueArray::new()), 2.into()],
                 CompactOptions::default(),
             ),
             (
-                vec![1.into(), Value::Array(vec![3.into()]), 2.into()],
+                vec![1.into(), Value::from(vec![Value::from(3)]), 2.into()],
                 vec![
                     1.into(),
-                    Value::Array(vec![Value::Null, 3.into(), Value::Null]),
+                    Value::from(vec![Value::Null, 3.into(), Value::Null]),
                     2.into(),
                 ],
                 CompactOptions::default(),
@@ -302,7 +306,7 @@ mod test {
                 vec![1.into(), 2.into()],
                 vec![
                     1.into(),
-                    Value::Array(vec![Value::Null, Value::Null]),
+                    Value::from(vec![Value::Null, Value::Null]),
                     2.into(),
                 ],
                 CompactOptions::default(),
@@ -359,13 +363,13 @@ mod test {
                 },
                 btreemap! {
                     "key1" => Value::from(1),
-                    "key2" => Value::Array(vec![]),
+                    "key2" => Value::Array(ValueArray::new()),
                     "key3" => Value::from(2),
                 },
                 CompactOptions::default(),
             ),
             (
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -373,7 +377,7 @@ mod test {
                     ),
                     (KeyString::from("key3"), Value::from(2)),
                 ]),
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -388,7 +392,7 @@ mod test {
                 CompactOptions::default(),
             ),
             (
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -396,7 +400,7 @@ mod test {
                     ),
                     (KeyString::from("key3"), Value::from(2)),
                 ]),
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -410,11 +414,11 @@ mod test {
                 },
             ),
             (
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (KeyString::from("key3"), Value::from(2)),
                 ]),
-                ObjectMap::from([
+                BTreeMap::from([
                     (KeyString::from("key1"), Value::from(1)),
                     (
                         KeyString::from("key2"),
@@ -427,12 +431,12 @@ mod test {
             (
                 btreemap! {
                     "key1" => Value::from(1),
-                    "key2" => Value::Array(vec![2.into()]),
+                    "key2" => Value::from(vec![Value::from(2)]),
                     "key3" => Value::from(2),
                 },
                 btreemap! {
                     "key1" => Value::from(1),
-                    "key2" => Value::Array(vec![Value::Null, 2.into(), Value::Null]),
+                    "key2" => Value::from(vec![Value::Null, 2.into(), Value::Null]),
                     "key3" => Value::from(2),
                 },
                 CompactOptions::default(),
@@ -440,7 +444,10 @@ mod test {
         ];
 
         for (expected, original, options) in cases {
-            assert_eq!(expected, compact_object(original, &options));
+            assert_eq!(
+                expected.into_iter().collect::<ObjectMap>(),
+                compact_object(original.into_iter().collect(), &options)
+            );
         }
     }
 
@@ -455,7 +462,7 @@ mod test {
 
         with_array {
             args: func_args![value: vec![Value::Null, Value::from(1), Value::from(""),]],
-            want: Ok(Value::Array(vec![Value::from(1)])),
+            want: Ok(Value::from(vec![Value::from(1)])),
             tdef: TypeDef::array(Collection::any()),
         }
 
@@ -0,0 +1,244 @@
+use crate::compiler::prelude::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod non_wasm {
+    use super::super::parse_groks::compile_grok_rules;
+    use crate::compiler::prelude::*;
+    use crate::datadog_grok::{parse_grok::diagnose_grok, parse_grok_rules::GrokRule};
+
+    #[derive(Clone, Debug)]
+    pub(super) struct DiagnoseGroksFn {
+        pub(super) value: Box<dyn Expression>,
+        pub(super) grok_rules: Vec<GrokRule>,
+    }
+
+    impl FunctionExpression for DiagnoseGroksFn {
+        fn resolve(&self, ctx: &mut Context) -> Resolved {
+            let value = self.value.resolve(ctx)?;
+            let bytes = value.try_bytes_utf8_lossy()?;
+
+            let diagnostics = diagnose_grok(bytes.as_ref(), &self.grok_rules)
+                .map_err(|err| format!("unable to diagnose grok: {err}"))?;
+
+            Ok(diagnostics
+                .into_iter()
+                .map(Value::from)
+                .collect::<Vec<_>>()
+                .into())
+        }
+
+        fn type_def(&self, _: &state::TypeState) -> TypeDef {
+            TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible()
+        }
+    }
+
+    pub(super) fn compile(
+        state: &state::TypeState,
+        ctx: &FunctionCompileContext,
+        arguments: &ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let grok_rules = compile_grok_rules(state, ctx, arguments)?;
+
+        Ok(DiagnoseGroksFn { value, grok_rules }.as_expr())
+    }
+}
+
+use super::parse_groks::{DEFAULT_DIALECT, DIALECT_ENUM};
+use std::sync::LazyLock;
+
+static DEFAULT_ALIASES: LazyLock<Value> = LazyLock::new(|| Value::Object(ObjectMap::new()));
+static DEFAULT_ALIAS_SOURCES: LazyLock<Value> = LazyLock::new(|| Value::Array(ValueArray::new()));
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required("value", kind::BYTES, "The string to explain the parsing of."),
+        Parameter::required(
+            "patterns",
+            kind::ARRAY,
+            "The grok patterns, as accepted by `parse_groks`, which are each applied to `value`.",
+        ),
+        Parameter::optional("aliases", kind::OBJECT, "The shared set of grok aliases that can be referenced in the patterns to simplify them.")
+            .default(&DEFAULT_ALIASES),
+        Parameter::optional("alias_sources", kind::ARRAY, "Path to the file containing aliases in a JSON format.")
+            .default(&DEFAULT_ALIAS_SOURCES),
+        Parameter::optional("dialect", kind::BYTES, "The grok dialect the patterns are written in.")
+            .default(&DEFAULT_DIALECT)
+            .enum_variants(DIALECT_ENUM),
+    ]
+});
+
+#[derive(Clone, Copy, Debug)]
+pub struct DiagnoseGroks;
+
+impl Function for DiagnoseGroks {
+    fn identifier(&self) -> &'static str {
+        "diagnose_groks"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Explains how each of the `patterns` of `parse_groks` matches the `value`, or why it
+            doesn't. For every pattern, returns whether it `matched`, the `matched_prefix` of the
+            value that its leading sub-patterns matched, the `failed_pattern` where matching failed,
+            the `fields` captured up to that point, and the `errors` of applying their filters.
+
+            A pattern whose sub-patterns all matched, but that didn't match the whole value, has a
+            null `failed_pattern`.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Debug.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &[
+            "The regex engine fails while matching a pattern against `value`.",
+            "`patterns` is not an array.",
+            "`aliases` is not an object.",
+            "`alias_sources` is not a string array or doesn't point to a valid file.",
+        ]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ARRAY
+    }
+
+    fn notices(&self) -> &'static [&'static str] {
+        &[indoc! {"
+            Diagnosing a pattern that doesn't match compiles each of its leading sub-patterns, so
+            this function is meant for debugging patterns rather than for parsing events.
+        "}]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[example! {
+            title: "Explain why a pattern doesn't match",
+            source: indoc! {r#"
+                diagnose_groks!(
+                    "info user=alice",
+                    patterns: ["%{word:level} user=%{integer:user}"]
+                )
+            "#},
+            result: Ok(indoc! {r#"
+                [{
+                    "rule": "%{word:level} user=%{integer:user}",
+                    "matched": false,
+                    "matched_prefix": "info user=",
+                    "failed_pattern": "%{integer:user}",
+                    "fields": { "level": "info" },
+                    "errors": []
+                }]
+            "#}),
+        }]
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        non_wasm::compile(state, ctx, &arguments)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        ctx: &mut FunctionCompileContext,
+        _: ArgumentList,
+    ) -> Compiled {
+        Ok(super::WasmUnsupportedFunction::new(
+            ctx.span(),
+            TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible(),
+        )
+        .as_expr())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::btreemap;
+    use crate::value;
+
+    use super::*;
+
+    test_function![
+        diagnose_groks => DiagnoseGroks;
+
+        matched {
+            args: func_args![ value: "2020-10-02T23:22:12.223222Z info Hello world",
+                              patterns: vec!["%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}"]],
+            want: Ok(value!([{
+                "rule": "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}",
+                "matched": true,
+                "matched_prefix": "2020-10-02T23:22:12.223222Z info Hello world",
+                "failed_pattern": null,
+                "fields": {
+                    "timestamp": "2020-10-02T23:22:12.223222Z",
+                    "level": "info",
+                    "message": "Hello world",
+                },
+                "errors": [],
+            }])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible(),
+        }
+
+        each_pattern_with_aliases {
+            args: func_args![
+                value: "2020-10-02T23:22:12.223222Z info hello world",
+                patterns: Value::from(vec![
+                    Value::from("%{common_prefix} %{_status} %{_message}"),
+                    Value::from("%{common_prefix} %{_message}"),
+                    ]),
+                aliases: value!({
+                    "common_prefix": "%{_timestamp} %{_loglevel}",
+                    "_timestamp": "%{TIMESTAMP_ISO8601:timestamp}",
+                    "_loglevel": "%{LOGLEVEL:level}",
+                    "_status": "%{POSINT:status}",
+                    "_message": "%{GREEDYDATA:message}"
+                })
+            ],
+            want: Ok(Value::from(vec![
+                Value::from(btreemap! {
+                    "rule" => "%{common_prefix} %{_status} %{_message}",
+                    "matched" => false,
+                    "matched_prefix" => "2020-10-02T23:22:12.223222Z info ",
+                    "failed_pattern" => "%{_status}",
+                    "fields" => btreemap! {
+                        "timestamp" => "2020-10-02T23:22:12.223222Z",
+                        "level" => "info",
+                    },
+                    "errors" => Vec::<Value>::new(),
+                }),
+                Value::from(btreemap! {
+                    "rule" => "%{common_prefix} %{_message}",
+                    "matched" => true,
+                    "matched_prefix" => "2020-10-02T23:22:12.223222Z info hello world",
+                    "failed_pattern" => Value::Null,
+                    "fields" => btreemap! {
+                        "timestamp" => "2020-10-02T23:22:12.223222Z",
+                        "level" => "info",
+                        "message" => "hello world",
+                    },
+                    "errors" => Vec::<Value>::new(),
+                }),
+            ])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible(),
+        }
+
+        invalid_grok {
+            args: func_args![ value: "foo",
+                              patterns: vec!["%{NOG}"]],
+            want: Err("failed to parse grok expression '(?m)\\A%{NOG}\\z': The given pattern definition name \"NOG\" could not be found in the definition map"),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::object(Collection::any()))).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,152 @@
+use crate::compiler::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required("from", kind::ANY, "The original value."),
+        Parameter::required("to", kind::ANY, "The changed value."),
+    ]
+});
+
+fn diff(from: &Value, to: &Value) -> Value {
+    from.diff(to).into_iter().map(Value::from).collect()
+}
+
+/// The type of a JSON Patch, as returned by `diff`.
+pub(crate) fn patch_kind() -> Kind {
+    Kind::array(Collection::from_unknown(Kind::object(
+        Collection::from_parts(
+            BTreeMap::from([
+                (Field::from("op"), Kind::bytes()),
+                (Field::from("path"), Kind::bytes()),
+            ]),
+            Kind::any(),
+        ),
+    )))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Diff;
+
+impl Function for Diff {
+    fn identifier(&self) -> &'static str {
+        "diff"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Computes the changes needed to turn `from` into `to`, as a JSON Patch (RFC 6902).
+
+            Objects are compared field by field, and arrays index by index. Each change is an
+            `add`, `remove` or `replace` operation, with the path formatted as a JSON Pointer.
+            The result can be applied to `from` using the `patch` function.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Object.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ARRAY
+    }
+
+    fn return_rules(&self) -> &'static [&'static str] {
+        &[
+            "An empty array is returned if both values are equal.",
+            "Elements removed from the end of an array are removed last to first, so the remaining indices stay valid.",
+        ]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Diff two objects",
+                source: r#"diff({ "a": 1, "b": { "c": 2 } }, { "b": { "c": 3 }, "d": true })"#,
+                result: Ok(indoc! {r#"[
+                    { "op": "remove", "path": "/a" },
+                    { "op": "replace", "path": "/b/c", "value": 3 },
+                    { "op": "add", "path": "/d", "value": true }
+                ]"#}),
+            },
+            example! {
+                title: "Diff two arrays",
+                source: "diff([1, 2, 3], [1, 4])",
+                result: Ok(indoc! {r#"[
+                    { "op": "replace", "path": "/1", "value": 4 },
+                    { "op": "remove", "path": "/2" }
+                ]"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let from = arguments.required("from");
+        let to = arguments.required("to");
+
+        Ok(DiffFn { from, to }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DiffFn {
+    from: Box<dyn Expression>,
+    to: Box<dyn Expression>,
+}
+
+impl FunctionExpression for DiffFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let from = self.from.resolve(ctx)?;
+        let to = self.to.resolve(ctx)?;
+
+        Ok(diff(&from, &to))
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        patch_kind().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        diff => Diff;
+
+        equal {
+            args: func_args![from: value!({ "a": [1] }), to: value!({ "a": [1] })],
+            want: Ok(value!([])),
+            tdef: patch_kind().into(),
+        }
+
+        replace_root {
+            args: func_args![from: value!(1), to: value!("1")],
+            want: Ok(value!([{ "op": "replace", "path": "", "value": "1" }])),
+            tdef: patch_kind().into(),
+        }
+
+        nested {
+            args: func_args![
+                from: value!({ "a": { "b": [1, 2] } }),
+                to: value!({ "a": { "b": [1, 2, 3] }, "c/d": null })
+            ],
+            want: Ok(value!([
+                { "op": "add", "path": "/a/b/2", "value": 3 },
+                { "op": "add", "path": "/c~1d", "value": null }
+            ])),
+            tdef: patch_kind().into(),
+        }
+    ];
+}
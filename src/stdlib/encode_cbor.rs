@@ -0,0 +1,169 @@
+use crate::compiler::prelude::*;
+use crate::value::value::timestamp_to_string;
+use ciborium::value::Value as CborValue;
+
+/// The CBOR tag for an RFC 3339 date/time string.
+const DATE_TIME_TAG: u64 = 0;
+
+fn to_cbor_value(value: &Value) -> CborValue {
+    match value {
+        Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(string) => CborValue::Text(string.to_owned()),
+            Err(_) => CborValue::Bytes(bytes.to_vec()),
+        },
+        Value::Regex(regex) => CborValue::Text(regex.as_str().to_owned()),
+        Value::Integer(integer) => CborValue::Integer((*integer).into()),
+        Value::Float(float) => CborValue::Float(float.into_inner()),
+        Value::Boolean(boolean) => CborValue::Bool(*boolean),
+        Value::Timestamp(timestamp) => CborValue::Tag(
+            DATE_TIME_TAG,
+            Box::new(CborValue::Text(timestamp_to_string(timestamp))),
+        ),
+        Value::Object(object) => CborValue::Map(
+            object
+                .iter()
+                .map(|(key, value)| (CborValue::Text(key.to_string()), to_cbor_value(value)))
+                .collect(),
+        ),
+        Value::Array(array) => CborValue::Array(array.iter().map(to_cbor_value).collect()),
+        Value::Null => CborValue::Null,
+    }
+}
+
+fn encode_cbor(value: &Value) -> Value {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(&to_cbor_value(value), &mut bytes)
+        .expect("writing to a vec can't fail");
+    Value::Bytes(bytes.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeCbor;
+
+impl Function for EncodeCbor {
+    fn identifier(&self) -> &'static str {
+        "encode_cbor"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Encodes the `value` to [CBOR](https://cbor.io).
+
+            Strings are encoded as text strings if they are valid UTF-8, and as byte strings
+            otherwise. Timestamps are encoded as tagged RFC 3339 date/time strings, and regular
+            expressions as their pattern.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Codec.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::BYTES
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Encode to CBOR",
+                source: r#"encode_base64(encode_cbor({ "field": "value" }))"#,
+                result: Ok("oWVmaWVsZGV2YWx1ZQ=="),
+            },
+            example! {
+                title: "Round trip",
+                source: "parse_cbor!(encode_cbor([true, 0]))",
+                result: Ok("[true, 0]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(EncodeCborFn { value }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "value",
+            kind::ANY,
+            "The value to encode.",
+        )];
+        PARAMETERS
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeCborFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EncodeCborFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(encode_cbor(&value))
+    }
+
+    fn type_def(&self, _state: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        encode_cbor => EncodeCbor;
+
+        object {
+            args: func_args![value: value!({ field: "value" })],
+            want: Ok(value!(b"\xa1efieldevalue".as_slice())),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        binary {
+            args: func_args![value: value!(b"\xff\x00".as_slice())],
+            want: Ok(value!(b"\x42\xff\x00".as_slice())),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        timestamp {
+            args: func_args![value: value!(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap())],
+            want: Ok(value!(b"\xc0\x742024-01-02T03:04:05Z".as_slice())),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+
+    #[test]
+    fn round_trip() {
+        let value = value!({
+            "string": "hello",
+            "integer": (-42),
+            "float": 1.5,
+            "boolean": true,
+            "null": null,
+            "array": [1, "two", [3]],
+            "object": { "nested": { "a": 1 } }
+        });
+        let mut with_binary = value.clone();
+        with_binary.insert("binary", Value::Bytes(vec![0xff, 0x00].into()));
+
+        for value in [value, with_binary] {
+            let Value::Bytes(bytes) = encode_cbor(&value) else {
+                panic!("not bytes");
+            };
+            let decoded: Value = ciborium::de::from_reader(bytes.as_ref()).unwrap();
+            assert_eq!(decoded, value);
+        }
+    }
+}
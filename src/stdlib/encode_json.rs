@@ -46,6 +46,13 @@ impl Function for EncodeJson {
         Category::Codec.as_ref()
     }
 
+    fn notices(&self) -> &'static [&'static str] {
+        &[indoc! {"
+            Object fields are encoded sorted by key. When VRL is built with the `preserve_order`
+            feature, fields are encoded in insertion order.
+        "}]
+    }
+
     fn return_kind(&self) -> u16 {
         kind::BYTES
     }
@@ -30,7 +30,8 @@ pub(crate) fn encode_key_value(
     .into())
 }
 
-pub(super) static DEFAULT_FIELDS_ORDERING: LazyLock<Value> = LazyLock::new(|| Value::Array(vec![]));
+pub(super) static DEFAULT_FIELDS_ORDERING: LazyLock<Value> =
+    LazyLock::new(|| Value::Array(ValueArray::new()));
 static DEFAULT_KEY_VALUE_DELIMITER: LazyLock<Value> =
     LazyLock::new(|| Value::Bytes(Bytes::from("=")));
 static DEFAULT_FIELD_DELIMITER: LazyLock<Value> = LazyLock::new(|| Value::Bytes(Bytes::from(" ")));
@@ -75,7 +76,13 @@ impl Function for EncodeKeyValue {
     }
 
     fn notices(&self) -> &'static [&'static str] {
-        &["If `fields_ordering` is specified then the function is fallible else it is infallible."]
+        &[
+            "If `fields_ordering` is specified then the function is fallible else it is infallible.",
+            indoc! {"
+                Fields not listed in `fields_ordering` are encoded sorted by key, including when
+                VRL is built with the `preserve_order` feature.
+            "},
+        ]
     }
 
     fn parameters(&self) -> &'static [Parameter] {
@@ -277,13 +284,7 @@ mod tests {
         let before: Value = r#"key="this has a \" quote""#.into();
 
         let after = encode_key_value(
-            Some(Value::Array(vec![
-                "key".into(),
-                "has".into(),
-                "a".into(),
-                r#"""#.into(),
-                "quote".into(),
-            ])),
+            Some(value!(["key", "has", "a", r#"""#, "quote"])),
             parse_key_value(
                 &before,
                 &Value::from("="),
@@ -40,7 +40,13 @@ impl Function for EncodeLogfmt {
     }
 
     fn notices(&self) -> &'static [&'static str] {
-        &["If `fields_ordering` is specified then the function is fallible else it is infallible."]
+        &[
+            "If `fields_ordering` is specified then the function is fallible else it is infallible.",
+            indoc! {"
+                Fields not listed in `fields_ordering` are encoded sorted by key, including when
+                VRL is built with the `preserve_order` feature.
+            "},
+        ]
     }
 
     fn parameters(&self) -> &'static [Parameter] {
@@ -0,0 +1,109 @@
+use crate::compiler::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeMsgpack;
+
+impl Function for EncodeMsgpack {
+    fn identifier(&self) -> &'static str {
+        "encode_msgpack"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Encodes the `value` to [MessagePack](https://msgpack.org).
+
+            Strings are encoded as MessagePack strings if they are valid UTF-8, and as binaries
+            otherwise. Timestamps are encoded with the timestamp extension, and regular expressions
+            as their pattern.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Codec.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::BYTES
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Encode to MessagePack",
+                source: r#"encode_base64(encode_msgpack({ "field": "value" }))"#,
+                result: Ok("gaVmaWVsZKV2YWx1ZQ=="),
+            },
+            example! {
+                title: "Encode a timestamp",
+                source: "encode_base64(encode_msgpack(t'2023-12-31T22:15:04Z'))",
+                result: Ok("1v9lkefo"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(EncodeMsgpackFn { value }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "value",
+            kind::ANY,
+            "The value to encode.",
+        )];
+        PARAMETERS
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeMsgpackFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EncodeMsgpackFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(Value::Bytes(value.to_msgpack().into()))
+    }
+
+    fn type_def(&self, _state: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::value;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        encode_msgpack => EncodeMsgpack;
+
+        object {
+            args: func_args![value: value!({ a: 1, b: [true, null] })],
+            want: Ok(value!(b"\x82\xa1a\x01\xa1b\x92\xc3\xc0".as_slice())),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        binary {
+            args: func_args![value: value!(b"\xff\x00".as_slice())],
+            want: Ok(value!(b"\xc4\x02\xff\x00".as_slice())),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        timestamp {
+            args: func_args![value: value!(Utc.timestamp_opt(1, 1).unwrap())],
+            want: Ok(value!(b"\xd7\xff\x00\x00\x00\x04\x00\x00\x00\x01".as_slice())),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
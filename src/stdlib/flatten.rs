@@ -1,8 +1,11 @@
-use std::collections::btree_map;
-
 use crate::compiler::prelude::*;
 use std::sync::LazyLock;
 
+#[cfg(not(feature = "preserve_order"))]
+type ObjectIter<'a> = std::collections::btree_map::Iter<'a, KeyString, Value>;
+#[cfg(feature = "preserve_order")]
+type ObjectIter<'a> = indexmap::map::Iter<'a, KeyString, Value>;
+
 static DEFAULT_SEPARATOR: LazyLock<Value> = LazyLock::new(|| Value::Bytes(Bytes::from(".")));
 
 static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
@@ -140,14 +143,14 @@ impl FunctionExpression for FlattenFn {
 
 /// An iterator to walk over maps allowing us to flatten nested maps to a single level.
 struct MapFlatten<'a> {
-    values: btree_map::Iter<'a, KeyString, Value>,
+    values: ObjectIter<'a>,
     separator: &'a str,
     inner: Option<Box<MapFlatten<'a>>>,
     parent: Option<KeyString>,
 }
 
 impl<'a> MapFlatten<'a> {
-    fn new(values: btree_map::Iter<'a, KeyString, Value>, separator: &'a str) -> Self {
+    fn new(values: ObjectIter<'a>, separator: &'a str) -> Self {
         Self {
             values,
             separator,
@@ -156,11 +159,7 @@ impl<'a> MapFlatten<'a> {
         }
     }
 
-    fn new_from_parent(
-        parent: KeyString,
-        values: btree_map::Iter<'a, KeyString, Value>,
-        separator: &'a str,
-    ) -> Self {
+    fn new_from_parent(parent: KeyString, values: ObjectIter<'a>, separator: &'a str) -> Self {
         Self {
             values,
             separator,
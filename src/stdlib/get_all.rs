@@ -0,0 +1,196 @@
+use crate::compiler::prelude::*;
+use crate::path::{OwnedValueQuery, parse_value_query};
+
+fn get_all(value: &Value, query: &OwnedValueQuery) -> Value {
+    value
+        .get_all(query)
+        .into_iter()
+        .map(|(_, value)| value.clone())
+        .collect()
+}
+
+/// Parses the literal `query` argument of `get_all` and `remove_all`.
+pub(crate) fn required_query(
+    arguments: &ArgumentList,
+    state: &state::TypeState,
+) -> Result<OwnedValueQuery, Box<dyn DiagnosticMessage>> {
+    let literal = arguments.required_literal("query", state)?;
+    let query = literal
+        .try_bytes_utf8_lossy()
+        .expect("query not bytes")
+        .into_owned();
+
+    parse_value_query(&query).map_err(|_| {
+        Box::new(function::Error::InvalidArgument {
+            keyword: "query",
+            value: literal,
+            error: "invalid query",
+        }) as Box<dyn DiagnosticMessage>
+    })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetAll;
+
+impl Function for GetAll {
+    fn identifier(&self) -> &'static str {
+        "get_all"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Returns all values matched by a query, in document order.
+
+            A query is a path that can also contain wildcards and recursive descent. `.*` and
+            `[*]` match every field of an object or every element of an array, and `..` matches
+            the rest of the query at any depth. For example, `.users[*].id` matches the `id` of
+            every user, and `..password` matches every `password` field, wherever it is nested.
+
+            Queries are passed as strings, since wildcards and recursive descent aren't part of
+            the path syntax of VRL: `get_all(., \".users[*].id\")` is valid, but `.users[*].id`
+            on its own isn't.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Path.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ARRAY
+    }
+
+    fn return_rules(&self) -> &'static [&'static str] {
+        &[
+            "An empty array is returned if nothing matches.",
+            "A value is returned before the values nested in it.",
+        ]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[
+            Parameter::required(
+                "value",
+                kind::OBJECT | kind::ARRAY,
+                "The object or array to query.",
+            ),
+            Parameter::required(
+                "query",
+                kind::BYTES,
+                "The query to match, such as `.users[*].id` or `..password`. Must be a string literal.",
+            ),
+        ];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Wildcard",
+                source: r#"get_all({ "users": [{ "id": 1 }, { "id": 2 }] }, ".users[*].id")"#,
+                result: Ok("[1, 2]"),
+            },
+            example! {
+                title: "Recursive descent",
+                source: r#"get_all({ "password": "a", "auth": { "password": "b" } }, "..password")"#,
+                result: Ok(r#"["a", "b"]"#),
+            },
+            example! {
+                title: "All fields",
+                source: r#"get_all({ "a": 1, "b": { "c": 2 } }, ".*")"#,
+                result: Ok(r#"[1, { "c": 2 }]"#),
+            },
+            example! {
+                title: "No match",
+                source: r#"get_all({ "a": 1 }, ".b[*]")"#,
+                result: Ok("[]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let query = required_query(&arguments, state)?;
+
+        Ok(GetAllFn { value, query }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetAllFn {
+    value: Box<dyn Expression>,
+    query: OwnedValueQuery,
+}
+
+impl FunctionExpression for GetAllFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(get_all(&value, &self.query))
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let kind = self.value.type_def(state).kind().get_all(&self.query);
+
+        if kind.is_never() {
+            TypeDef::array(Collection::empty())
+        } else {
+            TypeDef::array(Collection::from_unknown(kind))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use std::collections::BTreeMap;
+
+    test_function![
+        get_all => GetAll;
+
+        wildcard {
+            args: func_args![
+                value: value!({ "users": [{ "id": 1 }, { "id": "2" }] }),
+                query: ".users[*].id"
+            ],
+            want: Ok(value!([1, "2"])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer().or_bytes())),
+        }
+
+        descendants {
+            args: func_args![
+                value: value!({ "a": [{ "password": "x" }, { "b": { "password": true } }] }),
+                query: "..password"
+            ],
+            want: Ok(value!(["x", true])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::bytes().or_boolean())),
+        }
+
+        no_match {
+            args: func_args![value: value!({ "a": 1 }), query: "..b"],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::empty()),
+        }
+
+        index {
+            args: func_args![value: value!([[1, 2], [3]]), query: ".*[-1]"],
+            want: Ok(value!([2, 3])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())),
+        }
+
+        root {
+            args: func_args![value: value!({ "a": 1 }), query: "."],
+            want: Ok(value!([{ "a": 1 }])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::object(BTreeMap::from([(
+                "a".into(),
+                Kind::integer(),
+            )])))),
+        }
+    ];
+}
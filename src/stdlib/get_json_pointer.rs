@@ -0,0 +1,178 @@
+use crate::compiler::prelude::*;
+use crate::path::{OwnedSegment, OwnedValuePath, is_json_pointer_index};
+
+fn get_json_pointer(value: &Value, path: &OwnedValuePath) -> Value {
+    value.get_json_pointer(path).cloned().unwrap_or(Value::Null)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetJsonPointer;
+
+impl Function for GetJsonPointer {
+    fn identifier(&self) -> &'static str {
+        "get_json_pointer"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Returns the value at a JSON Pointer (RFC 6901), such as `/foo/0/bar`.
+
+            A JSON Pointer doesn't distinguish between object fields and array indices, so a
+            token like `0` refers to a field when resolved against an object, and to an index
+            when resolved against an array.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Path.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &["`pointer` is not a valid JSON Pointer."]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ANY
+    }
+
+    fn return_rules(&self) -> &'static [&'static str] {
+        &[
+            "Returns `null` if the pointer doesn't refer to an existing value.",
+            "If `pointer` is a string literal, it is validated at compile time and the function is infallible.",
+        ]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[
+            Parameter::required("value", kind::ANY, "The value to query."),
+            Parameter::required("pointer", kind::BYTES, "The JSON Pointer."),
+        ];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Nested field",
+                source: r#"get_json_pointer({ "foo": [{ "bar": true }] }, "/foo/0/bar")"#,
+                result: Ok("true"),
+            },
+            example! {
+                title: "Escaped characters",
+                source: r#"get_json_pointer({ "a/b": { "m~n": 1 } }, "/a~1b/m~0n")"#,
+                result: Ok("1"),
+            },
+            example! {
+                title: "Missing value",
+                source: r#"get_json_pointer({ "foo": [] }, "/foo/0")"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let pointer = arguments.required("pointer");
+
+        let pointer = match pointer.resolve_constant(state) {
+            Some(literal) => {
+                let path = OwnedValuePath::from_json_pointer(
+                    &literal.try_bytes_utf8_lossy().expect("pointer not bytes"),
+                )
+                .map_err(|_| function::Error::InvalidArgument {
+                    keyword: "pointer",
+                    value: literal,
+                    error: "invalid JSON Pointer",
+                })?;
+                Pointer::Static(path)
+            }
+            None => Pointer::Dynamic(pointer),
+        };
+
+        Ok(GetJsonPointerFn { value, pointer }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Pointer {
+    Static(OwnedValuePath),
+    Dynamic(Box<dyn Expression>),
+}
+
+#[derive(Debug, Clone)]
+struct GetJsonPointerFn {
+    value: Box<dyn Expression>,
+    pointer: Pointer,
+}
+
+impl FunctionExpression for GetJsonPointerFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        match &self.pointer {
+            Pointer::Static(path) => Ok(get_json_pointer(&value, path)),
+            Pointer::Dynamic(pointer) => {
+                let pointer = pointer.resolve(ctx)?;
+                let path = OwnedValuePath::from_json_pointer(&pointer.try_bytes_utf8_lossy()?)
+                    .map_err(|err| err.to_string())?;
+                Ok(get_json_pointer(&value, &path))
+            }
+        }
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        match &self.pointer {
+            // Numeric tokens can refer to either a field or an index, so the type is only known if
+            // there are none.
+            Pointer::Static(path)
+                if path.segments.iter().all(|segment| {
+                    matches!(segment, OwnedSegment::Field(field) if !is_json_pointer_index(field))
+                }) =>
+            {
+                self.value.type_def(state).kind().get(path).into()
+            }
+            Pointer::Static(_) => TypeDef::any(),
+            Pointer::Dynamic(_) => TypeDef::any().fallible(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use std::collections::BTreeMap;
+
+    test_function![
+        get_json_pointer => GetJsonPointer;
+
+        field {
+            args: func_args![value: value!({ "a": { "b": "c" } }), pointer: "/a/b"],
+            want: Ok(value!("c")),
+            tdef: TypeDef::bytes(),
+        }
+
+        index {
+            args: func_args![value: value!({ "a": [1, 2] }), pointer: "/a/1"],
+            want: Ok(value!(2)),
+            tdef: TypeDef::any(),
+        }
+
+        numeric_field {
+            args: func_args![value: value!({ "0": 1 }), pointer: "/0"],
+            want: Ok(value!(1)),
+            tdef: TypeDef::any(),
+        }
+
+        root {
+            args: func_args![value: value!([1]), pointer: ""],
+            want: Ok(value!([1])),
+            tdef: TypeDef::array(BTreeMap::from([(0.into(), Kind::integer())])),
+        }
+    ];
+}
@@ -0,0 +1,120 @@
+use crate::compiler::prelude::*;
+
+fn get_secret(ctx: &mut Context, key: &Value) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+
+    Ok(ctx
+        .target()
+        .get_secret(key.as_ref())
+        .map_or(Value::Null, Into::into))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetSecret;
+
+impl Function for GetSecret {
+    fn identifier(&self) -> &'static str {
+        "get_secret"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Returns the value of the given secret from an event, or `null` if
+            the secret does not exist.
+
+            Secrets are stored alongside the event, not inside it, so they are
+            never part of the event when it is printed or serialized.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Event.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::BYTES | kind::NULL
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "key",
+            kind::BYTES,
+            "The name of the secret.",
+        )];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[example! {
+            title: "Get the Datadog API key from the event metadata",
+            source: r#"get_secret("datadog_api_key")"#,
+            result: Ok("secret value"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+
+        Ok(GetSecretFn { key }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GetSecretFn {
+    key: Box<dyn Expression>,
+}
+
+impl FunctionExpression for GetSecretFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        get_secret(ctx, &key)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().add_null().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{SecretTarget, TargetValue};
+    use crate::value;
+    use crate::value::Secrets;
+
+    fn get_secret(target: &mut TargetValue, key: &'static str) -> Value {
+        let mut runtime_state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(target, &mut runtime_state, &tz);
+
+        GetSecretFn { key: expr!(key) }.resolve(&mut ctx).unwrap()
+    }
+
+    #[test]
+    fn get_existing_secret() {
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        target.insert_secret("my_secret", "secret value");
+
+        assert_eq!(get_secret(&mut target, "my_secret"), value!("secret value"));
+    }
+
+    #[test]
+    fn get_missing_secret() {
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+
+        assert_eq!(get_secret(&mut target, "my_secret"), Value::Null);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::compiler::prelude::*;
+
+fn group_by<T>(value: Value, ctx: &mut Context, runner: &closure::Runner<T>) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    let array = value.try_array()?;
+    let mut groups = ObjectMap::new();
+
+    for (index, value) in array.into_iter().enumerate() {
+        let key = runner
+            .run_index_value(ctx, index, &value)?
+            .try_bytes_utf8_lossy()?
+            .into();
+
+        match groups
+            .entry(key)
+            .or_insert_with(|| Value::Array(ValueArray::new()))
+        {
+            Value::Array(group) => group.push(value),
+            _ => unreachable!("groups are always arrays"),
+        }
+    }
+
+    Ok(groups.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GroupBy;
+
+impl Function for GroupBy {
+    fn identifier(&self) -> &'static str {
+        "group_by"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Groups the elements of an array by the string returned from the
+            closure for each element.
+
+            Returns an object where each key is a value returned by the
+            closure, and each value is an array of the elements for which the
+            closure returned that key, in their original order.
+
+            The same scoping rules apply to closure blocks as they do for
+            regular blocks. This means that any variable defined in parent scopes
+            is accessible, and mutations to those variables are preserved,
+            but any new variables instantiated in the closure block are
+            unavailable outside of the block.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Enumerate.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::OBJECT
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "value",
+            kind::ARRAY,
+            "The array to group.",
+        )];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Group objects by field",
+                source: indoc! {r#"
+                    logs = [
+                        { "level": "info", "message": "started" },
+                        { "level": "error", "message": "failed" },
+                        { "level": "info", "message": "stopped" }
+                    ]
+                    group_by(logs) -> |_index, value| { value.level }
+                "#},
+                result: Ok(indoc! {r#"{
+                    "error": [{ "level": "error", "message": "failed" }],
+                    "info": [
+                        { "level": "info", "message": "started" },
+                        { "level": "info", "message": "stopped" }
+                    ]
+                }"#}),
+            },
+            example! {
+                title: "Group numbers by parity",
+                source: r#"group_by([1, 2, 3, 4, 5]) -> |_index, value| { if mod(value, 2) == 0 { "even" } else { "odd" } }"#,
+                result: Ok(r#"{ "even": [2, 4], "odd": [1, 3, 5] }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(GroupByFn { value, closure }.as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::bytes()),
+                example: example! {
+                    title: "group by first letter",
+                    source: r#"group_by(["apple", "avocado", "banana"]) -> |_index, value| { truncate(value, 1) }"#,
+                    result: Ok(r#"{ "a": ["apple", "avocado"], "b": ["banana"] }"#),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GroupByFn {
+    value: Box<dyn Expression>,
+    closure: Closure,
+}
+
+impl FunctionExpression for GroupByFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let Closure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        group_by(value, ctx, &runner)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let element = self
+            .value
+            .type_def(state)
+            .as_array()
+            .map_or_else(Kind::any, Collection::reduced_kind);
+
+        TypeDef::object(Collection::from_unknown(Kind::array(
+            Collection::from_unknown(element),
+        )))
+    }
+}
@@ -268,8 +268,7 @@ use non_wasm::*;
 use std::sync::LazyLock;
 
 static DEFAULT_METHOD: LazyLock<Value> = LazyLock::new(|| Value::Bytes(Bytes::from("get")));
-static DEFAULT_HEADERS: LazyLock<Value> =
-    LazyLock::new(|| Value::Object(std::collections::BTreeMap::new()));
+static DEFAULT_HEADERS: LazyLock<Value> = LazyLock::new(|| Value::Object(ObjectMap::new()));
 static DEFAULT_BODY: LazyLock<Value> = LazyLock::new(|| Value::Bytes(Bytes::from("")));
 
 static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
@@ -325,6 +324,10 @@ impl Function for HttpRequest {
         Category::System.as_ref()
     }
 
+    fn deterministic(&self) -> bool {
+        false
+    }
+
     fn return_kind(&self) -> u16 {
         kind::BYTES
     }
@@ -1,8 +1,9 @@
 use crate::compiler::prelude::*;
+use crate::value::IntoCollection;
 
 fn keys(value: Value) -> Resolved {
     let object = value.try_object()?;
-    let keys = object.into_keys().map(Value::from);
+    let keys = object.into_collection().into_keys().map(Value::from);
     Ok(Value::Array(keys.collect()))
 }
 
@@ -112,7 +112,7 @@ mod tests {
 
         yes {
             args: func_args![value: "foobar",
-                             patterns: Value::Array(vec![
+                             patterns: Value::from(vec![
                                  Value::Regex(Regex::new("foo").unwrap().into()),
                                  Value::Regex(Regex::new("bar").unwrap().into()),
                                  Value::Regex(Regex::new("baz").unwrap().into()),
@@ -123,7 +123,7 @@ mod tests {
 
         no {
             args: func_args![value: "foo 2 bar",
-                             patterns: Value::Array(vec![
+                             patterns: Value::from(vec![
                                  Value::Regex(Regex::new("baz|quux").unwrap().into()),
                                  Value::Regex(Regex::new("foobar").unwrap().into()),
                              ])],
@@ -459,6 +459,8 @@ fn string_value(value: &Value) -> Cow<'_, str> {
 
 #[cfg(test)]
 mod test {
+    use crate::compiler::{CompileConfig, function::FunctionCompileContext, state::RuntimeState};
+    use crate::datadog_filter::cases::match_cases;
     use crate::value;
 
     use super::*;
@@ -466,1558 +468,35 @@ mod test {
     test_function![
         match_datadog_query => MatchDatadogQuery;
 
-        message_exists {
-            args: func_args![value: value!({"message": "test message"}), query: "_exists_:message"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_message_exists {
-            args: func_args![value: value!({"message": "test message"}), query: "NOT _exists_:message"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_message_exists {
-            args: func_args![value: value!({"message": "test message"}), query: "-_exists_:message"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        facet_exists {
-            args: func_args![value: value!({"a": "value" }), query: "_exists_:@a"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_facet_exists {
-            args: func_args![value: value!({"a": "value" }), query: "NOT _exists_:@a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_facet_exists {
-            args: func_args![value: value!({"a": "value" }), query: "-_exists_:@a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        hyphen_facet_exists {
-            args: func_args![value: value!({"a-b": "value"}), query: "_exists_:@a-b"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        tag_bare {
-            args: func_args![value: value!({"tags": ["a","b","c"]}), query: "tags:a"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        tag_bare_no_match {
-            args: func_args![value: value!({"tags": ["a","b","c"]}), query: "tags:d"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_tag_bare {
-            args: func_args![value: value!({"tags": ["a","b","c"]}), query: "NOT tags:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_tag_bare {
-            args: func_args![value: value!({"tags": ["a","b","c"]}), query: "-tags:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        tag_exists_bare {
-            args: func_args![value: value!({"tags": ["a","b","c"]}), query: "_exists_:a"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_tag_exists_bare {
-            args: func_args![value: value!({"tags": ["a","b","c"]}), query: "NOT _exists_:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_tag_exists_bare {
-            args: func_args![value: value!({"tags": ["a","b","c"]}), query: "-_exists_:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        tag_exists {
-            args: func_args![value: value!({"tags": ["a:1","b:2","c:3"]}), query: "_exists_:a"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_tag_exists {
-            args: func_args![value: value!({"tags": ["a:1","b:2","c:3"]}), query: "NOT _exists_:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_tag_exists {
-            args: func_args![value: value!({"tags": ["a:1","b:2","c:3"]}), query: "-_exists_:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        message_missing {
-            args: func_args![value: value!({}), query: "_missing_:message"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_message_missing {
-            args: func_args![value: value!({}), query: "NOT _missing_:message"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_message_missing {
-            args: func_args![value: value!({}), query: "-_missing_:message"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        facet_missing {
-            args: func_args![value: value!({"b": "value" }), query: "_missing_:@a"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_facet_missing {
-            args: func_args![value: value!({"b": "value" }), query: "NOT _missing_:@a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_facet_missing {
-            args: func_args![value: value!({"b": "value" }), query: "-_missing_:@a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        hyphen_facet_missing {
-            args: func_args![value: value!({"a-b": "value"}), query: "_missing_:@c-d"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        tag_bare_missing {
-            args: func_args![value: value!({"tags": ["b","c"]}), query: "_missing_:a"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_tag_bare_missing {
-            args: func_args![value: value!({"tags": ["b","c"]}), query: "NOT _missing_:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_tag_bare_missing {
-            args: func_args![value: value!({"tags": ["b","c"]}), query: "-_missing_:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        tag_missing {
-            args: func_args![value: value!({"tags": ["b:1","c:2"]}), query: "_missing_:a"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_tag_missing {
-            args: func_args![value: value!({"tags": ["b:1","c:2"]}), query: "NOT _missing_:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_tag_missing {
-            args: func_args![value: value!({"tags": ["b:1","c:2"]}), query: "-_missing_:a"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        equals_message {
-            args: func_args![value: value!({"message": "match by word boundary"}), query: "match"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_equals_message {
-            args: func_args![value: value!({"message": "match by word boundary"}), query: "NOT match"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_equals_message {
-            args: func_args![value: value!({"message": "match by word boundary"}), query: "-match"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        equals_message_no_match {
-            args: func_args![value: value!({"message": "another value"}), query: "match"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_equals_message_no_match {
-            args: func_args![value: value!({"message": "another value"}), query: "NOT match"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_equals_message_no_match {
-            args: func_args![value: value!({"message": "another value"}), query: "-match"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        equals_tag {
-            args: func_args![value: value!({"tags": ["x:1", "y:2", "z:3"]}), query: "y:2"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_equals_tag {
-            args: func_args![value: value!({"tags": ["x:1", "y:2", "z:3"]}), query: "NOT y:2"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_equals_tag {
-            args: func_args![value: value!({"tags": ["x:1", "y:2", "z:3"]}), query: "-y:2"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        equals_tag_no_match {
-            args: func_args![value: value!({"tags": ["x:1", "y:2", "z:3"]}), query: "y:3"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_equals_tag_no_match {
-            args: func_args![value: value!({"tags": ["x:1", "y:2", "z:3"]}), query: "NOT y:3"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_equals_tag_no_match {
-            args: func_args![value: value!({"tags": ["x:1", "y:2", "z:3"]}), query: "-y:3"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        equals_facet {
-            args: func_args![value: value!({"z": 1}), query: "@z:1"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_equals_facet {
-            args: func_args![value: value!({"z": 1}), query: "NOT @z:1"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_equals_facet {
-            args: func_args![value: value!({"z": 1}), query: "-@z:1"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_prefix_message {
-            args: func_args![value: value!({"message": "vector"}), query: "*tor"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_wildcard_prefix_message {
-            args: func_args![value: value!({"message": "vector"}), query: "NOT *tor"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_prefix_message {
-            args: func_args![value: value!({"message": "vector"}), query: "-*tor"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_prefix_message_no_match {
-            args: func_args![value: value!({"message": "torvec"}), query: "*tor"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_wildcard_prefix_message_no_match {
-            args: func_args![value: value!({"message": "torvec"}), query: "NOT *tor"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_prefix_message_no_match {
-            args: func_args![value: value!({"message": "torvec"}), query: "-*tor"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        wildcard_prefix_tag {
-            args: func_args![value: value!({"tags": ["a:vector"]}), query: "a:*tor"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_wildcard_prefix_tag {
-            args: func_args![value: value!({"tags": ["a:vector"]}), query: "NOT a:*tor"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_prefix_tag {
-            args: func_args![value: value!({"tags": ["a:vector"]}), query: "-a:*tor"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_prefix_tag_no_match {
-            args: func_args![value: value!({"tags": ["b:vector"]}), query: "a:*tor"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_wildcard_prefix_tag_no_match {
-            args: func_args![value: value!({"tags": ["b:vector"]}), query: "NOT a:*tor"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_prefix_tag_no_match {
-            args: func_args![value: value!({"tags": ["b:vector"]}), query: "-a:*tor"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        wildcard_prefix_facet {
-            args: func_args![value: value!({"a": "vector"}), query: "@a:*tor"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_wildcard_prefix_facet {
-            args: func_args![value: value!({"a": "vector"}), query: "NOT @a:*tor"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_prefix_facet {
-            args: func_args![value: value!({"a": "vector"}), query: "-@a:*tor"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_prefix_facet_no_match {
-            args: func_args![value: value!({"b": "vector"}), query: "@a:*tor"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_wildcard_prefix_facet_no_match {
-            args: func_args![value: value!({"b": "vector"}), query: "NOT @a:*tor"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_prefix_facet_no_match {
-            args: func_args![value: value!({"b": "vector"}), query: "-@a:*tor"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        wildcard_suffix_message {
-            args: func_args![value: value!({"message": "vector"}), query: "vec*"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_wildcard_suffix_message {
-            args: func_args![value: value!({"message": "vector"}), query: "NOT vec*"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_suffix_message {
-            args: func_args![value: value!({"message": "vector"}), query: "-vec*"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_suffix_message_no_match {
-            args: func_args![value: value!({"message": "torvec"}), query: "vec*"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_wildcard_suffix_message_no_match {
-            args: func_args![value: value!({"message": "torvec"}), query: "NOT vec*"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_suffix_message_no_match {
-            args: func_args![value: value!({"message": "torvec"}), query: "-vec*"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        wildcard_suffix_tag {
-            args: func_args![value: value!({"tags": ["a:vector"]}), query: "a:vec*"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_wildcard_suffix_tag {
-            args: func_args![value: value!({"tags": ["a:vector"]}), query: "NOT a:vec*"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_suffix_tag {
-            args: func_args![value: value!({"tags": ["a:vector"]}), query: "-a:vec*"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_suffix_tag_no_match {
-            args: func_args![value: value!({"tags": ["b:vector"]}), query: "a:vec*"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_wildcard_suffix_tag_no_match {
-            args: func_args![value: value!({"tags": ["b:vector"]}), query: "NOT a:vec*"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_suffix_tag_no_match {
-            args: func_args![value: value!({"tags": ["b:vector"]}), query: "-a:vec*"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        wildcard_suffix_facet {
-            args: func_args![value: value!({"a": "vector"}), query: "@a:vec*"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_wildcard_suffix_facet {
-            args: func_args![value: value!({"a": "vector"}), query: "NOT @a:vec*"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_suffix_facet {
-            args: func_args![value: value!({"a": "vector"}), query: "-@a:vec*"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_suffix_facet_no_match {
-            args: func_args![value: value!({"b": "vector"}), query: "@a:vec*"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_wildcard_suffix_facet_no_match {
-            args: func_args![value: value!({"b": "vector"}), query: "NOT @a:vec*"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_suffix_facet_no_match {
-            args: func_args![value: value!({"b": "vector"}), query: "-@a:vec*"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        wildcard_multiple_message {
-            args: func_args![value: value!({"message": "vector"}), query: "v*c*r"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_wildcard_multiple_message {
-            args: func_args![value: value!({"message": "vector"}), query: "NOT v*c*r"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_multiple_message {
-            args: func_args![value: value!({"message": "vector"}), query: "-v*c*r"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_multiple_message_no_match {
-            args: func_args![value: value!({"message": "torvec"}), query: "v*c*r"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_wildcard_multiple_message_no_match {
-            args: func_args![value: value!({"message": "torvec"}), query: "NOT v*c*r"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_multiple_message_no_match {
-            args: func_args![value: value!({"message": "torvec"}), query: "-v*c*r"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        wildcard_multiple_tag {
-            args: func_args![value: value!({"tags": ["a:vector"]}), query: "a:v*c*r"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_wildcard_multiple_tag {
-            args: func_args![value: value!({"tags": ["a:vector"]}), query: "NOT a:v*c*r"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_multiple_tag {
-            args: func_args![value: value!({"tags": ["a:vector"]}), query: "-a:v*c*r"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_multiple_tag_no_match {
-            args: func_args![value: value!({"tags": ["b:vector"]}), query: "a:v*c*r"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_wildcard_multiple_tag_no_match {
-            args: func_args![value: value!({"tags": ["b:vector"]}), query: "NOT a:v*c*r"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_multiple_tag_no_match {
-            args: func_args![value: value!({"tags": ["b:vector"]}), query: "-a:v*c*r"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        wildcard_multiple_facet {
-            args: func_args![value: value!({"a": "vector"}), query: "@a:v*c*r"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_wildcard_multiple_facet {
-            args: func_args![value: value!({"a": "vector"}), query: "NOT @a:v*c*r"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_multiple_facet {
-            args: func_args![value: value!({"a": "vector"}), query: "-@a:v*c*r"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        wildcard_multiple_facet_no_match {
-            args: func_args![value: value!({"b": "vector"}), query: "@a:v*c*r"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_wildcard_multiple_facet_no_match {
-            args: func_args![value: value!({"b": "vector"}), query: "NOT @a:v*c*r"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_wildcard_multiple_facet_no_match {
-            args: func_args![value: value!({"b": "vector"}), query: "-@a:v*c*r"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_message_unbounded {
-            args: func_args![value: value!({"message": "1"}), query: "[* TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_message_unbounded {
-            args: func_args![value: value!({"message": "1"}), query: "NOT [* TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_message_unbounded {
-            args: func_args![value: value!({"message": "1"}), query: "-[* TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_message_lower_bound {
-            args: func_args![value: value!({"message": "400"}), query: "[4 TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_message_lower_bound {
-            args: func_args![value: value!({"message": "400"}), query: "NOT [4 TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_message_lower_bound {
-            args: func_args![value: value!({"message": "400"}), query: "-[4 TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_message_lower_bound_no_match {
-            args: func_args![value: value!({"message": "400"}), query: "[50 TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_message_lower_bound_no_match {
-            args: func_args![value: value!({"message": "400"}), query: "NOT [50 TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_message_lower_bound_no_match {
-            args: func_args![value: value!({"message": "400"}), query: "-[50 TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_message_lower_bound_string {
-            args: func_args![value: value!({"message": "400"}), query: r#"["4" TO *]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_message_lower_bound_string {
-            args: func_args![value: value!({"message": "400"}), query: r#"NOT ["4" TO *]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_message_lower_bound_string {
-            args: func_args![value: value!({"message": "400"}), query: r#"-["4" TO *]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_message_lower_bound_string_no_match {
-            args: func_args![value: value!({"message": "400"}), query: r#"["50" TO *]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_message_lower_bound_string_no_match {
-            args: func_args![value: value!({"message": "400"}), query: r#"NOT ["50" TO *]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_message_lower_bound_string_no_match {
-            args: func_args![value: value!({"message": "400"}), query: r#"-["50" TO *]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_message_upper_bound {
-            args: func_args![value: value!({"message": "300"}), query: "[* TO 4]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_message_upper_bound {
-            args: func_args![value: value!({"message": "300"}), query: "NOT [* TO 4]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_message_upper_bound {
-            args: func_args![value: value!({"message": "300"}), query: "-[* TO 4]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_message_upper_bound_no_match {
-            args: func_args![value: value!({"message": "50"}), query: "[* TO 400]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_message_upper_bound_no_match {
-            args: func_args![value: value!({"message": "50"}), query: "NOT [* TO 400]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_message_upper_bound_no_match {
-            args: func_args![value: value!({"message": "50"}), query: "-[* TO 400]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_message_upper_bound_string {
-            args: func_args![value: value!({"message": "300"}), query: r#"[* TO "4"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_message_upper_bound_string {
-            args: func_args![value: value!({"message": "300"}), query: r#"NOT [* TO "4"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_message_upper_bound_string {
-            args: func_args![value: value!({"message": "300"}), query: r#"-[* TO "4"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_message_upper_bound_string_no_match {
-            args: func_args![value: value!({"message": "50"}), query: r#"[* TO "400"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_message_upper_bound_string_no_match {
-            args: func_args![value: value!({"message": "50"}), query: r#"NOT [* TO "400"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_message_upper_bound_string_no_match {
-            args: func_args![value: value!({"message": "50"}), query: r#"NOT [* TO "400"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_message_between {
-            args: func_args![value: value!({"message": 500}), query: "[1 TO 6]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_message_between {
-            args: func_args![value: value!({"message": 500}), query: "NOT [1 TO 6]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_message_between {
-            args: func_args![value: value!({"message": 500}), query: "-[1 TO 6]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_message_between_no_match {
-            args: func_args![value: value!({"message": 70}), query: "[1 TO 6]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_message_between_no_match {
-            args: func_args![value: value!({"message": 70}), query: "NOT [1 TO 6]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_message_between_no_match {
-            args: func_args![value: value!({"message": 70}), query: "-[1 TO 6]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_message_between_string {
-            args: func_args![value: value!({"message": "500"}), query: r#"["1" TO "6"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_message_between_string {
-            args: func_args![value: value!({"message": "500"}), query: r#"NOT ["1" TO "6"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_message_between_string {
-            args: func_args![value: value!({"message": "500"}), query: r#"-["1" TO "6"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_message_between_no_match_string {
-            args: func_args![value: value!({"message": "70"}), query: r#"["1" TO "6"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_message_between_no_match_string {
-            args: func_args![value: value!({"message": "70"}), query: r#"NOT ["1" TO "6"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_message_between_no_match_string {
-            args: func_args![value: value!({"message": "70"}), query: r#"-["1" TO "6"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_tag_key {
-            args: func_args![value: value!({"tags": ["a"]}), query: "a:[* TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_tag_key_no_match {
-            args: func_args![value: value!({"tags": ["b"]}), query: "a:[* TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_tag_unbounded {
-            args: func_args![value: value!({"tags": ["a:1"]}), query: "a:[* TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_tag_unbounded {
-            args: func_args![value: value!({"tags": ["a:1"]}), query: "NOT a:[* TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_unbounded {
-            args: func_args![value: value!({"tags": ["a:1"]}), query: "-a:[* TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_tag_lower_bound {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: "a:[4 TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_tag_lower_bound {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: "NOT a:[4 TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_lower_bound {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: "-a:[4 TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_tag_lower_bound_no_match {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: "a:[50 TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_tag_lower_bound_no_match {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: "NOT a:[50 TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_lower_bound_no_match {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: "-a:[50 TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_tag_lower_bound_string {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: r#"a:["4" TO *]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_tag_lower_bound_string {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: r#"NOT a:["4" TO *]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_lower_bound_string {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: r#"-a:["4" TO *]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_tag_lower_bound_string_no_match {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: r#"a:["50" TO *]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_tag_lower_bound_string_no_match {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: r#"NOT a:["50" TO *]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_lower_bound_string_no_match {
-            args: func_args![value: value!({"tags": ["a:400"]}), query: r#"-a:["50" TO *]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_tag_upper_bound {
-            args: func_args![value: value!({"tags": ["a:300"]}), query: "a:[* TO 4]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_tag_upper_bound {
-            args: func_args![value: value!({"tags": ["a:300"]}), query: "NOT a:[* TO 4]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_upper_bound {
-            args: func_args![value: value!({"tags": ["a:300"]}), query: "-a:[* TO 4]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_tag_upper_bound_no_match {
-            args: func_args![value: value!({"tags": ["a:50"]}), query: "a:[* TO 400]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_tag_upper_bound_no_match {
-            args: func_args![value: value!({"tags": ["a:50"]}), query: "NOT a:[* TO 400]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_upper_bound_no_match {
-            args: func_args![value: value!({"tags": ["a:50"]}), query: "-a:[* TO 400]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_tag_upper_bound_string {
-            args: func_args![value: value!({"tags": ["a:300"]}), query: r#"a:[* TO "4"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_tag_upper_bound_string {
-            args: func_args![value: value!({"tags": ["a:300"]}), query: r#"NOT a:[* TO "4"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_upper_bound_string {
-            args: func_args![value: value!({"tags": ["a:300"]}), query: r#"-a:[* TO "4"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_tag_upper_bound_string_no_match {
-            args: func_args![value: value!({"tags": ["a:50"]}), query: r#"a:[* TO "400"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_tag_upper_bound_string_no_match {
-            args: func_args![value: value!({"tags": ["a:50"]}), query: r#"NOT a:[* TO "400"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_upper_bound_string_no_match {
-            args: func_args![value: value!({"tags": ["a:50"]}), query: r#"-a:[* TO "400"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_tag_between {
-            args: func_args![value: value!({"tags": ["a:500"]}), query: "a:[1 TO 6]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_tag_between {
-            args: func_args![value: value!({"tags": ["a:500"]}), query: "NOT a:[1 TO 6]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_between {
-            args: func_args![value: value!({"tags": ["a:500"]}), query: "-a:[1 TO 6]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_tag_between_no_match {
-            args: func_args![value: value!({"tags": ["a:70"]}), query: "a:[1 TO 6]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_tag_between_no_match {
-            args: func_args![value: value!({"tags": ["a:70"]}), query: "NOT a:[1 TO 6]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_between_no_match {
-            args: func_args![value: value!({"tags": ["a:70"]}), query: "-a:[1 TO 6]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_tag_between_string {
-            args: func_args![value: value!({"tags": ["a:500"]}), query: r#"a:["1" TO "6"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_tag_between_string {
-            args: func_args![value: value!({"tags": ["a:500"]}), query: r#"NOT a:["1" TO "6"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_between_string {
-            args: func_args![value: value!({"tags": ["a:500"]}), query: r#"-a:["1" TO "6"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_tag_between_no_match_string {
-            args: func_args![value: value!({"tags": ["a:70"]}), query: r#"a:["1" TO "6"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_tag_between_no_match_string {
-            args: func_args![value: value!({"tags": ["a:70"]}), query: r#"NOT a:["1" TO "6"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_tag_between_no_match_string {
-            args: func_args![value: value!({"tags": ["a:70"]}), query: r#"-a:["1" TO "6"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_facet_unbounded {
-            args: func_args![value: value!({"a": 1}), query: "@a:[* TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_facet_unbounded {
-            args: func_args![value: value!({"a": 1}), query: "NOT @a:[* TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_unbounded {
-            args: func_args![value: value!({"a": 1}), query: "-@a:[* TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_facet_lower_bound {
-            args: func_args![value: value!({"a": 5}), query: "@a:[4 TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_facet_lower_bound {
-            args: func_args![value: value!({"a": 5}), query: "NOT @a:[4 TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_lower_bound {
-            args: func_args![value: value!({"a": 5}), query: "-@a:[4 TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_facet_lower_bound_no_match {
-            args: func_args![value: value!({"a": 5}), query: "@a:[50 TO *]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_facet_lower_bound_no_match {
-            args: func_args![value: value!({"a": 5}), query: "NOT @a:[50 TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_lower_bound_no_match {
-            args: func_args![value: value!({"a": 5}), query: "-@a:[50 TO *]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_facet_lower_bound_string {
-            args: func_args![value: value!({"a": "5"}), query: r#"@a:["4" TO *]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_facet_lower_bound_string {
-            args: func_args![value: value!({"a": "5"}), query: r#"NOT @a:["4" TO *]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_lower_bound_string {
-            args: func_args![value: value!({"a": "5"}), query: r#"-@a:["4" TO *]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_facet_lower_bound_string_no_match {
-            args: func_args![value: value!({"a": "400"}), query: r#"@a:["50" TO *]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_facet_lower_bound_string_no_match {
-            args: func_args![value: value!({"a": "400"}), query: r#"NOT @a:["50" TO *]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_lower_bound_string_no_match {
-            args: func_args![value: value!({"a": "400"}), query: r#"-@a:["50" TO *]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_facet_upper_bound {
-            args: func_args![value: value!({"a": 1}), query: "@a:[* TO 4]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_facet_upper_bound {
-            args: func_args![value: value!({"a": 1}), query: "NOT @a:[* TO 4]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_upper_bound {
-            args: func_args![value: value!({"a": 1}), query: "-@a:[* TO 4]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_facet_upper_bound_no_match {
-            args: func_args![value: value!({"a": 500}), query: "@a:[* TO 400]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_facet_upper_bound_no_match {
-            args: func_args![value: value!({"a": 500}), query: "NOT @a:[* TO 400]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_upper_bound_no_match {
-            args: func_args![value: value!({"a": 500}), query: "-@a:[* TO 400]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_facet_upper_bound_string {
-            args: func_args![value: value!({"a": "3"}), query: r#"@a:[* TO "4"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_facet_upper_bound_string {
-            args: func_args![value: value!({"a": "3"}), query: r#"NOT @a:[* TO "4"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_upper_bound_string {
-            args: func_args![value: value!({"a": "3"}), query: r#"-@a:[* TO "4"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_facet_upper_bound_string_no_match {
-            args: func_args![value: value!({"a": "5"}), query: r#"@a:[* TO "400"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_facet_upper_bound_string_no_match {
-            args: func_args![value: value!({"a": "5"}), query: r#"NOT @a:[* TO "400"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_upper_bound_string_no_match {
-            args: func_args![value: value!({"a": "5"}), query: r#"-@a:[* TO "400"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_facet_between {
-            args: func_args![value: value!({"a": 5}), query: "@a:[1 TO 6]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_facet_between {
-            args: func_args![value: value!({"a": 5}), query: "NOT @a:[1 TO 6]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_between {
-            args: func_args![value: value!({"a": 5}), query: "-@a:[1 TO 6]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_facet_between_no_match {
-            args: func_args![value: value!({"a": 200}), query: "@a:[1 TO 6]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_facet_between_no_match {
-            args: func_args![value: value!({"a": 200}), query: "NOT @a:[1 TO 6]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_between_no_match {
-            args: func_args![value: value!({"a": 200}), query: "-@a:[1 TO 6]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        range_facet_between_string {
-            args: func_args![value: value!({"a": "500"}), query: r#"@a:["1" TO "6"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_range_facet_between_string {
-            args: func_args![value: value!({"a": "500"}), query: r#"NOT @a:["1" TO "6"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_between_string {
-            args: func_args![value: value!({"a": "500"}), query: r#"-@a:["1" TO "6"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        range_facet_between_no_match_string {
-            args: func_args![value: value!({"a": "7"}), query: r#"@a:["1" TO "60"]"#],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_range_facet_between_no_match_string {
-            args: func_args![value: value!({"a": "7"}), query: r#"NOT @a:["1" TO "60"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_range_facet_between_no_match_string {
-            args: func_args![value: value!({"a": "7"}), query: r#"-@a:["1" TO "60"]"#],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        exclusive_range_message {
-            args: func_args![value: value!({"message": "100"}), query: "{1 TO 2}"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_exclusive_range_message {
-            args: func_args![value: value!({"message": "100"}), query: "NOT {1 TO 2}"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_exclusive_range_message {
-            args: func_args![value: value!({"message": "100"}), query: "-{1 TO 2}"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        exclusive_range_message_no_match {
-            args: func_args![value: value!({"message": "1"}), query: "{1 TO 2}"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_exclusive_range_message_no_match {
-            args: func_args![value: value!({"message": "1"}), query: "NOT {1 TO 2}"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_exclusive_range_message_no_match {
-            args: func_args![value: value!({"message": "1"}), query: "-{1 TO 2}"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        exclusive_range_message_lower {
-            args: func_args![value: value!({"message": "200"}), query: "{1 TO *}"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_exclusive_range_message_lower {
-            args: func_args![value: value!({"message": "200"}), query: "NOT {1 TO *}"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_exclusive_range_message_lower {
-            args: func_args![value: value!({"message": "200"}), query: "-{1 TO *}"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        exclusive_range_message_lower_no_match {
-            args: func_args![value: value!({"message": "1"}), query: "{1 TO *}"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_exclusive_range_message_lower_no_match {
-            args: func_args![value: value!({"message": "1"}), query: "NOT {1 TO *}"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_exclusive_range_message_lower_no_match {
-            args: func_args![value: value!({"message": "1"}), query: "-{1 TO *}"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        exclusive_range_message_upper {
-            args: func_args![value: value!({"message": "200"}), query: "{* TO 3}"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_exclusive_range_message_upper {
-            args: func_args![value: value!({"message": "200"}), query: "NOT {* TO 3}"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_exclusive_range_message_upper {
-            args: func_args![value: value!({"message": "200"}), query: "-{* TO 3}"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        exclusive_range_message_upper_no_match {
-            args: func_args![value: value!({"message": "3"}), query: "{* TO 3}"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        not_exclusive_range_message_upper_no_match {
-            args: func_args![value: value!({"message": "3"}), query: "NOT {* TO 3}"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        negate_exclusive_range_message_upper_no_match {
-            args: func_args![value: value!({"message": "3"}), query: "-{* TO 3}"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        message_and {
-            args: func_args![value: value!({"message": "this contains that"}), query: "this AND that"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        message_and_not {
-            args: func_args![value: value!({"message": "this contains that"}), query: "this AND NOT that"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        message_or {
-            args: func_args![value: value!({"message": "only contains that"}), query: "this OR that"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        message_or_not {
-            args: func_args![value: value!({"message": "only contains that"}), query: "this OR NOT that"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        message_and_or {
-            args: func_args![value: value!({"message": "this contains that"}), query: "this AND (that OR the_other)"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_message_and_or {
-            args: func_args![value: value!({"message": "this contains that"}), query: "this AND NOT (that OR the_other)"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_message_and_or {
-            args: func_args![value: value!({"message": "this contains that"}), query: "this AND -(that OR the_other)"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        message_and_or_2 {
-            args: func_args![value: value!({"message": "this contains the_other"}), query: "this AND (that OR the_other)"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        not_message_and_or_2 {
-            args: func_args![value: value!({"message": "this contains the_other"}), query: "this AND NOT (that OR the_other)"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        negate_message_and_or_2 {
-            args: func_args![value: value!({"message": "this contains the_other"}), query: "this AND -(that OR the_other)"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        message_or_and {
-            args: func_args![value: value!({"message": "just this"}), query: "this OR (that AND the_other)"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        message_or_and_no_match {
-            args: func_args![value: value!({"message": "that and nothing else"}), query: "this OR (that AND the_other)"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        message_or_and_2 {
-            args: func_args![value: value!({"message": "that plus the_other"}), query: "this OR (that AND the_other)"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        message_or_and_2_no_match {
-            args: func_args![value: value!({"message": "nothing plus the_other"}), query: "this OR (that AND the_other)"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        kitchen_sink {
-            args: func_args![value: value!({"host": "this"}), query: "host:this OR ((@b:test* AND c:that) AND d:the_other @e:[1 TO 5])"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        kitchen_sink_2 {
-            args: func_args![value: value!({"tags": ["c:that", "d:the_other"], "b": "testing", "e": 3}), query: "host:this OR ((@b:test* AND c:that) AND d:the_other @e:[1 TO 5])"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        integer_range_float_value_match {
-            args: func_args![value: value!({"level": 7.0}), query: "@level:[7 TO 10]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        integer_range_float_value_no_match {
-            args: func_args![value: value!({"level": 6.9}), query: "@level:[7 TO 10]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        float_range_integer_value_match {
-            args: func_args![value: value!({"level": 7}), query: "@level:[7.0 TO 10.0]"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
-        float_range_integer_value_no_match {
-            args: func_args![value: value!({"level": 6}), query: "@level:[7.0 TO 10.0]"],
-            want: Ok(false),
-            tdef: type_def(),
-        }
-
-        path_parser_hyphen {
-            args: func_args![value: value!({"a-b": "3"}), query: "@a-b:3"],
-            want: Ok(true),
-            tdef: type_def(),
-        }
-
         path_parser_failure {
             args: func_args![value: value!({"a-b": "3"}), query: "@a%:3"],
             want: Err("invalid argument"),
             tdef: type_def(),
         }
+    ];
 
-        quoted_query_key_match {
-            args: func_args![value: value!({"a-b": 1}), query: "@\\\"a-b\\\":1"],
-            want: Ok(true),
-            tdef: type_def(),
+    #[test]
+    fn shared_cases() {
+        let state = TypeState::default();
+        for (name, value, query, want) in match_cases() {
+            let expression = MatchDatadogQuery
+                .compile(
+                    &state,
+                    &mut FunctionCompileContext::new(Span::new(0, 0), CompileConfig::default()),
+                    func_args![value: value, query: query].into(),
+                )
+                .unwrap_or_else(|error| panic!("{name}: {}", error.message()));
+
+            let mut runtime_state = RuntimeState::default();
+            let mut target = value!({});
+            let tz = TimeZone::default();
+            let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+            assert_eq!(
+                expression.resolve(&mut ctx),
+                Ok(want.into()),
+                "{name}: {query}"
+            );
+            assert_eq!(expression.type_def(&state), type_def(), "{name}");
         }
-    ];
+    }
 }
@@ -1,5 +1,4 @@
 use crate::compiler::prelude::*;
-use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
 static DEFAULT_DEEP: LazyLock<Value> = LazyLock::new(|| Value::Boolean(false));
@@ -33,6 +32,14 @@ impl Function for Merge {
         Category::Object.as_ref()
     }
 
+    fn notices(&self) -> &'static [&'static str] {
+        &[indoc! {"
+            Object fields are stored sorted by key. When VRL is built with the `preserve_order`
+            feature, fields of `to` keep their position and fields only present in `from` are
+            appended in the order they appear in `from`.
+        "}]
+    }
+
     fn return_kind(&self) -> u16 {
         kind::OBJECT
     }
@@ -146,7 +153,7 @@ impl FunctionExpression for MergeFn {
     }
 }
 
-/// Merges two `BTreeMaps` of Symbol’s value as variable is void: Values. The
+/// Merges two `ObjectMaps` of Symbol’s value as variable is void: Values. The
 /// second map is merged into the first one.
 ///
 /// If Symbol’s value as variable is void: deep is true, only the top level
@@ -163,10 +170,7 @@ impl FunctionExpression for MergeFn {
 /// merge maps with a depth of 3,500 before encountering issues. So I think that
 /// is likely to be within acceptable limits. If it becomes a problem, we can
 /// unroll this function, but that will come at a cost of extra code complexity.
-fn merge_maps<K>(map1: &mut BTreeMap<K, Value>, map2: &BTreeMap<K, Value>, deep: bool)
-where
-    K: std::cmp::Ord + Clone,
-{
+fn merge_maps(map1: &mut ObjectMap, map2: &ObjectMap, deep: bool) {
     for (key2, value2) in map2 {
         match (deep, map1.get_mut(key2), value2) {
             (true, Some(Value::Object(child1)), Value::Object(child2)) => {
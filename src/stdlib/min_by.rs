@@ -0,0 +1,120 @@
+use crate::compiler::prelude::*;
+use crate::stdlib::closure_utils::{select_by, selected_type_def};
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MinBy;
+
+impl Function for MinBy {
+    fn identifier(&self) -> &'static str {
+        "min_by"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Returns the element of an array for which the closure returns the
+            smallest value, or `null` if the array is empty.
+
+            The values returned by the closure are compared using the same
+            ordering as the `sort` function. If several elements share the
+            smallest value, the first one is returned.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Enumerate.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ANY
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "value",
+            kind::ARRAY,
+            "The array to search.",
+        )];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Youngest user",
+                source: indoc! {r#"
+                    users = [{ "name": "bob", "age": 42 }, { "name": "alice", "age": 27 }]
+                    min_by(users) -> |_index, value| { value.age }
+                "#},
+                result: Ok(r#"{ "name": "alice", "age": 27 }"#),
+            },
+            example! {
+                title: "Empty array",
+                source: "min_by([]) -> |_index, value| { value }",
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let closure = arguments.required_closure()?;
+
+        Ok(MinByFn { value, closure }.as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::any()),
+                example: example! {
+                    title: "shortest string",
+                    source: r#"min_by(["ccc", "a", "bb"]) -> |_index, value| { strlen(value) }"#,
+                    result: Ok(r#""a""#),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MinByFn {
+    value: Box<dyn Expression>,
+    closure: Closure,
+}
+
+impl FunctionExpression for MinByFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let Closure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        select_by(value, Ordering::Less, ctx, &runner)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        selected_type_def(&self.value.type_def(state))
+    }
+}
@@ -3,6 +3,7 @@ pub use wasm_unsupported_function::WasmUnsupportedFunction;
 
 use crate::compiler::Function;
 
+mod closure_utils;
 mod ip_utils;
 mod json_utils;
 mod string_utils;
@@ -39,18 +40,22 @@ cfg_if::cfg_if! {
         mod decrypt;
         mod decrypt_ip;
         mod del;
+        mod diagnose_groks;
+        mod diff;
         mod dirname;
         #[cfg(feature = "enable_network_functions")]
         mod dns_lookup;
         mod downcase;
         mod encode_base16;
         mod encode_base64;
+        mod encode_cbor;
         mod encode_charset;
         mod encode_gzip;
         mod encode_lz4;
         mod encode_json;
         mod encode_key_value;
         mod encode_logfmt;
+        mod encode_msgpack;
         mod encode_percent;
         #[cfg(feature = "enable_system_functions")]
         mod encode_proto;
@@ -73,12 +78,16 @@ cfg_if::cfg_if! {
         mod format_timestamp;
         mod from_unix_timestamp;
         mod get;
+        mod get_all;
         #[cfg(feature = "enable_env_functions")]
         mod get_env_var;
         #[cfg(feature = "enable_system_functions")]
         mod get_hostname;
+        mod get_json_pointer;
+        mod get_secret;
         #[cfg(feature = "enable_system_functions")]
         mod get_timezone_name;
+        mod group_by;
         mod haversine;
         mod hmac;
         #[cfg(feature = "enable_network_functions")]
@@ -118,8 +127,10 @@ cfg_if::cfg_if! {
         mod match_any;
         mod match_array;
         mod match_datadog_query;
+        mod max_by;
         mod md5;
         mod merge;
+        mod min_by;
         mod mod_func;
         mod now;
         mod object;
@@ -147,6 +158,7 @@ cfg_if::cfg_if! {
         mod parse_klog;
         mod parse_linux_authorization;
         mod parse_logfmt;
+        mod parse_msgpack;
         mod parse_nginx_log;
         #[cfg(feature = "enable_system_functions")]
         mod parse_proto;
@@ -160,14 +172,19 @@ cfg_if::cfg_if! {
         mod parse_url;
         mod parse_user_agent;
         mod parse_xml;
+        mod patch;
         mod pop;
         mod push;
+        mod query_json_path;
         mod random_bool;
         mod random_bytes;
         mod random_float;
         mod random_int;
         mod redact;
+        mod reduce;
         mod remove;
+        mod remove_all;
+        mod remove_secret;
         mod replace;
         mod replace_with;
         #[cfg(feature = "enable_network_functions")]
@@ -175,12 +192,15 @@ cfg_if::cfg_if! {
         mod round;
         mod seahash;
         mod set;
+        mod set_secret;
         mod sha1;
         mod sha2;
         mod sha3;
         mod shannon_entropy;
         mod sieve;
         mod slice;
+        mod sort;
+        mod sort_by;
         mod split;
         mod split_path;
         mod starts_with;
@@ -275,6 +295,8 @@ cfg_if::cfg_if! {
             decrypt::Decrypt,
             decrypt_ip::DecryptIp,
             del::Del,
+            diagnose_groks::DiagnoseGroks,
+            diff::Diff,
             dirname::DirName,
             #[cfg(feature = "enable_network_functions")]
             dns_lookup::DnsLookup,
@@ -286,12 +308,14 @@ cfg_if::cfg_if! {
             casing::kebabcase::Kebabcase,
             encode_base16::EncodeBase16,
             encode_base64::EncodeBase64,
+            encode_cbor::EncodeCbor,
             encode_charset::EncodeCharset,
             encode_gzip::EncodeGzip,
             encode_lz4::EncodeLz4,
             encode_json::EncodeJson,
             encode_key_value::EncodeKeyValue,
             encode_logfmt::EncodeLogfmt,
+            encode_msgpack::EncodeMsgpack,
             encode_percent::EncodePercent,
             #[cfg(feature = "enable_system_functions")]
             encode_proto::EncodeProto,
@@ -315,14 +339,18 @@ cfg_if::cfg_if! {
             from_unix_timestamp::FromUnixTimestamp,
             self::community_id::CommunityID,
             get::Get,
+            get_all::GetAll,
             #[cfg(feature = "enable_env_functions")]
             get_env_var::GetEnvVar,
             #[cfg(feature = "enable_system_functions")]
             get_hostname::GetHostname,
+            get_json_pointer::GetJsonPointer,
+            get_secret::GetSecret,
             #[cfg(feature = "enable_system_functions")]
             get_timezone_name::GetTimezoneName,
             #[cfg(feature = "enable_network_functions")]
             http_request::HttpRequest,
+            group_by::GroupBy,
             haversine::Haversine,
             includes::Includes,
             integer::Integer,
@@ -357,7 +385,9 @@ cfg_if::cfg_if! {
             match_any::MatchAny,
             match_array::MatchArray,
             match_datadog_query::MatchDatadogQuery,
+            max_by::MaxBy,
             merge::Merge,
+            min_by::MinBy,
             mod_func::Mod,
             now::Now,
             object::Object,
@@ -385,6 +415,7 @@ cfg_if::cfg_if! {
             parse_klog::ParseKlog,
             parse_linux_authorization::ParseLinuxAuthorization,
             parse_logfmt::ParseLogFmt,
+            parse_msgpack::ParseMsgpack,
             parse_nginx_log::ParseNginxLog,
             #[cfg(feature = "enable_system_functions")]
             parse_proto::ParseProto,
@@ -398,26 +429,34 @@ cfg_if::cfg_if! {
             parse_url::ParseUrl,
             parse_user_agent::ParseUserAgent,
             parse_xml::ParseXml,
+            patch::Patch,
             pop::Pop,
             push::Push,
+            query_json_path::QueryJsonPath,
             r#match::Match,
             random_bool::RandomBool,
             random_bytes::RandomBytes,
             random_float::RandomFloat,
             random_int::RandomInt,
             redact::Redact,
+            reduce::Reduce,
             remove::Remove,
+            remove_all::RemoveAll,
+            remove_secret::RemoveSecret,
             replace::Replace,
             replace_with::ReplaceWith,
             #[cfg(feature = "enable_network_functions")]
             reverse_dns::ReverseDns,
             round::Round,
             set::Set,
+            set_secret::SetSecret,
             sha2::Sha2,
             sha3::Sha3,
             shannon_entropy::ShannonEntropy,
             sieve::Sieve,
             slice::Slice,
+            sort::Sort,
+            sort_by::SortBy,
             split::Split,
             split_path::SplitPath,
             starts_with::StartsWith,
@@ -17,6 +17,10 @@ impl Function for Now {
         Category::Timestamp.as_ref()
     }
 
+    fn deterministic(&self) -> bool {
+        false
+    }
+
     fn return_kind(&self) -> u16 {
         kind::TIMESTAMP
     }
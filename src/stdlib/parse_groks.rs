@@ -1,9 +1,10 @@
+use crate::compiler::function::EnumVariant;
 use crate::compiler::prelude::*;
 
 #[cfg(not(target_arch = "wasm32"))]
 mod non_wasm {
     use crate::compiler::prelude::*;
-    use crate::datadog_grok::{parse_grok, parse_grok_rules::GrokRule};
+    use crate::datadog_grok::rule_set::GrokRuleSet;
     use crate::diagnostic::{Label, Span};
     use std::fmt;
 
@@ -42,7 +43,7 @@ mod non_wasm {
     #[derive(Clone, Debug)]
     pub(super) struct ParseGroksFn {
         pub(super) value: Box<dyn Expression>,
-        pub(super) grok_rules: Vec<GrokRule>,
+        pub(super) grok_rules: GrokRuleSet,
     }
 
     impl FunctionExpression for ParseGroksFn {
@@ -50,7 +51,9 @@ mod non_wasm {
             let value = self.value.resolve(ctx)?;
             let bytes = value.try_bytes_utf8_lossy()?;
 
-            let v = parse_grok::parse_grok(bytes.as_ref(), &self.grok_rules)
+            let v = self
+                .grok_rules
+                .parse(bytes.as_ref())
                 .map_err(|err| format!("unable to parse grok: {err}"))?
                 .parsed;
 
@@ -70,9 +73,20 @@ use std::sync::LazyLock;
 #[cfg(not(target_arch = "wasm32"))]
 use std::{fs::File, io::BufReader, path::Path};
 
-static DEFAULT_ALIASES: LazyLock<Value> =
-    LazyLock::new(|| Value::Object(std::collections::BTreeMap::new()));
-static DEFAULT_ALIAS_SOURCES: LazyLock<Value> = LazyLock::new(|| Value::Array(vec![]));
+static DEFAULT_ALIASES: LazyLock<Value> = LazyLock::new(|| Value::Object(ObjectMap::new()));
+static DEFAULT_ALIAS_SOURCES: LazyLock<Value> = LazyLock::new(|| Value::Array(ValueArray::new()));
+pub(super) static DEFAULT_DIALECT: LazyLock<Value> = LazyLock::new(|| Value::from("datadog"));
+
+pub(super) static DIALECT_ENUM: &[EnumVariant] = &[
+    EnumVariant {
+        value: "datadog",
+        description: "Datadog grok patterns, `%{matcher:field:filter}`, which match the whole value.",
+    },
+    EnumVariant {
+        value: "logstash",
+        description: "Logstash grok patterns, `%{PATTERN:field:type}` with an optional `int` or `float` type, which match anywhere in the value. Aliases are used as additional pattern definitions.",
+    },
+];
 
 static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
     vec![
@@ -86,6 +100,9 @@ static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
             .default(&DEFAULT_ALIASES),
         Parameter::optional("alias_sources", kind::ARRAY, "Path to the file containing aliases in a JSON format.")
             .default(&DEFAULT_ALIAS_SOURCES),
+        Parameter::optional("dialect", kind::BYTES, "The grok dialect the patterns are written in.")
+            .default(&DEFAULT_DIALECT)
+            .enum_variants(DIALECT_ENUM),
     ]
 });
 
@@ -180,118 +197,13 @@ impl Function for ParseGroks {
     fn compile(
         &self,
         state: &state::TypeState,
-        _ctx: &mut FunctionCompileContext,
+        ctx: &mut FunctionCompileContext,
         arguments: ArgumentList,
     ) -> Compiled {
-        use std::collections::BTreeMap;
-
         let value = arguments.required("value");
-
-        let patterns = arguments
-            .required_array("patterns")?
-            .into_iter()
-            .map(|expr| {
-                let pattern = expr
-                    .clone()
-                    .resolve_constant(state)
-                    .ok_or(function::Error::ExpectedStaticExpression {
-                        keyword: "patterns",
-                        expr: expr.clone(),
-                    })?
-                    .try_bytes_utf8_lossy()
-                    .map_err(|_| function::Error::InvalidArgument {
-                        keyword: "patterns",
-                        value: format!("{expr:?}").into(),
-                        error: "grok pattern should be a string",
-                    })?
-                    .into_owned();
-                Ok(pattern)
-            })
-            .collect::<std::result::Result<Vec<String>, function::Error>>()?;
-
-        let mut aliases = arguments
-            .optional_object("aliases")?
-            .unwrap_or_default()
-            .into_iter()
-            .map(|(key, expr)| {
-                let alias = expr
-                    .clone()
-                    .resolve_constant(state)
-                    .ok_or(function::Error::ExpectedStaticExpression {
-                        keyword: "aliases",
-                        expr: expr.clone(),
-                    })?
-                    .try_bytes_utf8_lossy()
-                    .map_err(|_| function::Error::InvalidArgument {
-                        keyword: "aliases",
-                        value: format!("{expr:?}").into(),
-                        error: "alias pattern should be a string",
-                    })?
-                    .into_owned();
-                Ok((key, alias))
-            })
-            .collect::<std::result::Result<BTreeMap<KeyString, String>, function::Error>>()?;
-
-        let alias_sources = arguments
-            .optional_array("alias_sources")?
-            .unwrap_or_default();
-
-        // With enable_system_functions feature disabled, alias_sources is not allowed
-        // to be used because it uses file operations.
-        #[cfg(not(feature = "enable_system_functions"))]
-        if !alias_sources.is_empty() {
-            return Err(function::Error::InvalidArgument {
-                keyword: "alias_sources",
-                value: "alias_sources".into(),
-                error: "alias_sources is disabled when enable_system_functions feature is disabled",
-            }
-            .into());
-        }
-
-        let alias_sources = alias_sources
-            .into_iter()
-            .map(|expr| {
-                let path = expr
-                    .clone()
-                    .resolve_constant(state)
-                    .ok_or(function::Error::ExpectedStaticExpression {
-                        keyword: "alias_sources",
-                        expr: expr.clone(),
-                    })?
-                    .try_bytes_utf8_lossy()
-                    .map_err(|_| function::Error::InvalidArgument {
-                        keyword: "alias_sources",
-                        value: format!("{expr:?}").into(),
-                        error: "alias source should be a string",
-                    })?
-                    .into_owned();
-                Ok(path)
-            })
-            .collect::<std::result::Result<Vec<String>, function::Error>>()?;
-
-        for src in alias_sources {
-            let path = Path::new(&src);
-            let file = File::open(path).map_err(|_| function::Error::InvalidArgument {
-                keyword: "alias_sources",
-                value: format!("{}", path.display()).into(),
-                error: "Unable to open alias source file",
-            })?;
-            let reader = BufReader::new(file);
-            let mut src_aliases =
-                serde_json::from_reader(reader).map_err(|_| function::Error::InvalidArgument {
-                    keyword: "alias_sources",
-                    value: format!("{}", path.display()).into(),
-                    error: "Unable to read alias source",
-                })?;
-
-            aliases.append(&mut src_aliases);
-        }
-
-        // we use a datadog library here because it is a superset of grok
-        let grok_rules = crate::datadog_grok::parse_grok_rules::parse_grok_rules(
-            &patterns, aliases,
-        )
-        .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)?;
+        let grok_rules = crate::datadog_grok::rule_set::GrokRuleSet::new(compile_grok_rules(
+            state, ctx, &arguments,
+        )?);
 
         Ok(ParseGroksFn { value, grok_rules }.as_expr())
     }
@@ -311,6 +223,144 @@ impl Function for ParseGroks {
     }
 }
 
+/// Compiles the grok rules from the `patterns`, `aliases`, `alias_sources` and `dialect`
+/// arguments, which are shared by the grok functions that try multiple patterns. Patterns of the
+/// `GrokPatternLibrary` in the compile config are available to the rules.
+#[cfg(not(target_arch = "wasm32"))]
+#[allow(clippy::too_many_lines)]
+pub(super) fn compile_grok_rules(
+    state: &state::TypeState,
+    ctx: &FunctionCompileContext,
+    arguments: &ArgumentList,
+) -> std::result::Result<
+    Vec<crate::datadog_grok::parse_grok_rules::GrokRule>,
+    Box<dyn DiagnosticMessage>,
+> {
+    use crate::datadog_grok::{
+        parse_grok_rules::{GrokDialect, parse_grok_rules_with},
+        pattern_library::GrokPatternLibrary,
+    };
+    use std::collections::BTreeMap;
+
+    let patterns = arguments
+        .required_array("patterns")?
+        .into_iter()
+        .map(|expr| {
+            let pattern = expr
+                .clone()
+                .resolve_constant(state)
+                .ok_or(function::Error::ExpectedStaticExpression {
+                    keyword: "patterns",
+                    expr: expr.clone(),
+                })?
+                .try_bytes_utf8_lossy()
+                .map_err(|_| function::Error::InvalidArgument {
+                    keyword: "patterns",
+                    value: format!("{expr:?}").into(),
+                    error: "grok pattern should be a string",
+                })?
+                .into_owned();
+            Ok(pattern)
+        })
+        .collect::<std::result::Result<Vec<String>, function::Error>>()?;
+
+    let mut aliases = arguments
+        .optional_object("aliases")?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, expr)| {
+            let alias = expr
+                .clone()
+                .resolve_constant(state)
+                .ok_or(function::Error::ExpectedStaticExpression {
+                    keyword: "aliases",
+                    expr: expr.clone(),
+                })?
+                .try_bytes_utf8_lossy()
+                .map_err(|_| function::Error::InvalidArgument {
+                    keyword: "aliases",
+                    value: format!("{expr:?}").into(),
+                    error: "alias pattern should be a string",
+                })?
+                .into_owned();
+            Ok((key, alias))
+        })
+        .collect::<std::result::Result<BTreeMap<KeyString, String>, function::Error>>()?;
+
+    let alias_sources = arguments
+        .optional_array("alias_sources")?
+        .unwrap_or_default();
+
+    // With enable_system_functions feature disabled, alias_sources is not allowed
+    // to be used because it uses file operations.
+    #[cfg(not(feature = "enable_system_functions"))]
+    if !alias_sources.is_empty() {
+        return Err(function::Error::InvalidArgument {
+            keyword: "alias_sources",
+            value: "alias_sources".into(),
+            error: "alias_sources is disabled when enable_system_functions feature is disabled",
+        }
+        .into());
+    }
+
+    let alias_sources = alias_sources
+        .into_iter()
+        .map(|expr| {
+            let path = expr
+                .clone()
+                .resolve_constant(state)
+                .ok_or(function::Error::ExpectedStaticExpression {
+                    keyword: "alias_sources",
+                    expr: expr.clone(),
+                })?
+                .try_bytes_utf8_lossy()
+                .map_err(|_| function::Error::InvalidArgument {
+                    keyword: "alias_sources",
+                    value: format!("{expr:?}").into(),
+                    error: "alias source should be a string",
+                })?
+                .into_owned();
+            Ok(path)
+        })
+        .collect::<std::result::Result<Vec<String>, function::Error>>()?;
+
+    for src in alias_sources {
+        let path = Path::new(&src);
+        let file = File::open(path).map_err(|_| function::Error::InvalidArgument {
+            keyword: "alias_sources",
+            value: format!("{}", path.display()).into(),
+            error: "Unable to open alias source file",
+        })?;
+        let reader = BufReader::new(file);
+        let mut src_aliases =
+            serde_json::from_reader(reader).map_err(|_| function::Error::InvalidArgument {
+                keyword: "alias_sources",
+                value: format!("{}", path.display()).into(),
+                error: "Unable to read alias source",
+            })?;
+
+        aliases.append(&mut src_aliases);
+    }
+
+    let dialects = DIALECT_ENUM
+        .iter()
+        .map(|variant| Value::from(variant.value))
+        .collect::<Vec<_>>();
+    let dialect = match arguments.optional_enum("dialect", &dialects, state)? {
+        Some(dialect) if dialect == Value::from("logstash") => GrokDialect::Logstash,
+        _ => GrokDialect::Datadog,
+    };
+
+    let default_library = GrokPatternLibrary::default();
+    let library = ctx
+        .get_external_context::<GrokPatternLibrary>()
+        .unwrap_or(&default_library);
+
+    // we use a datadog library here because it is a superset of grok
+    parse_grok_rules_with(&patterns, aliases, dialect, library)
+        .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)
+}
+
 #[cfg(test)]
 mod test {
     use crate::btreemap;
@@ -376,9 +426,9 @@ mod test {
         multiple_patterns_and_aliases_first_pattern_matches {
             args: func_args![
                 value: "2020-10-02T23:22:12.223222Z info 200 hello world",
-                patterns: Value::Array(vec![
-                    "%{common_prefix} %{_status} %{_message}".into(),
-                    "%{common_prefix} %{_message}".into(),
+                patterns: Value::from(vec![
+                    Value::from("%{common_prefix} %{_status} %{_message}"),
+                    Value::from("%{common_prefix} %{_message}"),
                     ]),
                 aliases: value!({
                     "common_prefix": "%{_timestamp} %{_loglevel}",
@@ -400,9 +450,9 @@ mod test {
         presence_of_alias_sources_argument {
             args: func_args![
                 value: "2020-10-02T23:22:12.223222Z info 200 hello world",
-                patterns: Value::Array(vec![
-                    "%{common_prefix} %{_status} %{_message}".into(),
-                    "%{common_prefix} %{_message}".into(),
+                patterns: Value::from(vec![
+                    Value::from("%{common_prefix} %{_status} %{_message}"),
+                    Value::from("%{common_prefix} %{_message}"),
                     ]),
                 aliases: value!({
                     "common_prefix": "%{_timestamp} %{_loglevel}",
@@ -411,7 +461,7 @@ mod test {
                     "_status": "%{POSINT:status}",
                     "_message": "%{GREEDYDATA:message}"
                 }),
-                alias_sources: Value::Array(vec![]),
+                alias_sources: Value::Array(ValueArray::new()),
             ],
             want: Ok(Value::from(btreemap! {
                 "timestamp" => "2020-10-02T23:22:12.223222Z",
@@ -425,9 +475,9 @@ mod test {
         multiple_patterns_and_aliases_second_pattern_matches {
             args: func_args![
                 value: "2020-10-02T23:22:12.223222Z info hello world",
-                patterns: Value::Array(vec![
-                    "%{common_prefix} %{_status} %{_message}".into(),
-                    "%{common_prefix} %{_message}".into(),
+                patterns: Value::from(vec![
+                    Value::from("%{common_prefix} %{_status} %{_message}"),
+                    Value::from("%{common_prefix} %{_message}"),
                     ]),
                 aliases: value!({
                     "common_prefix": "%{_timestamp} %{_loglevel}",
@@ -448,9 +498,9 @@ mod test {
         datadog_nginx {
             args: func_args![
                 value: r#"127.0.0.1 - frank [13/Jul/2016:10:55:36] "GET /apache_pb.gif HTTP/1.0" 200 2326 0.202 "http://www.perdu.com/" "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/55.0.2883.87 Safari/537.36" "-""#,
-                patterns: Value::Array(vec![
-                    "%{access_common}".into(),
-                    r#"%{access_common} (%{number:duration:scale(1000000000)} )?"%{_referer}" "%{_user_agent}"( "%{_x_forwarded_for}")?.*"#.into(),
+                patterns: Value::from(vec![
+                    Value::from("%{access_common}"),
+                    Value::from(r#"%{access_common} (%{number:duration:scale(1000000000)} )?"%{_referer}" "%{_user_agent}"( "%{_x_forwarded_for}")?.*"#),
                     ]),
                 aliases: value!({
                     "access_common": r#"%{_client_ip} %{_ident} %{_auth} \[%{_date_access}\] "(?>%{_method} |)%{_url}(?> %{_version}|)" %{_status_code} (?>%{_bytes_written}|-)"#,
@@ -468,7 +518,7 @@ mod test {
                     "_x_forwarded_for": r#"%{regex("[^\\\"]*"):http._x_forwarded_for:nullIf("-")}"#
                 })
             ],
-            want: Ok(Value::Object(btreemap! {
+            want: Ok(Value::from(btreemap! {
                 "date_access" => "13/Jul/2016:10:55:36",
                 "duration" => 202_000_000,
                 "http" => btreemap! {
@@ -490,8 +540,55 @@ mod test {
             })),
             tdef: TypeDef::object(Collection::any()).fallible(),
         }
+
+        logstash_dialect {
+            args: func_args![
+                value: "client 10.0.0.1 sent 1024 bytes",
+                patterns: vec!["%{IP:[client][ip]} sent %{NUMBER:bytes:int}"],
+                dialect: "logstash",
+            ],
+            want: Ok(Value::from(btreemap! {
+                "client" => btreemap! { "ip" => "10.0.0.1" },
+                "bytes" => 1024,
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
     ];
 
+    #[test]
+    fn uses_pattern_library_from_compile_config() {
+        use crate::compiler::{CompileConfig, TypeState, compile_with_state};
+        use crate::datadog_grok::pattern_library::GrokPatternLibrary;
+
+        let src = r#"parse_groks!("12:30:00 user=alice", patterns: ["%{APP_LINE}"], dialect: "logstash")"#;
+        let fns = crate::stdlib::all();
+        let state = TypeState::default();
+
+        assert!(compile_with_state(src, &fns, &state, CompileConfig::default()).is_err());
+
+        let mut config = CompileConfig::default();
+        config.set_custom(GrokPatternLibrary::from_dir("tests/data/grok/patterns").unwrap());
+        let program = compile_with_state(src, &fns, &state, config)
+            .expect("program should compile")
+            .program;
+
+        let mut target = crate::compiler::TargetValue {
+            value: Value::Null,
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: crate::value::Secrets::default(),
+        };
+        let value = crate::compiler::runtime::Runtime::default()
+            .resolve(&mut target, &program, &crate::compiler::TimeZone::default())
+            .unwrap();
+        assert_eq!(
+            value,
+            Value::from(btreemap! {
+                "time" => "12:30:00",
+                "user" => "user=alice",
+            })
+        );
+    }
+
     // Test that alias_sources errors when enable_system_functions is NOT enabled
     #[cfg(not(feature = "enable_system_functions"))]
     #[test]
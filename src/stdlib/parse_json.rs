@@ -166,10 +166,16 @@ impl Function for ParseJson {
     }
 
     fn notices(&self) -> &'static [&'static str] {
-        &[indoc! {"
-            Only JSON types are returned. If you need to convert a `string` into a `timestamp`,
-            consider the [`parse_timestamp`](#parse_timestamp) function.
-        "}]
+        &[
+            indoc! {"
+                Only JSON types are returned. If you need to convert a `string` into a `timestamp`,
+                consider the [`parse_timestamp`](#parse_timestamp) function.
+            "},
+            indoc! {"
+                Object fields are returned sorted by key. When VRL is built with the `preserve_order`
+                feature, fields keep the order in which they appear in the input.
+            "},
+        ]
     }
 
     fn parameters(&self) -> &'static [Parameter] {
@@ -1,6 +1,7 @@
 use crate::compiler::function::EnumVariant;
 use crate::compiler::prelude::*;
 use crate::value;
+use crate::value::ObjectEntry as Entry;
 use nom::{
     self, IResult, Parser,
     branch::alt,
@@ -14,7 +15,6 @@ use nom::{
 use nom_language::error::VerboseError;
 use std::{
     borrow::Cow,
-    collections::{BTreeMap, btree_map::Entry},
     iter::Peekable,
     str::{Chars, FromStr},
     sync::LazyLock,
@@ -73,7 +73,7 @@ pub fn parse_key_value(
 
     // Construct Value::Object by grouping values with the same key into an array.
     // This logic depends on values not being arrays which is true for this parser.
-    let mut map = BTreeMap::new();
+    let mut map = ObjectMap::new();
     for (key, value) in values {
         match map.entry(key) {
             Entry::Vacant(entry) => {
@@ -90,7 +90,7 @@ pub fn parse_key_value(
                         Value::Array(array) => array.push(value),
                         _ => {
                             let values = vec![std::mem::replace(existing, Value::Null), value];
-                            *existing = Value::Array(values);
+                            *existing = Value::from(values);
                         }
                     }
                 }
@@ -130,10 +130,16 @@ impl Function for ParseKeyValue {
     }
 
     fn notices(&self) -> &'static [&'static str] {
-        &[indoc! {"
-            All values are returned as strings or as an array of strings for duplicate keys. We
-            recommend manually coercing values to desired types as you see fit.
-        "}]
+        &[
+            indoc! {"
+                All values are returned as strings or as an array of strings for duplicate keys. We
+                recommend manually coercing values to desired types as you see fit.
+            "},
+            indoc! {"
+                Fields are returned sorted by key. When VRL is built with the `preserve_order`
+                feature, fields keep the order in which they appear in the input.
+            "},
+        ]
     }
 
     fn parameters(&self) -> &'static [Parameter] {
@@ -23,6 +23,13 @@ impl Function for ParseLogFmt {
         Category::Parse.as_ref()
     }
 
+    fn notices(&self) -> &'static [&'static str] {
+        &[indoc! {"
+            Fields are returned sorted by key. When VRL is built with the `preserve_order`
+            feature, fields keep the order in which they appear in the input.
+        "}]
+    }
+
     fn internal_failure_reasons(&self) -> &'static [&'static str] {
         &["`value` is not a properly formatted key-value string"]
     }
@@ -0,0 +1,160 @@
+use crate::compiler::prelude::*;
+
+fn parse_msgpack(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let value = Value::from_msgpack(&bytes).map_err(|e| format!("unable to parse msgpack: {e}"))?;
+    Ok(value)
+}
+
+fn inner_kind() -> Kind {
+    Kind::null()
+        | Kind::bytes()
+        | Kind::integer()
+        | Kind::float()
+        | Kind::boolean()
+        | Kind::timestamp()
+        | Kind::array(Collection::any())
+        | Kind::object(Collection::any())
+}
+
+fn type_def() -> TypeDef {
+    TypeDef::bytes()
+        .fallible()
+        .or_boolean()
+        .or_integer()
+        .or_float()
+        .or_timestamp()
+        .or_null()
+        .or_array(Collection::from_unknown(inner_kind()))
+        .or_object(Collection::from_unknown(inner_kind()))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseMsgpack;
+
+impl Function for ParseMsgpack {
+    fn identifier(&self) -> &'static str {
+        "parse_msgpack"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Parses the `value` as [MessagePack](https://msgpack.org).
+
+            Strings and binaries are both returned as strings. The timestamp extension, and
+            Fluentd's `EventTime` extension, are returned as timestamps.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Parse.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &[
+            "`value` is not a single valid MessagePack value.",
+            "`value` contains an unsupported extension type, a map key that isn't a string, or an integer larger than the maximum signed 64-bit integer.",
+        ]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::BOOLEAN
+            | kind::INTEGER
+            | kind::FLOAT
+            | kind::BYTES
+            | kind::TIMESTAMP
+            | kind::OBJECT
+            | kind::ARRAY
+            | kind::NULL
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Parse MessagePack",
+                source: r#"parse_msgpack!(decode_base64!("gaVmaWVsZKV2YWx1ZQ=="))"#,
+                result: Ok(r#"{ "field": "value" }"#),
+            },
+            example! {
+                title: "Timestamp extension",
+                source: r#"parse_msgpack!(decode_base64!("1v9lkefo"))"#,
+                result: Ok("t'2023-12-31T22:15:04Z'"),
+            },
+            example! {
+                title: "Round trip",
+                source: r#"parse_msgpack!(encode_msgpack({ "tags": ["a", "b"], "count": 2 }))"#,
+                result: Ok(r#"{ "tags": ["a", "b"], "count": 2 }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        Ok(ParseMsgpackFn { value }.as_expr())
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "value",
+            kind::BYTES,
+            "The MessagePack payload to parse.",
+        )];
+        PARAMETERS
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseMsgpackFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseMsgpackFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        parse_msgpack(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        type_def()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use chrono::{TimeZone, Utc};
+
+    test_function![
+        parse_msgpack => ParseMsgpack;
+
+        parses {
+            args: func_args![value: value!(b"\x82\xa1a\x01\xa1b\x92\xc3\xc0".as_slice())],
+            want: Ok(value!({ a: 1, b: [true, null] })),
+            tdef: type_def(),
+        }
+
+        binary {
+            args: func_args![value: value!(b"\xc4\x02\xff\x00".as_slice())],
+            want: Ok(value!(b"\xff\x00".as_slice())),
+            tdef: type_def(),
+        }
+
+        timestamp {
+            args: func_args![value: value!(b"\xd7\xff\x00\x00\x00\x04\x00\x00\x00\x01".as_slice())],
+            want: Ok(value!(Utc.timestamp_opt(1, 1).unwrap())),
+            tdef: type_def(),
+        }
+
+        invalid {
+            args: func_args![value: value!(b"\x92\x01".as_slice())],
+            want: Err("unable to parse msgpack: I/O error while reading marker byte: failed to fill whole buffer"),
+            tdef: type_def(),
+        }
+    ];
+}
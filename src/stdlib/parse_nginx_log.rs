@@ -1,6 +1,7 @@
 use crate::compiler::function::EnumVariant;
 use crate::compiler::prelude::*;
 use crate::value;
+use crate::value::value::remove_field;
 use regex::Regex;
 use std::collections::BTreeMap;
 
@@ -262,7 +263,7 @@ fn time_format_for_format(format: &[u8]) -> String {
 
 fn rename_referrer(mut value: Value) -> Value {
     if let Some(obj) = value.as_object_mut()
-        && let Some(referer) = obj.remove("referrer")
+        && let Some(referer) = remove_field(obj, "referrer")
     {
         obj.insert("referer".into(), referer);
     }
@@ -22,10 +22,16 @@ impl Function for ParseQueryString {
     }
 
     fn notices(&self) -> &'static [&'static str] {
-        &[indoc! {"
-            All values are returned as strings. We recommend manually coercing values to desired
-            types as you see fit. Empty keys and values are allowed.
-        "}]
+        &[
+            indoc! {"
+                All values are returned as strings. We recommend manually coercing values to desired
+                types as you see fit. Empty keys and values are allowed.
+            "},
+            indoc! {"
+                Fields are returned sorted by key. When VRL is built with the `preserve_order`
+                feature, fields keep the order in which they appear in the input.
+            "},
+        ]
     }
 
     fn examples(&self) -> &'static [Example] {
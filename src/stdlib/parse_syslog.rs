@@ -130,7 +130,7 @@ fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
 
 /// Create a `Value::Map` from the fields of the given syslog message.
 fn message_to_value(message: Message<&str>) -> Value {
-    let mut result = BTreeMap::new();
+    let mut result = ObjectMap::new();
 
     result.insert("message".to_string().into(), message.msg.to_string().into());
 
@@ -178,7 +178,7 @@ fn message_to_value(message: Message<&str>) -> Value {
     }
 
     for element in message.structured_data {
-        let mut sdata = BTreeMap::new();
+        let mut sdata = ObjectMap::new();
         for (name, value) in element.params() {
             sdata.insert((*name).into(), value.into());
         }
@@ -387,4 +387,40 @@ mod tests {
             tdef: TypeDef::object(inner_kind()).fallible(),
         }
     ];
+
+    #[cfg(feature = "preserve_order")]
+    #[test]
+    fn fields_keep_message_order() {
+        let message = syslog_loose::parse_message(
+            r#"<13>1 2020-03-13T20:45:38.119Z dynamicwireless.name non 2426 ID931 [exampleSDID@32473 iut="3" eventSource= "Application" eventID="1011"] Try to override the THX port"#,
+            Variant::Either,
+        );
+        let value = message_to_value(message);
+        let object = value.as_object().unwrap();
+
+        assert_eq!(
+            object.keys().map(KeyString::as_str).collect::<Vec<_>>(),
+            [
+                "message",
+                "hostname",
+                "severity",
+                "facility",
+                "version",
+                "appname",
+                "msgid",
+                "timestamp",
+                "procid",
+                "exampleSDID@32473",
+            ]
+        );
+        assert_eq!(
+            object["exampleSDID@32473"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(KeyString::as_str)
+                .collect::<Vec<_>>(),
+            ["iut", "eventSource", "eventID"]
+        );
+    }
 }
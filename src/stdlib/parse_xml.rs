@@ -85,7 +85,13 @@ impl Function for ParseXml {
     }
 
     fn notices(&self) -> &'static [&'static str] {
-        &["Valid XML must contain exactly one root node. Always returns an object."]
+        &[
+            "Valid XML must contain exactly one root node. Always returns an object.",
+            indoc! {"
+                Object fields are returned sorted by key. When VRL is built with the `preserve_order`
+                feature, fields keep the order in which they appear in the input.
+            "},
+        ]
     }
 
     fn examples(&self) -> &'static [Example] {
@@ -0,0 +1,183 @@
+use crate::compiler::prelude::*;
+use crate::value::PatchOperation;
+use std::sync::LazyLock;
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required("value", kind::ANY, "The value to patch."),
+        Parameter::required(
+            "operations",
+            kind::ARRAY,
+            "The JSON Patch (RFC 6902) operations to apply.",
+        ),
+    ]
+});
+
+fn patch(mut value: Value, operations: Value) -> Resolved {
+    let operations = operations
+        .try_array()?
+        .iter()
+        .map(PatchOperation::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| err.to_string())?;
+
+    value
+        .apply_patch(&operations)
+        .map_err(|err| err.to_string())?;
+
+    Ok(value)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Patch;
+
+impl Function for Patch {
+    fn identifier(&self) -> &'static str {
+        "patch"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Applies a JSON Patch (RFC 6902) to `value`.
+
+            All operations are supported: `add`, `remove`, `replace`, `move`, `copy` and `test`.
+            Paths are JSON Pointers. The operations are applied in order, and the patch fails as a
+            whole if any of them fails.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Object.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &[
+            "`operations` contains an invalid operation.",
+            "An operation refers to a path that does not exist.",
+            "A `test` operation fails.",
+        ]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ANY
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Patch an object",
+                source: indoc! {r#"
+                    patch!({ "a": 1, "b": [1] }, [
+                        { "op": "replace", "path": "/a", "value": 2 },
+                        { "op": "add", "path": "/b/-", "value": 2 },
+                        { "op": "move", "from": "/a", "path": "/c" }
+                    ])
+                "#},
+                result: Ok(r#"{ "b": [1, 2], "c": 2 }"#),
+            },
+            example! {
+                title: "Apply a diff",
+                source: indoc! {r#"
+                    from = { "a": 1 }
+                    to = { "b": 2 }
+                    patch!(from, diff(from, to)) == to
+                "#},
+                result: Ok("true"),
+            },
+            example! {
+                title: "Failed test",
+                source: r#"patch!({ "a": 1 }, [{ "op": "test", "path": "/a", "value": 2 }])"#,
+                result: Err(
+                    r#"function call error for "patch" at (0:64): test failed for path "/a""#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let operations = arguments.required("operations");
+
+        Ok(PatchFn { value, operations }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PatchFn {
+    value: Box<dyn Expression>,
+    operations: Box<dyn Expression>,
+}
+
+impl FunctionExpression for PatchFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let operations = self.operations.resolve(ctx)?;
+
+        patch(value, operations)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::any().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        patch => Patch;
+
+        add_and_remove {
+            args: func_args![
+                value: value!({ "a": 1, "b": [1, 2] }),
+                operations: value!([
+                    { "op": "remove", "path": "/a" },
+                    { "op": "add", "path": "/b/0", "value": 0 }
+                ])
+            ],
+            want: Ok(value!({ "b": [0, 1, 2] })),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        copy_and_test {
+            args: func_args![
+                value: value!({ "a": { "b": true } }),
+                operations: value!([
+                    { "op": "copy", "from": "/a", "path": "/c" },
+                    { "op": "test", "path": "/c/b", "value": true }
+                ])
+            ],
+            want: Ok(value!({ "a": { "b": true }, "c": { "b": true } })),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        missing_path {
+            args: func_args![
+                value: value!({ "a": 1 }),
+                operations: value!([{ "op": "replace", "path": "/b", "value": 2 }])
+            ],
+            want: Err(r#"path "/b" does not exist"#),
+            tdef: TypeDef::any().fallible(),
+        }
+
+        invalid_operation {
+            args: func_args![
+                value: value!({ "a": 1 }),
+                operations: value!([{ "op": "frobnicate", "path": "/a" }])
+            ],
+            want: Err(r#"invalid patch operation: unknown operation "frobnicate""#),
+            tdef: TypeDef::any().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,218 @@
+use crate::compiler::prelude::*;
+use crate::path::{JsonPath, OwnedValueQuery};
+
+fn query_json_path(value: &Value, query: &JsonPath) -> Value {
+    value
+        .query_json_path(query)
+        .into_iter()
+        .map(|(_, value)| value.clone())
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct QueryJsonPath;
+
+impl Function for QueryJsonPath {
+    fn identifier(&self) -> &'static str {
+        "query_json_path"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Evaluates a JSONPath (RFC 9535) query against `value`, and returns all selected values.
+
+            All of RFC 9535 is supported, including slices, recursive descent and filter selectors
+            with the `length`, `count`, `match`, `search` and `value` functions. Members of an
+            object are visited in the order of the object.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Path.as_ref()
+    }
+
+    fn internal_failure_reasons(&self) -> &'static [&'static str] {
+        &["`query` is not a valid JSONPath query."]
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ARRAY
+    }
+
+    fn return_rules(&self) -> &'static [&'static str] {
+        &[
+            "An empty array is returned if nothing is selected.",
+            "If `query` is a string literal, it is validated at compile time and the function is infallible.",
+        ]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[
+            Parameter::required("value", kind::ANY, "The value to query."),
+            Parameter::required("query", kind::BYTES, "The JSONPath query."),
+        ];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Filter",
+                source: indoc! {r#"
+                    query_json_path(
+                        { "books": [{ "title": "A", "price": 8 }, { "title": "B", "price": 12 }] },
+                        "$.books[?@.price < 10].title"
+                    )
+                "#},
+                result: Ok(r#"["A"]"#),
+            },
+            example! {
+                title: "Recursive descent",
+                source: r#"query_json_path({ "a": { "id": 1 }, "b": [{ "id": 2 }] }, "$..id")"#,
+                result: Ok("[1, 2]"),
+            },
+            example! {
+                title: "Slice",
+                source: r#"query_json_path([1, 2, 3, 4, 5], "$[::-2]")"#,
+                result: Ok("[5, 3, 1]"),
+            },
+            example! {
+                title: "Computed query",
+                source: indoc! {r#"
+                    query = "$.a" + "[0]"
+                    query_json_path!({ "a": [1, 2] }, query)
+                "#},
+                result: Ok("[1]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let query = arguments.required("query");
+
+        let query = match query.resolve_constant(state) {
+            Some(literal) => {
+                let query =
+                    JsonPath::parse(&literal.try_bytes_utf8_lossy().expect("query not bytes"))
+                        .map_err(|_| {
+                            Box::new(function::Error::InvalidArgument {
+                                keyword: "query",
+                                value: literal.clone(),
+                                error: "invalid JSONPath query",
+                            }) as Box<dyn DiagnosticMessage>
+                        })?;
+                Query::Static {
+                    value_query: query.to_value_query(),
+                    query,
+                }
+            }
+            None => Query::Dynamic(query),
+        };
+
+        Ok(QueryJsonPathFn { value, query }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Query {
+    Static {
+        query: JsonPath,
+        /// The query as a value query, which is faster to evaluate and has a more precise type.
+        value_query: Option<OwnedValueQuery>,
+    },
+    Dynamic(Box<dyn Expression>),
+}
+
+#[derive(Debug, Clone)]
+struct QueryJsonPathFn {
+    value: Box<dyn Expression>,
+    query: Query,
+}
+
+impl FunctionExpression for QueryJsonPathFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        match &self.query {
+            Query::Static {
+                value_query: Some(value_query),
+                ..
+            } => Ok(value
+                .get_all(value_query)
+                .into_iter()
+                .map(|(_, value)| value.clone())
+                .collect()),
+            Query::Static { query, .. } => Ok(query_json_path(&value, query)),
+            Query::Dynamic(query) => {
+                let query = query.resolve(ctx)?;
+                let query = JsonPath::parse(&query.try_bytes_utf8_lossy()?)
+                    .map_err(|err| err.to_string())?;
+                Ok(query_json_path(&value, &query))
+            }
+        }
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        match &self.query {
+            Query::Static {
+                value_query: Some(value_query),
+                ..
+            } => {
+                let kind = self.value.type_def(state).kind().get_all(value_query);
+                if kind.is_never() {
+                    TypeDef::array(Collection::empty())
+                } else {
+                    TypeDef::array(Collection::from_unknown(kind))
+                }
+            }
+            Query::Static { .. } => TypeDef::array(Collection::any()),
+            Query::Dynamic(_) => TypeDef::array(Collection::any()).fallible(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        query_json_path => QueryJsonPath;
+
+        translated {
+            args: func_args![
+                value: value!({ "users": [{ "id": 1 }, { "id": 2 }] }),
+                query: "$.users[*].id"
+            ],
+            want: Ok(value!([1, 2])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())),
+        }
+
+        filter {
+            args: func_args![
+                value: value!({ "users": [{ "id": 1, "admin": true }, { "id": 2 }] }),
+                query: "$.users[?@.admin == true].id"
+            ],
+            want: Ok(value!([1])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        union {
+            args: func_args![value: value!({ "a": 1, "b": 2 }), query: "$['b', 'a', 'c']"],
+            want: Ok(value!([2, 1])),
+            tdef: TypeDef::array(Collection::any()),
+        }
+
+        no_match {
+            args: func_args![value: value!({ "a": 1 }), query: "$.b"],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::empty()),
+        }
+    ];
+}
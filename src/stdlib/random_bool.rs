@@ -24,6 +24,10 @@ impl Function for RandomBool {
         Category::Random.as_ref()
     }
 
+    fn deterministic(&self) -> bool {
+        false
+    }
+
     fn return_kind(&self) -> u16 {
         kind::BOOLEAN
     }
@@ -37,6 +37,10 @@ impl Function for RandomBytes {
         ]
     }
 
+    fn deterministic(&self) -> bool {
+        false
+    }
+
     fn return_kind(&self) -> u16 {
         kind::BYTES
     }
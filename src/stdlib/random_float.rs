@@ -48,6 +48,10 @@ impl Function for RandomFloat {
         &["`max` is not greater than `min`."]
     }
 
+    fn deterministic(&self) -> bool {
+        false
+    }
+
     fn return_kind(&self) -> u16 {
         kind::FLOAT
     }
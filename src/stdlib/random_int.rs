@@ -43,6 +43,10 @@ impl Function for RandomInt {
         &["`max` is not greater than `min`."]
     }
 
+    fn deterministic(&self) -> bool {
+        false
+    }
+
     fn return_kind(&self) -> u16 {
         kind::INTEGER
     }
@@ -0,0 +1,168 @@
+use crate::compiler::prelude::*;
+
+fn reduce<T>(
+    value: Value,
+    initial: Value,
+    ctx: &mut Context,
+    runner: &closure::Runner<T>,
+) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    value
+        .try_array()?
+        .iter()
+        .try_fold(initial, |accumulator, value| {
+            runner.run_accumulator_value(ctx, accumulator, value)
+        })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Reduce;
+
+impl Function for Reduce {
+    fn identifier(&self) -> &'static str {
+        "reduce"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Reduces the elements of an array to a single value.
+
+            The closure is called once for each element, in order, with the
+            current accumulator and the element. The value returned by the
+            closure becomes the accumulator for the next element. The
+            accumulator starts as the `initial` value, and its final value is
+            returned. If the array is empty, `initial` is returned.
+
+            The type of the accumulator is inferred from the `initial` value and
+            from the values returned by the closure.
+
+            The same scoping rules apply to closure blocks as they do for
+            regular blocks. This means that any variable defined in parent scopes
+            is accessible, and mutations to those variables are preserved,
+            but any new variables instantiated in the closure block are
+            unavailable outside of the block.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Enumerate.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ANY
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[
+            Parameter::required("value", kind::ARRAY, "The array to reduce."),
+            Parameter::required(
+                "initial",
+                kind::ANY,
+                "The initial value of the accumulator.",
+            ),
+        ];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Sum numbers",
+                source: "reduce([1, 2, 3], initial: 0) -> |accumulator, value| { accumulator + value }",
+                result: Ok("6"),
+            },
+            example! {
+                title: "Build an object",
+                source: indoc! {r#"
+                    reduce(["a=1", "b=2"], initial: {}) -> |accumulator, value| {
+                        parts = split(value, "=")
+                        set!(accumulator, [parts[0]], parts[1])
+                    }
+                "#},
+                result: Ok(r#"{ "a": "1", "b": "2" }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let initial = arguments.required("initial");
+        let closure = arguments.required_closure()?;
+
+        Ok(ReduceFn {
+            value,
+            initial,
+            closure,
+        }
+        .as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::Accumulator("initial"),
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::any()),
+                example: example! {
+                    title: "concatenate strings",
+                    source: r#"reduce(["a", "b"], initial: "") -> |accumulator, value| { accumulator + value }"#,
+                    result: Ok(r#""ab""#),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ReduceFn {
+    value: Box<dyn Expression>,
+    initial: Box<dyn Expression>,
+    closure: Closure,
+}
+
+impl FunctionExpression for ReduceFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let initial = self.initial.resolve(ctx)?;
+        let Closure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        reduce(value, initial, ctx, &runner)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        // The result is either the initial value, if the array is empty, or
+        // the value returned by the last run of the closure.
+        let block_type_def = &self.closure.block_type_def;
+        let kind = self
+            .initial
+            .type_def(state)
+            .kind()
+            .union(block_type_def.kind().clone())
+            .union(block_type_def.returns().clone());
+
+        TypeDef::from(kind)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::compiler::prelude::*;
+use crate::path::OwnedValueQuery;
+
+use super::get_all::required_query;
+
+#[derive(Clone, Copy, Debug)]
+pub struct RemoveAll;
+
+impl Function for RemoveAll {
+    fn identifier(&self) -> &'static str {
+        "remove_all"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Removes all values matched by a query.
+
+            Queries support the same wildcards and recursive descent as `get_all`. For example,
+            `remove_all(., \"..password\")` removes every `password` field, wherever it is nested.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Path.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::OBJECT | kind::ARRAY
+    }
+
+    fn return_rules(&self) -> &'static [&'static str] {
+        &["If a match is nested in another match, both are removed."]
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[
+            Parameter::required(
+                "value",
+                kind::OBJECT | kind::ARRAY,
+                "The object or array to remove data from.",
+            ),
+            Parameter::required(
+                "query",
+                kind::BYTES,
+                "The query to match, such as `.users[*].id` or `..password`. Must be a string literal.",
+            ),
+        ];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Recursive descent",
+                source: r#"remove_all({ "password": "a", "auth": { "user": "b", "password": "c" } }, "..password")"#,
+                result: Ok(r#"{ "auth": { "user": "b" } }"#),
+            },
+            example! {
+                title: "Wildcard",
+                source: r#"remove_all({ "users": [{ "id": 1, "name": "a" }, { "id": 2 }] }, ".users[*].id")"#,
+                result: Ok(r#"{ "users": [{ "name": "a" }, {}] }"#),
+            },
+            example! {
+                title: "Remove array elements",
+                source: r#"remove_all({ "a": [1, 2, 3] }, ".a[*]")"#,
+                result: Ok(r#"{ "a": [] }"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let query = required_query(&arguments, state)?;
+
+        Ok(RemoveAllFn { value, query }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RemoveAllFn {
+    value: Box<dyn Expression>,
+    query: OwnedValueQuery,
+}
+
+impl FunctionExpression for RemoveAllFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut value = self.value.resolve(ctx)?;
+        value.remove_all(&self.query);
+
+        Ok(value)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        let mut kind = self.value.type_def(state).kind().clone();
+        kind.remove_all(&self.query);
+
+        kind.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+    use std::collections::BTreeMap;
+
+    test_function![
+        remove_all => RemoveAll;
+
+        descendants {
+            args: func_args![
+                value: value!({ "a": [{ "password": "x", "b": 1 }], "password": "y" }),
+                query: "..password"
+            ],
+            want: Ok(value!({ "a": [{ "b": 1 }] })),
+            tdef: TypeDef::object(BTreeMap::from([(
+                "a".into(),
+                Kind::array(BTreeMap::from([(
+                    0.into(),
+                    Kind::object(BTreeMap::from([("b".into(), Kind::integer())])),
+                )])),
+            )])),
+        }
+
+        wildcard {
+            args: func_args![value: value!({ "a": 1, "b": 2 }), query: ".*"],
+            want: Ok(value!({})),
+            tdef: TypeDef::object(Collection::empty()),
+        }
+    ];
+}
@@ -0,0 +1,108 @@
+use crate::compiler::prelude::*;
+
+fn remove_secret(ctx: &mut Context, key: &Value) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+    ctx.target_mut().remove_secret(key.as_ref());
+
+    Ok(Value::Null)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RemoveSecret;
+
+impl Function for RemoveSecret {
+    fn identifier(&self) -> &'static str {
+        "remove_secret"
+    }
+
+    fn usage(&self) -> &'static str {
+        "Removes a secret from an event. Removing a secret that does not exist is a no-op."
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Event.as_ref()
+    }
+
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::NULL
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[Parameter::required(
+            "key",
+            kind::BYTES,
+            "The name of the secret to remove.",
+        )];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[example! {
+            title: "Remove the Datadog API key",
+            source: r#"remove_secret("datadog_api_key")"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+
+        Ok(RemoveSecretFn { key }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RemoveSecretFn {
+    key: Box<dyn Expression>,
+}
+
+impl FunctionExpression for RemoveSecretFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        remove_secret(ctx, &key)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::null().infallible().impure()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{SecretTarget, TargetValue};
+    use crate::value;
+    use crate::value::Secrets;
+
+    #[test]
+    fn remove_secret() {
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        target.insert_secret("my_secret", "secret value");
+        target.insert_secret("other_secret", "other value");
+
+        let mut runtime_state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+        let result = RemoveSecretFn {
+            key: expr!("my_secret"),
+        }
+        .resolve(&mut ctx);
+
+        assert_eq!(result, Ok(Value::Null));
+        assert_eq!(target.get_secret("my_secret"), None);
+        assert_eq!(target.get_secret("other_secret"), Some("other value"));
+    }
+}
@@ -95,7 +95,7 @@ const STRING_NAME: &str = "string";
 const CAPTURES_NAME: &str = "captures";
 
 fn captures_to_value(captures: &Captures, capture_names: CaptureNames) -> Value {
-    let mut object: ObjectMap = BTreeMap::new();
+    let mut object = ObjectMap::new();
 
     // The full match, named "string"
     object.insert(STRING_NAME.into(), captures.get(0).unwrap().as_str().into());
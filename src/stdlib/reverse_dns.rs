@@ -54,6 +54,10 @@ impl Function for ReverseDns {
         Category::System.as_ref()
     }
 
+    fn deterministic(&self) -> bool {
+        false
+    }
+
     fn return_kind(&self) -> u16 {
         kind::BYTES
     }
@@ -0,0 +1,119 @@
+use crate::compiler::prelude::*;
+
+fn set_secret(ctx: &mut Context, key: &Value, secret: &Value) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+    let secret = secret.try_bytes_utf8_lossy()?;
+
+    ctx.target_mut()
+        .insert_secret(key.as_ref(), secret.as_ref());
+
+    Ok(Value::Null)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SetSecret;
+
+impl Function for SetSecret {
+    fn identifier(&self) -> &'static str {
+        "set_secret"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Sets the given secret in the event, overwriting any existing secret
+            with the same name.
+
+            Secrets are stored alongside the event, not inside it, so they are
+            never part of the event when it is printed or serialized.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Event.as_ref()
+    }
+
+    fn pure(&self) -> bool {
+        false
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::NULL
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        const PARAMETERS: &[Parameter] = &[
+            Parameter::required("key", kind::BYTES, "The name of the secret."),
+            Parameter::required("secret", kind::BYTES, "The secret value."),
+        ];
+        PARAMETERS
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[example! {
+            title: "Set the Datadog API key to the given value",
+            source: r#"set_secret("datadog_api_key", "abc122")"#,
+            result: Ok("null"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let secret = arguments.required("secret");
+
+        Ok(SetSecretFn { key, secret }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SetSecretFn {
+    key: Box<dyn Expression>,
+    secret: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SetSecretFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let secret = self.secret.resolve(ctx)?;
+        set_secret(ctx, &key, &secret)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::null().infallible().impure()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::{SecretTarget, TargetValue};
+    use crate::value;
+    use crate::value::Secrets;
+
+    #[test]
+    fn set_secret() {
+        let mut target = TargetValue {
+            value: value!({}),
+            metadata: value!({}),
+            secrets: Secrets::new(),
+        };
+        target.insert_secret("my_secret", "old value");
+
+        let mut runtime_state = state::RuntimeState::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+        let result = SetSecretFn {
+            key: expr!("my_secret"),
+            secret: expr!("new value"),
+        }
+        .resolve(&mut ctx);
+
+        assert_eq!(result, Ok(Value::Null));
+        assert_eq!(target.get_secret("my_secret"), Some("new value"));
+        assert_eq!(target.value, value!({}));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::compiler::prelude::*;
+use std::sync::LazyLock;
+
+static DEFAULT_REVERSE: LazyLock<Value> = LazyLock::new(|| Value::Boolean(false));
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required("value", kind::ARRAY, "The array to sort."),
+        Parameter::optional(
+            "reverse",
+            kind::BOOLEAN,
+            "Whether to sort the array in descending order.",
+        )
+        .default(&DEFAULT_REVERSE),
+    ]
+});
+
+fn sort(value: Value, reverse: bool) -> Resolved {
+    let mut array = value.try_array()?;
+
+    if reverse {
+        array.sort_by(|a, b| b.total_cmp(a));
+    } else {
+        array.sort_by(Value::total_cmp);
+    }
+
+    Ok(array.into())
+}
+
+/// Returns an array type whose elements can be any of the element kinds of
+/// the given array type, as sorting moves elements to unknown positions.
+pub(crate) fn sorted_type_def(type_def: &TypeDef) -> TypeDef {
+    let element = type_def
+        .as_array()
+        .map_or_else(Kind::any, Collection::reduced_kind);
+
+    TypeDef::array(Collection::from_unknown(element))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sort;
+
+impl Function for Sort {
+    fn identifier(&self) -> &'static str {
+        "sort"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Sorts the elements of an array.
+
+            Elements of different types are ordered by type first, in the
+            following order: `null`, booleans, numbers, strings, regular
+            expressions, timestamps, arrays and objects. Integers and floats are
+            compared numerically against each other.
+
+            The sort is stable, so elements that compare equal keep their
+            original order.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Array.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ARRAY
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Sort an array",
+                source: "sort([3, 1.5, 2])",
+                result: Ok("[1.5, 2, 3]"),
+            },
+            example! {
+                title: "Sort an array in descending order",
+                source: r#"sort(["b", "c", "a"], reverse: true)"#,
+                result: Ok(r#"["c", "b", "a"]"#),
+            },
+            example! {
+                title: "Sort mixed types",
+                source: r#"sort(["a", 1, null, true])"#,
+                result: Ok(r#"[null, true, 1, "a"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let reverse = arguments.optional("reverse");
+
+        Ok(SortFn { value, reverse }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SortFn {
+    value: Box<dyn Expression>,
+    reverse: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for SortFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let reverse = self
+            .reverse
+            .map_resolve_with_default(ctx, || DEFAULT_REVERSE.clone())?
+            .try_boolean()?;
+
+        sort(value, reverse)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        sorted_type_def(&self.value.type_def(state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value;
+
+    test_function![
+        sort => Sort;
+
+        integers {
+            args: func_args![value: value!([3, 1, 2])],
+            want: Ok(value!([1, 2, 3])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())),
+        }
+
+        reverse {
+            args: func_args![value: value!([3, 1, 2]), reverse: true],
+            want: Ok(value!([3, 2, 1])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())),
+        }
+
+        numbers {
+            args: func_args![value: value!([2.5, 1, 2.0, 2])],
+            want: Ok(value!([1, 2, 2.0, 2.5])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer().or_float())),
+        }
+
+        mixed_types {
+            args: func_args![value: value!(["b", 1, 1.5, true, null])],
+            want: Ok(value!([null, true, 1, 1.5, "b"])),
+            tdef: TypeDef::array(Collection::from_unknown(
+                Kind::null().or_boolean().or_integer().or_float().or_bytes()
+            )),
+        }
+    ];
+}
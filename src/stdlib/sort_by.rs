@@ -0,0 +1,174 @@
+use super::sort::sorted_type_def;
+use crate::compiler::prelude::*;
+use std::sync::LazyLock;
+
+static DEFAULT_REVERSE: LazyLock<Value> = LazyLock::new(|| Value::Boolean(false));
+
+static PARAMETERS: LazyLock<Vec<Parameter>> = LazyLock::new(|| {
+    vec![
+        Parameter::required("value", kind::ARRAY, "The array to sort."),
+        Parameter::optional(
+            "reverse",
+            kind::BOOLEAN,
+            "Whether to sort the array in descending order.",
+        )
+        .default(&DEFAULT_REVERSE),
+    ]
+});
+
+fn sort_by<T>(
+    value: Value,
+    reverse: bool,
+    ctx: &mut Context,
+    runner: &closure::Runner<T>,
+) -> Resolved
+where
+    T: Fn(&mut Context) -> Resolved,
+{
+    let array = value.try_array()?;
+
+    let mut keyed = array
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| Ok((runner.run_index_value(ctx, index, &value)?, value)))
+        .collect::<ExpressionResult<Vec<_>>>()?;
+
+    if reverse {
+        keyed.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    } else {
+        keyed.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    }
+
+    Ok(keyed.into_iter().map(|(_, value)| value).collect())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SortBy;
+
+impl Function for SortBy {
+    fn identifier(&self) -> &'static str {
+        "sort_by"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {"
+            Sorts the elements of an array by the value returned from the
+            closure for each element.
+
+            The closure is called exactly once per element, and the returned
+            keys are compared using the same ordering as the `sort` function.
+            The sort is stable, so elements with equal keys keep their original
+            order.
+
+            The same scoping rules apply to closure blocks as they do for
+            regular blocks. This means that any variable defined in parent scopes
+            is accessible, and mutations to those variables are preserved,
+            but any new variables instantiated in the closure block are
+            unavailable outside of the block.
+        "}
+    }
+
+    fn category(&self) -> &'static str {
+        Category::Enumerate.as_ref()
+    }
+
+    fn return_kind(&self) -> u16 {
+        kind::ARRAY
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        PARAMETERS.as_slice()
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            example! {
+                title: "Sort objects by field",
+                source: indoc! {r#"
+                    users = [{ "name": "bob", "age": 42 }, { "name": "alice", "age": 27 }]
+                    sort_by(users) -> |_index, value| { value.age }
+                "#},
+                result: Ok(r#"[{ "name": "alice", "age": 27 }, { "name": "bob", "age": 42 }]"#),
+            },
+            example! {
+                title: "Sort strings by length in descending order",
+                source: r#"sort_by(["a", "ccc", "bb"], reverse: true) -> |_index, value| { strlen(value) }"#,
+                result: Ok(r#"["ccc", "bb", "a"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let reverse = arguments.optional("reverse");
+        let closure = arguments.required_closure()?;
+
+        Ok(SortByFn {
+            value,
+            reverse,
+            closure,
+        }
+        .as_expr())
+    }
+
+    fn closure(&self) -> Option<closure::Definition> {
+        use closure::{Definition, Input, Output, Variable, VariableKind};
+
+        Some(Definition {
+            inputs: vec![Input {
+                parameter_keyword: "value",
+                kind: Kind::array(Collection::any()),
+                variables: vec![
+                    Variable {
+                        kind: VariableKind::TargetInnerKey,
+                    },
+                    Variable {
+                        kind: VariableKind::TargetInnerValue,
+                    },
+                ],
+                output: Output::Kind(Kind::any()),
+                example: example! {
+                    title: "sort by absolute value",
+                    source: "sort_by([-3, 1, -2]) -> |_index, value| { abs(value) }",
+                    result: Ok("[1, -2, -3]"),
+                },
+            }],
+            is_iterator: true,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SortByFn {
+    value: Box<dyn Expression>,
+    reverse: Option<Box<dyn Expression>>,
+    closure: Closure,
+}
+
+impl FunctionExpression for SortByFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let reverse = self
+            .reverse
+            .map_resolve_with_default(ctx, || DEFAULT_REVERSE.clone())?
+            .try_boolean()?;
+
+        let Closure {
+            variables,
+            block,
+            block_type_def: _,
+        } = &self.closure;
+        let runner = closure::Runner::new(variables, |ctx| block.resolve(ctx));
+
+        sort_by(value, reverse, ctx, &runner)
+    }
+
+    fn type_def(&self, state: &state::TypeState) -> TypeDef {
+        sorted_type_def(&self.value.type_def(state))
+    }
+}
@@ -48,7 +48,7 @@ fn unnest_root(root: &Value, path: &OwnedValuePath) -> Resolved {
             event.insert(path, value);
             event
         })
-        .collect::<Vec<_>>();
+        .collect::<ValueArray>();
 
     Ok(Value::Array(events))
 }
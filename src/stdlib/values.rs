@@ -1,8 +1,9 @@
 use crate::compiler::prelude::*;
+use crate::value::IntoCollection;
 
 fn values(value: Value) -> Resolved {
     let object = value.try_object()?;
-    let values = object.into_values();
+    let values = object.into_collection().into_values();
     Ok(Value::Array(values.collect()))
 }
 
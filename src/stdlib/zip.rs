@@ -6,7 +6,7 @@ fn zip2(value0: Value, value1: Value) -> Resolved {
         .try_array()?
         .into_iter()
         .zip(value1.try_array()?)
-        .map(|(v0, v1)| Value::Array(vec![v0, v1]))
+        .map(|(v0, v1)| Value::from(vec![v0, v1]))
         .collect())
 }
 
@@ -0,0 +1,894 @@
+//! Conversion between [`Value`]s and Apache Arrow [`RecordBatch`]es.
+//!
+//! A [`Kind`] is used as the schema of a batch. It must be an object, and each of its known fields
+//! becomes a column:
+//!
+//! - `boolean` becomes `Boolean`.
+//! - `integer` becomes `Int64`.
+//! - `float`, and `integer or float`, become `Float64`.
+//! - `string` and `regex` become `Utf8`. Invalid UTF-8 is replaced.
+//! - `timestamp` becomes `Timestamp(Nanosecond, "UTC")`.
+//! - An object becomes a `Struct` with one child per known field. Unknown fields aren't part of
+//!   the schema, and are dropped when converting values.
+//! - An array becomes a `List` of the union of its element kinds.
+//! - `null` (and `undefined`) on its own becomes `Null`.
+//!
+//! If a kind contains `null` or `undefined`, the field is nullable. Any other union, such as
+//! `string or integer`, can't be represented and is rejected.
+//!
+//! When converting a batch back into values, the types above are supported along with the other
+//! integer and float widths, large and view variants of strings, binaries and lists, fixed size
+//! lists, dates and timestamps of any unit. Nulls become [`Value::Null`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Date64Type, Float16Type, Float32Type, Float64Type, Int8Type, Int16Type, Int32Type,
+    Int64Type, TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType, UInt8Type, UInt16Type, UInt32Type, UInt64Type,
+};
+use arrow_array::{
+    Array, ArrayRef, ArrowPrimitiveType, BooleanArray, FixedSizeListArray, Float64Array,
+    GenericListArray, Int64Array, ListArray, NullArray, OffsetSizeTrait, RecordBatch,
+    RecordBatchOptions, StringArray, StructArray, TimestampNanosecondArray,
+};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_schema::{ArrowError, DataType, Field, FieldRef, Fields, Schema, TimeUnit};
+use chrono::{DateTime, Utc};
+use ordered_float::NotNan;
+
+use crate::value::kind::Collection;
+use crate::value::{KeyString, Kind, ObjectMap, Value};
+
+/// An error returned when converting to or from Arrow.
+#[derive(Debug)]
+pub enum ArrowConversionError {
+    /// The kind can't be represented as an Arrow data type.
+    UnsupportedKind {
+        /// The path of the offending field.
+        path: String,
+        /// The kind of the field.
+        kind: Kind,
+    },
+
+    /// The Arrow data type can't be converted into values.
+    UnsupportedDataType {
+        /// The path of the offending field.
+        path: String,
+        /// The data type of the field.
+        data_type: DataType,
+    },
+
+    /// A value doesn't match the schema it is converted with.
+    InvalidValue {
+        /// The path of the offending field.
+        path: String,
+        /// Why the value is invalid.
+        reason: String,
+    },
+
+    /// Arrow rejected the converted data.
+    Arrow(ArrowError),
+}
+
+impl fmt::Display for ArrowConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedKind { path, kind } => {
+                write!(f, "unsupported kind at \"{path}\": {kind}")
+            }
+            Self::UnsupportedDataType { path, data_type } => {
+                write!(f, "unsupported data type at \"{path}\": {data_type}")
+            }
+            Self::InvalidValue { path, reason } => {
+                write!(f, "invalid value at \"{path}\": {reason}")
+            }
+            Self::Arrow(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ArrowConversionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Arrow(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ArrowError> for ArrowConversionError {
+    fn from(error: ArrowError) -> Self {
+        Self::Arrow(error)
+    }
+}
+
+impl Kind {
+    /// Convert this kind into an Arrow schema.
+    ///
+    /// See the [module documentation](crate::value::arrow) for how kinds are mapped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the kind isn't an object, or if one of its fields can't be represented
+    /// in Arrow.
+    pub fn to_arrow_schema(&self) -> Result<Schema, ArrowConversionError> {
+        match data_type(self, ".")? {
+            DataType::Struct(fields) if self.is_object() => Ok(Schema::new(fields)),
+            _ => Err(ArrowConversionError::UnsupportedKind {
+                path: ".".to_owned(),
+                kind: self.clone(),
+            }),
+        }
+    }
+
+    /// Build a kind from an Arrow schema.
+    ///
+    /// The result is an object with a known field for each field of the schema. Nullable fields
+    /// include `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema contains a data type that can't be converted into values.
+    pub fn from_arrow_schema(schema: &Schema) -> Result<Self, ArrowConversionError> {
+        fields_kind(schema.fields(), "")
+    }
+}
+
+/// Convert `values` into a record batch, using `kind` as its schema.
+///
+/// # Errors
+///
+/// Returns an error if the kind can't be converted into a schema, or if a value doesn't match the
+/// kind.
+pub fn to_record_batch(values: &[Value], kind: &Kind) -> Result<RecordBatch, ArrowConversionError> {
+    let schema = kind.to_arrow_schema()?;
+
+    let rows = values
+        .iter()
+        .map(|value| {
+            value
+                .as_object()
+                .ok_or_else(|| invalid(".", format!("expected object, got {}", value.kind_str())))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let path = field_path("", field.name());
+            let values = rows
+                .iter()
+                .map(|row| row.get(field.name().as_str()))
+                .collect::<Vec<_>>();
+            to_array(&values, field, &path)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let options = RecordBatchOptions::new().with_row_count(Some(values.len()));
+    Ok(RecordBatch::try_new_with_options(
+        Arc::new(schema),
+        columns,
+        &options,
+    )?)
+}
+
+/// Convert each row of a record batch into an object.
+///
+/// # Errors
+///
+/// Returns an error if the batch contains a data type that can't be converted into values, or a
+/// value that can't be represented, such as a `NaN` float.
+pub fn from_record_batch(batch: &RecordBatch) -> Result<Vec<Value>, ArrowConversionError> {
+    let columns = batch
+        .schema()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, array)| {
+            let values = to_values(array.as_ref(), &field_path("", field.name()))?;
+            Ok((field.name().as_str().into(), values))
+        })
+        .collect::<Result<Vec<_>, ArrowConversionError>>()?;
+
+    Ok(zip_objects(columns, batch.num_rows()))
+}
+
+// -----------------------------------------------------------------------------
+// Kind <-> DataType
+
+fn data_type(kind: &Kind, path: &str) -> Result<DataType, ArrowConversionError> {
+    let mut defined = kind.clone();
+    defined.remove_null();
+    defined.remove_undefined();
+
+    let data_type = if defined.is_never() {
+        DataType::Null
+    } else if defined.is_boolean() {
+        DataType::Boolean
+    } else if defined.is_integer() {
+        DataType::Int64
+    } else if defined.is_float() || defined == Kind::integer().or_float() {
+        DataType::Float64
+    } else if defined.is_bytes() || defined.is_regex() || defined == Kind::bytes().or_regex() {
+        DataType::Utf8
+    } else if defined.is_timestamp() {
+        DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into()))
+    } else if let (true, Some(object)) = (defined.is_object(), defined.as_object()) {
+        let fields = object
+            .known()
+            .iter()
+            .map(|(name, kind)| {
+                let path = field_path(path, name.as_str());
+                let nullable = kind.contains_null() || kind.contains_undefined();
+                Ok(Field::new(name.as_str(), data_type(kind, &path)?, nullable))
+            })
+            .collect::<Result<Fields, ArrowConversionError>>()?;
+        DataType::Struct(fields)
+    } else if let (true, Some(array)) = (defined.is_array(), defined.as_array()) {
+        // The kind of unknown elements always includes `undefined`, which doesn't make the
+        // elements nullable.
+        let element = array.reduced_kind().without_undefined();
+        let data_type = data_type(&element, &format!("{path}[*]"))?;
+        DataType::List(Arc::new(Field::new_list_field(
+            data_type,
+            element.contains_null(),
+        )))
+    } else {
+        return Err(ArrowConversionError::UnsupportedKind {
+            path: path.to_owned(),
+            kind: kind.clone(),
+        });
+    };
+
+    Ok(data_type)
+}
+
+fn fields_kind(fields: &Fields, path: &str) -> Result<Kind, ArrowConversionError> {
+    let known = fields
+        .iter()
+        .map(|field| {
+            let kind = field_kind(field, &field_path(path, field.name()))?;
+            Ok((field.name().as_str().into(), kind))
+        })
+        .collect::<Result<BTreeMap<_, _>, ArrowConversionError>>()?;
+
+    Ok(Kind::object(known))
+}
+
+fn field_kind(field: &Field, path: &str) -> Result<Kind, ArrowConversionError> {
+    let kind = match field.data_type() {
+        DataType::Null => Kind::null(),
+        DataType::Boolean => Kind::boolean(),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64 => Kind::integer(),
+        DataType::Float16 | DataType::Float32 | DataType::Float64 => Kind::float(),
+        DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Utf8View
+        | DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView => Kind::bytes(),
+        DataType::Timestamp(..) | DataType::Date32 | DataType::Date64 => Kind::timestamp(),
+        DataType::Struct(fields) => fields_kind(fields, path)?,
+        DataType::List(element)
+        | DataType::LargeList(element)
+        | DataType::FixedSizeList(element, _) => Kind::array(Collection::from_unknown(field_kind(
+            element,
+            &format!("{path}[*]"),
+        )?)),
+        data_type => {
+            return Err(ArrowConversionError::UnsupportedDataType {
+                path: path.to_owned(),
+                data_type: data_type.clone(),
+            });
+        }
+    };
+
+    Ok(if field.is_nullable() {
+        kind.or_null()
+    } else {
+        kind
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Value -> Array
+
+/// Convert a column of values into an array of the type of `field`. A `None` value is missing.
+fn to_array(
+    values: &[Option<&Value>],
+    field: &Field,
+    path: &str,
+) -> Result<ArrayRef, ArrowConversionError> {
+    let values = values
+        .iter()
+        .map(|value| value.filter(|value| !value.is_null()))
+        .collect::<Vec<_>>();
+
+    if !field.is_nullable() && values.iter().any(Option::is_none) {
+        return Err(invalid(path, "missing value for non-nullable field"));
+    }
+
+    to_array_of_type(&values, field.data_type(), path)
+}
+
+fn to_array_of_type(
+    values: &[Option<&Value>],
+    data_type: &DataType,
+    path: &str,
+) -> Result<ArrayRef, ArrowConversionError> {
+    let mismatch = |value: &Value| {
+        invalid(
+            path,
+            format!("expected {data_type}, got {}", value.kind_str()),
+        )
+    };
+
+    let array: ArrayRef = match data_type {
+        DataType::Null => {
+            if let Some(value) = values.iter().flatten().next() {
+                return Err(mismatch(value));
+            }
+            Arc::new(NullArray::new(values.len()))
+        }
+        DataType::Boolean => Arc::new(BooleanArray::from(map_values(
+            values,
+            |value| match value {
+                Value::Boolean(boolean) => Ok(*boolean),
+                value => Err(mismatch(value)),
+            },
+        )?)),
+        DataType::Int64 => Arc::new(Int64Array::from(map_values(values, |value| match value {
+            Value::Integer(integer) => Ok(*integer),
+            value => Err(mismatch(value)),
+        })?)),
+        DataType::Float64 => Arc::new(Float64Array::from(map_values(values, |value| {
+            #[allow(clippy::cast_precision_loss)]
+            match value {
+                Value::Float(float) => Ok(float.into_inner()),
+                Value::Integer(integer) => Ok(*integer as f64),
+                value => Err(mismatch(value)),
+            }
+        })?)),
+        DataType::Utf8 => Arc::new(StringArray::from(map_values(
+            values,
+            |value| match value {
+                Value::Bytes(bytes) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+                Value::Regex(regex) => Ok(regex.as_str().to_owned()),
+                value => Err(mismatch(value)),
+            },
+        )?)),
+        DataType::Timestamp(TimeUnit::Nanosecond, timezone) => Arc::new(
+            TimestampNanosecondArray::from(map_values(values, |value| match value {
+                Value::Timestamp(timestamp) => timestamp
+                    .timestamp_nanos_opt()
+                    .ok_or_else(|| invalid(path, format!("timestamp {timestamp} is out of range"))),
+                value => Err(mismatch(value)),
+            })?)
+            .with_timezone_opt(timezone.clone()),
+        ),
+        DataType::Struct(fields) => struct_array(values, fields, path)?,
+        DataType::List(element) => list_array(values, element, path)?,
+        data_type => {
+            return Err(ArrowConversionError::UnsupportedDataType {
+                path: path.to_owned(),
+                data_type: data_type.clone(),
+            });
+        }
+    };
+
+    Ok(array)
+}
+
+fn struct_array(
+    values: &[Option<&Value>],
+    fields: &Fields,
+    path: &str,
+) -> Result<ArrayRef, ArrowConversionError> {
+    let objects = map_values(values, |value| {
+        value
+            .as_object()
+            .ok_or_else(|| invalid(path, format!("expected object, got {}", value.kind_str())))
+    })?;
+
+    let children = fields
+        .iter()
+        .map(|field| {
+            let path = field_path(path, field.name());
+            let values = objects
+                .iter()
+                .map(|object| {
+                    object
+                        .and_then(|object| object.get(field.name().as_str()))
+                        .filter(|value| !value.is_null())
+                })
+                .collect::<Vec<_>>();
+
+            // Missing children of a null struct are allowed, even if the child isn't nullable.
+            if !field.is_nullable()
+                && objects
+                    .iter()
+                    .zip(&values)
+                    .any(|(object, value)| object.is_some() && value.is_none())
+            {
+                return Err(invalid(&path, "missing value for non-nullable field"));
+            }
+
+            to_array_of_type(&values, field.data_type(), &path)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let nulls = objects.iter().map(Option::is_some).collect::<NullBuffer>();
+    Ok(Arc::new(StructArray::try_new(
+        fields.clone(),
+        children,
+        Some(nulls),
+    )?))
+}
+
+fn list_array(
+    values: &[Option<&Value>],
+    element: &FieldRef,
+    path: &str,
+) -> Result<ArrayRef, ArrowConversionError> {
+    let arrays = map_values(values, |value| {
+        value
+            .as_array()
+            .ok_or_else(|| invalid(path, format!("expected array, got {}", value.kind_str())))
+    })?;
+
+    let offsets =
+        OffsetBuffer::from_lengths(arrays.iter().map(|array| array.map_or(0, <[_]>::len)));
+    let elements = arrays
+        .iter()
+        .flatten()
+        .flat_map(|array| array.iter().map(Some))
+        .collect::<Vec<_>>();
+    let elements = to_array(&elements, element, &format!("{path}[*]"))?;
+
+    let nulls = arrays.iter().map(Option::is_some).collect::<NullBuffer>();
+    Ok(Arc::new(ListArray::try_new(
+        element.clone(),
+        offsets,
+        elements,
+        Some(nulls),
+    )?))
+}
+
+fn map_values<'a, T>(
+    values: &[Option<&'a Value>],
+    f: impl Fn(&'a Value) -> Result<T, ArrowConversionError>,
+) -> Result<Vec<Option<T>>, ArrowConversionError> {
+    values
+        .iter()
+        .map(|value| value.map(&f).transpose())
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// Array -> Value
+
+fn to_values(array: &dyn Array, path: &str) -> Result<Vec<Value>, ArrowConversionError> {
+    let integer = |integer: i64| Ok(Value::Integer(integer));
+    let float = |float: f64| {
+        NotNan::new(float)
+            .map(Value::Float)
+            .map_err(|_| invalid(path, "NaN is not supported"))
+    };
+    let timestamp = |timestamp: Option<DateTime<Utc>>| {
+        timestamp
+            .map(Value::Timestamp)
+            .ok_or_else(|| invalid(path, "timestamp is out of range"))
+    };
+
+    let values = match array.data_type() {
+        DataType::Null => vec![Value::Null; array.len()],
+        DataType::Boolean => array
+            .as_boolean()
+            .iter()
+            .map(|value| value.map_or(Value::Null, Value::Boolean))
+            .collect(),
+        DataType::Int8 => primitives::<Int8Type>(array, |value| integer(value.into()))?,
+        DataType::Int16 => primitives::<Int16Type>(array, |value| integer(value.into()))?,
+        DataType::Int32 => primitives::<Int32Type>(array, |value| integer(value.into()))?,
+        DataType::Int64 => primitives::<Int64Type>(array, integer)?,
+        DataType::UInt8 => primitives::<UInt8Type>(array, |value| integer(value.into()))?,
+        DataType::UInt16 => primitives::<UInt16Type>(array, |value| integer(value.into()))?,
+        DataType::UInt32 => primitives::<UInt32Type>(array, |value| integer(value.into()))?,
+        DataType::UInt64 => primitives::<UInt64Type>(array, |value| {
+            i64::try_from(value)
+                .map(Value::Integer)
+                .map_err(|_| invalid(path, format!("integer {value} is out of range")))
+        })?,
+        DataType::Float16 => primitives::<Float16Type>(array, |value| float(value.to_f64()))?,
+        DataType::Float32 => primitives::<Float32Type>(array, |value| float(value.into()))?,
+        DataType::Float64 => primitives::<Float64Type>(array, float)?,
+        DataType::Utf8 => bytes(array.as_string::<i32>().iter()),
+        DataType::LargeUtf8 => bytes(array.as_string::<i64>().iter()),
+        DataType::Utf8View => bytes(array.as_string_view().iter()),
+        DataType::Binary => bytes(array.as_binary::<i32>().iter()),
+        DataType::LargeBinary => bytes(array.as_binary::<i64>().iter()),
+        DataType::BinaryView => bytes(array.as_binary_view().iter()),
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            primitives::<TimestampSecondType>(array, |value| {
+                timestamp(DateTime::from_timestamp(value, 0))
+            })?
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            primitives::<TimestampMillisecondType>(array, |value| {
+                timestamp(DateTime::from_timestamp_millis(value))
+            })?
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            primitives::<TimestampMicrosecondType>(array, |value| {
+                timestamp(DateTime::from_timestamp_micros(value))
+            })?
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            primitives::<TimestampNanosecondType>(array, |value| {
+                timestamp(Some(DateTime::from_timestamp_nanos(value)))
+            })?
+        }
+        DataType::Date32 => primitives::<Date32Type>(array, |days| {
+            timestamp(DateTime::from_timestamp(i64::from(days) * 86_400, 0))
+        })?,
+        DataType::Date64 => primitives::<Date64Type>(array, |millis| {
+            timestamp(DateTime::from_timestamp_millis(millis))
+        })?,
+        DataType::Struct(fields) => structs(array.as_struct(), fields, path)?,
+        DataType::List(_) => lists(array.as_list::<i32>(), path)?,
+        DataType::LargeList(_) => lists(array.as_list::<i64>(), path)?,
+        DataType::FixedSizeList(_, _) => fixed_size_lists(array.as_fixed_size_list(), path)?,
+        data_type => {
+            return Err(ArrowConversionError::UnsupportedDataType {
+                path: path.to_owned(),
+                data_type: data_type.clone(),
+            });
+        }
+    };
+
+    Ok(values)
+}
+
+fn primitives<T: ArrowPrimitiveType>(
+    array: &dyn Array,
+    f: impl Fn(T::Native) -> Result<Value, ArrowConversionError>,
+) -> Result<Vec<Value>, ArrowConversionError> {
+    array
+        .as_primitive::<T>()
+        .iter()
+        .map(|value| value.map_or(Ok(Value::Null), &f))
+        .collect()
+}
+
+fn bytes<T: AsRef<[u8]>>(values: impl Iterator<Item = Option<T>>) -> Vec<Value> {
+    values
+        .map(|value| value.map_or(Value::Null, |value| Value::from(value.as_ref())))
+        .collect()
+}
+
+fn structs(
+    array: &StructArray,
+    fields: &Fields,
+    path: &str,
+) -> Result<Vec<Value>, ArrowConversionError> {
+    let columns = fields
+        .iter()
+        .zip(array.columns())
+        .map(|(field, column)| {
+            let values = to_values(column.as_ref(), &field_path(path, field.name()))?;
+            Ok((field.name().as_str().into(), values))
+        })
+        .collect::<Result<Vec<_>, ArrowConversionError>>()?;
+
+    Ok(zip_objects(columns, array.len())
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            if array.is_null(index) {
+                Value::Null
+            } else {
+                value
+            }
+        })
+        .collect())
+}
+
+fn lists<O: OffsetSizeTrait>(
+    array: &GenericListArray<O>,
+    path: &str,
+) -> Result<Vec<Value>, ArrowConversionError> {
+    let elements = to_values(array.values().as_ref(), &format!("{path}[*]"))?;
+
+    Ok(array
+        .offsets()
+        .windows(2)
+        .enumerate()
+        .map(|(index, offsets)| {
+            if array.is_null(index) {
+                Value::Null
+            } else {
+                let (start, end) = (offsets[0].as_usize(), offsets[1].as_usize());
+                Value::Array(elements[start..end].to_vec().into())
+            }
+        })
+        .collect())
+}
+
+fn fixed_size_lists(
+    array: &FixedSizeListArray,
+    path: &str,
+) -> Result<Vec<Value>, ArrowConversionError> {
+    let length = usize::try_from(array.value_length()).unwrap_or_default();
+    let elements = to_values(array.values().as_ref(), &format!("{path}[*]"))?;
+
+    Ok((0..array.len())
+        .map(|index| {
+            if array.is_null(index) {
+                Value::Null
+            } else {
+                let start = index * length;
+                Value::Array(elements[start..start + length].to_vec().into())
+            }
+        })
+        .collect())
+}
+
+/// Turn columns of values into a list of objects.
+fn zip_objects(columns: Vec<(KeyString, Vec<Value>)>, len: usize) -> Vec<Value> {
+    let mut objects = vec![ObjectMap::new(); len];
+
+    for (name, values) in columns {
+        for (object, value) in objects.iter_mut().zip(values) {
+            object.insert(name.clone(), value);
+        }
+    }
+
+    objects.into_iter().map(Value::Object).collect()
+}
+
+// -----------------------------------------------------------------------------
+
+fn field_path(path: &str, name: &str) -> String {
+    format!("{}.{name}", path.trim_end_matches('.'))
+}
+
+fn invalid(path: &str, reason: impl Into<String>) -> ArrowConversionError {
+    ArrowConversionError::InvalidValue {
+        path: path.to_owned(),
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::value;
+
+    fn kind() -> Kind {
+        Kind::object(BTreeMap::from([
+            ("message".into(), Kind::bytes()),
+            ("count".into(), Kind::integer().or_undefined()),
+            ("ratio".into(), Kind::float().or_integer()),
+            ("at".into(), Kind::timestamp()),
+            (
+                "http".into(),
+                Kind::object(BTreeMap::from([
+                    ("status".into(), Kind::integer()),
+                    ("ok".into(), Kind::boolean().or_null()),
+                ]))
+                .or_null(),
+            ),
+            (
+                "tags".into(),
+                Kind::array(Collection::from_unknown(Kind::bytes())),
+            ),
+        ]))
+    }
+
+    #[test]
+    fn schema() {
+        let schema = kind().to_arrow_schema().unwrap();
+        let http = Fields::from(vec![
+            Field::new("ok", DataType::Boolean, true),
+            Field::new("status", DataType::Int64, false),
+        ]);
+
+        assert_eq!(
+            schema,
+            Schema::new(vec![
+                Field::new(
+                    "at",
+                    DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into())),
+                    false
+                ),
+                Field::new("count", DataType::Int64, true),
+                Field::new("http", DataType::Struct(http), true),
+                Field::new("message", DataType::Utf8, false),
+                Field::new("ratio", DataType::Float64, false),
+                Field::new(
+                    "tags",
+                    DataType::List(Arc::new(Field::new_list_field(DataType::Utf8, false))),
+                    false
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn unsupported_kind() {
+        let kind = Kind::object(BTreeMap::from([(
+            "a".into(),
+            Kind::object(BTreeMap::from([("b".into(), Kind::bytes().or_integer())])),
+        )]));
+
+        assert_eq!(
+            kind.to_arrow_schema().unwrap_err().to_string(),
+            r#"unsupported kind at ".a.b": string or integer"#
+        );
+        assert!(Kind::bytes().to_arrow_schema().is_err());
+        assert!(Kind::any_object().to_arrow_schema().is_ok());
+    }
+
+    #[test]
+    fn round_trip() {
+        let at = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let mut first = value!({
+            "message": "hello",
+            "count": 3,
+            "ratio": 1,
+            "http": { "status": 200, "ok": true },
+            "tags": ["a", "b"],
+            "ignored": true
+        });
+        first.insert("at", at);
+        let mut second = value!({
+            "message": "world",
+            "ratio": 0.5,
+            "http": null,
+            "tags": []
+        });
+        second.insert("at", at);
+
+        let batch = to_record_batch(&[first, second], &kind()).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), 6);
+
+        let mut first = value!({
+            "message": "hello",
+            "count": 3,
+            "ratio": 1.0,
+            "http": { "status": 200, "ok": true },
+            "tags": ["a", "b"]
+        });
+        first.insert("at", at);
+        let mut second = value!({
+            "message": "world",
+            "count": null,
+            "ratio": 0.5,
+            "http": null,
+            "tags": []
+        });
+        second.insert("at", at);
+
+        assert_eq!(from_record_batch(&batch).unwrap(), vec![first, second]);
+        assert_eq!(
+            Kind::from_arrow_schema(&batch.schema()).unwrap(),
+            Kind::object(BTreeMap::from([
+                ("message".into(), Kind::bytes()),
+                ("count".into(), Kind::integer().or_null()),
+                ("ratio".into(), Kind::float()),
+                ("at".into(), Kind::timestamp()),
+                (
+                    "http".into(),
+                    Kind::object(BTreeMap::from([
+                        ("status".into(), Kind::integer()),
+                        ("ok".into(), Kind::boolean().or_null()),
+                    ]))
+                    .or_null(),
+                ),
+                (
+                    "tags".into(),
+                    Kind::array(Collection::from_unknown(Kind::bytes())),
+                ),
+            ]))
+        );
+    }
+
+    #[test]
+    fn invalid_values() {
+        let kind = Kind::object(BTreeMap::from([(
+            "a".into(),
+            Kind::array(Collection::from_unknown(Kind::integer())),
+        )]));
+
+        for (value, error) in [
+            (
+                value!(1),
+                r#"invalid value at ".": expected object, got integer"#,
+            ),
+            (
+                value!({}),
+                r#"invalid value at ".a": missing value for non-nullable field"#,
+            ),
+            (
+                value!({ "a": [1, "b"] }),
+                r#"invalid value at ".a[*]": expected Int64, got string"#,
+            ),
+            (
+                value!({ "a": [1, null] }),
+                r#"invalid value at ".a[*]": missing value for non-nullable field"#,
+            ),
+        ] {
+            assert_eq!(
+                to_record_batch(&[value], &kind).unwrap_err().to_string(),
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn other_data_types() {
+        use arrow_array::{
+            Date32Array, Int32Array, LargeStringArray, TimestampMillisecondArray, UInt64Array,
+        };
+
+        let schema = Schema::new(vec![
+            Field::new("small", DataType::Int32, true),
+            Field::new("unsigned", DataType::UInt64, false),
+            Field::new("large", DataType::LargeUtf8, false),
+            Field::new("date", DataType::Date32, false),
+            Field::new(
+                "millis",
+                DataType::Timestamp(TimeUnit::Millisecond, None),
+                false,
+            ),
+            Field::new(
+                "pair",
+                DataType::FixedSizeList(Arc::new(Field::new_list_field(DataType::Int32, false)), 2),
+                false,
+            ),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![None])),
+                Arc::new(UInt64Array::from(vec![7])),
+                Arc::new(LargeStringArray::from(vec!["text"])),
+                Arc::new(Date32Array::from(vec![1])),
+                Arc::new(TimestampMillisecondArray::from(vec![1_500])),
+                Arc::new(FixedSizeListArray::new(
+                    Arc::new(Field::new_list_field(DataType::Int32, false)),
+                    2,
+                    Arc::new(Int32Array::from(vec![1, 2])),
+                    None,
+                )),
+            ],
+        )
+        .unwrap();
+
+        let mut expected = value!({
+            "small": null,
+            "unsigned": 7,
+            "large": "text",
+            "pair": [1, 2]
+        });
+        expected.insert("date", Utc.with_ymd_and_hms(1970, 1, 2, 0, 0, 0).unwrap());
+        expected.insert("millis", Utc.timestamp_millis_opt(1_500).unwrap());
+
+        assert_eq!(from_record_batch(&batch).unwrap(), vec![expected]);
+    }
+}
@@ -6,6 +6,9 @@ mod comparison;
 mod conversion;
 mod crud;
 mod debug;
+mod infer;
+
+pub mod json_schema;
 
 pub mod merge;
 
@@ -14,6 +17,7 @@ pub use crud::*;
 use std::collections::BTreeMap;
 
 pub use collection::{Collection, Field, Index, Unknown};
+pub use infer::{InferOptions, Inferrer};
 
 use super::Value;
 
@@ -56,6 +56,12 @@ impl<T: Ord + Clone> Collection<T> {
         output
     }
 
+    /// Create a new collection from its known kinds, and the state of its unknown kinds.
+    #[must_use]
+    pub fn from_unknown_state(known: BTreeMap<T, Kind>, unknown: Unknown) -> Self {
+        Self { known, unknown }
+    }
+
     /// Create a new collection with a defined "unknown fields" value, and no known fields.
     #[must_use]
     pub fn from_unknown(unknown: impl Into<Kind>) -> Self {
@@ -119,6 +125,12 @@ impl<T: Ord + Clone> Collection<T> {
         self.unknown.to_kind()
     }
 
+    /// Gets the state of "unknown" elements in the collection.
+    #[must_use]
+    pub fn unknown(&self) -> &Unknown {
+        &self.unknown
+    }
+
     /// Returns true if the unknown variant is "Exact" (vs "Infinite").
     /// This can be used to determine when to stop recursing into an unknown kind.
     /// Once the unknown is infinite, this will return false and all unknowns after that
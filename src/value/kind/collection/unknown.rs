@@ -82,6 +82,15 @@ impl Unknown {
         matches!(self.0, Inner::Exact(_))
     }
 
+    /// Get the `Kind` of an "exact" `Unknown`, as it is stored.
+    #[must_use]
+    pub fn as_exact(&self) -> Option<&Kind> {
+        match &self.0 {
+            Inner::Exact(kind) => Some(kind),
+            Inner::Infinite(_) => None,
+        }
+    }
+
     /// Get the `Kind` stored in this `Unknown`.
     /// This represents the kind of any type not "known".
     /// It will always include "undefined", since unknown
@@ -1,3 +1,4 @@
 pub mod get;
 pub mod insert;
+pub mod query;
 pub mod remove;
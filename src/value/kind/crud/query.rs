@@ -0,0 +1,432 @@
+//! All code related to querying a [`Kind`] for multiple values.
+
+use crate::path::{OwnedSegment, OwnedValuePath, OwnedValueQuery, QuerySegment};
+use crate::value::Kind;
+use crate::value::kind::{Collection, Field, Index};
+
+impl Kind {
+    /// Returns the union of the types of all values matched by the query.
+    ///
+    /// This has the same behavior as `Value::get_all`. Since only existing values are matched,
+    /// the returned type never includes "undefined". If no value can be matched, this returns
+    /// `never`.
+    #[must_use]
+    pub fn get_all(&self, query: &OwnedValueQuery) -> Self {
+        self.get_all_inner(&query.segments)
+    }
+
+    /// Removes all values matched by the query from `self`.
+    ///
+    /// This has the same behavior as `Value::remove_all`. When it can't be known whether a value
+    /// is matched (e.g. a field of the "unknown" type of an object), the resulting type is the
+    /// union of the type with and without the removal.
+    pub fn remove_all(&mut self, query: &OwnedValueQuery) {
+        self.remove_all_inner(&query.segments);
+    }
+
+    fn get_all_inner(&self, segments: &[QuerySegment]) -> Self {
+        if self.is_never() {
+            return Self::never();
+        }
+
+        let Some((segment, rest)) = segments.split_first() else {
+            return self.without_undefined();
+        };
+
+        match segment {
+            QuerySegment::Field(field) => self.as_object().map_or_else(Self::never, |object| {
+                object
+                    .known()
+                    .get(&Field::from(field.clone()))
+                    .cloned()
+                    .unwrap_or_else(|| object.unknown_kind())
+                    .get_all_inner(rest)
+            }),
+            QuerySegment::Index(index) => self.as_array().map_or_else(Self::never, |array| {
+                let known = usize::try_from(*index)
+                    .ok()
+                    .and_then(|index| array.known().get(&Index::from(index)));
+
+                match known {
+                    Some(kind) => kind.get_all_inner(rest),
+                    // A negative index can point to any element.
+                    None if *index < 0 => union(
+                        array
+                            .known()
+                            .values()
+                            .chain(std::iter::once(&array.unknown_kind()))
+                            .map(|kind| kind.get_all_inner(rest)),
+                    ),
+                    None => array.unknown_kind().get_all_inner(rest),
+                }
+            }),
+            QuerySegment::Wildcard => union(children(self).map(|kind| kind.get_all_inner(rest))),
+            QuerySegment::Descendants => {
+                let mut kind = self.get_all_inner(rest);
+                for collection in [
+                    self.as_object().map(CollectionRef::Object),
+                    self.as_array().map(CollectionRef::Array),
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    for known in collection.known() {
+                        kind = kind.union(known.get_all_inner(segments));
+                    }
+
+                    // An "infinite" unknown type nests itself, so matching the rest of the query
+                    // against it covers all depths.
+                    let unknown = collection.unknown_kind();
+                    kind = kind.union(if collection.is_unknown_exact() {
+                        unknown.get_all_inner(segments)
+                    } else {
+                        unknown.get_all_inner(rest)
+                    });
+                }
+                kind
+            }
+        }
+    }
+
+    fn remove_all_inner(&mut self, segments: &[QuerySegment]) {
+        if self.is_never() {
+            return;
+        }
+
+        let Some((segment, rest)) = segments.split_first() else {
+            let _removed = self.remove(&OwnedValuePath::root(), false);
+            return;
+        };
+
+        match segment {
+            QuerySegment::Field(field) if rest.is_empty() => {
+                let _removed = self.remove(&vec![OwnedSegment::Field(field.clone())].into(), false);
+            }
+            QuerySegment::Index(index) if rest.is_empty() => {
+                let _removed = self.remove(&vec![OwnedSegment::Index(*index)].into(), false);
+            }
+            QuerySegment::Wildcard if rest.is_empty() => {
+                if let Some(object) = self.as_object_mut() {
+                    *object = Collection::empty();
+                }
+                if let Some(array) = self.as_array_mut() {
+                    *array = Collection::empty();
+                }
+            }
+            QuerySegment::Field(field) => {
+                if let Some(object) = self.as_object_mut() {
+                    match object.known_mut().get_mut(&Field::from(field.clone())) {
+                        Some(kind) => kind.remove_all_inner(rest),
+                        None => remove_from_unknown(object, rest, false),
+                    }
+                }
+            }
+            QuerySegment::Index(index) => {
+                if let Some(array) = self.as_array_mut() {
+                    let known = usize::try_from(*index)
+                        .ok()
+                        .and_then(|index| array.known_mut().get_mut(&Index::from(index)));
+
+                    match known {
+                        Some(kind) => kind.remove_all_inner(rest),
+                        // A negative index can point to any element.
+                        None if *index < 0 => {
+                            for kind in array.known_mut().values_mut() {
+                                remove_maybe(kind, rest);
+                            }
+                            remove_from_unknown(array, rest, false);
+                        }
+                        None => remove_from_unknown(array, rest, false),
+                    }
+                }
+            }
+            QuerySegment::Wildcard => self.remove_from_children(rest),
+            QuerySegment::Descendants => {
+                self.remove_from_children(segments);
+                self.remove_all_inner(rest);
+            }
+        }
+    }
+
+    fn remove_from_children(&mut self, segments: &[QuerySegment]) {
+        if let Some(object) = self.as_object_mut() {
+            for kind in object.known_mut().values_mut() {
+                kind.remove_all_inner(segments);
+            }
+            remove_from_unknown(object, segments, true);
+        }
+        if let Some(array) = self.as_array_mut() {
+            for kind in array.known_mut().values_mut() {
+                kind.remove_all_inner(segments);
+            }
+            remove_from_unknown(array, segments, true);
+        }
+    }
+}
+
+/// Removes the matches from a type that may or may not be matched.
+fn remove_maybe(kind: &mut Kind, segments: &[QuerySegment]) {
+    let mut removed = kind.clone();
+    removed.remove_all_inner(segments);
+    *kind = kind.union(removed);
+}
+
+/// Removes the matches from the unknown type of a collection.
+///
+/// If `all` is true, every unknown value is matched, otherwise only some of them might be.
+fn remove_from_unknown<T: Ord + Clone>(
+    collection: &mut Collection<T>,
+    segments: &[QuerySegment],
+    all: bool,
+) {
+    // An "infinite" unknown type already includes every possible shape, including the ones
+    // without the removed values.
+    if !collection.is_unknown_exact() {
+        return;
+    }
+
+    let mut unknown = collection.unknown_kind();
+    if all {
+        unknown.remove_all_inner(segments);
+    } else {
+        remove_maybe(&mut unknown, segments);
+    }
+    collection.set_unknown(unknown);
+}
+
+fn children(kind: &Kind) -> impl Iterator<Item = Kind> + '_ {
+    let object = kind.as_object().into_iter().flat_map(|object| {
+        object
+            .known()
+            .values()
+            .cloned()
+            .chain(std::iter::once(object.unknown_kind()))
+    });
+    let array = kind.as_array().into_iter().flat_map(|array| {
+        array
+            .known()
+            .values()
+            .cloned()
+            .chain(std::iter::once(array.unknown_kind()))
+    });
+    object.chain(array)
+}
+
+fn union(kinds: impl Iterator<Item = Kind>) -> Kind {
+    kinds.fold(Kind::never(), |acc, kind| acc.union(kind))
+}
+
+enum CollectionRef<'a> {
+    Object(&'a Collection<Field>),
+    Array(&'a Collection<Index>),
+}
+
+impl CollectionRef<'_> {
+    fn known(&self) -> Vec<&Kind> {
+        match self {
+            Self::Object(object) => object.known().values().collect(),
+            Self::Array(array) => array.known().values().collect(),
+        }
+    }
+
+    fn unknown_kind(&self) -> Kind {
+        match self {
+            Self::Object(object) => object.unknown_kind(),
+            Self::Array(array) => array.unknown_kind(),
+        }
+    }
+
+    fn is_unknown_exact(&self) -> bool {
+        match self {
+            Self::Object(object) => object.is_unknown_exact(),
+            Self::Array(array) => array.is_unknown_exact(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::*;
+    use crate::path::parse_value_query;
+
+    fn user() -> Kind {
+        Kind::object(BTreeMap::from([
+            ("id".into(), Kind::integer()),
+            ("password".into(), Kind::bytes()),
+        ]))
+    }
+
+    fn users() -> Kind {
+        Kind::object(BTreeMap::from([
+            (
+                "users".into(),
+                Kind::array(Collection::from_unknown(user())),
+            ),
+            ("password".into(), Kind::bytes().or_null()),
+            ("count".into(), Kind::integer()),
+        ]))
+    }
+
+    #[test]
+    fn test_get_all() {
+        struct TestCase {
+            kind: Kind,
+            query: &'static str,
+            want: Kind,
+        }
+
+        for (title, TestCase { kind, query, want }) in HashMap::from([
+            (
+                "root",
+                TestCase {
+                    kind: Kind::integer(),
+                    query: ".",
+                    want: Kind::integer(),
+                },
+            ),
+            (
+                "wildcard",
+                TestCase {
+                    kind: users(),
+                    query: ".users[*].id",
+                    want: Kind::integer(),
+                },
+            ),
+            (
+                "object wildcard",
+                TestCase {
+                    kind: user(),
+                    query: ".*",
+                    want: Kind::integer().or_bytes(),
+                },
+            ),
+            (
+                "descendants",
+                TestCase {
+                    kind: users(),
+                    query: "..password",
+                    want: Kind::bytes().or_null(),
+                },
+            ),
+            (
+                "no match",
+                TestCase {
+                    kind: users(),
+                    query: "..name",
+                    want: Kind::never(),
+                },
+            ),
+            (
+                "optional field",
+                TestCase {
+                    kind: Kind::object(BTreeMap::from([(
+                        "a".into(),
+                        Kind::integer().or_undefined(),
+                    )])),
+                    query: ".*",
+                    want: Kind::integer(),
+                },
+            ),
+            (
+                "negative index",
+                TestCase {
+                    kind: Kind::array(BTreeMap::from([
+                        (0.into(), Kind::integer()),
+                        (1.into(), Kind::bytes()),
+                    ])),
+                    query: ".[-1]",
+                    want: Kind::integer().or_bytes(),
+                },
+            ),
+            (
+                "infinite unknown",
+                TestCase {
+                    kind: Kind::json(),
+                    query: "..a",
+                    want: Kind::json(),
+                },
+            ),
+        ]) {
+            let query = parse_value_query(query).unwrap();
+            assert_eq!(kind.get_all(&query), want, "{title}");
+        }
+    }
+
+    #[test]
+    fn test_remove_all() {
+        struct TestCase {
+            kind: Kind,
+            query: &'static str,
+            want: Kind,
+        }
+
+        for (title, TestCase { kind, query, want }) in HashMap::from([
+            (
+                "wildcard",
+                TestCase {
+                    kind: users(),
+                    query: ".users[*].password",
+                    want: Kind::object(BTreeMap::from([
+                        (
+                            "users".into(),
+                            Kind::array(Collection::from_unknown(Kind::object(BTreeMap::from([
+                                ("id".into(), Kind::integer()),
+                            ])))),
+                        ),
+                        ("password".into(), Kind::bytes().or_null()),
+                        ("count".into(), Kind::integer()),
+                    ])),
+                },
+            ),
+            (
+                "descendants",
+                TestCase {
+                    kind: users(),
+                    query: "..password",
+                    want: Kind::object(BTreeMap::from([
+                        (
+                            "users".into(),
+                            Kind::array(Collection::from_unknown(Kind::object(BTreeMap::from([
+                                ("id".into(), Kind::integer()),
+                            ])))),
+                        ),
+                        ("count".into(), Kind::integer()),
+                    ])),
+                },
+            ),
+            (
+                "remove all fields",
+                TestCase {
+                    kind: user(),
+                    query: ".*",
+                    want: Kind::object(Collection::empty()),
+                },
+            ),
+            (
+                "unknown field",
+                TestCase {
+                    kind: Kind::object(Collection::from_unknown(user())),
+                    query: ".a.id",
+                    want: Kind::object(Collection::from_unknown(Kind::object(BTreeMap::from([
+                        ("id".into(), Kind::integer().or_undefined()),
+                        ("password".into(), Kind::bytes()),
+                    ])))),
+                },
+            ),
+            (
+                "infinite unknown",
+                TestCase {
+                    kind: Kind::json(),
+                    query: "..a",
+                    want: Kind::json(),
+                },
+            ),
+        ]) {
+            let mut kind = kind;
+            kind.remove_all(&parse_value_query(query).unwrap());
+            assert_eq!(kind, want, "{title}");
+        }
+    }
+}
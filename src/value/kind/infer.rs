@@ -0,0 +1,249 @@
+//! Inference of a [`Kind`] from sample values.
+
+use std::collections::BTreeMap;
+
+use super::{
+    Collection, Kind,
+    merge::{CollisionStrategy, Strategy},
+};
+use crate::value::Value;
+
+/// Options that control how a [`Kind`] is inferred from sample values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InferOptions {
+    /// The number of nested collection levels to track.
+    ///
+    /// Collections nested deeper than this are inferred as collections of any kind. For example,
+    /// with a depth of `1` the fields of the root object are known, but any objects or arrays in
+    /// those fields are not.
+    ///
+    /// If `None`, all levels are tracked.
+    pub max_depth: Option<usize>,
+
+    /// The maximum number of known fields (or indices) a collection can have.
+    ///
+    /// Collections that end up with more known fields than this are collapsed, meaning all of their
+    /// fields are merged into the kind of "unknown" fields. This keeps objects with dynamic keys
+    /// (such as IDs or hostnames used as keys) and long arrays from producing huge kinds.
+    ///
+    /// If `None`, collections are never collapsed.
+    pub max_fields: Option<usize>,
+}
+
+/// Folds a stream of sample values into a single [`Kind`].
+///
+/// The kinds of the samples are merged using the [`Union`](CollisionStrategy::Union) strategy, so
+/// fields that are missing from some samples can be `undefined`.
+#[derive(Debug, Clone)]
+pub struct Inferrer {
+    options: InferOptions,
+    kind: Option<Kind>,
+}
+
+impl Inferrer {
+    /// Create a new inferrer with the given options.
+    #[must_use]
+    pub const fn new(options: InferOptions) -> Self {
+        Self {
+            options,
+            kind: None,
+        }
+    }
+
+    /// Merge the kind of `value` into the inferred kind.
+    pub fn add(&mut self, value: &Value) {
+        let kind = infer_value(value, &self.options, 0);
+
+        match &mut self.kind {
+            Some(inferred) => {
+                inferred.merge(
+                    kind,
+                    Strategy {
+                        collisions: CollisionStrategy::Union,
+                    },
+                );
+                collapse(inferred, &self.options);
+            }
+            None => self.kind = Some(kind),
+        }
+    }
+
+    /// Returns the inferred kind.
+    ///
+    /// If no samples were added, this returns [`Kind::never`].
+    #[must_use]
+    pub fn finish(self) -> Kind {
+        self.kind.unwrap_or_else(Kind::never)
+    }
+}
+
+impl Kind {
+    /// Infer a kind from a stream of sample values.
+    ///
+    /// See [`Inferrer`] for details.
+    #[must_use]
+    pub fn infer<'a>(values: impl IntoIterator<Item = &'a Value>, options: InferOptions) -> Self {
+        let mut inferrer = Inferrer::new(options);
+        for value in values {
+            inferrer.add(value);
+        }
+        inferrer.finish()
+    }
+}
+
+fn infer_value(value: &Value, options: &InferOptions, depth: usize) -> Kind {
+    let limited = options
+        .max_depth
+        .is_some_and(|max_depth| depth >= max_depth);
+
+    let mut kind = match value {
+        Value::Object(_) if limited => Kind::object(Collection::any()),
+        Value::Array(_) if limited => Kind::array(Collection::any()),
+
+        Value::Object(object) => Kind::object(
+            object
+                .iter()
+                .map(|(key, value)| (key.clone().into(), infer_value(value, options, depth + 1)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+        Value::Array(array) => Kind::array(
+            array
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (index.into(), infer_value(value, options, depth + 1)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+        value => return value.into(),
+    };
+
+    collapse(&mut kind, options);
+    kind
+}
+
+/// Collapse all collections in `kind` that have more known fields than allowed.
+fn collapse(kind: &mut Kind, options: &InferOptions) {
+    let Some(max_fields) = options.max_fields else {
+        return;
+    };
+
+    if let Some(object) = &mut kind.object {
+        collapse_collection(object, max_fields, options);
+    }
+    if let Some(array) = &mut kind.array {
+        collapse_collection(array, max_fields, options);
+    }
+}
+
+fn collapse_collection<T: Ord + Clone>(
+    collection: &mut Collection<T>,
+    max_fields: usize,
+    options: &InferOptions,
+) {
+    for kind in collection.known_mut().values_mut() {
+        collapse(kind, options);
+    }
+
+    if collection.known().len() > max_fields {
+        collection.anonymize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::value;
+
+    #[test]
+    fn test_infer() {
+        struct TestCase {
+            values: Vec<Value>,
+            options: InferOptions,
+            want: Kind,
+        }
+
+        for (
+            title,
+            TestCase {
+                values,
+                options,
+                want,
+            },
+        ) in HashMap::from([
+            (
+                "no samples",
+                TestCase {
+                    values: vec![],
+                    options: InferOptions::default(),
+                    want: Kind::never(),
+                },
+            ),
+            (
+                "primitives",
+                TestCase {
+                    values: vec![value!(1), value!("foo"), value!(null)],
+                    options: InferOptions::default(),
+                    want: Kind::integer().or_bytes().or_null(),
+                },
+            ),
+            (
+                "optional fields",
+                TestCase {
+                    values: vec![value!({ "a": 1, "b": "x" }), value!({ "a": 2.0 })],
+                    options: InferOptions::default(),
+                    want: Kind::object(BTreeMap::from([
+                        ("a".into(), Kind::integer().or_float()),
+                        ("b".into(), Kind::bytes().or_undefined()),
+                    ])),
+                },
+            ),
+            (
+                "max depth",
+                TestCase {
+                    values: vec![value!({ "a": { "b": 1 }, "c": [1] })],
+                    options: InferOptions {
+                        max_depth: Some(1),
+                        max_fields: None,
+                    },
+                    want: Kind::object(BTreeMap::from([
+                        ("a".into(), Kind::object(Collection::any())),
+                        ("c".into(), Kind::array(Collection::any())),
+                    ])),
+                },
+            ),
+            (
+                "max fields",
+                TestCase {
+                    values: vec![
+                        value!({ "hosts": { "a": 1, "b": 2 } }),
+                        value!({ "hosts": { "c": "3" } }),
+                    ],
+                    options: InferOptions {
+                        max_depth: None,
+                        max_fields: Some(2),
+                    },
+                    want: Kind::object(BTreeMap::from([(
+                        "hosts".into(),
+                        Kind::object(Collection::from_unknown(
+                            Kind::integer().or_bytes().or_undefined(),
+                        )),
+                    )])),
+                },
+            ),
+            (
+                "long arrays",
+                TestCase {
+                    values: vec![value!([1, 2, 3])],
+                    options: InferOptions {
+                        max_depth: None,
+                        max_fields: Some(2),
+                    },
+                    want: Kind::array(Collection::from_unknown(Kind::integer())),
+                },
+            ),
+        ]) {
+            assert_eq!(Kind::infer(&values, options), want, "{title}");
+        }
+    }
+}
@@ -0,0 +1,1059 @@
+//! Conversion between [`Kind`] and [JSON Schema](https://json-schema.org).
+//!
+//! Both directions target JSON Schema draft 2020-12. When reading a schema, the draft-07 tuple
+//! form of `items` and `additionalItems` is accepted as well.
+//!
+//! Neither direction is lossless. `Kind` to JSON Schema:
+//!
+//! - `timestamp` becomes a string with `"format": "date-time"` and `regex` becomes a string with
+//!   `"format": "regex"`. The format is omitted if it would be ambiguous, e.g. for
+//!   `string or timestamp`.
+//! - `float` becomes `number`, which also accepts integers.
+//! - `undefined` can only be expressed for object fields (by leaving them out of `required`) and
+//!   trailing array elements (through `minItems`). It is dropped everywhere else.
+//! - Collections that are recursively "any" or "json" are left unconstrained below their first
+//!   level.
+//!
+//! JSON Schema to `Kind`:
+//!
+//! - Only the structural keywords (`type`, `properties`, `required`, `additionalProperties`,
+//!   `patternProperties`, `prefixItems`, `items`, `additionalItems`, `minItems`, `const`, `enum`,
+//!   `anyOf`, `oneOf`, `allOf` and local `$ref`s) are used. Value constraints such as `pattern`,
+//!   `minimum` or `maxLength`, and negations such as `not`, are ignored, so the resulting kind may
+//!   be wider than the schema.
+//! - `number` becomes `integer or float`.
+//! - Strings with `"format": "date-time"` become `timestamp` and strings with `"format": "regex"`
+//!   become `regex`. Any other format is a plain string.
+//! - `patternProperties` are folded into the kind of unknown fields.
+//! - Recursive `$ref`s are resolved to `json` once they recurse.
+//! - `allOf` is approximated by intersecting the kinds of its subschemas, and `oneOf` is treated
+//!   like `anyOf`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use serde_json::{Map, Value as JsonValue, json};
+
+use super::{Collection, Field, Index, Kind};
+use crate::value::Value;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// An error returned when a JSON Schema can't be converted to a [`Kind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonSchemaError {
+    /// The schema, or one of its keywords, is malformed.
+    Invalid {
+        /// The JSON Pointer to the offending schema.
+        pointer: String,
+        /// Why the schema is invalid.
+        reason: String,
+    },
+
+    /// The schema references a location that is not part of the document.
+    UnresolvedRef {
+        /// The JSON Pointer to the offending schema.
+        pointer: String,
+        /// The `$ref` value.
+        reference: String,
+    },
+}
+
+impl fmt::Display for JsonSchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid { pointer, reason } => {
+                write!(f, "invalid schema at \"{pointer}\": {reason}")
+            }
+            Self::UnresolvedRef { pointer, reference } => {
+                write!(f, "unresolved reference \"{reference}\" at \"{pointer}\"")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonSchemaError {}
+
+impl Kind {
+    /// Convert this kind into a JSON Schema (draft 2020-12) document.
+    ///
+    /// See the [module documentation](crate::value::kind::json_schema) for the cases in which
+    /// type information is lost.
+    #[must_use]
+    pub fn to_json_schema(&self) -> JsonValue {
+        let mut schema = to_schema(self, false);
+
+        if let JsonValue::Object(map) = &mut schema {
+            map.insert("$schema".to_owned(), DRAFT.into());
+        }
+
+        schema
+    }
+
+    /// Build a kind from a JSON Schema document.
+    ///
+    /// Schemas that accept any value result in [`Kind::json`], since JSON Schema can only
+    /// describe JSON types. See the [module documentation](crate::value::kind::json_schema) for
+    /// the keywords that are taken into account.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema is malformed, or if it contains a `$ref` that doesn't point
+    /// into the document itself.
+    pub fn from_json_schema(schema: &JsonValue) -> Result<Self, JsonSchemaError> {
+        Reader {
+            root: schema,
+            refs: Vec::new(),
+            resolved: HashMap::new(),
+            recursed: false,
+        }
+        .read(schema, "")
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Kind -> JSON Schema
+
+/// Convert `kind` into a schema.
+///
+/// If `shallow` is set, nested collections are not described. This is used for recursive
+/// ("infinite") unknown kinds, which can't be expanded.
+fn to_schema(kind: &Kind, shallow: bool) -> JsonValue {
+    let mut types = Vec::new();
+    let mut schema = Map::new();
+
+    // The `contains_*` methods treat `never` as containing everything, so the states are checked
+    // directly instead.
+    let (bytes, timestamp, regex) = (
+        kind.bytes.is_some(),
+        kind.timestamp.is_some(),
+        kind.regex.is_some(),
+    );
+
+    if bytes || timestamp || regex {
+        types.push("string");
+
+        match (bytes, timestamp, regex) {
+            (false, true, false) => {
+                schema.insert("format".to_owned(), "date-time".into());
+            }
+            (false, false, true) => {
+                schema.insert("format".to_owned(), "regex".into());
+            }
+            _ => {}
+        }
+    }
+    if kind.integer.is_some() {
+        types.push("integer");
+    }
+    if kind.float.is_some() {
+        types.push("number");
+    }
+    if kind.boolean.is_some() {
+        types.push("boolean");
+    }
+    if kind.null.is_some() {
+        types.push("null");
+    }
+    if let Some(array) = kind.as_array() {
+        types.push("array");
+
+        if !shallow {
+            array_keywords(array, &mut schema);
+        }
+    }
+    if let Some(object) = kind.as_object() {
+        types.push("object");
+
+        if !shallow {
+            object_keywords(object, &mut schema);
+        }
+    }
+
+    match types.as_slice() {
+        [] => JsonValue::Bool(false),
+        [
+            "string",
+            "integer",
+            "number",
+            "boolean",
+            "null",
+            "array",
+            "object",
+        ] if schema.is_empty() => JsonValue::Object(schema),
+        [ty] => {
+            schema.insert("type".to_owned(), (*ty).into());
+            JsonValue::Object(schema)
+        }
+        _ => {
+            schema.insert("type".to_owned(), types.into());
+            JsonValue::Object(schema)
+        }
+    }
+}
+
+/// The schema of the unknown elements of a collection, or `None` if they are unconstrained.
+fn unknown_schema<T: Ord + Clone>(collection: &Collection<T>) -> Option<JsonValue> {
+    let schema = to_schema(
+        &collection.unknown_kind().without_undefined(),
+        !collection.is_unknown_exact(),
+    );
+
+    (schema != json!({})).then_some(schema)
+}
+
+fn array_keywords(array: &Collection<Index>, schema: &mut Map<String, JsonValue>) {
+    let known = array.known();
+
+    if let Some(last) = known.keys().last() {
+        let unknown = array.unknown_kind();
+        let items = (0..=last.to_usize())
+            .map(|index| {
+                let kind = known.get(&index.into()).unwrap_or(&unknown);
+                to_schema(&kind.without_undefined(), false)
+            })
+            .collect::<Vec<_>>();
+
+        schema.insert("prefixItems".to_owned(), items.into());
+    }
+
+    let min_length = array.min_length();
+    if min_length > 0 {
+        schema.insert("minItems".to_owned(), min_length.into());
+    }
+
+    if let Some(items) = unknown_schema(array) {
+        schema.insert("items".to_owned(), items);
+    }
+}
+
+fn object_keywords(object: &Collection<Field>, schema: &mut Map<String, JsonValue>) {
+    let known = object.known();
+
+    if !known.is_empty() {
+        let properties = known
+            .iter()
+            .map(|(field, kind)| {
+                (
+                    field.as_str().to_owned(),
+                    to_schema(&kind.without_undefined(), false),
+                )
+            })
+            .collect::<Map<_, _>>();
+
+        let required = known
+            .iter()
+            .filter(|(_, kind)| kind.undefined.is_none())
+            .map(|(field, _)| JsonValue::from(field.as_str()))
+            .collect::<Vec<_>>();
+
+        schema.insert("properties".to_owned(), properties.into());
+        if !required.is_empty() {
+            schema.insert("required".to_owned(), required.into());
+        }
+    }
+
+    if let Some(additional) = unknown_schema(object) {
+        schema.insert("additionalProperties".to_owned(), additional);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// JSON Schema -> Kind
+
+struct Reader<'a> {
+    root: &'a JsonValue,
+
+    /// The `$ref`s currently being resolved, used to detect recursive schemas.
+    refs: Vec<&'a str>,
+
+    /// The kinds of the schemas that `$ref`s resolved to, by pointer, so that schemas referenced
+    /// many times are only read once.
+    resolved: HashMap<&'a str, Kind>,
+
+    /// Whether a recursive `$ref` was found since the current `$ref` started being resolved, in
+    /// which case its kind depends on where it's referenced from, and isn't memoized.
+    recursed: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn read(&mut self, schema: &'a JsonValue, pointer: &str) -> Result<Kind, JsonSchemaError> {
+        let map = match schema {
+            JsonValue::Bool(true) => return Ok(Kind::json()),
+            JsonValue::Bool(false) => return Ok(Kind::never()),
+            JsonValue::Object(map) => map,
+            _ => return Err(invalid(pointer, "expected an object or a boolean")),
+        };
+
+        let mut kind = self.read_types(map, pointer)?;
+
+        if let Some(value) = map.get("const") {
+            kind = intersect(&kind, &Kind::from(Value::from(value.clone())));
+        }
+
+        if let Some(values) = map.get("enum") {
+            let values = values
+                .as_array()
+                .ok_or_else(|| invalid(&format!("{pointer}/enum"), "expected an array"))?;
+
+            let allowed = values.iter().fold(Kind::never(), |acc, value| {
+                acc.union(Kind::from(Value::from(value.clone())))
+            });
+            kind = intersect(&kind, &allowed);
+        }
+
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(schemas) = self.read_all(map, keyword, pointer)? {
+                let allowed = schemas
+                    .into_iter()
+                    .fold(Kind::never(), |acc, kind| acc.union(kind));
+                kind = intersect(&kind, &allowed);
+            }
+        }
+
+        if let Some(schemas) = self.read_all(map, "allOf", pointer)? {
+            kind = schemas
+                .iter()
+                .fold(kind, |acc, schema| intersect(&acc, schema));
+        }
+
+        if let Some(reference) = map.get("$ref") {
+            let target = self.read_ref(reference, pointer)?;
+            kind = intersect(&kind, &target);
+        }
+
+        Ok(kind)
+    }
+
+    /// Read the kind described by the `type` keyword, along with the keywords that only apply to
+    /// specific types.
+    fn read_types(
+        &mut self,
+        map: &'a Map<String, JsonValue>,
+        pointer: &str,
+    ) -> Result<Kind, JsonSchemaError> {
+        let types = match map.get("type") {
+            None => vec![
+                "string", "integer", "number", "boolean", "null", "array", "object",
+            ],
+            Some(JsonValue::String(ty)) => vec![ty.as_str()],
+            Some(JsonValue::Array(types)) => types
+                .iter()
+                .map(|ty| {
+                    ty.as_str().ok_or_else(|| {
+                        invalid(&format!("{pointer}/type"), "expected an array of strings")
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => {
+                return Err(invalid(
+                    &format!("{pointer}/type"),
+                    "expected a string or an array of strings",
+                ));
+            }
+        };
+
+        let mut kind = Kind::never();
+
+        for ty in types {
+            match ty {
+                "string" => match map.get("format").and_then(JsonValue::as_str) {
+                    Some("date-time") => kind.add_timestamp(),
+                    Some("regex") => kind.add_regex(),
+                    _ => kind.add_bytes(),
+                },
+                "integer" => kind.add_integer(),
+                "number" => {
+                    kind.add_integer();
+                    kind.add_float()
+                }
+                "boolean" => kind.add_boolean(),
+                "null" => kind.add_null(),
+                "array" => kind.add_array(self.read_array(map, pointer)?),
+                "object" => kind.add_object(self.read_object(map, pointer)?),
+                _ => {
+                    return Err(invalid(
+                        &format!("{pointer}/type"),
+                        &format!("unknown type \"{ty}\""),
+                    ));
+                }
+            };
+        }
+
+        Ok(kind)
+    }
+
+    fn read_array(
+        &mut self,
+        map: &'a Map<String, JsonValue>,
+        pointer: &str,
+    ) -> Result<Collection<Index>, JsonSchemaError> {
+        // Draft 2020-12 uses `prefixItems` and `items`, earlier drafts use `items` (as an array)
+        // and `additionalItems`.
+        let (prefix, prefix_keyword, rest, rest_keyword) = match map.get("items") {
+            Some(JsonValue::Array(items)) => (
+                Some(items),
+                "items",
+                map.get("additionalItems"),
+                "additionalItems",
+            ),
+            items => (
+                map.get("prefixItems")
+                    .map(|items| {
+                        items.as_array().ok_or_else(|| {
+                            invalid(&format!("{pointer}/prefixItems"), "expected an array")
+                        })
+                    })
+                    .transpose()?,
+                "prefixItems",
+                items,
+                "items",
+            ),
+        };
+
+        let min_items = match map.get("minItems") {
+            None => 0,
+            Some(value) => value
+                .as_u64()
+                .and_then(|value| usize::try_from(value).ok())
+                .ok_or_else(|| {
+                    invalid(
+                        &format!("{pointer}/minItems"),
+                        "expected a positive integer",
+                    )
+                })?,
+        };
+
+        let mut known = BTreeMap::new();
+        for (index, item) in prefix.into_iter().flatten().enumerate() {
+            let mut kind = self.read(item, &format!("{pointer}/{prefix_keyword}/{index}"))?;
+            if index >= min_items {
+                kind.add_undefined();
+            }
+            known.insert(index.into(), kind);
+        }
+
+        let unknown = match rest {
+            Some(rest) => self.read(rest, &format!("{pointer}/{rest_keyword}"))?,
+            None => Kind::json(),
+        };
+
+        // Unknown elements can always be absent. This also keeps `never` (e.g. from a `false`
+        // schema) from being treated as "any".
+        Ok(Collection::from_parts(known, unknown.or_undefined()))
+    }
+
+    fn read_object(
+        &mut self,
+        map: &'a Map<String, JsonValue>,
+        pointer: &str,
+    ) -> Result<Collection<Field>, JsonSchemaError> {
+        let required = match map.get("required") {
+            None => Vec::new(),
+            Some(JsonValue::Array(fields)) => fields
+                .iter()
+                .map(|field| {
+                    field.as_str().ok_or_else(|| {
+                        invalid(
+                            &format!("{pointer}/required"),
+                            "expected an array of strings",
+                        )
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => {
+                return Err(invalid(
+                    &format!("{pointer}/required"),
+                    "expected an array of strings",
+                ));
+            }
+        };
+
+        let mut known = BTreeMap::new();
+        for (field, schema) in read_map(map, "properties", pointer)? {
+            let mut kind = self.read(schema, &format!("{pointer}/properties/{}", escape(field)))?;
+            if !required.contains(&field.as_str()) {
+                kind.add_undefined();
+            }
+            known.insert(Field::from(field.as_str()), kind);
+        }
+
+        // Fields that are required but not described can have any value.
+        for field in required {
+            known.entry(Field::from(field)).or_insert_with(Kind::json);
+        }
+
+        let mut unknown = match map.get("additionalProperties") {
+            Some(schema) => self.read(schema, &format!("{pointer}/additionalProperties"))?,
+            None => Kind::json(),
+        };
+
+        for (pattern, schema) in read_map(map, "patternProperties", pointer)? {
+            let kind = self.read(
+                schema,
+                &format!("{pointer}/patternProperties/{}", escape(pattern)),
+            )?;
+            unknown = unknown.union(kind);
+        }
+
+        // Unknown elements can always be absent. This also keeps `never` (e.g. from a `false`
+        // schema) from being treated as "any".
+        Ok(Collection::from_parts(known, unknown.or_undefined()))
+    }
+
+    fn read_all(
+        &mut self,
+        map: &'a Map<String, JsonValue>,
+        keyword: &str,
+        pointer: &str,
+    ) -> Result<Option<Vec<Kind>>, JsonSchemaError> {
+        let Some(schemas) = map.get(keyword) else {
+            return Ok(None);
+        };
+
+        let schemas = schemas
+            .as_array()
+            .ok_or_else(|| invalid(&format!("{pointer}/{keyword}"), "expected an array"))?;
+
+        schemas
+            .iter()
+            .enumerate()
+            .map(|(index, schema)| self.read(schema, &format!("{pointer}/{keyword}/{index}")))
+            .collect::<Result<_, _>>()
+            .map(Some)
+    }
+
+    fn read_ref(
+        &mut self,
+        reference: &'a JsonValue,
+        pointer: &str,
+    ) -> Result<Kind, JsonSchemaError> {
+        let reference = reference
+            .as_str()
+            .ok_or_else(|| invalid(&format!("{pointer}/$ref"), "expected a string"))?;
+
+        // Recursive schemas can't be expressed as a kind.
+        if self.refs.contains(&reference) {
+            self.recursed = true;
+            return Ok(Kind::json());
+        }
+
+        let (target_pointer, target) = reference
+            .strip_prefix('#')
+            .and_then(|target| Some((target, self.root.pointer(target)?)))
+            .ok_or_else(|| JsonSchemaError::UnresolvedRef {
+                pointer: pointer.to_owned(),
+                reference: reference.to_owned(),
+            })?;
+
+        if let Some(kind) = self.resolved.get(target_pointer) {
+            return Ok(kind.clone());
+        }
+
+        let recursed = std::mem::take(&mut self.recursed);
+        self.refs.push(reference);
+        let kind = self.read(target, target_pointer)?;
+        self.refs.pop();
+
+        if !self.recursed {
+            self.resolved.insert(target_pointer, kind.clone());
+        }
+        self.recursed |= recursed;
+
+        Ok(kind)
+    }
+}
+
+/// Get the schemas of a keyword that maps names to schemas, such as `properties`.
+fn read_map<'a>(
+    map: &'a Map<String, JsonValue>,
+    keyword: &str,
+    pointer: &str,
+) -> Result<impl Iterator<Item = (&'a String, &'a JsonValue)>, JsonSchemaError> {
+    match map.get(keyword) {
+        None => Ok(None.into_iter().flatten()),
+        Some(JsonValue::Object(map)) => Ok(Some(map).into_iter().flatten()),
+        Some(_) => Err(invalid(
+            &format!("{pointer}/{keyword}"),
+            "expected an object",
+        )),
+    }
+}
+
+/// Escape a name for use as a token of a JSON Pointer.
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+fn invalid(pointer: &str, reason: &str) -> JsonSchemaError {
+    JsonSchemaError::Invalid {
+        pointer: pointer.to_owned(),
+        reason: reason.to_owned(),
+    }
+}
+
+/// Approximate the intersection of two kinds.
+///
+/// The result is exact for primitive states. For collections, known elements are intersected
+/// with the matching known or unknown element of the other collection.
+fn intersect(lhs: &Kind, rhs: &Kind) -> Kind {
+    // This also guarantees termination for recursive "any" and "json" collections, which are the
+    // only recursive kinds produced by the reader.
+    if lhs.is_superset(rhs).is_ok() {
+        return rhs.clone();
+    }
+    if rhs.is_superset(lhs).is_ok() {
+        return lhs.clone();
+    }
+
+    Kind {
+        bytes: lhs.bytes.and(rhs.bytes),
+        integer: lhs.integer.and(rhs.integer),
+        float: lhs.float.and(rhs.float),
+        boolean: lhs.boolean.and(rhs.boolean),
+        timestamp: lhs.timestamp.and(rhs.timestamp),
+        regex: lhs.regex.and(rhs.regex),
+        null: lhs.null.and(rhs.null),
+        undefined: lhs.undefined.and(rhs.undefined),
+        array: lhs
+            .array
+            .as_ref()
+            .zip(rhs.array.as_ref())
+            .map(|(lhs, rhs)| intersect_collection(lhs, rhs)),
+        object: lhs
+            .object
+            .as_ref()
+            .zip(rhs.object.as_ref())
+            .map(|(lhs, rhs)| intersect_collection(lhs, rhs)),
+    }
+}
+
+fn intersect_collection<T: Ord + Clone>(lhs: &Collection<T>, rhs: &Collection<T>) -> Collection<T> {
+    let lhs_unknown = lhs.unknown_kind();
+    let rhs_unknown = rhs.unknown_kind();
+
+    let known = lhs
+        .known()
+        .keys()
+        .chain(rhs.known().keys())
+        .map(|key| {
+            let lhs = lhs.known().get(key).unwrap_or(&lhs_unknown);
+            let rhs = rhs.known().get(key).unwrap_or(&rhs_unknown);
+            (key.clone(), intersect(lhs, rhs))
+        })
+        .collect();
+
+    Collection::from_parts(known, intersect(&lhs_unknown, &rhs_unknown))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn test_to_json_schema() {
+        struct TestCase {
+            kind: Kind,
+            want: JsonValue,
+        }
+
+        for (title, TestCase { kind, want }) in HashMap::from([
+            (
+                "any",
+                TestCase {
+                    kind: Kind::any(),
+                    want: json!({}),
+                },
+            ),
+            (
+                "json",
+                TestCase {
+                    kind: Kind::json(),
+                    want: json!({}),
+                },
+            ),
+            (
+                "never",
+                TestCase {
+                    kind: Kind::never(),
+                    want: json!(false),
+                },
+            ),
+            (
+                "primitives",
+                TestCase {
+                    kind: Kind::bytes().or_integer().or_null(),
+                    want: json!({ "type": ["string", "integer", "null"] }),
+                },
+            ),
+            (
+                "timestamp",
+                TestCase {
+                    kind: Kind::timestamp(),
+                    want: json!({ "type": "string", "format": "date-time" }),
+                },
+            ),
+            (
+                "ambiguous format",
+                TestCase {
+                    kind: Kind::timestamp().or_bytes(),
+                    want: json!({ "type": "string" }),
+                },
+            ),
+            (
+                "object",
+                TestCase {
+                    kind: Kind::object(BTreeMap::from([
+                        ("foo".into(), Kind::integer()),
+                        ("bar".into(), Kind::bytes().or_undefined()),
+                    ])),
+                    want: json!({
+                        "type": "object",
+                        "properties": {
+                            "bar": { "type": "string" },
+                            "foo": { "type": "integer" },
+                        },
+                        "required": ["foo"],
+                        "additionalProperties": false,
+                    }),
+                },
+            ),
+            (
+                "open object",
+                TestCase {
+                    kind: Kind::object(Collection::from_unknown(Kind::float())),
+                    want: json!({
+                        "type": "object",
+                        "additionalProperties": { "type": "number" },
+                    }),
+                },
+            ),
+            (
+                "any object",
+                TestCase {
+                    kind: Kind::any_object(),
+                    want: json!({ "type": "object" }),
+                },
+            ),
+            (
+                "array",
+                TestCase {
+                    kind: Kind::array(Collection::from_parts(
+                        BTreeMap::from([(0.into(), Kind::bytes()), (2.into(), Kind::null())]),
+                        Kind::boolean(),
+                    )),
+                    want: json!({
+                        "type": "array",
+                        "prefixItems": [
+                            { "type": "string" },
+                            { "type": "boolean" },
+                            { "type": "null" },
+                        ],
+                        "minItems": 3,
+                        "items": { "type": "boolean" },
+                    }),
+                },
+            ),
+            (
+                "empty array",
+                TestCase {
+                    kind: Kind::array(Collection::empty()),
+                    want: json!({ "type": "array", "items": false }),
+                },
+            ),
+            (
+                "json array",
+                TestCase {
+                    kind: Kind::array(Collection::json()),
+                    want: json!({ "type": "array" }),
+                },
+            ),
+        ]) {
+            let mut want = want;
+            if let JsonValue::Object(map) = &mut want {
+                map.insert("$schema".to_owned(), DRAFT.into());
+            }
+
+            assert_eq!(kind.to_json_schema(), want, "{title}");
+        }
+    }
+
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn test_from_json_schema() {
+        struct TestCase {
+            schema: JsonValue,
+            want: Result<Kind, JsonSchemaError>,
+        }
+
+        for (title, TestCase { schema, want }) in HashMap::from([
+            (
+                "true",
+                TestCase {
+                    schema: json!(true),
+                    want: Ok(Kind::json()),
+                },
+            ),
+            (
+                "empty",
+                TestCase {
+                    schema: json!({}),
+                    want: Ok(Kind::json()),
+                },
+            ),
+            (
+                "false",
+                TestCase {
+                    schema: json!(false),
+                    want: Ok(Kind::never()),
+                },
+            ),
+            (
+                "primitives",
+                TestCase {
+                    schema: json!({ "type": ["string", "number", "null"] }),
+                    want: Ok(Kind::bytes().or_integer().or_float().or_null()),
+                },
+            ),
+            (
+                "formats",
+                TestCase {
+                    schema: json!({ "type": "string", "format": "date-time" }),
+                    want: Ok(Kind::timestamp()),
+                },
+            ),
+            (
+                "object",
+                TestCase {
+                    schema: json!({
+                        "type": "object",
+                        "properties": {
+                            "foo": { "type": "integer" },
+                            "bar": { "type": "string" },
+                        },
+                        "required": ["foo", "baz"],
+                        "additionalProperties": false,
+                    }),
+                    want: Ok(Kind::object(BTreeMap::from([
+                        ("foo".into(), Kind::integer()),
+                        ("bar".into(), Kind::bytes().or_undefined()),
+                        ("baz".into(), Kind::json()),
+                    ]))),
+                },
+            ),
+            (
+                "pattern properties",
+                TestCase {
+                    schema: json!({
+                        "type": "object",
+                        "patternProperties": { "^x-": { "type": "string" } },
+                        "additionalProperties": { "type": "boolean" },
+                    }),
+                    want: Ok(Kind::object(Collection::from_unknown(
+                        Kind::boolean().or_bytes(),
+                    ))),
+                },
+            ),
+            (
+                "tuple",
+                TestCase {
+                    schema: json!({
+                        "type": "array",
+                        "prefixItems": [{ "type": "string" }, { "type": "integer" }],
+                        "minItems": 1,
+                        "items": false,
+                    }),
+                    want: Ok(Kind::array(BTreeMap::from([
+                        (0.into(), Kind::bytes()),
+                        (1.into(), Kind::integer().or_undefined()),
+                    ]))),
+                },
+            ),
+            (
+                "draft-07 tuple",
+                TestCase {
+                    schema: json!({
+                        "type": "array",
+                        "items": [{ "type": "string" }],
+                        "minItems": 1,
+                        "additionalItems": { "type": "null" },
+                    }),
+                    want: Ok(Kind::array(Collection::from_parts(
+                        BTreeMap::from([(0.into(), Kind::bytes())]),
+                        Kind::null(),
+                    ))),
+                },
+            ),
+            (
+                "enum",
+                TestCase {
+                    schema: json!({ "enum": ["a", 1, null] }),
+                    want: Ok(Kind::bytes().or_integer().or_null()),
+                },
+            ),
+            (
+                "type and enum",
+                TestCase {
+                    schema: json!({ "type": "string", "enum": ["a", 1] }),
+                    want: Ok(Kind::bytes()),
+                },
+            ),
+            (
+                "any of",
+                TestCase {
+                    schema: json!({ "anyOf": [{ "type": "string" }, { "type": "boolean" }] }),
+                    want: Ok(Kind::bytes().or_boolean()),
+                },
+            ),
+            (
+                "all of",
+                TestCase {
+                    schema: json!({
+                        "allOf": [
+                            { "type": "object", "properties": { "a": { "type": "string" } } },
+                            { "type": "object", "required": ["a"] },
+                        ],
+                    }),
+                    want: Ok(Kind::object(Collection::from_parts(
+                        BTreeMap::from([("a".into(), Kind::bytes())]),
+                        Kind::json(),
+                    ))),
+                },
+            ),
+            (
+                "ref",
+                TestCase {
+                    schema: json!({
+                        "$defs": { "id": { "type": "integer" } },
+                        "type": "object",
+                        "properties": { "id": { "$ref": "#/$defs/id" } },
+                        "required": ["id"],
+                        "additionalProperties": false,
+                    }),
+                    want: Ok(Kind::object(BTreeMap::from([(
+                        "id".into(),
+                        Kind::integer(),
+                    )]))),
+                },
+            ),
+            (
+                "recursive ref",
+                TestCase {
+                    schema: json!({
+                        "type": "object",
+                        "properties": { "child": { "$ref": "#" } },
+                        "required": ["child"],
+                        "additionalProperties": false,
+                    }),
+                    want: Ok(Kind::object(BTreeMap::from([(
+                        "child".into(),
+                        Kind::object(BTreeMap::from([("child".into(), Kind::json())])),
+                    )]))),
+                },
+            ),
+            (
+                "external ref",
+                TestCase {
+                    schema: json!({ "properties": { "a": { "$ref": "other.json" } } }),
+                    want: Err(JsonSchemaError::UnresolvedRef {
+                        pointer: "/properties/a".to_owned(),
+                        reference: "other.json".to_owned(),
+                    }),
+                },
+            ),
+            (
+                "invalid schema of escaped property",
+                TestCase {
+                    schema: json!({ "properties": { "a/b": { "properties": { "~c": 1 } } } }),
+                    want: Err(JsonSchemaError::Invalid {
+                        pointer: "/properties/a~1b/properties/~0c".to_owned(),
+                        reason: "expected an object or a boolean".to_owned(),
+                    }),
+                },
+            ),
+            (
+                "invalid type",
+                TestCase {
+                    schema: json!({ "type": "date" }),
+                    want: Err(JsonSchemaError::Invalid {
+                        pointer: "/type".to_owned(),
+                        reason: "unknown type \"date\"".to_owned(),
+                    }),
+                },
+            ),
+        ]) {
+            assert_eq!(Kind::from_json_schema(&schema), want, "{title}");
+        }
+    }
+
+    #[test]
+    fn test_memoized_refs() {
+        let schema = json!({
+            "$defs": {
+                "id": { "type": "integer" },
+                "node": {
+                    "type": "object",
+                    "properties": { "child": { "$ref": "#/$defs/node" } },
+                    "additionalProperties": false,
+                },
+            },
+            "type": "object",
+            "properties": {
+                "a": { "$ref": "#/$defs/id" },
+                "b": { "$ref": "#/$defs/id" },
+                "c": { "$ref": "#/$defs/node" },
+                "d": { "$ref": "#/$defs/node" },
+            },
+            "required": ["a", "b", "c", "d"],
+            "additionalProperties": false,
+        });
+        let mut reader = Reader {
+            root: &schema,
+            refs: Vec::new(),
+            resolved: HashMap::new(),
+            recursed: false,
+        };
+
+        let node = Kind::object(BTreeMap::from([(
+            "child".into(),
+            Kind::json().or_undefined(),
+        )]));
+        assert_eq!(
+            reader.read(&schema, ""),
+            Ok(Kind::object(BTreeMap::from([
+                ("a".into(), Kind::integer()),
+                ("b".into(), Kind::integer()),
+                ("c".into(), node.clone()),
+                ("d".into(), node),
+            ])))
+        );
+
+        // Recursive schemas depend on where they are referenced from, so they aren't memoized.
+        assert_eq!(
+            reader.resolved,
+            HashMap::from([("/$defs/id", Kind::integer())])
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let kind = Kind::object(BTreeMap::from([
+            ("message".into(), Kind::bytes()),
+            ("timestamp".into(), Kind::timestamp()),
+            ("status".into(), Kind::integer().or_undefined()),
+            (
+                "tags".into(),
+                Kind::array(Collection::from_unknown(Kind::bytes())),
+            ),
+            (
+                "host".into(),
+                Kind::object(Collection::from_unknown(Kind::bytes().or_null())),
+            ),
+        ]));
+
+        assert_eq!(Kind::from_json_schema(&kind.to_json_schema()), Ok(kind));
+    }
+}
@@ -4,6 +4,8 @@
 #![deny(warnings, clippy::pedantic)]
 #![allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
 pub mod kind;
 pub mod secrets;
 
@@ -17,29 +19,36 @@ pub use kind::Kind;
 
 pub use self::keystring::KeyString;
 pub use self::secrets::Secrets;
+pub(crate) use self::value::IntoCollection;
 #[allow(clippy::module_name_repetitions)]
-pub use self::value::{ObjectMap, Value, ValueRegex};
+#[cfg(feature = "msgpack")]
+pub use self::value::MsgpackError;
+#[cfg(feature = "shared_collections")]
+pub use self::value::Shared;
+pub use self::value::{
+    ObjectEntry, ObjectMap, PatchError, PatchOperation, Value, ValueArray, ValueRegex,
+};
 
 /// A macro to easily generate Values
 #[macro_export]
 macro_rules! value {
     ([]) => ({
-        $crate::value::Value::Array(vec![])
+        $crate::value::Value::Array(vec![].into())
     });
 
     ([$($v:tt),+ $(,)?]) => ({
         let vec: Vec<$crate::value::Value> = vec![$($crate::value!($v)),+];
-        $crate::value::Value::Array(vec)
+        $crate::value::Value::Array(vec.into())
     });
 
     ({}) => ({
-        $crate::value::Value::Object(::std::collections::BTreeMap::default())
+        $crate::value::Value::Object($crate::value::ObjectMap::default())
     });
 
     ({$($($k1:literal)? $($k2:ident)?: $v:tt),+ $(,)?}) => ({
         let map = vec![$((String::from($($k1)? $(stringify!($k2))?).into(), $crate::value!($v))),+]
             .into_iter()
-            .collect::<::std::collections::BTreeMap<_, $crate::value::Value>>();
+            .collect::<$crate::value::ObjectMap>();
 
         $crate::value::Value::Object(map)
     });
@@ -4,12 +4,17 @@
 pub use super::value::regex::ValueRegex;
 #[allow(clippy::module_name_repetitions)]
 pub use iter::{IterItem, ValueIter};
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgpackError;
+pub use patch::{PatchError, PatchOperation};
+#[cfg(feature = "shared_collections")]
+pub use shared::Shared;
 
 use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, SecondsFormat, Utc};
 use ordered_float::NotNan;
 use std::borrow::Cow;
-use std::{cmp::Ordering, collections::BTreeMap};
+use std::cmp::Ordering;
 
 use super::KeyString;
 use crate::path::ValuePath;
@@ -18,8 +23,15 @@ mod convert;
 mod crud;
 mod display;
 mod iter;
+mod jsonpath;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+mod patch;
 mod path;
+mod query;
 mod regex;
+#[cfg(feature = "shared_collections")]
+mod shared;
 
 #[cfg(any(test, feature = "arbitrary"))]
 mod arbitrary;
@@ -31,10 +43,86 @@ mod serde;
 pub type StdError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 /// The storage mapping for the `Object` variant.
-pub type ObjectMap = BTreeMap<KeyString, Value>;
+///
+/// Fields are sorted by key, unless the `preserve_order` feature is enabled, in
+/// which case fields are kept in the order in which they were inserted.
+#[cfg(all(not(feature = "preserve_order"), not(feature = "shared_collections")))]
+pub type ObjectMap = std::collections::BTreeMap<KeyString, Value>;
+
+/// The storage mapping for the `Object` variant.
+///
+/// Fields are sorted by key, unless the `preserve_order` feature is enabled, in
+/// which case fields are kept in the order in which they were inserted.
+#[cfg(all(feature = "preserve_order", not(feature = "shared_collections")))]
+pub type ObjectMap = indexmap::IndexMap<KeyString, Value>;
+
+/// The storage mapping for the `Object` variant.
+///
+/// Fields are sorted by key, unless the `preserve_order` feature is enabled, in
+/// which case fields are kept in the order in which they were inserted.
+///
+/// The map is shared between clones, and copied on write. See [`Shared`].
+#[cfg(all(not(feature = "preserve_order"), feature = "shared_collections"))]
+pub type ObjectMap = Shared<std::collections::BTreeMap<KeyString, Value>>;
+
+/// The storage mapping for the `Object` variant.
+///
+/// Fields are sorted by key, unless the `preserve_order` feature is enabled, in
+/// which case fields are kept in the order in which they were inserted.
+///
+/// The map is shared between clones, and copied on write. See [`Shared`].
+#[cfg(all(feature = "preserve_order", feature = "shared_collections"))]
+pub type ObjectMap = Shared<indexmap::IndexMap<KeyString, Value>>;
+
+/// The storage for the `Array` variant.
+#[cfg(not(feature = "shared_collections"))]
+pub type ValueArray = Vec<Value>;
+
+/// The storage for the `Array` variant.
+///
+/// The array is shared between clones, and copied on write. See [`Shared`].
+#[cfg(feature = "shared_collections")]
+pub type ValueArray = Shared<Vec<Value>>;
+
+/// Takes the collection out of the storage of an `Object` or `Array` variant.
+///
+/// With the `shared_collections` feature, the collection is copied if it is
+/// shared. Otherwise the storage is the collection itself.
+pub(crate) trait IntoCollection {
+    type Collection;
+
+    fn into_collection(self) -> Self::Collection;
+}
+
+#[cfg(not(feature = "shared_collections"))]
+impl<T> IntoCollection for T {
+    type Collection = T;
+
+    fn into_collection(self) -> T {
+        self
+    }
+}
+
+#[cfg(feature = "shared_collections")]
+impl<T: Clone> IntoCollection for Shared<T> {
+    type Collection = T;
+
+    fn into_collection(self) -> T {
+        self.into_inner()
+    }
+}
+
+/// A view into a single field of an [`ObjectMap`].
+#[cfg(not(feature = "preserve_order"))]
+pub use std::collections::btree_map::Entry as ObjectEntry;
+
+/// A view into a single field of an [`ObjectMap`].
+#[cfg(feature = "preserve_order")]
+pub use indexmap::map::Entry as ObjectEntry;
 
 /// The main value type used in Vector events, and VRL.
-#[derive(Eq, PartialEq, Hash, Debug, Clone)]
+#[derive(Eq, PartialEq, Debug, Clone)]
+#[cfg_attr(not(feature = "preserve_order"), derive(Hash))]
 pub enum Value {
     /// Bytes - usually representing a UTF8 String.
     Bytes(Bytes),
@@ -60,7 +148,7 @@ pub enum Value {
     Object(ObjectMap),
 
     /// Array.
-    Array(Vec<Value>),
+    Array(ValueArray),
 
     /// Null.
     Null,
@@ -173,6 +261,72 @@ impl Value {
     pub fn contains<'a>(&self, path: impl ValuePath<'a>) -> bool {
         self.get(path).is_some()
     }
+
+    /// Compares two values using a total ordering.
+    ///
+    /// Unlike [`PartialOrd`], this allows comparing values of different types. Values are first
+    /// ordered by type, in the following order:
+    ///
+    /// `null` < `boolean` < `integer`/`float` < `string` < `regex` < `timestamp` < `array` < `object`
+    ///
+    /// Integers and floats are compared numerically against each other. If an integer and a float
+    /// are numerically equal, the integer is ordered first. Floats are ordered as by
+    /// [`f64::total_cmp`], so `-0.0` is ordered before `0.0`. Arrays are compared element-wise, and
+    /// objects are compared as sorted sequences of key/value pairs.
+    ///
+    /// ```rust
+    /// use std::cmp::Ordering;
+    /// use vrl::value::Value;
+    ///
+    /// assert_eq!(Value::from(1).total_cmp(&Value::from(2)), Ordering::Less);
+    /// assert_eq!(Value::from("a").total_cmp(&Value::from(2)), Ordering::Greater);
+    /// assert_eq!(Value::Null.total_cmp(&Value::from(false)), Ordering::Less);
+    /// ```
+    #[must_use]
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        const fn rank(value: &Value) -> u8 {
+            match value {
+                Value::Null => 0,
+                Value::Boolean(_) => 1,
+                Value::Integer(_) | Value::Float(_) => 2,
+                Value::Bytes(_) => 3,
+                Value::Regex(_) => 4,
+                Value::Timestamp(_) => 5,
+                Value::Array(_) => 6,
+                Value::Object(_) => 7,
+            }
+        }
+
+        match (self, other) {
+            (Self::Boolean(a), Self::Boolean(b)) => a.cmp(b),
+            (Self::Integer(a), Self::Integer(b)) => a.cmp(b),
+            (Self::Float(a), Self::Float(b)) => a.into_inner().total_cmp(&b.into_inner()),
+            #[allow(clippy::cast_precision_loss)]
+            (Self::Integer(a), Self::Float(b)) => {
+                (*a as f64).total_cmp(&b.into_inner()).then(Ordering::Less)
+            }
+            #[allow(clippy::cast_precision_loss)]
+            (Self::Float(a), Self::Integer(b)) => a
+                .into_inner()
+                .total_cmp(&(*b as f64))
+                .then(Ordering::Greater),
+            (Self::Bytes(a), Self::Bytes(b)) => a.cmp(b),
+            (Self::Regex(a), Self::Regex(b)) => a.as_str().cmp(b.as_str()),
+            (Self::Timestamp(a), Self::Timestamp(b)) => a.cmp(b),
+            (Self::Array(a), Self::Array(b)) => a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| a.total_cmp(b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            (Self::Object(a), Self::Object(b)) => sorted_fields(a)
+                .zip(sorted_fields(b))
+                .map(|((ak, av), (bk, bv))| ak.cmp(bk).then_with(|| av.total_cmp(bv)))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            (a, b) => rank(a).cmp(&rank(b)),
+        }
+    }
 }
 
 impl PartialOrd for Value {
@@ -187,13 +341,66 @@ impl PartialOrd for Value {
             (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
             (Self::Boolean(a), Self::Boolean(b)) => a.partial_cmp(b),
             (Self::Timestamp(a), Self::Timestamp(b)) => a.partial_cmp(b),
-            (Self::Object(a), Self::Object(b)) => a.partial_cmp(b),
+            (Self::Object(a), Self::Object(b)) => sorted_fields(a).partial_cmp(sorted_fields(b)),
             (Self::Array(a), Self::Array(b)) => a.partial_cmp(b),
             _ => None,
         }
     }
 }
 
+// Objects with the same fields are equal regardless of their field order, so
+// hashing has to visit the fields in a fixed order.
+#[cfg(feature = "preserve_order")]
+impl std::hash::Hash for Value {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Bytes(v) => v.hash(state),
+            Self::Regex(v) => v.hash(state),
+            Self::Integer(v) => v.hash(state),
+            Self::Float(v) => v.hash(state),
+            Self::Boolean(v) => v.hash(state),
+            Self::Timestamp(v) => v.hash(state),
+            Self::Object(v) => {
+                v.len().hash(state);
+                for field in sorted_fields(v) {
+                    field.hash(state);
+                }
+            }
+            Self::Array(v) => v.hash(state),
+            Self::Null => {}
+        }
+    }
+}
+
+/// Returns the fields of an object ordered by key, regardless of the order in
+/// which they are stored.
+#[cfg(not(feature = "preserve_order"))]
+pub(crate) fn sorted_fields(object: &ObjectMap) -> impl Iterator<Item = (&KeyString, &Value)> {
+    object.iter()
+}
+
+/// Returns the fields of an object ordered by key, regardless of the order in
+/// which they are stored.
+#[cfg(feature = "preserve_order")]
+pub(crate) fn sorted_fields(object: &ObjectMap) -> impl Iterator<Item = (&KeyString, &Value)> {
+    let mut fields = object.iter().collect::<Vec<_>>();
+    fields.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    fields.into_iter()
+}
+
+/// Removes a field from an object, keeping the order of the remaining fields.
+#[cfg(not(feature = "preserve_order"))]
+pub(crate) fn remove_field(object: &mut ObjectMap, key: &str) -> Option<Value> {
+    object.remove(key)
+}
+
+/// Removes a field from an object, keeping the order of the remaining fields.
+#[cfg(feature = "preserve_order")]
+pub(crate) fn remove_field(object: &mut ObjectMap, key: &str) -> Option<Value> {
+    object.shift_remove(key)
+}
+
 /// Converts a slice of bytes to a string, including invalid characters.
 #[must_use]
 pub fn simdutf_bytes_utf8_lossy(v: &[u8]) -> Cow<'_, str> {
@@ -228,6 +435,7 @@ mod test {
     use crate::path::BorrowedSegment;
 
     use super::*;
+    use std::collections::BTreeMap;
 
     mod corner_cases {
         use super::*;
@@ -321,4 +529,45 @@ mod test {
         );
         assert_eq!(Value::from(10.5).partial_cmp(&Value::from(10)), None);
     }
+
+    #[test]
+    fn total_cmp_value() {
+        assert_eq!(Value::from(2).total_cmp(&Value::from(10)), Ordering::Less);
+        assert_eq!(
+            Value::from(10.5).total_cmp(&Value::from(10)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            Value::from(10).total_cmp(&Value::from(10.0)),
+            Ordering::Less
+        );
+        assert_eq!(
+            Value::from(10.0).total_cmp(&Value::from(10)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            Value::from("a").total_cmp(&Value::from(10)),
+            Ordering::Greater
+        );
+        assert_eq!(Value::Null.total_cmp(&Value::from(false)), Ordering::Less);
+
+        // The order is transitive across signed zeros of floats and integers.
+        let (negative_zero, zero, positive_zero) =
+            (Value::from(-0.0), Value::from(0), Value::from(0.0));
+        assert_eq!(negative_zero.total_cmp(&zero), Ordering::Less);
+        assert_eq!(zero.total_cmp(&positive_zero), Ordering::Less);
+        assert_eq!(negative_zero.total_cmp(&positive_zero), Ordering::Less);
+        assert_eq!(positive_zero.total_cmp(&negative_zero), Ordering::Greater);
+
+        assert_eq!(
+            Value::from(vec![Value::from(1), Value::from(2)])
+                .total_cmp(&Value::from(vec![Value::from(1)])),
+            Ordering::Greater
+        );
+        assert_eq!(
+            Value::from(BTreeMap::from([("a".into(), Value::from(1))]))
+                .total_cmp(&Value::from(BTreeMap::from([("b".into(), Value::from(0))]))),
+            Ordering::Less
+        );
+    }
 }
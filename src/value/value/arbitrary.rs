@@ -50,7 +50,7 @@ impl Arbitrary for Value {
             }
             6 => {
                 let mut generator = Gen::new(MAX_ARRAY_SIZE);
-                Self::Array(Vec::arbitrary(&mut generator))
+                Self::from(Vec::<Self>::arbitrary(&mut generator))
             }
             7 => Self::Null,
             _ => unreachable!(),
@@ -8,7 +8,7 @@ use chrono::{DateTime, Utc};
 use ordered_float::NotNan;
 use regex::Regex;
 
-use super::super::{KeyString, Kind, ObjectMap, Value};
+use super::super::{KeyString, Kind, ObjectMap, Value, ValueArray};
 
 impl Value {
     /// Returns self as `NotNan<f64>`, only if self is `Value::Float`.
@@ -274,6 +274,13 @@ impl From<&[u8]> for Value {
     }
 }
 
+#[cfg(feature = "shared_collections")]
+impl From<ValueArray> for Value {
+    fn from(value: ValueArray) -> Self {
+        Self::Array(value)
+    }
+}
+
 impl<T: Into<Self>> From<Vec<T>> for Value {
     fn from(set: Vec<T>) -> Self {
         set.into_iter()
@@ -350,9 +357,30 @@ impl From<ObjectMap> for Value {
     }
 }
 
+/// Converts a sorted map into an object, with its fields in key order.
+#[cfg(any(feature = "preserve_order", feature = "shared_collections"))]
+impl From<std::collections::BTreeMap<KeyString, Value>> for Value {
+    #[cfg(not(feature = "preserve_order"))]
+    fn from(value: std::collections::BTreeMap<KeyString, Value>) -> Self {
+        Self::Object(value.into())
+    }
+
+    #[cfg(feature = "preserve_order")]
+    fn from(value: std::collections::BTreeMap<KeyString, Value>) -> Self {
+        Self::Object(value.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "shared_collections")]
+impl<const N: usize> From<[(KeyString, Value); N]> for ObjectMap {
+    fn from(fields: [(KeyString, Value); N]) -> Self {
+        fields.into_iter().collect()
+    }
+}
+
 impl FromIterator<Self> for Value {
     fn from_iter<I: IntoIterator<Item = Self>>(iter: I) -> Self {
-        Self::Array(iter.into_iter().collect::<Vec<Self>>())
+        Self::Array(iter.into_iter().collect::<ValueArray>())
     }
 }
 
@@ -1,4 +1,5 @@
-use crate::value::{KeyString, ObjectMap, Value};
+use crate::value::value::remove_field;
+use crate::value::{KeyString, ObjectMap, Value, ValueArray};
 use std::borrow::Borrow;
 
 mod get;
@@ -70,7 +71,7 @@ impl ValueCollection for ObjectMap {
     }
 
     fn remove_value(&mut self, key: &str) -> Option<Value> {
-        self.remove(key)
+        remove_field(self, key)
     }
 
     fn is_empty_collection(&self) -> bool {
@@ -91,7 +92,7 @@ fn array_index(array: &[Value], index: isize) -> Option<usize> {
     }
 }
 
-impl ValueCollection for Vec<Value> {
+impl ValueCollection for ValueArray {
     type Key = isize;
     type BorrowedKey = isize;
 
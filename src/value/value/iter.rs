@@ -1,6 +1,7 @@
 use std::{marker::PhantomData, ops::IndexMut};
 
 use super::{KeyString, Value};
+use crate::value::IntoCollection;
 
 impl Value {
     /// Create an iterator over the `Value`.
@@ -37,7 +38,7 @@ impl Value {
     pub fn into_iter<'a>(self, recursive: bool) -> ValueIter<'a> {
         let data = match self {
             Self::Object(object) => IterData::Object(object.into_iter().collect()),
-            Self::Array(array) => IterData::Array(array),
+            Self::Array(array) => IterData::Array(array.into_collection()),
             value => IterData::Value(value),
         };
 
@@ -124,7 +125,7 @@ impl<'a> Iterator for ValueIter<'a> {
                 Value::Object(object) => {
                     Some(IterData::Object(object.clone().into_iter().collect()))
                 }
-                Value::Array(array) => Some(IterData::Array(array.clone())),
+                Value::Array(array) => Some(IterData::Array(array.clone().into_collection())),
 
                 // It's possible the [`Value`] we're trying to iterate over is
                 // a non-collection type. This happens if the caller changed the
@@ -259,7 +260,7 @@ impl From<IterData> for Value {
         match iter {
             IterData::Value(value) => value,
             IterData::Object(object) => Self::Object(object.into_iter().collect()),
-            IterData::Array(array) => Self::Array(array),
+            IterData::Array(array) => Self::from(array),
         }
     }
 }
@@ -314,7 +315,7 @@ mod tests {
             (
                 "object non-recursive",
                 TestCase {
-                    value: Value::Object(BTreeMap::from([("foo".into(), true.into())])),
+                    value: Value::from(BTreeMap::from([("foo".into(), true.into())])),
                     recursive: false,
                     items: vec![true.into()],
                 },
@@ -0,0 +1,462 @@
+//! Evaluation of `JSONPath` (RFC 9535) queries.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+use regex::Regex;
+
+use crate::path::jsonpath::{
+    Comparable, ComparisonOp, FilterQuery, Function, JsonPathSegment, LogicalExpr, Pattern,
+    Selector,
+};
+use crate::path::{JsonPath, OwnedSegment, OwnedValuePath};
+
+use super::Value;
+
+type Node<'a> = (OwnedValuePath, &'a Value);
+
+impl Value {
+    /// Returns all values selected by a `JSONPath` query, together with their paths.
+    ///
+    /// The nodes are returned in the order defined by the RFC. Members of an object are visited in
+    /// the order of the object.
+    #[must_use]
+    pub fn query_json_path(&self, query: &JsonPath) -> Vec<(OwnedValuePath, &Self)> {
+        evaluate_segments(self, vec![(OwnedValuePath::root(), self)], &query.segments)
+    }
+}
+
+fn evaluate_segments<'a>(
+    root: &'a Value,
+    mut nodes: Vec<Node<'a>>,
+    segments: &[JsonPathSegment],
+) -> Vec<Node<'a>> {
+    for segment in segments {
+        let mut output = vec![];
+        for (path, value) in nodes {
+            if segment.descendant {
+                select_descendants(root, &path, value, &segment.selectors, &mut output);
+            } else {
+                select(root, &path, value, &segment.selectors, &mut output);
+            }
+        }
+        nodes = output;
+    }
+    nodes
+}
+
+/// Applies the selectors to `value` and all of its descendants, in document order.
+fn select_descendants<'a>(
+    root: &'a Value,
+    path: &OwnedValuePath,
+    value: &'a Value,
+    selectors: &[Selector],
+    output: &mut Vec<Node<'a>>,
+) {
+    select(root, path, value, selectors, output);
+
+    for (segment, child) in children(value) {
+        select_descendants(root, &child_path(path, segment), child, selectors, output);
+    }
+}
+
+fn select<'a>(
+    root: &'a Value,
+    path: &OwnedValuePath,
+    value: &'a Value,
+    selectors: &[Selector],
+    output: &mut Vec<Node<'a>>,
+) {
+    for selector in selectors {
+        match (selector, value) {
+            (Selector::Name(name), Value::Object(object)) => {
+                if let Some(child) = object.get(name) {
+                    output.push((child_path(path, OwnedSegment::Field(name.clone())), child));
+                }
+            }
+            (Selector::Index(index), Value::Array(array)) => {
+                if let Some(index) = normalize_index(*index, array.len())
+                    && let Some(child) = array.get(index)
+                {
+                    output.push((child_path(path, index_segment(index)), child));
+                }
+            }
+            (Selector::Slice { start, end, step }, Value::Array(array)) => {
+                for index in slice_indices(*start, *end, *step, array.len()) {
+                    output.push((child_path(path, index_segment(index)), &array[index]));
+                }
+            }
+            (Selector::Wildcard, value) => output
+                .extend(children(value).map(|(segment, child)| (child_path(path, segment), child))),
+            (Selector::Filter(expr), value) => output.extend(
+                children(value)
+                    .filter(|(_, child)| evaluate_logical(root, child, expr))
+                    .map(|(segment, child)| (child_path(path, segment), child)),
+            ),
+            _ => {}
+        }
+    }
+}
+
+fn children(value: &Value) -> Box<dyn Iterator<Item = (OwnedSegment, &Value)> + '_> {
+    match value {
+        Value::Object(object) => Box::new(
+            object
+                .iter()
+                .map(|(key, child)| (OwnedSegment::Field(key.clone()), child)),
+        ),
+        Value::Array(array) => Box::new(
+            array
+                .iter()
+                .enumerate()
+                .map(|(index, child)| (index_segment(index), child)),
+        ),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn child_path(path: &OwnedValuePath, segment: OwnedSegment) -> OwnedValuePath {
+    let mut path = path.clone();
+    path.push_segment(segment);
+    path
+}
+
+fn index_segment(index: usize) -> OwnedSegment {
+    OwnedSegment::Index(isize::try_from(index).expect("array index fits in isize"))
+}
+
+fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)
+    } else {
+        usize::try_from(index).ok()
+    }
+}
+
+/// Returns the indices selected by a slice, following section 2.3.4.2.2 of the RFC.
+fn slice_indices(
+    start: Option<i64>,
+    end: Option<i64>,
+    step: Option<i64>,
+    len: usize,
+) -> Box<dyn Iterator<Item = usize>> {
+    let step = step.unwrap_or(1);
+    let len = i64::try_from(len).expect("array length fits in i64");
+    let normalize = |index: i64| if index < 0 { len + index } else { index };
+
+    match step.cmp(&0) {
+        Ordering::Greater => {
+            let lower = normalize(start.unwrap_or(0)).clamp(0, len);
+            let upper = normalize(end.unwrap_or(len)).clamp(0, len);
+            let step = usize::try_from(step).expect("positive step");
+            Box::new((lower..upper).step_by(step).map(to_usize))
+        }
+        Ordering::Less => {
+            let upper = normalize(start.unwrap_or(len - 1)).clamp(-1, len - 1);
+            let lower = normalize(end.unwrap_or(-len - 1)).clamp(-1, len - 1);
+            let step = usize::try_from(step.unsigned_abs()).unwrap_or(usize::MAX);
+            Box::new(((lower + 1)..=upper).rev().step_by(step).map(to_usize))
+        }
+        Ordering::Equal => Box::new(std::iter::empty()),
+    }
+}
+
+fn to_usize(index: i64) -> usize {
+    usize::try_from(index).expect("index is not negative")
+}
+
+fn evaluate_logical(root: &Value, current: &Value, expr: &LogicalExpr) -> bool {
+    match expr {
+        LogicalExpr::Or(exprs) => exprs
+            .iter()
+            .any(|expr| evaluate_logical(root, current, expr)),
+        LogicalExpr::And(exprs) => exprs
+            .iter()
+            .all(|expr| evaluate_logical(root, current, expr)),
+        LogicalExpr::Not(expr) => !evaluate_logical(root, current, expr),
+        LogicalExpr::Comparison(left, op, right) => {
+            let left = evaluate_comparable(root, current, left);
+            let right = evaluate_comparable(root, current, right);
+            compare(left.as_deref(), *op, right.as_deref())
+        }
+        LogicalExpr::Exists(query) => !evaluate_query(root, current, query).is_empty(),
+        LogicalExpr::Function(function) => evaluate_function(root, current, function)
+            .is_some_and(|value| value == Value::Boolean(true)),
+    }
+}
+
+fn evaluate_query<'a>(root: &'a Value, current: &'a Value, query: &FilterQuery) -> Vec<Node<'a>> {
+    let start = if query.relative { current } else { root };
+    evaluate_segments(root, vec![(OwnedValuePath::root(), start)], &query.segments)
+}
+
+/// Evaluates a comparable, returning `None` if it results in "nothing".
+fn evaluate_comparable<'a>(
+    root: &'a Value,
+    current: &'a Value,
+    comparable: &'a Comparable,
+) -> Option<Cow<'a, Value>> {
+    match comparable {
+        Comparable::Literal(value) => Some(Cow::Borrowed(value)),
+        Comparable::Query(query) => {
+            singular(&evaluate_query(root, current, query)).map(Cow::Borrowed)
+        }
+        Comparable::Function(function) => {
+            evaluate_function(root, current, function).map(Cow::Owned)
+        }
+    }
+}
+
+fn singular<'a>(nodes: &[Node<'a>]) -> Option<&'a Value> {
+    match nodes {
+        [(_, value)] => Some(value),
+        _ => None,
+    }
+}
+
+fn evaluate_function(root: &Value, current: &Value, function: &Function) -> Option<Value> {
+    match function {
+        Function::Length(value) => {
+            let length = match evaluate_comparable(root, current, value)?.as_ref() {
+                Value::Bytes(bytes) => String::from_utf8_lossy(bytes).chars().count(),
+                Value::Array(array) => array.len(),
+                Value::Object(object) => object.len(),
+                _ => return None,
+            };
+            i64::try_from(length).ok().map(Value::Integer)
+        }
+        Function::Count(query) => i64::try_from(evaluate_query(root, current, query).len())
+            .ok()
+            .map(Value::Integer),
+        Function::Value(query) => singular(&evaluate_query(root, current, query)).cloned(),
+        Function::Match(value, pattern) | Function::Search(value, pattern) => {
+            let anchored = matches!(function, Function::Match(..));
+            let matched = match evaluate_comparable(root, current, value).as_deref() {
+                Some(Value::Bytes(bytes)) => {
+                    let string = String::from_utf8_lossy(bytes);
+                    match pattern {
+                        Pattern::Literal(regex) => {
+                            regex.as_ref().is_some_and(|regex| regex.is_match(&string))
+                        }
+                        Pattern::Dynamic(pattern) => {
+                            match evaluate_comparable(root, current, pattern).as_deref() {
+                                Some(Value::Bytes(pattern)) => {
+                                    let pattern = String::from_utf8_lossy(pattern);
+                                    let pattern = if anchored {
+                                        Cow::Owned(format!("^(?:{pattern})$"))
+                                    } else {
+                                        pattern
+                                    };
+                                    Regex::new(&pattern).is_ok_and(|regex| regex.is_match(&string))
+                                }
+                                _ => false,
+                            }
+                        }
+                    }
+                }
+                _ => false,
+            };
+            Some(Value::Boolean(matched))
+        }
+    }
+}
+
+/// Compares two values, as defined in section 2.3.5.2.2 of the RFC.
+fn compare(left: Option<&Value>, op: ComparisonOp, right: Option<&Value>) -> bool {
+    match op {
+        ComparisonOp::Eq => equal(left, right),
+        ComparisonOp::Ne => !equal(left, right),
+        ComparisonOp::Lt => less_than(left, right),
+        ComparisonOp::Le => less_than(left, right) || equal(left, right),
+        ComparisonOp::Gt => less_than(right, left),
+        ComparisonOp::Ge => less_than(right, left) || equal(left, right),
+    }
+}
+
+fn equal(left: Option<&Value>, right: Option<&Value>) -> bool {
+    match (left, right) {
+        (None, None) => true,
+        (Some(left), Some(right)) => values_equal(left, right),
+        _ => false,
+    }
+}
+
+fn values_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Array(left), Value::Array(right)) => {
+            left.len() == right.len()
+                && left
+                    .iter()
+                    .zip(right)
+                    .all(|(left, right)| values_equal(left, right))
+        }
+        (Value::Object(left), Value::Object(right)) => {
+            left.len() == right.len()
+                && left.iter().all(|(key, left)| {
+                    right
+                        .get(key)
+                        .is_some_and(|right| values_equal(left, right))
+                })
+        }
+        _ => number_ordering(left, right).map_or_else(|| left == right, Ordering::is_eq),
+    }
+}
+
+fn less_than(left: Option<&Value>, right: Option<&Value>) -> bool {
+    match (left, right) {
+        (Some(Value::Bytes(left)), Some(Value::Bytes(right))) => {
+            // UTF-8 byte order is the same as code point order.
+            left < right
+        }
+        (Some(left), Some(right)) => number_ordering(left, right).is_some_and(Ordering::is_lt),
+        _ => false,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)] // JSONPath numbers are compared as doubles
+fn number_ordering(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Integer(left), Value::Integer(right)) => Some(left.cmp(right)),
+        (Value::Integer(left), Value::Float(right)) => {
+            (*left as f64).partial_cmp(&right.into_inner())
+        }
+        (Value::Float(left), Value::Integer(right)) => {
+            left.into_inner().partial_cmp(&(*right as f64))
+        }
+        (Value::Float(left), Value::Float(right)) => Some(left.cmp(right)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{owned_value_path, value};
+
+    fn query(value: &Value, query: &str) -> Vec<Value> {
+        value
+            .query_json_path(&JsonPath::parse(query).unwrap())
+            .into_iter()
+            .map(|(_, value)| value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn selectors() {
+        let value = value!({
+            "a": [5, 3, [{ "j": 4 }, { "k": 6 }]],
+            "o": { "j": 1, "k": 2 }
+        });
+
+        let test_cases = [
+            ("$", vec![value.clone()]),
+            ("$.o['j', 'k', 'j']", vec![value!(1), value!(2), value!(1)]),
+            ("$['o']['j']", vec![value!(1)]),
+            ("$.o.*", vec![value!(1), value!(2)]),
+            ("$.a[-2]", vec![value!(3)]),
+            ("$.a[3]", vec![]),
+            ("$.a[0, 0]", vec![value!(5), value!(5)]),
+            ("$.a[1:]", vec![value!(3), value!([{ "j": 4 }, { "k": 6 }])]),
+            ("$.a[::-1][0]", vec![value!({ "j": 4 })]),
+            (
+                "$.a[0:3:2]",
+                vec![value!(5), value!([{ "j": 4 }, { "k": 6 }])],
+            ),
+            ("$.a[::0]", vec![]),
+            ("$..j", vec![value!(4), value!(1)]),
+            ("$.a..[0]", vec![value!(5), value!({ "j": 4 })]),
+            ("$.o[0]", vec![]),
+            ("$.a.j", vec![]),
+        ];
+
+        for (json_path, expected) in test_cases {
+            assert_eq!(query(&value, json_path), expected, "{json_path}");
+        }
+    }
+
+    #[test]
+    fn filters() {
+        // The examples of section 2.3.5.3 of the RFC.
+        let a =
+            value!([3, 5, 1, 2, 4, 6, { "b": "j" }, { "b": "k" }, { "b": {} }, { "b": "kilo" }]);
+        let o = value!({ "p": 1, "q": 2, "r": 3, "s": 5, "t": { "u": 6 } });
+        let mut value = value!({ "e": "f" });
+        value.insert("a", a.clone());
+        value.insert("o", o.clone());
+
+        let test_cases = [
+            ("$.a[?@.b == 'kilo']", vec![value!({ "b": "kilo" })]),
+            ("$.a[?(@.b == 'kilo')]", vec![value!({ "b": "kilo" })]),
+            ("$.a[?@>3.5]", vec![value!(5), value!(4), value!(6)]),
+            (
+                "$.a[?@.b]",
+                vec![
+                    value!({ "b": "j" }),
+                    value!({ "b": "k" }),
+                    value!({ "b": {} }),
+                    value!({ "b": "kilo" }),
+                ],
+            ),
+            ("$[?@.*]", vec![a.clone(), o.clone()]),
+            ("$[?@[?@.b]]", vec![a.clone()]),
+            (
+                "$.o[?@<3, ?@<3]",
+                vec![value!(1), value!(2), value!(1), value!(2)],
+            ),
+            (
+                r#"$.a[?@<2 || @.b == "k"]"#,
+                vec![value!(1), value!({ "b": "k" })],
+            ),
+            (
+                r#"$.a[?match(@.b, "[jk]")]"#,
+                vec![value!({ "b": "j" }), value!({ "b": "k" })],
+            ),
+            (
+                r#"$.a[?search(@.b, "[jk]")]"#,
+                vec![
+                    value!({ "b": "j" }),
+                    value!({ "b": "k" }),
+                    value!({ "b": "kilo" }),
+                ],
+            ),
+            ("$.o[?@>1 && @<4]", vec![value!(2), value!(3)]),
+            ("$.o[?@.u || @.x]", vec![value!({ "u": 6 })]),
+            (
+                "$.a[?@.b == $.x]",
+                vec![3, 5, 1, 2, 4, 6]
+                    .into_iter()
+                    .map(Value::from)
+                    .collect(),
+            ),
+            ("$.a[?@ == @]", a.as_array().unwrap().to_vec()),
+            ("$.a[?length(@.b) == 4]", vec![value!({ "b": "kilo" })]),
+            ("$[?count(@.*) == 5]", vec![o.clone()]),
+            ("$.o[?value(@..u) == 6]", vec![value!({ "u": 6 })]),
+            ("$.a[?@ == 2.0]", vec![value!(2)]),
+            ("$.a[?match(@.b, $.e)]", vec![]),
+            ("$.a[?match(@.b, '(')]", vec![]),
+        ];
+
+        for (json_path, expected) in test_cases {
+            assert_eq!(query(&value, json_path), expected, "{json_path}");
+        }
+    }
+
+    #[test]
+    fn paths() {
+        let value = value!({ "a": [{ "b": 1 }, { "b": 2 }] });
+        let paths = value
+            .query_json_path(&JsonPath::parse("$..b").unwrap())
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            vec![
+                owned_value_path!("a", 0, "b"),
+                owned_value_path!("a", 1, "b")
+            ]
+        );
+    }
+}
@@ -2,7 +2,7 @@ use mlua::prelude::LuaResult;
 use mlua::{FromLua, IntoLua, Lua, Value as LuaValue};
 use ordered_float::NotNan;
 
-use crate::value::Value;
+use crate::value::{KeyString, Value};
 
 impl IntoLua for Value {
     #![allow(clippy::wrong_self_convention)] // this trait is defined by mlua
@@ -39,11 +39,12 @@ impl FromLua for Value {
             LuaValue::Boolean(b) => Ok(Self::Boolean(b)),
             LuaValue::Table(t) => {
                 if t.len()? > 0 {
-                    <_>::from_lua(LuaValue::Table(t), lua).map(Self::Array)
+                    <Vec<Self>>::from_lua(LuaValue::Table(t), lua).map(Self::from)
                 } else if table_is_timestamp(&t)? {
                     table_to_timestamp(t).map(Self::Timestamp)
                 } else {
-                    <_>::from_lua(LuaValue::Table(t), lua).map(Self::Object)
+                    <std::collections::BTreeMap<KeyString, Self>>::from_lua(LuaValue::Table(t), lua)
+                        .map(|map| Self::Object(map.into_iter().collect()))
                 }
             }
             other => Err(mlua::Error::FromLuaConversionError {
@@ -157,7 +158,11 @@ mod test {
             ),
             (
                 "{1, '2', 0.57721566}",
-                Value::Array(vec![1_i64.into(), "2".into(), 0.577_215_66.into()]),
+                Value::from(vec![
+                    Value::from(1_i64),
+                    Value::from("2"),
+                    Value::from(0.577_215_66),
+                ]),
             ),
             (
                 "os.date('!*t', 1584297428)",
@@ -255,7 +260,11 @@ mod test {
                 ",
             ),
             (
-                Value::Array(vec![1_i64.into(), "2".into(), 0.577_215_66.into()]),
+                Value::from(vec![
+                    Value::from(1_i64),
+                    Value::from("2"),
+                    Value::from(0.577_215_66),
+                ]),
                 "
                 function (value)
                     return value[1] == 1 and
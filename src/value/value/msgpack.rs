@@ -0,0 +1,309 @@
+//! Conversion between [`Value`] and [`MessagePack`](https://msgpack.org).
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use ordered_float::NotNan;
+use rmpv::Value as MsgpackValue;
+
+use super::{ObjectMap, Value};
+
+/// The extension type of the `MessagePack` timestamp extension.
+const TIMESTAMP_EXT: i8 = -1;
+
+/// The extension type of Fluentd's `EventTime`.
+const FLUENTD_EVENT_TIME_EXT: i8 = 0;
+
+/// An error returned when `MessagePack` data can't be converted into a [`Value`].
+#[derive(Debug)]
+pub enum MsgpackError {
+    /// The data is not valid `MessagePack`.
+    Decode(rmpv::decode::Error),
+
+    /// The data contains more than one value.
+    TrailingBytes,
+
+    /// The data contains a value that has no equivalent `Value`.
+    Unsupported(String),
+}
+
+impl fmt::Display for MsgpackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(error) => error.fmt(f),
+            Self::TrailingBytes => f.write_str("trailing bytes after value"),
+            Self::Unsupported(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl std::error::Error for MsgpackError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Value {
+    /// Decode a single `MessagePack` value.
+    ///
+    /// Strings and binaries both become bytes. Timestamp extensions, as well as Fluentd's
+    /// `EventTime` extension, become timestamps. Map keys must be strings or binaries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data is not a single valid `MessagePack` value, or if it contains
+    /// another extension type, a `NaN` float, an integer larger than `i64::MAX`, or a map key that
+    /// isn't a string.
+    pub fn from_msgpack(mut bytes: &[u8]) -> Result<Self, MsgpackError> {
+        let value = rmpv::decode::read_value(&mut bytes).map_err(MsgpackError::Decode)?;
+
+        if !bytes.is_empty() {
+            return Err(MsgpackError::TrailingBytes);
+        }
+
+        from_msgpack_value(value)
+    }
+
+    /// Encode this value as `MessagePack`.
+    ///
+    /// Bytes are encoded as a string if they are valid UTF-8, and as a binary otherwise.
+    /// Timestamps use the timestamp extension, and regexes are encoded as their pattern.
+    ///
+    /// # Panics
+    ///
+    /// If writing the encoded value fails, which can't happen when writing to a `Vec`.
+    #[must_use]
+    pub fn to_msgpack(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &to_msgpack_value(self))
+            .expect("writing to a vec can't fail");
+        bytes
+    }
+}
+
+fn from_msgpack_value(value: MsgpackValue) -> Result<Value, MsgpackError> {
+    let value = match value {
+        MsgpackValue::Nil => Value::Null,
+        MsgpackValue::Boolean(boolean) => Value::Boolean(boolean),
+        MsgpackValue::Integer(integer) => match integer.as_i64() {
+            Some(integer) => Value::Integer(integer),
+            None => {
+                return Err(MsgpackError::Unsupported(format!(
+                    "integer {integer} is out of range"
+                )));
+            }
+        },
+        MsgpackValue::F32(float) => float_value(f64::from(float))?,
+        MsgpackValue::F64(float) => float_value(float)?,
+        MsgpackValue::String(string) => Value::Bytes(string.into_bytes().into()),
+        MsgpackValue::Binary(bytes) => Value::Bytes(bytes.into()),
+        MsgpackValue::Array(array) => Value::Array(
+            array
+                .into_iter()
+                .map(from_msgpack_value)
+                .collect::<Result<_, _>>()?,
+        ),
+        MsgpackValue::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        MsgpackValue::String(key) => {
+                            String::from_utf8_lossy(key.as_bytes()).into_owned()
+                        }
+                        MsgpackValue::Binary(key) => String::from_utf8_lossy(&key).into_owned(),
+                        key => {
+                            return Err(MsgpackError::Unsupported(format!(
+                                "map key {key} is not a string"
+                            )));
+                        }
+                    };
+                    Ok((key.into(), from_msgpack_value(value)?))
+                })
+                .collect::<Result<ObjectMap, _>>()?,
+        ),
+        MsgpackValue::Ext(kind @ (TIMESTAMP_EXT | FLUENTD_EVENT_TIME_EXT), data) => {
+            let timestamp = if kind == TIMESTAMP_EXT {
+                read_timestamp(&data)
+            } else {
+                read_event_time(&data)
+            };
+            Value::Timestamp(timestamp.ok_or_else(|| {
+                MsgpackError::Unsupported("invalid timestamp extension".to_owned())
+            })?)
+        }
+        MsgpackValue::Ext(kind, _) => {
+            return Err(MsgpackError::Unsupported(format!(
+                "unsupported extension type {kind}"
+            )));
+        }
+    };
+
+    Ok(value)
+}
+
+fn float_value(float: f64) -> Result<Value, MsgpackError> {
+    NotNan::new(float)
+        .map(Value::Float)
+        .map_err(|_| MsgpackError::Unsupported("NaN is not supported".to_owned()))
+}
+
+/// Read the 32, 64 or 96-bit layout of the timestamp extension.
+fn read_timestamp(data: &[u8]) -> Option<DateTime<Utc>> {
+    let (seconds, nanoseconds) = match data.len() {
+        4 => (i64::from(u32::from_be_bytes(data.try_into().ok()?)), 0),
+        8 => {
+            let value = u64::from_be_bytes(data.try_into().ok()?);
+            (
+                i64::try_from(value & 0x0003_ffff_ffff).ok()?,
+                u32::try_from(value >> 34).ok()?,
+            )
+        }
+        12 => (
+            i64::from_be_bytes(data[4..].try_into().ok()?),
+            u32::from_be_bytes(data[..4].try_into().ok()?),
+        ),
+        _ => return None,
+    };
+
+    DateTime::from_timestamp(seconds, nanoseconds)
+}
+
+/// Read Fluentd's `EventTime`: 32 bits of seconds followed by 32 bits of nanoseconds.
+fn read_event_time(data: &[u8]) -> Option<DateTime<Utc>> {
+    let (seconds, nanoseconds) = data.split_at_checked(4)?;
+    DateTime::from_timestamp(
+        i64::from(u32::from_be_bytes(seconds.try_into().ok()?)),
+        u32::from_be_bytes(nanoseconds.try_into().ok()?),
+    )
+}
+
+/// Write the smallest layout of the timestamp extension that fits `timestamp`.
+fn write_timestamp(timestamp: &DateTime<Utc>) -> Vec<u8> {
+    let seconds = timestamp.timestamp();
+    let nanoseconds = timestamp.timestamp_subsec_nanos();
+
+    if nanoseconds == 0
+        && let Ok(seconds) = u32::try_from(seconds)
+    {
+        seconds.to_be_bytes().to_vec()
+    } else if let Ok(seconds) = u64::try_from(seconds)
+        && seconds < 1 << 34
+    {
+        ((u64::from(nanoseconds) << 34) | seconds)
+            .to_be_bytes()
+            .to_vec()
+    } else {
+        let mut data = nanoseconds.to_be_bytes().to_vec();
+        data.extend(seconds.to_be_bytes());
+        data
+    }
+}
+
+fn to_msgpack_value(value: &Value) -> MsgpackValue {
+    match value {
+        Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(string) => MsgpackValue::from(string),
+            Err(_) => MsgpackValue::Binary(bytes.to_vec()),
+        },
+        Value::Regex(regex) => MsgpackValue::from(regex.as_str()),
+        Value::Integer(integer) => MsgpackValue::from(*integer),
+        Value::Float(float) => MsgpackValue::F64(float.into_inner()),
+        Value::Boolean(boolean) => MsgpackValue::Boolean(*boolean),
+        Value::Timestamp(timestamp) => MsgpackValue::Ext(TIMESTAMP_EXT, write_timestamp(timestamp)),
+        Value::Object(object) => MsgpackValue::Map(
+            object
+                .iter()
+                .map(|(key, value)| (MsgpackValue::from(key.as_str()), to_msgpack_value(value)))
+                .collect(),
+        ),
+        Value::Array(array) => MsgpackValue::Array(array.iter().map(to_msgpack_value).collect()),
+        Value::Null => MsgpackValue::Nil,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::value;
+
+    #[test]
+    fn round_trip() {
+        let mut value = value!({
+            "string": "hello",
+            "integer": (-42),
+            "float": 1.5,
+            "boolean": true,
+            "null": null,
+            "array": [1, "two", [3]],
+            "object": { "nested": { "a": 1 } }
+        });
+        value.insert("binary", Value::Bytes(vec![0xff, 0x00, 0xc3].into()));
+
+        for timestamp in [
+            Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+            Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap(),
+            Utc.timestamp_opt(-1_000, 5).unwrap(),
+            Utc.with_ymd_and_hms(2600, 1, 1, 0, 0, 0).unwrap(),
+        ] {
+            value.insert("timestamp", timestamp);
+            assert_eq!(Value::from_msgpack(&value.to_msgpack()).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn timestamp_layouts() {
+        // 32, 64 and 96-bit layouts.
+        for (bytes, expected) in [
+            (
+                vec![0xd6, 0xff, 0x00, 0x00, 0x00, 0x01],
+                Utc.timestamp_opt(1, 0).unwrap(),
+            ),
+            (
+                vec![0xd7, 0xff, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01],
+                Utc.timestamp_opt(1, 1).unwrap(),
+            ),
+            (
+                vec![
+                    0xc7, 0x0c, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                    0xff, 0xff,
+                ],
+                Utc.timestamp_opt(-1, 0).unwrap(),
+            ),
+        ] {
+            assert_eq!(Value::from_msgpack(&bytes).unwrap(), Value::from(expected));
+        }
+    }
+
+    #[test]
+    fn fluentd_event_time() {
+        let bytes = [0xd7, 0x00, 0x5b, 0x8b, 0x4d, 0x57, 0x00, 0x00, 0x00, 0x07];
+
+        assert_eq!(
+            Value::from_msgpack(&bytes).unwrap(),
+            Value::from(Utc.timestamp_opt(1_535_855_959, 7).unwrap())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        for (bytes, error) in [
+            (vec![0x01, 0x02], "trailing bytes after value"),
+            (vec![0xd4, 0x05, 0x00], "unsupported extension type 5"),
+            (vec![0x81, 0x01, 0x02], "map key 1 is not a string"),
+            (
+                vec![0xcf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+                "integer 18446744073709551615 is out of range",
+            ),
+        ] {
+            assert_eq!(Value::from_msgpack(&bytes).unwrap_err().to_string(), error);
+        }
+
+        assert!(Value::from_msgpack(&[0x92, 0x01]).is_err());
+    }
+}
@@ -0,0 +1,618 @@
+//! Structural diffs between values, and JSON Patch (RFC 6902) support.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{ObjectMap, Value, remove_field};
+use crate::path::{OwnedSegment, OwnedValuePath, is_json_pointer_index};
+
+/// A single operation of a JSON Patch (RFC 6902).
+///
+/// A list of operations is produced by [`Value::diff`], and can be applied using
+/// [`Value::apply_patch`]. Operations (de)serialize to and from their RFC 6902 representation,
+/// with paths formatted as JSON Pointers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchOperation {
+    /// Add a value to an object, insert it into an array, or replace the root value.
+    Add { path: OwnedValuePath, value: Value },
+
+    /// Remove the value at the path.
+    Remove { path: OwnedValuePath },
+
+    /// Replace the (existing) value at the path.
+    Replace { path: OwnedValuePath, value: Value },
+
+    /// Remove the value at `from`, and add it at `path`.
+    Move {
+        from: OwnedValuePath,
+        path: OwnedValuePath,
+    },
+
+    /// Add a copy of the value at `from` at `path`.
+    Copy {
+        from: OwnedValuePath,
+        path: OwnedValuePath,
+    },
+
+    /// Check that the value at the path is equal to the given value.
+    Test { path: OwnedValuePath, value: Value },
+}
+
+/// An error returned when a patch can't be applied, or an operation can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// The value at the path (or its parent) does not exist.
+    NotFound { path: OwnedValuePath },
+
+    /// A `test` operation failed.
+    TestFailed { path: OwnedValuePath },
+
+    /// The operation is malformed, or can't be applied to the value.
+    InvalidOperation { reason: String },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { path } => {
+                write!(f, "path \"{}\" does not exist", path.to_json_pointer())
+            }
+            Self::TestFailed { path } => {
+                write!(f, "test failed for path \"{}\"", path.to_json_pointer())
+            }
+            Self::InvalidOperation { reason } => write!(f, "invalid patch operation: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {}
+
+impl Value {
+    /// Compute the operations needed to turn `self` into `other`.
+    ///
+    /// Objects are compared field by field and arrays index by index, so an element inserted in
+    /// the middle of an array results in changes to all following elements. Applying the result to
+    /// `self` using [`Value::apply_patch`] results in `other`.
+    ///
+    /// ```rust
+    /// use vrl::value;
+    ///
+    /// let before = value!({ "a": 1, "b": [1, 2] });
+    /// let after = value!({ "b": [1, 3], "c": true });
+    ///
+    /// let mut patched = before.clone();
+    /// patched.apply_patch(&before.diff(&after)).unwrap();
+    /// assert_eq!(patched, after);
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Vec<PatchOperation> {
+        let mut operations = Vec::new();
+        diff(self, other, &mut OwnedValuePath::root(), &mut operations);
+        operations
+    }
+
+    /// Apply a list of patch operations.
+    ///
+    /// Operations are applied in order. If any of them fails, `self` is left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a path doesn't exist, a `test` operation fails, or an operation can't
+    /// be applied (for example when adding a field to a string).
+    pub fn apply_patch(&mut self, operations: &[PatchOperation]) -> Result<(), PatchError> {
+        let mut patched = self.clone();
+        for operation in operations {
+            operation.apply(&mut patched)?;
+        }
+        *self = patched;
+
+        Ok(())
+    }
+
+    /// Returns the value at a path parsed from a JSON Pointer, using
+    /// [`OwnedValuePath::from_json_pointer`].
+    ///
+    /// Unlike [`Value::get`], index segments are resolved as fields against an object, and
+    /// negative indices never match.
+    #[must_use]
+    pub fn get_json_pointer(&self, path: &OwnedValuePath) -> Option<&Self> {
+        path.segments
+            .iter()
+            .try_fold(self, |value, segment| match value {
+                Value::Object(object) => object.get(object_key(segment).as_str()),
+                Value::Array(array) => array_index(segment).and_then(|index| array.get(index)),
+                _ => None,
+            })
+    }
+}
+
+fn diff(lhs: &Value, rhs: &Value, path: &mut OwnedValuePath, operations: &mut Vec<PatchOperation>) {
+    match (lhs, rhs) {
+        (Value::Object(lhs), Value::Object(rhs)) => {
+            for (key, lhs) in lhs {
+                path.push_field(key);
+                match rhs.get(key) {
+                    Some(rhs) => diff(lhs, rhs, path, operations),
+                    None => operations.push(PatchOperation::Remove { path: path.clone() }),
+                }
+                path.segments.pop();
+            }
+
+            for (key, rhs) in rhs {
+                if !lhs.contains_key(key) {
+                    operations.push(PatchOperation::Add {
+                        path: path.with_field_appended(key),
+                        value: rhs.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(lhs), Value::Array(rhs)) => {
+            for (index, (lhs, rhs)) in lhs.iter().zip(rhs).enumerate() {
+                path.push_index(to_isize(index));
+                diff(lhs, rhs, path, operations);
+                path.segments.pop();
+            }
+
+            for (index, rhs) in rhs.iter().enumerate().skip(lhs.len()) {
+                operations.push(PatchOperation::Add {
+                    path: path.with_index_appended(to_isize(index)),
+                    value: rhs.clone(),
+                });
+            }
+
+            // Remove from the end, so the indices of the remaining elements stay valid.
+            for index in (rhs.len()..lhs.len()).rev() {
+                operations.push(PatchOperation::Remove {
+                    path: path.with_index_appended(to_isize(index)),
+                });
+            }
+        }
+        (lhs, rhs) if lhs == rhs => {}
+        (_, rhs) => operations.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: rhs.clone(),
+        }),
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+const fn to_isize(index: usize) -> isize {
+    index as isize
+}
+
+impl PatchOperation {
+    /// The name of the operation, as used in the `op` member of a JSON Patch.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Add { .. } => "add",
+            Self::Remove { .. } => "remove",
+            Self::Replace { .. } => "replace",
+            Self::Move { .. } => "move",
+            Self::Copy { .. } => "copy",
+            Self::Test { .. } => "test",
+        }
+    }
+
+    /// The path the operation applies to.
+    #[must_use]
+    pub const fn path(&self) -> &OwnedValuePath {
+        match self {
+            Self::Add { path, .. }
+            | Self::Remove { path }
+            | Self::Replace { path, .. }
+            | Self::Move { path, .. }
+            | Self::Copy { path, .. }
+            | Self::Test { path, .. } => path,
+        }
+    }
+
+    fn apply(&self, target: &mut Value) -> Result<(), PatchError> {
+        match self {
+            Self::Add { path, value } => add(target, path, value.clone()),
+            Self::Remove { path } => remove(target, path).map(drop),
+            Self::Replace { path, value } => {
+                *get_mut(target, path)? = value.clone();
+                Ok(())
+            }
+            Self::Move { from, path } => {
+                if from == path {
+                    return Ok(());
+                }
+                if path.segments.starts_with(&from.segments) {
+                    return Err(PatchError::InvalidOperation {
+                        reason: format!(
+                            "can't move \"{}\" into one of its children",
+                            from.to_json_pointer()
+                        ),
+                    });
+                }
+                let value = remove(target, from)?;
+                add(target, path, value)
+            }
+            Self::Copy { from, path } => {
+                let value = get_mut(target, from)?.clone();
+                add(target, path, value)
+            }
+            Self::Test { path, value } => {
+                if get_mut(target, path)? == value {
+                    Ok(())
+                } else {
+                    Err(PatchError::TestFailed { path: path.clone() })
+                }
+            }
+        }
+    }
+}
+
+/// Resolve a segment against an array, returning `None` if it isn't a valid index.
+fn array_index(segment: &OwnedSegment) -> Option<usize> {
+    match segment {
+        OwnedSegment::Index(index) => usize::try_from(*index).ok(),
+        OwnedSegment::Field(field) if is_json_pointer_index(field) => field.parse().ok(),
+        OwnedSegment::Field(_) => None,
+    }
+}
+
+/// Resolve a segment against an object. Index segments are treated as fields, since a JSON
+/// Pointer doesn't distinguish between the two.
+fn object_key(segment: &OwnedSegment) -> String {
+    match segment {
+        OwnedSegment::Field(field) => field.to_string(),
+        OwnedSegment::Index(index) => index.to_string(),
+    }
+}
+
+fn get_mut<'a>(target: &'a mut Value, path: &OwnedValuePath) -> Result<&'a mut Value, PatchError> {
+    let not_found = || PatchError::NotFound { path: path.clone() };
+
+    path.segments
+        .iter()
+        .try_fold(target, |value, segment| match value {
+            Value::Object(object) => object.get_mut(object_key(segment).as_str()),
+            Value::Array(array) => array_index(segment).and_then(|index| array.get_mut(index)),
+            _ => None,
+        })
+        .ok_or_else(not_found)
+}
+
+/// Split the path into the (existing) parent value and the last segment.
+fn parent_mut<'a, 'b>(
+    target: &'a mut Value,
+    path: &'b OwnedValuePath,
+) -> Result<(&'a mut Value, Option<&'b OwnedSegment>), PatchError> {
+    match path.segments.split_last() {
+        Some((last, parent)) => {
+            let parent = get_mut(target, &parent.to_vec().into())
+                .map_err(|_| PatchError::NotFound { path: path.clone() })?;
+            Ok((parent, Some(last)))
+        }
+        None => Ok((target, None)),
+    }
+}
+
+fn add(target: &mut Value, path: &OwnedValuePath, value: Value) -> Result<(), PatchError> {
+    match parent_mut(target, path)? {
+        (root, None) => *root = value,
+        (Value::Object(object), Some(segment)) => {
+            object.insert(object_key(segment).into(), value);
+        }
+        (Value::Array(array), Some(segment)) => {
+            let index = match segment {
+                OwnedSegment::Field(field) if field.as_str() == "-" => array.len(),
+                segment => array_index(segment)
+                    .filter(|index| *index <= array.len())
+                    .ok_or_else(|| PatchError::NotFound { path: path.clone() })?,
+            };
+            array.insert(index, value);
+        }
+        (_, Some(_)) => {
+            return Err(PatchError::InvalidOperation {
+                reason: format!(
+                    "can't add \"{}\" to a value that is not an object or array",
+                    path.to_json_pointer()
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn remove(target: &mut Value, path: &OwnedValuePath) -> Result<Value, PatchError> {
+    let not_found = || PatchError::NotFound { path: path.clone() };
+
+    match parent_mut(target, path)? {
+        (_, None) => Err(PatchError::InvalidOperation {
+            reason: "can't remove the root value".to_owned(),
+        }),
+        (Value::Object(object), Some(segment)) => {
+            remove_field(object, object_key(segment).as_str()).ok_or_else(not_found)
+        }
+        (Value::Array(array), Some(segment)) => array_index(segment)
+            .filter(|index| *index < array.len())
+            .map(|index| array.remove(index))
+            .ok_or_else(not_found),
+        (_, Some(_)) => Err(not_found()),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// (de)serialization
+
+impl From<&PatchOperation> for Value {
+    fn from(operation: &PatchOperation) -> Self {
+        let mut object = ObjectMap::new();
+        object.insert("op".into(), operation.name().into());
+
+        if let PatchOperation::Move { from, .. } | PatchOperation::Copy { from, .. } = operation {
+            object.insert("from".into(), from.to_json_pointer().into());
+        }
+
+        object.insert("path".into(), operation.path().to_json_pointer().into());
+
+        if let PatchOperation::Add { value, .. }
+        | PatchOperation::Replace { value, .. }
+        | PatchOperation::Test { value, .. } = operation
+        {
+            object.insert("value".into(), value.clone());
+        }
+
+        Self::Object(object)
+    }
+}
+
+impl From<PatchOperation> for Value {
+    fn from(operation: PatchOperation) -> Self {
+        (&operation).into()
+    }
+}
+
+impl TryFrom<&Value> for PatchOperation {
+    type Error = PatchError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let invalid = |reason: &str| PatchError::InvalidOperation {
+            reason: reason.to_owned(),
+        };
+
+        let object = value
+            .as_object()
+            .ok_or_else(|| invalid("operation must be an object"))?;
+
+        let pointer = |key: &str| {
+            let pointer = object
+                .get(key)
+                .ok_or_else(|| invalid(&format!("missing \"{key}\"")))?
+                .as_str()
+                .ok_or_else(|| invalid(&format!("\"{key}\" must be a string")))?;
+
+            OwnedValuePath::from_json_pointer(&pointer)
+                .map_err(|_| invalid(&format!("\"{pointer}\" is not a valid JSON Pointer")))
+        };
+        let value = || {
+            object
+                .get("value")
+                .cloned()
+                .ok_or_else(|| invalid("missing \"value\""))
+        };
+
+        let op = object
+            .get("op")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid("missing \"op\""))?;
+
+        let path = pointer("path")?;
+        Ok(match op.as_ref() {
+            "add" => Self::Add {
+                path,
+                value: value()?,
+            },
+            "remove" => Self::Remove { path },
+            "replace" => Self::Replace {
+                path,
+                value: value()?,
+            },
+            "move" => Self::Move {
+                from: pointer("from")?,
+                path,
+            },
+            "copy" => Self::Copy {
+                from: pointer("from")?,
+                path,
+            },
+            "test" => Self::Test {
+                path,
+                value: value()?,
+            },
+            op => return Err(invalid(&format!("unknown operation \"{op}\""))),
+        })
+    }
+}
+
+impl Serialize for PatchOperation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Value::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PatchOperation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        Self::try_from(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::owned_value_path;
+    use crate::value;
+
+    #[test]
+    fn diff_and_patch() {
+        let cases = [
+            (value!(1), value!(1)),
+            (value!(1), value!("one")),
+            (value!({ "a": 1 }), value!([1])),
+            (
+                value!({ "a": 1, "b": { "c": true, "d": null } }),
+                value!({ "a": 2, "b": { "c": true }, "e": [] }),
+            ),
+            (value!([1, 2, 3, 4]), value!([1, 3])),
+            (value!([1]), value!([0, 1, { "a": 1 }])),
+            (
+                value!({ "a/b": { "~": [1, { "x": 1 }] } }),
+                value!({ "a/b": { "~": [1, { "x": 2 }] } }),
+            ),
+        ];
+
+        for (before, after) in cases {
+            let operations = before.diff(&after);
+            let mut patched = before.clone();
+            patched.apply_patch(&operations).unwrap();
+            assert_eq!(patched, after, "{operations:?}");
+        }
+    }
+
+    #[test]
+    fn diff_operations() {
+        let before = value!({ "a": 1, "b": [1, 2, 3], "c": { "d": true } });
+        let after = value!({ "b": [1, 5], "c": { "d": true, "e": "x" } });
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                PatchOperation::Remove {
+                    path: owned_value_path!("a"),
+                },
+                PatchOperation::Replace {
+                    path: owned_value_path!("b", 1),
+                    value: value!(5),
+                },
+                PatchOperation::Remove {
+                    path: owned_value_path!("b", 2),
+                },
+                PatchOperation::Add {
+                    path: owned_value_path!("c", "e"),
+                    value: value!("x"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn json_patch() {
+        // The example from RFC 6902, appendix A.16, followed by a mix of other operations.
+        let patch = serde_json::json!([
+            { "op": "add", "path": "/foo/-", "value": ["abc", "def"] },
+            { "op": "copy", "from": "/foo/0", "path": "/bar" },
+            { "op": "move", "from": "/foo/1/0", "path": "/baz" },
+            { "op": "test", "path": "/bar", "value": "bar" },
+            { "op": "replace", "path": "/0", "value": 0 },
+            { "op": "remove", "path": "/foo/0" },
+        ]);
+        let operations: Vec<PatchOperation> = serde_json::from_value(patch.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&operations).unwrap(), patch);
+
+        let mut target = value!({ "foo": ["bar"], "0": null });
+        target.apply_patch(&operations).unwrap();
+        assert_eq!(
+            target,
+            value!({ "foo": [["def"]], "bar": "bar", "baz": "abc", "0": 0 })
+        );
+    }
+
+    #[test]
+    fn patch_errors() {
+        let cases = [
+            (
+                PatchOperation::Remove {
+                    path: owned_value_path!("missing"),
+                },
+                PatchError::NotFound {
+                    path: owned_value_path!("missing"),
+                },
+            ),
+            (
+                PatchOperation::Add {
+                    path: owned_value_path!("a", "b", "c"),
+                    value: value!(1),
+                },
+                PatchError::NotFound {
+                    path: owned_value_path!("a", "b", "c"),
+                },
+            ),
+            (
+                PatchOperation::Add {
+                    path: owned_value_path!("list", 3),
+                    value: value!(1),
+                },
+                PatchError::NotFound {
+                    path: owned_value_path!("list", 3),
+                },
+            ),
+            (
+                PatchOperation::Test {
+                    path: owned_value_path!("a"),
+                    value: value!(2),
+                },
+                PatchError::TestFailed {
+                    path: owned_value_path!("a"),
+                },
+            ),
+        ];
+
+        for (operation, error) in cases {
+            let mut target = value!({ "a": 1, "list": [1] });
+            let operations = [
+                PatchOperation::Remove {
+                    path: owned_value_path!("list", 0),
+                },
+                operation,
+            ];
+
+            assert_eq!(target.apply_patch(&operations), Err(error));
+            // Failed patches leave the value untouched.
+            assert_eq!(target, value!({ "a": 1, "list": [1] }));
+        }
+    }
+
+    #[test]
+    fn json_pointer() {
+        // The examples of section 5 of RFC 6901.
+        let value = value!({
+            "foo": ["bar", "baz"],
+            "": 0,
+            "a/b": 1,
+            "m~n": 8,
+            "0": "zero"
+        });
+
+        let cases = [
+            ("", Some(value.clone())),
+            ("/foo", Some(value!(["bar", "baz"]))),
+            ("/foo/0", Some(value!("bar"))),
+            ("/", Some(value!(0))),
+            ("/a~1b", Some(value!(1))),
+            ("/m~0n", Some(value!(8))),
+            ("/0", Some(value!("zero"))),
+            ("/foo/2", None),
+            ("/foo/-", None),
+            ("/foo/01", None),
+            ("/foo/+1", None),
+            ("/bar", None),
+        ];
+
+        for (pointer, expected) in cases {
+            let path = OwnedValuePath::from_json_pointer(pointer).unwrap();
+            assert_eq!(
+                value.get_json_pointer(&path).cloned(),
+                expected,
+                "{pointer}"
+            );
+        }
+    }
+}
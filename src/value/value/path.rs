@@ -29,10 +29,10 @@ mod at_path_tests {
         let path = parse_value_path(".foo.bar.baz").unwrap();
         let value = Value::Integer(12);
 
-        let bar_value = Value::Object(BTreeMap::from([("baz".into(), value.clone())]));
-        let foo_value = Value::Object(BTreeMap::from([("bar".into(), bar_value)]));
+        let bar_value = Value::from(BTreeMap::from([("baz".into(), value.clone())]));
+        let foo_value = Value::from(BTreeMap::from([("bar".into(), bar_value)]));
 
-        let object = Value::Object(BTreeMap::from([("foo".into(), foo_value)]));
+        let object = Value::from(BTreeMap::from([("foo".into(), foo_value)]));
 
         assert_eq!(value.at_path(&path), object);
     }
@@ -51,7 +51,7 @@ mod at_path_tests {
         let path = parse_value_path("[2]").unwrap();
         let value = Value::Integer(12);
 
-        let object = Value::Array(vec![Value::Null, Value::Null, Value::Integer(12)]);
+        let object = Value::from(vec![Value::Null, Value::Null, Value::Integer(12)]);
 
         assert_eq!(value.at_path(&path), object);
     }
@@ -61,13 +61,13 @@ mod at_path_tests {
         let path = parse_value_path("[2].foo.baz[1]").unwrap();
         let value = Value::Object([("bar".into(), vec![12].into())].into()); //value!({ "bar": [12] });
 
-        let baz_value = Value::Array(vec![Value::Null, value.clone()]);
-        let foo_value = Value::Object(BTreeMap::from([("baz".into(), baz_value)]));
+        let baz_value = Value::from(vec![Value::Null, value.clone()]);
+        let foo_value = Value::from(BTreeMap::from([("baz".into(), baz_value)]));
 
-        let object = Value::Array(vec![
+        let object = Value::from(vec![
             Value::Null,
             Value::Null,
-            Value::Object(BTreeMap::from([("foo".into(), foo_value)])),
+            Value::from(BTreeMap::from([("foo".into(), foo_value)])),
         ]);
 
         assert_eq!(value.at_path(&path), object);
@@ -0,0 +1,201 @@
+//! Retrieving and removing all values matched by a query.
+
+use crate::path::{OwnedSegment, OwnedValuePath, OwnedValueQuery, QuerySegment};
+
+use super::Value;
+
+impl Value {
+    /// Returns all values matched by the query, together with their paths.
+    ///
+    /// Matches are returned in document order, with a value preceding the values nested in it.
+    #[must_use]
+    pub fn get_all(&self, query: &OwnedValueQuery) -> Vec<(OwnedValuePath, &Self)> {
+        let mut matches = vec![];
+        collect(
+            self,
+            &mut OwnedValuePath::root(),
+            &query.segments,
+            &mut matches,
+        );
+        matches
+    }
+
+    /// Removes all values matched by the query, and returns them in document order.
+    ///
+    /// Values are removed last to first, so removing array elements doesn't change the indices of
+    /// the other matches. If a match is nested in another match, the inner value is removed first,
+    /// and the outer value is returned without it.
+    pub fn remove_all(&mut self, query: &OwnedValueQuery) -> Vec<Self> {
+        let paths = self
+            .get_all(query)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+
+        let mut removed = paths
+            .iter()
+            .rev()
+            .filter_map(|path| self.remove(path, false))
+            .collect::<Vec<_>>();
+        removed.reverse();
+        removed
+    }
+}
+
+fn collect<'a>(
+    value: &'a Value,
+    path: &mut OwnedValuePath,
+    segments: &[QuerySegment],
+    matches: &mut Vec<(OwnedValuePath, &'a Value)>,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        matches.push((path.clone(), value));
+        return;
+    };
+
+    match (segment, value) {
+        (QuerySegment::Field(field), Value::Object(map)) => {
+            if let Some(value) = map.get(field) {
+                path.push_segment(OwnedSegment::Field(field.clone()));
+                collect(value, path, rest, matches);
+                path.segments.pop();
+            }
+        }
+        (QuerySegment::Index(index), Value::Array(array)) => {
+            let index = if *index < 0 {
+                array.len().checked_sub(index.unsigned_abs())
+            } else {
+                Some(index.unsigned_abs())
+            };
+
+            if let Some((index, value)) = index.and_then(|i| Some((i, array.get(i)?))) {
+                path.push_segment(OwnedSegment::Index(index_segment(index)));
+                collect(value, path, rest, matches);
+                path.segments.pop();
+            }
+        }
+        (QuerySegment::Wildcard, value) => collect_children(value, path, rest, matches),
+        (QuerySegment::Descendants, value) => {
+            collect(value, path, rest, matches);
+            collect_children(value, path, segments, matches);
+        }
+        _ => {}
+    }
+}
+
+fn collect_children<'a>(
+    value: &'a Value,
+    path: &mut OwnedValuePath,
+    segments: &[QuerySegment],
+    matches: &mut Vec<(OwnedValuePath, &'a Value)>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                path.push_segment(OwnedSegment::Field(key.clone()));
+                collect(value, path, segments, matches);
+                path.segments.pop();
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.iter().enumerate() {
+                path.push_segment(OwnedSegment::Index(index_segment(index)));
+                collect(value, path, segments, matches);
+                path.segments.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn index_segment(index: usize) -> isize {
+    isize::try_from(index).expect("array index fits in isize")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::path::parse_value_query;
+    use crate::{owned_value_path, value};
+
+    #[test]
+    fn get_all() {
+        let value = value!({
+            "password": "c",
+            "users": [
+                { "id": 1, "password": "a" },
+                { "id": 2, "auth": { "password": "b" } }
+            ]
+        });
+
+        let test_cases = [
+            (".", vec![(owned_value_path!(), value.clone())]),
+            (
+                ".users[-1].id",
+                vec![(owned_value_path!("users", 1, "id"), value!(2))],
+            ),
+            (".users[5]", vec![]),
+            (
+                ".users[*].id",
+                vec![
+                    (owned_value_path!("users", 0, "id"), value!(1)),
+                    (owned_value_path!("users", 1, "id"), value!(2)),
+                ],
+            ),
+            (
+                ".users[0].*",
+                vec![
+                    (owned_value_path!("users", 0, "id"), value!(1)),
+                    (owned_value_path!("users", 0, "password"), value!("a")),
+                ],
+            ),
+            (
+                "..password",
+                vec![
+                    (owned_value_path!("password"), value!("c")),
+                    (owned_value_path!("users", 0, "password"), value!("a")),
+                    (
+                        owned_value_path!("users", 1, "auth", "password"),
+                        value!("b"),
+                    ),
+                ],
+            ),
+            (
+                "..[1].auth.*",
+                vec![(
+                    owned_value_path!("users", 1, "auth", "password"),
+                    value!("b"),
+                )],
+            ),
+        ];
+
+        for (query, expected) in test_cases {
+            let matches = value
+                .get_all(&parse_value_query(query).unwrap())
+                .into_iter()
+                .map(|(path, value)| (path, value.clone()))
+                .collect::<Vec<_>>();
+            assert_eq!(matches, expected, "{query}");
+        }
+    }
+
+    #[test]
+    fn remove_all() {
+        let mut value = value!({
+            "a": [1, 2, 3],
+            "b": { "password": "x", "c": { "password": "y" } },
+        });
+
+        let removed = value.remove_all(&parse_value_query("..password").unwrap());
+        assert_eq!(removed, vec![value!("x"), value!("y")]);
+        assert_eq!(value, value!({ "a": [1, 2, 3], "b": { "c": {} } }));
+
+        let removed = value.remove_all(&parse_value_query(".a[*]").unwrap());
+        assert_eq!(removed, vec![value!(1), value!(2), value!(3)]);
+        assert_eq!(value, value!({ "a": [], "b": { "c": {} } }));
+
+        let mut value = value!({ "a": { "a": 1, "b": 2 } });
+        let removed = value.remove_all(&parse_value_query("..a").unwrap());
+        assert_eq!(removed, vec![value!({ "b": 2 }), value!(1)]);
+        assert_eq!(value, value!({}));
+    }
+}
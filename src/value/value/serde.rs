@@ -1,6 +1,8 @@
-use std::{borrow::Cow, collections::BTreeMap, fmt};
+use std::{borrow::Cow, fmt};
 
-use crate::value::value::{StdError, Value, simdutf_bytes_utf8_lossy, timestamp_to_string};
+use crate::value::value::{
+    ObjectMap, StdError, Value, ValueArray, simdutf_bytes_utf8_lossy, timestamp_to_string,
+};
 use bytes::Bytes;
 use ordered_float::NotNan;
 use serde::de::Error as SerdeError;
@@ -142,6 +144,16 @@ impl<'de> Deserialize<'de> for Value {
                 Ok(Value::Bytes(value.into()))
             }
 
+            #[inline]
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<Value, E> {
+                Ok(Value::Bytes(Bytes::copy_from_slice(value)))
+            }
+
+            #[inline]
+            fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Value, E> {
+                Ok(Value::Bytes(value.into()))
+            }
+
             #[inline]
             fn visit_none<E>(self) -> Result<Value, E> {
                 Ok(Value::Null)
@@ -165,7 +177,7 @@ impl<'de> Deserialize<'de> for Value {
             where
                 V: SeqAccess<'de>,
             {
-                let mut vec = Vec::new();
+                let mut vec = ValueArray::new();
                 while let Some(value) = visitor.next_element()? {
                     vec.push(value);
                 }
@@ -177,7 +189,7 @@ impl<'de> Deserialize<'de> for Value {
             where
                 V: MapAccess<'de>,
             {
-                let mut map = BTreeMap::new();
+                let mut map = ObjectMap::new();
                 while let Some((key, value)) = visitor.next_entry()? {
                     map.insert(key, value);
                 }
@@ -308,4 +320,18 @@ mod test {
             );
         }
     }
+
+    #[cfg(feature = "preserve_order")]
+    #[test]
+    fn object_fields_keep_insertion_order() {
+        let input = r#"{"zebra":1,"apple":{"y":true,"x":null},"mango":"m"}"#;
+        let value: Value = serde_json::from_str(input).unwrap();
+
+        assert_eq!(serde_json::to_string(&value).unwrap(), input);
+
+        // Ordering is not significant for equality.
+        let sorted: Value =
+            serde_json::from_str(r#"{"apple":{"x":null,"y":true},"mango":"m","zebra":1}"#).unwrap();
+        assert_eq!(value, sorted);
+    }
 }
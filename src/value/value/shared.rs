@@ -0,0 +1,227 @@
+//! A reference-counted, copy-on-write container for the collections of a [`Value`].
+//!
+//! [`Value`]: super::Value
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A reference-counted, copy-on-write wrapper around `T`.
+///
+/// Cloning is `O(1)`, as clones share the same allocation. Mutable access, through [`DerefMut`],
+/// copies the wrapped value first if it is shared. Since the collections of a `Value` store their
+/// nested collections in a `Shared` as well, this only copies one level: mutating a value at a
+/// path copies the collections along that path, and none of their siblings.
+///
+/// Read access, through [`Deref`], never copies.
+pub struct Shared<T>(Arc<T>);
+
+impl<T: Default> Shared<T> {
+    /// Creates an empty collection.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T> Shared<Vec<T>> {
+    /// Creates an empty array with space for at least `capacity` elements.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from(Vec::with_capacity(capacity))
+    }
+}
+
+impl<T> Shared<T> {
+    /// Returns `true` if both wrappers share the same allocation, in which case they are
+    /// trivially equal.
+    #[must_use]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl<T: Clone> Shared<T> {
+    /// Unwraps the value, copying it if it is shared.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        Arc::unwrap_or_clone(self.0)
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Clone> DerefMut for Shared<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T: Default> Default for Shared<T> {
+    fn default() -> Self {
+        Self::from(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for Shared<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Self::ptr_eq(self, other) || *self.0 == *other.0
+    }
+}
+
+impl<T: Eq> Eq for Shared<T> {}
+
+impl<T: PartialOrd> PartialOrd for Shared<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<T: Ord> Ord for Shared<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: Hash> Hash for Shared<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<T> From<T> for Shared<T> {
+    fn from(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+}
+
+impl<T: FromIterator<A>, A> FromIterator<A> for Shared<T> {
+    fn from_iter<I: IntoIterator<Item = A>>(iter: I) -> Self {
+        Self::from(T::from_iter(iter))
+    }
+}
+
+impl<T: Extend<A> + Clone, A> Extend<A> for Shared<T> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        (**self).extend(iter);
+    }
+}
+
+impl<T: IntoIterator + Clone> IntoIterator for Shared<T> {
+    type Item = T::Item;
+    type IntoIter = T::IntoIter;
+
+    fn into_iter(self) -> T::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+// `iter` and `iter_mut` are available on the wrapped collection, through `Deref` and `DerefMut`.
+#[allow(clippy::into_iter_without_iter)]
+impl<'a, T> IntoIterator for &'a Shared<T>
+where
+    &'a T: IntoIterator,
+{
+    type Item = <&'a T as IntoIterator>::Item;
+    type IntoIter = <&'a T as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.as_ref().into_iter()
+    }
+}
+
+#[allow(clippy::into_iter_without_iter)]
+impl<'a, T: Clone> IntoIterator for &'a mut Shared<T>
+where
+    &'a mut T: IntoIterator,
+{
+    type Item = <&'a mut T as IntoIterator>::Item;
+    type IntoIter = <&'a mut T as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (&mut **self).into_iter()
+    }
+}
+
+impl<T: Serialize> Serialize for Shared<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Shared<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::path;
+    use crate::value;
+    use crate::value::{ObjectMap, Value};
+
+    #[test]
+    fn clone_shares() {
+        let a = ObjectMap::from([("a".into(), value!(1))]);
+        let b = a.clone();
+
+        assert!(Shared::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn mutation_copies_touched_path() {
+        let original = value!({ "a": { "b": 1 }, "c": { "d": [1, 2] } });
+        let mut copy = original.clone();
+        copy.insert(path!("a", "b"), 2);
+
+        assert_eq!(original, value!({ "a": { "b": 1 }, "c": { "d": [1, 2] } }));
+        assert_eq!(copy, value!({ "a": { "b": 2 }, "c": { "d": [1, 2] } }));
+
+        let (Value::Object(original), Value::Object(copy)) = (&original, &copy) else {
+            unreachable!();
+        };
+        assert!(!Shared::ptr_eq(original, copy));
+        assert!(!Shared::ptr_eq(
+            original["a"].as_object().unwrap(),
+            copy["a"].as_object().unwrap()
+        ));
+        assert!(Shared::ptr_eq(
+            original["c"].as_object().unwrap(),
+            copy["c"].as_object().unwrap()
+        ));
+    }
+
+    #[test]
+    fn unshared_mutation_does_not_copy() {
+        let mut array = crate::value::ValueArray::from(vec![value!(1)]);
+        let before = Arc::as_ptr(&array.0);
+        array.push(value!(2));
+
+        assert_eq!(before, Arc::as_ptr(&array.0));
+        assert_eq!(*array, vec![value!(1), value!(2)]);
+    }
+}